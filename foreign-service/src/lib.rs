@@ -2,5 +2,6 @@
 
 //! Provides pseudo foreign services to springql-core for testing purpose.
 
+pub mod mqtt_broker;
 pub mod sink;
 pub mod source;
@@ -0,0 +1,289 @@
+// This file is part of https://github.com/SpringQL/SpringQL which is licensed under MIT OR Apache-2.0. See file LICENSE-MIT or LICENSE-APACHE for full license details.
+
+use std::{
+    io::{Read, Write},
+    net::{IpAddr, Shutdown, SocketAddr, TcpListener, TcpStream},
+    sync::{mpsc, Arc, Mutex},
+    thread,
+    time::Duration,
+};
+
+use anyhow::{anyhow, bail, ensure, Result};
+
+/// Minimal MQTT 3.1.1 broker (QoS 0 and 1, no retained messages) for testing.
+///
+/// Messages published by clients are routed to subscribers and also returned by `try_receive()`.
+pub struct MqttBroker {
+    my_addr: SocketAddr,
+    sessions: Arc<Mutex<Vec<Session>>>,
+    rx: mpsc::Receiver<(String, Vec<u8>)>,
+}
+
+struct Session {
+    stream: TcpStream,
+    /// (topic filter, QoS)
+    subscriptions: Vec<(String, u8)>,
+    last_packet_id: u16,
+}
+
+impl MqttBroker {
+    pub fn start() -> Result<Self> {
+        Self::start_inner(None)
+    }
+
+    /// Accepts only clients with `username` and `password`.
+    pub fn start_with_credentials(username: &str, password: &str) -> Result<Self> {
+        Self::start_inner(Some((username.to_string(), password.to_string())))
+    }
+
+    fn start_inner(credentials: Option<(String, String)>) -> Result<Self> {
+        let listener = TcpListener::bind("127.0.0.1:0")?;
+        let my_addr = listener.local_addr()?;
+
+        let sessions = Arc::new(Mutex::new(Vec::new()));
+        let (tx, rx) = mpsc::channel();
+
+        {
+            let sessions = sessions.clone();
+            let _ = thread::Builder::new()
+                .name("MqttBroker".into())
+                .spawn(move || {
+                    for stream in listener.incoming() {
+                        let stream = stream.unwrap();
+                        let sessions = sessions.clone();
+                        let credentials = credentials.clone();
+                        let tx = tx.clone();
+                        let _ = thread::Builder::new()
+                            .name("MqttBrokerSession".into())
+                            .spawn(move || {
+                                if let Err(e) =
+                                    Self::session_loop(stream, &sessions, credentials, tx)
+                                {
+                                    log::info!("[MqttBroker] session closed: {:#}", e);
+                                }
+                            });
+                    }
+                });
+        }
+
+        Ok(Self {
+            my_addr,
+            sessions,
+            rx,
+        })
+    }
+
+    pub fn host_ip(&self) -> IpAddr {
+        self.my_addr.ip()
+    }
+
+    pub fn port(&self) -> u16 {
+        self.my_addr.port()
+    }
+
+    /// Publishes a message to subscribers (QoS is downgraded to each subscription's).
+    pub fn publish(&self, topic: &str, payload: &[u8], qos: u8) {
+        route(&self.sessions, topic, payload, qos);
+    }
+
+    /// Returns a message published by clients.
+    pub fn try_receive(&self, timeout: Duration) -> Option<(String, Vec<u8>)> {
+        self.rx.recv_timeout(timeout).ok()
+    }
+
+    /// Number of clients connected.
+    pub fn n_sessions(&self) -> usize {
+        self.sessions.lock().unwrap().len()
+    }
+
+    /// Closes all client connections (as if the broker restarted).
+    pub fn disconnect_all(&self) {
+        for session in self.sessions.lock().unwrap().drain(..) {
+            let _ = session.stream.shutdown(Shutdown::Both);
+        }
+    }
+
+    fn session_loop(
+        mut stream: TcpStream,
+        sessions: &Mutex<Vec<Session>>,
+        credentials: Option<(String, String)>,
+        tx: mpsc::Sender<(String, Vec<u8>)>,
+    ) -> Result<()> {
+        let (header, body) = read_packet(&mut stream)?;
+        ensure!(header >> 4 == 1, "expected CONNECT");
+        let (username, password) = parse_connect(&body)?;
+        let authorized = match &credentials {
+            Some((u, p)) => username.as_ref() == Some(u) && password.as_ref() == Some(p),
+            None => true,
+        };
+        if !authorized {
+            stream.write_all(&[0x20, 0x02, 0x00, 0x04])?;
+            bail!("bad user name or password");
+        }
+        stream.write_all(&[0x20, 0x02, 0x00, 0x00])?;
+
+        let my_stream = stream.try_clone()?;
+        let peer_addr = stream.peer_addr()?;
+        sessions.lock().unwrap().push(Session {
+            stream: stream.try_clone()?,
+            subscriptions: Vec::new(),
+            last_packet_id: 0,
+        });
+
+        let res = (|| loop {
+            let (header, body) = read_packet(&mut stream)?;
+            match header >> 4 {
+                3 => {
+                    let qos = (header >> 1) & 0x03;
+                    let (topic, mut rest) = take_string(&body)?;
+                    if qos > 0 {
+                        ensure!(rest.len() >= 2, "malformed PUBLISH");
+                        let mut puback = vec![0x40, 0x02];
+                        puback.extend_from_slice(&rest[..2]);
+                        stream.write_all(&puback)?;
+                        rest = &rest[2..];
+                    }
+                    let _ = tx.send((topic.clone(), rest.to_vec()));
+                    route(sessions, &topic, rest, qos);
+                }
+                4 => {} // PUBACK
+                8 => {
+                    ensure!(body.len() >= 2, "malformed SUBSCRIBE");
+                    let mut rest = &body[2..];
+                    let mut suback = body[..2].to_vec();
+                    let mut subscriptions = Vec::new();
+                    while !rest.is_empty() {
+                        let (topic_filter, r) = take_string(rest)?;
+                        ensure!(!r.is_empty(), "malformed SUBSCRIBE");
+                        let qos = r[0].min(1);
+                        subscriptions.push((topic_filter, qos));
+                        suback.push(qos);
+                        rest = &r[1..];
+                    }
+                    if let Some(session) = sessions
+                        .lock()
+                        .unwrap()
+                        .iter_mut()
+                        .find(|s| s.stream.peer_addr().ok() == Some(peer_addr))
+                    {
+                        session.subscriptions.extend(subscriptions);
+                    }
+                    stream.write_all(&packet(0x90, suback))?;
+                }
+                12 => stream.write_all(&[0xd0, 0x00])?,
+                14 => return Ok(()),
+                t => bail!("unexpected packet type: {}", t),
+            }
+        })();
+
+        sessions
+            .lock()
+            .unwrap()
+            .retain(|s| s.stream.peer_addr().ok() != Some(peer_addr));
+        let _ = my_stream.shutdown(Shutdown::Both);
+        res
+    }
+}
+
+/// `true` if `topic` matches `topic_filter` with `+` and `#` wildcards.
+fn topic_matches(topic_filter: &str, topic: &str) -> bool {
+    let mut filter_levels = topic_filter.split('/');
+    let mut topic_levels = topic.split('/');
+    loop {
+        match (filter_levels.next(), topic_levels.next()) {
+            (Some("#"), _) => return true,
+            (Some("+"), Some(_)) => {}
+            (Some(f), Some(t)) if f == t => {}
+            (None, None) => return true,
+            _ => return false,
+        }
+    }
+}
+
+fn route(sessions: &Mutex<Vec<Session>>, topic: &str, payload: &[u8], qos: u8) {
+    for session in sessions.lock().unwrap().iter_mut() {
+        let sub_qos = session
+            .subscriptions
+            .iter()
+            .filter(|(filter, _)| topic_matches(filter, topic))
+            .map(|(_, qos)| *qos)
+            .max();
+        if let Some(sub_qos) = sub_qos {
+            let mut body = (topic.len() as u16).to_be_bytes().to_vec();
+            body.extend_from_slice(topic.as_bytes());
+            let header = if sub_qos.min(qos) > 0 {
+                session.last_packet_id = session.last_packet_id.wrapping_add(1).max(1);
+                body.extend_from_slice(&session.last_packet_id.to_be_bytes());
+                0x32
+            } else {
+                0x30
+            };
+            body.extend_from_slice(payload);
+            let _ = session.stream.write_all(&packet(header, body));
+        }
+    }
+}
+
+/// Returns (user name, password).
+fn parse_connect(body: &[u8]) -> Result<(Option<String>, Option<String>)> {
+    let (protocol, rest) = take_string(body)?;
+    ensure!(protocol == "MQTT", "unknown protocol: {}", protocol);
+    ensure!(rest.len() >= 4, "malformed CONNECT");
+    let flags = rest[1];
+    let (_client_id, mut rest) = take_string(&rest[4..])?;
+
+    let mut username = None;
+    let mut password = None;
+    if flags & 0x80 != 0 {
+        let (u, r) = take_string(rest)?;
+        username = Some(u);
+        rest = r;
+    }
+    if flags & 0x40 != 0 {
+        password = Some(take_string(rest)?.0);
+    }
+    Ok((username, password))
+}
+
+fn read_packet(stream: &mut TcpStream) -> Result<(u8, Vec<u8>)> {
+    let mut byte = [0u8; 1];
+    stream.read_exact(&mut byte)?;
+    let header = byte[0];
+
+    let mut len = 0usize;
+    for i in 0..4 {
+        stream.read_exact(&mut byte)?;
+        len += ((byte[0] & 0x7f) as usize) << (7 * i);
+        if byte[0] & 0x80 == 0 {
+            let mut body = vec![0u8; len];
+            stream.read_exact(&mut body)?;
+            return Ok((header, body));
+        }
+    }
+    Err(anyhow!("malformed remaining length"))
+}
+
+fn packet(header: u8, body: Vec<u8>) -> Vec<u8> {
+    let mut packet = vec![header];
+    let mut len = body.len();
+    loop {
+        let byte = (len % 128) as u8;
+        len /= 128;
+        if len > 0 {
+            packet.push(byte | 0x80);
+        } else {
+            packet.push(byte);
+            break;
+        }
+    }
+    packet.extend(body);
+    packet
+}
+
+fn take_string(buf: &[u8]) -> Result<(String, &[u8])> {
+    ensure!(buf.len() >= 2, "packet too short");
+    let len = u16::from_be_bytes([buf[0], buf[1]]) as usize;
+    ensure!(buf.len() >= 2 + len, "packet too short");
+    let s = String::from_utf8(buf[2..2 + len].to_vec())?;
+    Ok((s, &buf[2 + len..]))
+}
@@ -4,20 +4,34 @@ pub mod error;
 
 mod spring_config;
 mod spring_pipeline;
+mod spring_pipeline_info;
+mod spring_shutdown_mode;
 mod spring_sink_row;
+#[cfg(feature = "async")]
+mod spring_sink_stream;
 mod spring_source_row;
+#[cfg(feature = "async")]
+mod spring_source_sink;
 
 pub use crate::{
     api::{
         error::{Result, SpringError},
         spring_config::*,
         spring_pipeline::SpringPipeline,
+        spring_pipeline_info::{
+            SpringColumnInfo, SpringPumpInfo, SpringSinkWriterInfo, SpringSourceReaderInfo,
+            SpringStreamInfo,
+        },
+        spring_shutdown_mode::SpringShutdownMode,
         spring_sink_row::SpringSinkRow,
         spring_source_row::{SpringSourceRow, SpringSourceRowBuilder},
         SpringConfig,
     },
     stream_engine::{
         time::{SpringEventDuration, SpringTimestamp},
-        SpringValue,
+        SpringColumnValue, SpringValue,
     },
 };
+
+#[cfg(feature = "async")]
+pub use crate::api::{spring_sink_stream::SpringSinkStream, spring_source_sink::SpringSourceSink};
@@ -7,30 +7,46 @@ use std::{
     net::SocketAddr,
 };
 
-use crate::pipeline::QueueName;
+use crate::pipeline::{CallbackName, QueueName};
 
 /// Foreign system information for error reporting.
 pub enum ForeignInfo {
     /// Generic TCP connection.
     GenericTcp(SocketAddr),
 
+    /// Generic UDP socket.
+    GenericUdp(SocketAddr),
+
     /// HTTP client
     Http(SocketAddr),
 
+    /// File
+    File(String),
+
+    /// MQTT broker (`host:port`)
+    Mqtt(String),
+
     /// Socket CAN interface
     SocketCAN(String),
 
     /// In memory queue
     InMemoryQueue(QueueName),
+
+    /// Sink callback registered by an application
+    SinkCallback(CallbackName),
 }
 
 impl Display for ForeignInfo {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         let detail = match self {
             ForeignInfo::GenericTcp(addr) => format!("TCP connection to {:?}", addr),
+            ForeignInfo::GenericUdp(addr) => format!("UDP socket of {:?}", addr),
             ForeignInfo::Http(addr) => format!("HTTP connection to {:?}", addr),
+            ForeignInfo::File(path) => format!("File {}", path),
+            ForeignInfo::Mqtt(broker) => format!("MQTT broker {}", broker),
             ForeignInfo::SocketCAN(interface) => format!("Socket CAN interface {}", interface),
             ForeignInfo::InMemoryQueue(queue_name) => format!("In-memory queue {}", queue_name),
+            ForeignInfo::SinkCallback(callback_name) => format!("Sink callback {}", callback_name),
         };
 
         write!(f, "[foreign info.] {}", detail)
@@ -58,14 +58,79 @@ timeout_msec = 3_000
 net_connect_timeout_msec = 1_000
 net_read_timeout_msec = 100
 
+# Maximum size of a UDP datagram received by NET_CLIENT / NET_SERVER source readers.
+# Larger datagrams are dropped and reported as errors.
+net_max_datagram_bytes = 65_507
+
+# Number of rows a NET_SERVER source reader receives from clients and keeps until they are read.
+# While it is full, the source reader stops reading from clients (backpressure).
+net_server_queue_capacity = 10_000
+
+# Maximum length of a line (excluding the line break) a NET_SERVER source reader receives from TCP clients.
+# Longer lines are dropped and reported as errors.
+net_server_max_line_bytes = 1_048_576
+
+# Reconnection after a connection of NET_CLIENT (TCP) and MQTT source readers is lost.
+# The first attempt is made after `reconnect_initial_backoff_msec`, and the wait doubles after each failed attempt up to `reconnect_max_backoff_msec`.
+# Each wait is shortened by a random ratio up to `reconnect_jitter_percent` % so that many clients do not reconnect at once.
+# Gives up after `reconnect_max_retries` failed attempts in a row. 0 disables reconnection.
+# The source reader reaches end-of-stream when it gives up.
+reconnect_max_retries = 5
+reconnect_initial_backoff_msec = 100
+reconnect_max_backoff_msec = 5_000
+reconnect_jitter_percent = 20
+
+mqtt_connect_timeout_msec = 1_000
+mqtt_read_timeout_msec = 100
+mqtt_keep_alive_sec = 30
+
+# Number of messages an MQTT source reader receives from the broker and keeps until they are read.
+# While it is full, the source reader stops reading from the broker (backpressure).
+mqtt_queue_capacity = 10_000
+
+# How long a FILE source reader waits for the next row to be due (`REPLAY_SPEED`) or appended (`FOLLOW`) in a read.
+file_read_timeout_msec = 100
+
 can_read_timeout_msec = 100
 
 [sink_writer]
 net_connect_timeout_msec = 1_000
 net_write_timeout_msec = 100
 
+# Maximum size of a UDP datagram sent by NET_CLIENT sink writers.
+# Rows encoded into larger datagrams are not sent and reported as errors.
+net_max_datagram_bytes = 65_507
+
 http_connect_timeout_msec = 1_000
 http_timeout_msec = 100
+
+# Reconnection after a connection of NET_CLIENT (TCP), HTTP1_CLIENT and MQTT sink writers is lost.
+# Rows are kept in the input queue of the sink while disconnected, and a row failed to be sent is sent again after reconnection.
+# The first attempt is made after `reconnect_initial_backoff_msec`, and the wait doubles after each failed attempt up to `reconnect_max_backoff_msec`.
+# Each wait is shortened by a random ratio up to `reconnect_jitter_percent` % so that many clients do not reconnect at once.
+# Gives up after `reconnect_max_retries` failed attempts in a row. 0 to retry forever.
+# After giving up, rows to the sink writer are dropped and reported as errors.
+reconnect_max_retries = 0
+reconnect_initial_backoff_msec = 100
+reconnect_max_backoff_msec = 5_000
+reconnect_jitter_percent = 20
+
+mqtt_connect_timeout_msec = 1_000
+mqtt_keep_alive_sec = 30
+
+# How long an MQTT sink writer waits for the broker to acknowledge a message published in QoS 1.
+mqtt_ack_timeout_msec = 1_000
+
+[pump]
+# Number of rows kept in an input queue of a stopped pump (`CREATE PUMP ... STOPPED` / `ALTER PUMP ... STOP`) until it is started.
+# Rows arriving at a queue already holding this number of rows are dropped. 0 drops all rows arriving while the pump is stopped.
+stopped_queue_capacity = 10_000
+
+[sql]
+# Result of division (`/`) or remainder (`%`) by zero.
+# "null": evaluated to NULL.
+# "error": the row is not processed and `SpringError::Sql` is reported.
+division_by_zero = "null"
 "#;
 
 /// Top-level config.
@@ -77,6 +142,8 @@ pub struct SpringConfig {
     pub web_console: SpringWebConsoleConfig,
     pub source_reader: SpringSourceReaderConfig,
     pub sink_writer: SpringSinkWriterConfig,
+    pub pump: SpringPumpConfig,
+    pub sql: SpringSqlConfig,
 }
 
 impl Default for SpringConfig {
@@ -179,6 +246,21 @@ pub struct SpringWebConsoleConfig {
 pub struct SpringSourceReaderConfig {
     pub net_connect_timeout_msec: u32,
     pub net_read_timeout_msec: u32,
+    pub net_max_datagram_bytes: u32,
+    pub net_server_queue_capacity: u32,
+    pub net_server_max_line_bytes: u32,
+
+    pub reconnect_max_retries: u32,
+    pub reconnect_initial_backoff_msec: u32,
+    pub reconnect_max_backoff_msec: u32,
+    pub reconnect_jitter_percent: u32,
+
+    pub mqtt_connect_timeout_msec: u32,
+    pub mqtt_read_timeout_msec: u32,
+    pub mqtt_keep_alive_sec: u16,
+    pub mqtt_queue_capacity: u32,
+
+    pub file_read_timeout_msec: u32,
 
     pub can_read_timeout_msec: u32,
 }
@@ -189,7 +271,41 @@ pub struct SpringSourceReaderConfig {
 pub struct SpringSinkWriterConfig {
     pub net_connect_timeout_msec: u32,
     pub net_write_timeout_msec: u32,
+    pub net_max_datagram_bytes: u32,
 
     pub http_timeout_msec: u32,
     pub http_connect_timeout_msec: u32,
+
+    pub reconnect_max_retries: u32,
+    pub reconnect_initial_backoff_msec: u32,
+    pub reconnect_max_backoff_msec: u32,
+    pub reconnect_jitter_percent: u32,
+
+    pub mqtt_connect_timeout_msec: u32,
+    pub mqtt_keep_alive_sec: u16,
+    pub mqtt_ack_timeout_msec: u32,
+}
+
+/// Config related to pumps.
+#[allow(missing_docs)]
+#[derive(Copy, Clone, Eq, PartialEq, Debug, Deserialize)]
+pub struct SpringPumpConfig {
+    pub stopped_queue_capacity: u32,
+}
+
+/// Config related to SQL evaluation.
+#[allow(missing_docs)]
+#[derive(Copy, Clone, Eq, PartialEq, Debug, Deserialize)]
+pub struct SpringSqlConfig {
+    pub division_by_zero: DivisionByZero,
+}
+
+/// How division (`/`) and remainder (`%`) by zero are evaluated.
+#[derive(Copy, Clone, Eq, PartialEq, Debug, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum DivisionByZero {
+    /// Evaluated to NULL.
+    Null,
+    /// Evaluation fails with `SpringError::Sql`.
+    Error,
 }
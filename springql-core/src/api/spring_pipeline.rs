@@ -1,11 +1,34 @@
 // This file is part of https://github.com/SpringQL/SpringQL which is licensed under MIT OR Apache-2.0. See file LICENSE-MIT or LICENSE-APACHE for full license details.
 
+use std::time::Duration;
+
 use crate::{
-    api::{error::Result, spring_source_row::SpringSourceRow, SpringConfig, SpringSinkRow},
+    api::{
+        error::Result,
+        spring_pipeline_info::{
+            SpringPumpInfo, SpringSinkWriterInfo, SpringSourceReaderInfo, SpringStreamInfo,
+        },
+        spring_source_row::SpringSourceRow,
+        SpringConfig, SpringError, SpringShutdownMode, SpringSinkRow,
+    },
     connection::Connection,
 };
 
+#[cfg(feature = "async")]
+use crate::{
+    api::{
+        spring_sink_stream::{PopFuture, SpringSinkStream},
+        spring_source_sink::SpringSourceSink,
+    },
+    connection::queue_closed,
+};
+
 /// Pipeline.
+///
+/// In-memory queues (`IN_MEMORY_QUEUE` source readers and sink writers) belong to each pipeline,
+/// so pipelines in the same process can use the same queue name independently.
+/// To connect pipelines, give `SHARED 'TRUE'` option to a sink writer of a pipeline and a source reader of another pipeline with the same queue name:
+/// rows written by the sink writer are read by the source reader.
 #[derive(Debug)]
 pub struct SpringPipeline(Connection);
 
@@ -32,18 +55,40 @@ impl SpringPipeline {
 
     /// Pop a row from an in memory queue. This is a blocking function.
     ///
-    /// **Do not call this function from threads.**
-    /// If you need to pop from multiple in-memory queues using threads, use `pop_non_blocking()`.
-    /// See: <https://github.com/SpringQL/SpringQL/issues/125>
+    /// It returns as soon as a row is pushed to the queue.
+    /// Other calls to this pipeline (`command()`, `push()`, `pop()` for other queues, ...) are not blocked while waiting,
+    /// so threads can pop from different queues concurrently.
     ///
     /// # Failure
     ///
     /// - [SpringError::Unavailable](crate::api::error::SpringError::Unavailable) when:
     ///   - queue named `queue` does not exist.
+    ///   - the queue is empty and [end_of_stream()](Self::end_of_stream) is called for it, or it is removed from the pipeline
+    ///     (e.g. its sink writer is dropped). Waiting pops also return in these cases.
     pub fn pop(&self, queue: &str) -> Result<SpringSinkRow> {
         self.0.pop(queue).map(SpringSinkRow::new)
     }
 
+    /// Pop a row from an in memory queue, blocking at most `timeout`.
+    ///
+    /// Like [pop()](Self::pop), other calls to this pipeline are not blocked while waiting.
+    ///
+    /// # Returns
+    ///
+    /// - `Ok(Some)` when a row is popped within `timeout`.
+    /// - `Ok(None)` when no row is pushed within `timeout`.
+    ///
+    /// # Failure
+    ///
+    /// - [SpringError::Unavailable](crate::api::error::SpringError::Unavailable) when:
+    ///   - queue named `queue` does not exist.
+    ///   - the queue is empty and ended or removed, as in [pop()](Self::pop).
+    pub fn pop_timeout(&self, queue: &str, timeout: Duration) -> Result<Option<SpringSinkRow>> {
+        self.0
+            .pop_timeout(queue, timeout)
+            .map(|opt_row| opt_row.map(SpringSinkRow::new))
+    }
+
     /// Pop a row from an in memory queue. This is a non-blocking function.
     ///
     /// # Returns
@@ -67,7 +112,194 @@ impl SpringPipeline {
     ///
     /// - [SpringError::Unavailable](crate::api::error::SpringError::Unavailable) when:
     ///   - queue named `queue` does not exist.
+    ///   - [end_of_stream()](Self::end_of_stream) is already called for the queue.
     pub fn push(&self, queue: &str, row: SpringSourceRow) -> Result<()> {
         self.0.push(queue, row.into_schemaless_row()?)
     }
+
+    /// Pop a row from an in memory queue asynchronously.
+    ///
+    /// Waiting for a row does not block the executor thread, nor other calls to this pipeline.
+    ///
+    /// # Failure
+    ///
+    /// - [SpringError::Unavailable](crate::api::error::SpringError::Unavailable) when:
+    ///   - queue named `queue` does not exist.
+    ///   - the queue is empty and ended or removed, as in [pop()](Self::pop).
+    #[cfg(feature = "async")]
+    pub async fn pop_async(&self, queue: &str) -> Result<SpringSinkRow> {
+        let q = self.0.in_memory_queue(queue)?;
+        PopFuture::new(q).await.ok_or_else(|| queue_closed(queue))
+    }
+
+    /// Get a stream of rows popped from an in memory queue.
+    ///
+    /// The stream keeps popping from the queue even after the pipeline is shut down.
+    /// It ends when the queue is empty and [end_of_stream()](Self::end_of_stream) is called for it, or it is removed from the pipeline
+    /// (e.g. its sink writer is dropped).
+    ///
+    /// # Failure
+    ///
+    /// - [SpringError::Unavailable](crate::api::error::SpringError::Unavailable) when:
+    ///   - queue named `queue` does not exist.
+    #[cfg(feature = "async")]
+    pub fn sink_stream(&self, queue: &str) -> Result<SpringSinkStream> {
+        let q = self.0.in_memory_queue(queue)?;
+        Ok(SpringSinkStream::new(q))
+    }
+
+    /// Get a sink to push rows into an in memory queue.
+    ///
+    /// Sending a row fails after [end_of_stream()](Self::end_of_stream) is called for the queue, or after the pipeline is shut down, as [push()](Self::push) does.
+    /// Closing the sink tells end-of-stream of the queue.
+    ///
+    /// # Failure
+    ///
+    /// - [SpringError::Unavailable](crate::api::error::SpringError::Unavailable) when:
+    ///   - queue named `queue` does not exist.
+    #[cfg(feature = "async")]
+    pub fn source_sink(&self, queue: &str) -> Result<SpringSourceSink> {
+        let q = self.0.in_memory_queue(queue)?;
+        let shutdown_state = self.0.shutdown_state()?;
+        Ok(SpringSourceSink::new(queue.to_string(), q, shutdown_state))
+    }
+
+    /// Register a callback called for each row written by `CALLBACK` sink writers with `NAME` option equal to `name`.
+    ///
+    /// Register a callback before `CREATE SINK WRITER ... TYPE CALLBACK`, which fails if no callback is registered with the name.
+    /// Registering with the same name again replaces the callback for sink writers created afterwards.
+    ///
+    /// The callback is called from a worker thread of the pipeline, so it should return quickly.
+    /// To hand rows over to another thread, send them to a channel:
+    ///
+    /// ```
+    /// use std::sync::mpsc;
+    /// use springql_core::api::{SpringConfig, SpringPipeline};
+    ///
+    /// let pipeline = SpringPipeline::new(&SpringConfig::default()).unwrap();
+    ///
+    /// let (tx, rx) = mpsc::channel();
+    /// pipeline
+    ///     .register_sink_callback("cb", move |row| {
+    ///         let _ = tx.send(row);
+    ///     })
+    ///     .unwrap();
+    /// # drop(rx);
+    /// ```
+    ///
+    /// A panic in the callback does not stop the pipeline: it is caught, the row is dropped, and the callback is called again for the next row.
+    /// The panic is logged at `ERROR` level with a backtrace by the panic hook, and then as a sink error at `WARN` level.
+    /// To handle failures in the application, use [register_sink_callback_with_error_handler()](Self::register_sink_callback_with_error_handler).
+    pub fn register_sink_callback<F>(&self, name: &str, mut callback: F) -> Result<()>
+    where
+        F: FnMut(SpringSinkRow) + Send + 'static,
+    {
+        self.0.register_sink_callback(
+            name,
+            Box::new(move |row| callback(SpringSinkRow::new(row))),
+            None,
+        )
+    }
+
+    /// Same as [register_sink_callback()](Self::register_sink_callback), and `on_error` is called each time the callback fails.
+    ///
+    /// `on_error` is called from the same worker thread right after the callback panicked, with
+    /// [SpringError::ForeignIo](crate::api::error::SpringError::ForeignIo) describing the panic.
+    /// A panic in `on_error` is caught and ignored.
+    pub fn register_sink_callback_with_error_handler<F, E>(
+        &self,
+        name: &str,
+        mut callback: F,
+        on_error: E,
+    ) -> Result<()>
+    where
+        F: FnMut(SpringSinkRow) + Send + 'static,
+        E: FnMut(SpringError) + Send + 'static,
+    {
+        self.0.register_sink_callback(
+            name,
+            Box::new(move |row| callback(SpringSinkRow::new(row))),
+            Some(Box::new(on_error)),
+        )
+    }
+
+    /// Tell that no more row is pushed into an in-memory source queue.
+    ///
+    /// After the rows already pushed are read, the source reader reaches end-of-stream.
+    /// When all the sources upstream of a window reach end-of-stream, the watermark of the window moves to the end of time and all the open windows emit their results.
+    /// A `NET_CLIENT` source reader reaches end-of-stream only when it gives up reconnecting after the connection is lost
+    /// (see `source_reader.reconnect_max_retries` in [SpringConfig](crate::api::SpringConfig)).
+    ///
+    /// # Failure
+    ///
+    /// - [SpringError::Unavailable](crate::api::error::SpringError::Unavailable) when:
+    ///   - queue named `queue` does not exist.
+    ///   - the pipeline is already shut down.
+    pub fn end_of_stream(&self, queue: &str) -> Result<()> {
+        self.0.end_of_stream(queue)
+    }
+
+    /// Stop the pipeline.
+    ///
+    /// In [SpringShutdownMode::Drain], source readers are stopped first and then rows remaining in the pipeline (including open windows) are processed until they reach sink writers, within `timeout`.
+    /// In [SpringShutdownMode::Immediate], rows remaining in the pipeline are discarded.
+    ///
+    /// Rows already in in-memory sink queues can still be popped after shutdown, while `command()` and `push()` fail.
+    ///
+    /// # Failure
+    ///
+    /// - [SpringError::Unavailable](crate::api::error::SpringError::Unavailable) when:
+    ///   - the pipeline is already shut down.
+    ///   - rows still remain in the pipeline after `timeout` in [SpringShutdownMode::Drain]. The pipeline is stopped anyway.
+    pub fn shutdown(&self, mode: SpringShutdownMode, timeout: Duration) -> Result<()> {
+        self.0.shutdown(mode, timeout)
+    }
+
+    /// Streams in the current pipeline, sorted by name.
+    pub fn streams(&self) -> Result<Vec<SpringStreamInfo>> {
+        let mut streams = self
+            .0
+            .streams()?
+            .iter()
+            .map(|stream| SpringStreamInfo::from(stream.as_ref()))
+            .collect::<Vec<_>>();
+        streams.sort_by(|a, b| a.name().cmp(b.name()));
+        Ok(streams)
+    }
+
+    /// Pumps in the current pipeline, sorted by name.
+    pub fn pumps(&self) -> Result<Vec<SpringPumpInfo>> {
+        let mut pumps = self
+            .0
+            .pumps()?
+            .iter()
+            .map(|pump| SpringPumpInfo::from(pump.as_ref()))
+            .collect::<Vec<_>>();
+        pumps.sort_by(|a, b| a.name().cmp(b.name()));
+        Ok(pumps)
+    }
+
+    /// Source readers in the current pipeline, sorted by name.
+    pub fn source_readers(&self) -> Result<Vec<SpringSourceReaderInfo>> {
+        let mut source_readers = self
+            .0
+            .source_readers()?
+            .iter()
+            .map(SpringSourceReaderInfo::from)
+            .collect::<Vec<_>>();
+        source_readers.sort_by(|a, b| a.name().cmp(b.name()));
+        Ok(source_readers)
+    }
+
+    /// Sink writers in the current pipeline, sorted by name.
+    pub fn sink_writers(&self) -> Result<Vec<SpringSinkWriterInfo>> {
+        let mut sink_writers = self
+            .0
+            .sink_writers()?
+            .iter()
+            .map(SpringSinkWriterInfo::from)
+            .collect::<Vec<_>>();
+        sink_writers.sort_by(|a, b| a.name().cmp(b.name()));
+        Ok(sink_writers)
+    }
 }
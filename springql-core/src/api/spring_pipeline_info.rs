@@ -0,0 +1,235 @@
+// This file is part of https://github.com/SpringQL/SpringQL which is licensed under MIT OR Apache-2.0. See file LICENSE-MIT or LICENSE-APACHE for full license details.
+
+//! Snapshots of objects in a pipeline, for introspection.
+
+use crate::pipeline::{
+    ColumnConstraint, ColumnDefinition, PumpModel, PumpState, SinkWriterModel, SourceReaderModel,
+    StreamModel,
+};
+
+/// A stream in a pipeline.
+#[derive(Clone, Eq, PartialEq, Debug)]
+pub struct SpringStreamInfo {
+    name: String,
+    columns: Vec<SpringColumnInfo>,
+}
+
+impl SpringStreamInfo {
+    /// Name of the stream.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Columns in definition order.
+    pub fn columns(&self) -> &[SpringColumnInfo] {
+        &self.columns
+    }
+
+    /// Name of the ROWTIME column, if any.
+    pub fn rowtime(&self) -> Option<&str> {
+        self.columns
+            .iter()
+            .find(|c| c.is_rowtime())
+            .map(|c| c.name())
+    }
+}
+
+impl From<&StreamModel> for SpringStreamInfo {
+    fn from(stream: &StreamModel) -> Self {
+        Self {
+            name: stream.name().to_string(),
+            columns: stream
+                .shape()
+                .columns()
+                .iter()
+                .map(SpringColumnInfo::from)
+                .collect(),
+        }
+    }
+}
+
+/// A column of a stream.
+#[derive(Clone, Eq, PartialEq, Debug)]
+pub struct SpringColumnInfo {
+    name: String,
+    data_type: String,
+    nullable: bool,
+    rowtime: bool,
+}
+
+impl SpringColumnInfo {
+    /// Name of the column.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// SQL type name like `INTEGER` or `TIMESTAMP`.
+    pub fn data_type(&self) -> &str {
+        &self.data_type
+    }
+
+    /// `false` if the column is `NOT NULL`.
+    pub fn is_nullable(&self) -> bool {
+        self.nullable
+    }
+
+    /// `true` if the column has `ROWTIME` constraint.
+    pub fn is_rowtime(&self) -> bool {
+        self.rowtime
+    }
+}
+
+impl From<&ColumnDefinition> for SpringColumnInfo {
+    fn from(column: &ColumnDefinition) -> Self {
+        let data_type = column.column_data_type();
+        Self {
+            name: column.column_name().to_string(),
+            data_type: data_type.sql_type().to_string(),
+            nullable: data_type.nullable(),
+            rowtime: column
+                .column_constraints()
+                .iter()
+                .any(|cc| matches!(cc, ColumnConstraint::Rowtime)),
+        }
+    }
+}
+
+/// A pump in a pipeline.
+#[derive(Clone, Eq, PartialEq, Debug)]
+pub struct SpringPumpInfo {
+    name: String,
+    sql: String,
+    plan: String,
+    upstreams: Vec<String>,
+    downstream: String,
+    started: bool,
+}
+
+impl SpringPumpInfo {
+    /// Name of the pump.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// `CREATE PUMP` statement the pump is created from.
+    pub fn sql(&self) -> &str {
+        &self.sql
+    }
+
+    /// Human-readable dump of the query plan. The format is not stable.
+    pub fn plan(&self) -> &str {
+        &self.plan
+    }
+
+    /// Streams the pump reads from (2 or more on JOIN).
+    pub fn upstreams(&self) -> &[String] {
+        &self.upstreams
+    }
+
+    /// Stream the pump inserts into.
+    pub fn downstream(&self) -> &str {
+        &self.downstream
+    }
+
+    /// `false` if created with `STOPPED` or after `ALTER PUMP ... STOP`.
+    pub fn is_started(&self) -> bool {
+        self.started
+    }
+}
+
+impl From<&PumpModel> for SpringPumpInfo {
+    fn from(pump: &PumpModel) -> Self {
+        Self {
+            name: pump.name().to_string(),
+            sql: pump.sql().to_string(),
+            plan: format!("{:#?}", pump.query_plan()),
+            upstreams: pump.upstreams().iter().map(|s| s.to_string()).collect(),
+            downstream: pump.downstream().to_string(),
+            started: pump.state() == PumpState::Started,
+        }
+    }
+}
+
+/// A source reader in a pipeline.
+#[derive(Clone, Eq, PartialEq, Debug)]
+pub struct SpringSourceReaderInfo {
+    name: String,
+    reader_type: String,
+    stream: String,
+    options: Vec<(String, String)>,
+}
+
+impl SpringSourceReaderInfo {
+    /// Name of the source reader.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Type name like `NET_CLIENT`.
+    pub fn reader_type(&self) -> &str {
+        &self.reader_type
+    }
+
+    /// Source stream the reader feeds.
+    pub fn stream(&self) -> &str {
+        &self.stream
+    }
+
+    /// Options sorted by key. Values of secrets (passwords, tokens, authorization headers, ...) are redacted.
+    pub fn options(&self) -> &[(String, String)] {
+        &self.options
+    }
+}
+
+impl From<&SourceReaderModel> for SpringSourceReaderInfo {
+    fn from(source_reader: &SourceReaderModel) -> Self {
+        Self {
+            name: source_reader.name().to_string(),
+            reader_type: source_reader.source_reader_type().to_string(),
+            stream: source_reader.dest_source_stream().to_string(),
+            options: source_reader.options().as_redacted_key_values(),
+        }
+    }
+}
+
+/// A sink writer in a pipeline.
+#[derive(Clone, Eq, PartialEq, Debug)]
+pub struct SpringSinkWriterInfo {
+    name: String,
+    writer_type: String,
+    stream: String,
+    options: Vec<(String, String)>,
+}
+
+impl SpringSinkWriterInfo {
+    /// Name of the sink writer.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Type name like `IN_MEMORY_QUEUE`.
+    pub fn writer_type(&self) -> &str {
+        &self.writer_type
+    }
+
+    /// Sink stream the writer reads.
+    pub fn stream(&self) -> &str {
+        &self.stream
+    }
+
+    /// Options sorted by key. Values of secrets (passwords, tokens, authorization headers, ...) are redacted.
+    pub fn options(&self) -> &[(String, String)] {
+        &self.options
+    }
+}
+
+impl From<&SinkWriterModel> for SpringSinkWriterInfo {
+    fn from(sink_writer: &SinkWriterModel) -> Self {
+        Self {
+            name: sink_writer.name().to_string(),
+            writer_type: sink_writer.sink_writer_type().to_string(),
+            stream: sink_writer.sink_upstream().to_string(),
+            options: sink_writer.options().as_redacted_key_values(),
+        }
+    }
+}
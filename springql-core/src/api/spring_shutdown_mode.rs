@@ -0,0 +1,13 @@
+// This file is part of https://github.com/SpringQL/SpringQL which is licensed under MIT OR Apache-2.0. See file LICENSE-MIT or LICENSE-APACHE for full license details.
+
+/// How [SpringPipeline::shutdown()](crate::api::SpringPipeline::shutdown) treats rows remaining in a pipeline.
+#[derive(Clone, Copy, Eq, PartialEq, Hash, Debug)]
+pub enum SpringShutdownMode {
+    /// Stop source readers, then process all rows remaining in the pipeline until they reach sink writers.
+    ///
+    /// Open windows are closed even if the watermark has not reached their end, so that the last aggregation or join results are emitted.
+    Drain,
+
+    /// Stop all workers at once. Rows remaining in the pipeline are discarded.
+    Immediate,
+}
@@ -1,11 +1,17 @@
 // This file is part of https://github.com/SpringQL/SpringQL which is licensed under MIT OR Apache-2.0. See file LICENSE-MIT or LICENSE-APACHE for full license details.
 
+use anyhow::{anyhow, Context};
+use serde::de::DeserializeOwned;
+
 use crate::{
     api::{
         error::{Result, SpringError},
         spring_source_row::SpringSourceRow,
     },
-    stream_engine::{autonomous_executor::SchemalessRow, SpringValue, SqlValue},
+    stream_engine::{
+        autonomous_executor::{JsonObject, SchemalessRow},
+        SpringColumnValue, SpringValue,
+    },
 };
 
 /// Row object from an in memory sink queue.
@@ -28,13 +34,99 @@ impl SpringSinkRow {
     pub fn get_not_null_by_index<T>(&self, i_col: usize) -> Result<T>
     where
         T: SpringValue,
+    {
+        self.get(i_col)
+    }
+
+    /// Get a i-th column value from the row.
+    ///
+    /// Use `Option<T>` for nullable columns to get `None` for NULL.
+    ///
+    /// # Failure
+    ///
+    /// - [SpringError::Sql](crate::api::error::SpringError::Sql) when:
+    ///   - Column index out of range
+    ///   - Column value cannot be converted into `T`
+    /// - [SpringError::Null](crate::api::error::SpringError::Null) when:
+    ///   - Column value is NULL and `T` is not `Option`
+    pub fn get<T>(&self, i_col: usize) -> Result<T>
+    where
+        T: SpringColumnValue,
     {
         let sql_value = self.0.get_by_index(i_col)?;
+        T::try_from_sql_value(sql_value, i_col)
+    }
 
-        match sql_value {
-            SqlValue::Null => Err(SpringError::Null { i_col }),
-            SqlValue::NotNull(nn_sql_value) => nn_sql_value.unpack(),
-        }
+    /// Get a column value by its name from the row.
+    ///
+    /// Use `Option<T>` for nullable columns to get `None` for NULL.
+    ///
+    /// # Failure
+    ///
+    /// - [SpringError::Sql](crate::api::error::SpringError::Sql) when:
+    ///   - Column named `column_name` is not in the row
+    ///   - Column value cannot be converted into `T`
+    /// - [SpringError::Null](crate::api::error::SpringError::Null) when:
+    ///   - Column value is NULL and `T` is not `Option`
+    pub fn get_by_name<T>(&self, column_name: &str) -> Result<T>
+    where
+        T: SpringColumnValue,
+    {
+        let i_col = self
+            .0
+            .column_names()
+            .position(|col| col.as_ref() == column_name)
+            .ok_or_else(|| {
+                SpringError::Sql(anyhow!(r#"column "{}" not found in the row"#, column_name))
+            })?;
+        self.get(i_col)
+    }
+
+    /// Column names in the order of columns in the sink stream.
+    pub fn column_names(&self) -> Vec<&str> {
+        self.0.column_names().map(|col| col.as_ref()).collect()
+    }
+
+    /// Number of columns.
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    /// `true` if the row has no column.
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Convert the row into a JSON object keyed by column names.
+    ///
+    /// NULL is converted into `null`, and TIMESTAMP into a string like `"2022-01-01 13:00:00.000000000"`.
+    pub fn to_json(&self) -> serde_json::Value {
+        JsonObject::from(self.0.clone()).into()
+    }
+
+    /// Deserialize the row into `T` through [to_json()](Self::to_json), typically into a struct with `#[derive(Deserialize)]` whose field names match column names.
+    ///
+    /// # Failure
+    ///
+    /// - [SpringError::InvalidFormat](crate::api::error::SpringError::InvalidFormat) when:
+    ///   - the row does not match `T` (missing columns, type mismatch, ...)
+    pub fn deserialize<T>(&self) -> Result<T>
+    where
+        T: DeserializeOwned,
+    {
+        let json = self.to_json();
+        T::deserialize(&json)
+            .with_context(|| "failed to deserialize a sink row")
+            .map_err(|e| SpringError::InvalidFormat {
+                s: json.to_string(),
+                source: e,
+            })
+    }
+}
+
+impl From<SpringSinkRow> for serde_json::Value {
+    fn from(sink_row: SpringSinkRow) -> Self {
+        JsonObject::from(sink_row.0).into()
     }
 }
 
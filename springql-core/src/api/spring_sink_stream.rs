@@ -0,0 +1,55 @@
+// This file is part of https://github.com/SpringQL/SpringQL which is licensed under MIT OR Apache-2.0. See file LICENSE-MIT or LICENSE-APACHE for full license details.
+
+use std::{
+    future::Future,
+    pin::Pin,
+    sync::Arc,
+    task::{Context, Poll},
+};
+
+use futures_core::Stream;
+
+use crate::{api::SpringSinkRow, stream_engine::InMemoryQueue};
+
+/// Rows popped from an in-memory sink queue, created by [SpringPipeline::sink_stream()](crate::api::SpringPipeline::sink_stream).
+///
+/// Waiting for a row does not block the executor thread.
+/// The stream ends when the queue is empty and ended or removed from the pipeline (e.g. its sink writer is dropped).
+#[derive(Debug)]
+pub struct SpringSinkStream(Arc<InMemoryQueue>);
+
+impl SpringSinkStream {
+    pub(crate) fn new(queue: Arc<InMemoryQueue>) -> Self {
+        Self(queue)
+    }
+}
+
+impl Stream for SpringSinkStream {
+    type Item = SpringSinkRow;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        self.0
+            .poll_pop(cx)
+            .map(|opt_row| opt_row.map(SpringSinkRow::new))
+    }
+}
+
+/// Resolves to the next row in an in-memory queue, or `None` when the queue is empty and ended or removed.
+#[derive(Debug)]
+pub(crate) struct PopFuture(Arc<InMemoryQueue>);
+
+impl PopFuture {
+    pub(crate) fn new(queue: Arc<InMemoryQueue>) -> Self {
+        Self(queue)
+    }
+}
+
+impl Future for PopFuture {
+    type Output = Option<SpringSinkRow>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        self.0
+            .poll_pop(cx)
+            .map(|opt_row| opt_row.map(SpringSinkRow::new))
+    }
+}
@@ -0,0 +1,82 @@
+// This file is part of https://github.com/SpringQL/SpringQL which is licensed under MIT OR Apache-2.0. See file LICENSE-MIT or LICENSE-APACHE for full license details.
+
+use std::{
+    pin::Pin,
+    sync::Arc,
+    task::{Context, Poll},
+};
+
+use anyhow::anyhow;
+use futures_sink::Sink;
+
+use crate::{
+    api::{error::SpringError, SpringSourceRow},
+    stream_engine::{InMemoryQueue, ShutdownState},
+};
+
+/// Pushes rows into an in-memory source queue, created by [SpringPipeline::source_sink()](crate::api::SpringPipeline::source_sink).
+///
+/// The queue is unbounded, so sending a row never waits.
+/// Sending fails after the pipeline is shut down, as [SpringPipeline::push()](crate::api::SpringPipeline::push) does.
+/// Closing the sink tells end-of-stream like [SpringPipeline::end_of_stream()](crate::api::SpringPipeline::end_of_stream).
+#[derive(Debug)]
+pub struct SpringSourceSink {
+    queue_name: String,
+    queue: Arc<InMemoryQueue>,
+    shutdown_state: ShutdownState,
+}
+
+impl SpringSourceSink {
+    pub(crate) fn new(
+        queue_name: String,
+        queue: Arc<InMemoryQueue>,
+        shutdown_state: ShutdownState,
+    ) -> Self {
+        Self {
+            queue_name,
+            queue,
+            shutdown_state,
+        }
+    }
+}
+
+impl Sink<SpringSourceRow> for SpringSourceSink {
+    type Error = SpringError;
+
+    /// # Failure
+    ///
+    /// - [SpringError::Unavailable](crate::api::error::SpringError::Unavailable) when:
+    ///   - the pipeline is already shut down.
+    fn poll_ready(self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        Poll::Ready(self.shutdown_state.ensure_running())
+    }
+
+    /// # Failure
+    ///
+    /// - [SpringError::Unavailable](crate::api::error::SpringError::Unavailable) when:
+    ///   - the pipeline is already shut down.
+    ///   - the queue already reached end-of-stream.
+    /// - [SpringError::InvalidFormat](crate::api::error::SpringError::InvalidFormat) when:
+    ///   - the row cannot be converted into the internal form.
+    fn start_send(self: Pin<&mut Self>, item: SpringSourceRow) -> Result<(), Self::Error> {
+        self.shutdown_state.ensure_running()?;
+        if self.queue.is_ended() {
+            Err(SpringError::Unavailable {
+                resource: self.queue_name.clone(),
+                source: anyhow!("queue already reached end-of-stream"),
+            })
+        } else {
+            self.queue.push(item.into_schemaless_row()?);
+            Ok(())
+        }
+    }
+
+    fn poll_flush(self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn poll_close(self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.queue.end();
+        Poll::Ready(Ok(()))
+    }
+}
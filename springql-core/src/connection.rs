@@ -1,14 +1,35 @@
 // This file is part of https://github.com/SpringQL/SpringQL which is licensed under MIT OR Apache-2.0. See file LICENSE-MIT or LICENSE-APACHE for full license details.
 
-use std::{sync::Once, thread, time::Duration};
+use std::{
+    sync::{Arc, Once},
+    time::Duration,
+};
+
+use anyhow::anyhow;
 
 use crate::{
-    api::{error::Result, SpringConfig},
-    pipeline::QueueName,
+    api::{
+        error::{Result, SpringError},
+        SpringConfig, SpringShutdownMode,
+    },
+    pipeline::{
+        CallbackName, PumpModel, QueueName, SinkWriterModel, SourceReaderModel, StreamModel,
+    },
     sql_processor::SqlProcessor,
-    stream_engine::{autonomous_executor::SchemalessRow, command::Command, EngineMutex},
+    stream_engine::{
+        autonomous_executor::SchemalessRow, command::Command, EngineMutex, InMemoryQueue,
+        SinkCallback, SinkCallbackErrorHandler,
+    },
 };
 
+/// Error for pops on an empty queue which no more row is pushed into.
+pub(crate) fn queue_closed(queue: &str) -> SpringError {
+    SpringError::Unavailable {
+        resource: queue.to_string(),
+        source: anyhow!("queue is empty and ended or removed"),
+    }
+}
+
 fn setup_logger() {
     static INIT: Once = Once::new();
 
@@ -36,7 +57,7 @@ impl Connection {
         setup_logger();
 
         let engine = EngineMutex::new(config);
-        let sql_processor = SqlProcessor::default();
+        let sql_processor = SqlProcessor::new(config.sql);
 
         Self {
             engine,
@@ -54,22 +75,46 @@ impl Connection {
         }
     }
 
+    /// Blocks without holding the engine lock.
+    ///
+    /// # Failure
+    ///
+    /// - `SpringError::Unavailable` when:
+    ///   - queue named `queue` does not exist.
+    ///   - the queue is empty and closed (ended or removed) before a row is pushed.
     pub fn pop(&self, queue: &str) -> Result<SchemalessRow> {
-        const SLEEP_MSECS: u64 = 10;
-
-        let mut engine = self.engine.get()?;
+        let q = self.in_memory_queue(queue)?;
+        q.pop().ok_or_else(|| queue_closed(queue))
+    }
 
-        loop {
-            if let Some(sink_row) =
-                engine.pop_in_memory_queue_non_blocking(QueueName::new(queue.to_string()))?
-            {
-                return Ok(sink_row);
-            } else {
-                thread::sleep(Duration::from_millis(SLEEP_MSECS));
-            }
+    /// Blocks without holding the engine lock.
+    ///
+    /// # Failure
+    ///
+    /// - `SpringError::Unavailable` when:
+    ///   - queue named `queue` does not exist.
+    ///   - the queue is empty and closed (ended or removed) before a row is pushed.
+    pub fn pop_timeout(&self, queue: &str, timeout: Duration) -> Result<Option<SchemalessRow>> {
+        let q = self.in_memory_queue(queue)?;
+        match q.pop_timeout(timeout) {
+            None if q.is_closed() => Err(queue_closed(queue)),
+            opt_row => Ok(opt_row),
         }
     }
 
+    /// Get an in-memory queue to access it without holding the engine lock.
+    pub fn in_memory_queue(&self, queue: &str) -> Result<Arc<InMemoryQueue>> {
+        let engine = self.engine.get()?;
+        engine.in_memory_queue(QueueName::new(queue.to_string()))
+    }
+
+    /// Get the shutdown state of the engine to check it without holding the engine lock.
+    #[cfg(feature = "async")]
+    pub fn shutdown_state(&self) -> Result<crate::stream_engine::ShutdownState> {
+        let engine = self.engine.get()?;
+        Ok(engine.shutdown_state())
+    }
+
     pub fn pop_non_blocking(&self, queue: &str) -> Result<Option<SchemalessRow>> {
         let mut engine = self.engine.get()?;
         let sink_row =
@@ -81,4 +126,55 @@ impl Connection {
         let mut engine = self.engine.get()?;
         engine.push_in_memory_queue(QueueName::new(queue.to_string()), row)
     }
+
+    pub fn register_sink_callback(
+        &self,
+        name: &str,
+        callback: SinkCallback,
+        on_error: Option<SinkCallbackErrorHandler>,
+    ) -> Result<()> {
+        let mut engine = self.engine.get()?;
+        engine.register_sink_callback(CallbackName::new(name.to_string()), callback, on_error);
+        Ok(())
+    }
+
+    pub fn end_of_stream(&self, queue: &str) -> Result<()> {
+        let mut engine = self.engine.get()?;
+        engine.end_in_memory_queue(QueueName::new(queue.to_string()))
+    }
+
+    pub fn shutdown(&self, mode: SpringShutdownMode, timeout: Duration) -> Result<()> {
+        let mut engine = self.engine.get()?;
+        engine.shutdown(mode, timeout)
+    }
+
+    pub fn streams(&self) -> Result<Vec<Arc<StreamModel>>> {
+        let engine = self.engine.get()?;
+        Ok(engine.current_pipeline().all_streams())
+    }
+
+    pub fn pumps(&self) -> Result<Vec<Arc<PumpModel>>> {
+        let engine = self.engine.get()?;
+        Ok(engine.current_pipeline().all_pumps())
+    }
+
+    pub fn source_readers(&self) -> Result<Vec<SourceReaderModel>> {
+        let engine = self.engine.get()?;
+        Ok(engine
+            .current_pipeline()
+            .all_sources()
+            .into_iter()
+            .cloned()
+            .collect())
+    }
+
+    pub fn sink_writers(&self) -> Result<Vec<SinkWriterModel>> {
+        let engine = self.engine.get()?;
+        Ok(engine
+            .current_pipeline()
+            .all_sinks()
+            .into_iter()
+            .cloned()
+            .collect())
+    }
 }
@@ -8,11 +8,14 @@ use std::collections::HashMap;
 use anyhow::anyhow;
 
 use crate::{
-    api::error::{Result, SpringError},
+    api::{
+        error::{Result, SpringError},
+        SpringSqlConfig,
+    },
     expression::{AggrExpr, ValueExpr},
     pipeline::{AggrAlias, ValueAlias},
     sql_processor::SelectFieldSyntax,
-    stream_engine::{SqlValue, Tuple},
+    stream_engine::{autonomous_executor::AggregatedAndGroupingValues, SqlValue, Tuple},
 };
 
 /// ExprResolver is to:
@@ -30,52 +33,54 @@ pub struct ExprResolver {
     aggr_expressions: HashMap<AggrExprLabel, AggrExpr>,
     aggr_aliased_labels: HashMap<AggrAlias, AggrExprLabel>,
     aggr_expression_results: HashMap<AggrExprLabel, SqlValue>,
+
+    config: SpringSqlConfig,
 }
 
 impl ExprResolver {
     /// # Returns
     ///
     /// `(instance, value/aggr expr labels in select_list)
-    pub fn new(select_list: Vec<SelectFieldSyntax>) -> (Self, Vec<ExprLabel>) {
-        let mut label_gen = ExprLabelGenerator::default();
-        let mut value_expressions = HashMap::new();
-        let mut value_aliased_labels = HashMap::new();
-        let mut aggr_expressions = HashMap::new();
-        let mut aggr_aliased_labels = HashMap::new();
+    pub fn new(
+        select_list: Vec<SelectFieldSyntax>,
+        config: SpringSqlConfig,
+    ) -> (Self, Vec<ExprLabel>) {
+        let mut resolver = Self {
+            label_gen: ExprLabelGenerator::default(),
+            value_expressions: HashMap::new(),
+            value_aliased_labels: HashMap::new(),
+            aggr_expressions: HashMap::new(),
+            aggr_aliased_labels: HashMap::new(),
+            aggr_expression_results: HashMap::new(),
+            config,
+        };
 
         let expr_labels = select_list
             .into_iter()
             .map(|select_field| match select_field {
                 SelectFieldSyntax::ValueExpr { value_expr, alias } => {
-                    let label = label_gen.next_value();
-                    value_expressions.insert(label, value_expr);
+                    // aggregate expressions inside a value expression (e.g. `MAX(c) - MIN(c)`)
+                    for aggr_expr in value_expr.aggr_exprs() {
+                        resolver.register_aggr_expr(aggr_expr.clone());
+                    }
+
+                    let label = resolver.register_value_expr(value_expr);
                     if let Some(alias) = alias {
-                        value_aliased_labels.insert(alias, label);
+                        resolver.value_aliased_labels.insert(alias, label);
                     }
                     ExprLabel::Value(label)
                 }
                 SelectFieldSyntax::AggrExpr { aggr_expr, alias } => {
-                    let label = label_gen.next_aggr();
-                    aggr_expressions.insert(label, aggr_expr);
+                    let label = resolver.register_aggr_expr(aggr_expr);
                     if let Some(alias) = alias {
-                        aggr_aliased_labels.insert(alias, label);
+                        resolver.aggr_aliased_labels.insert(alias, label);
                     }
                     ExprLabel::Aggr(label)
                 }
             })
             .collect();
 
-        (
-            Self {
-                label_gen,
-                value_expressions,
-                value_aliased_labels,
-                aggr_expressions,
-                aggr_aliased_labels,
-                aggr_expression_results: HashMap::new(),
-            },
-            expr_labels,
-        )
+        (resolver, expr_labels)
     }
 
     /// # Failures
@@ -128,14 +133,31 @@ impl ExprResolver {
         label
     }
 
-    /// TODO use in HAVING clause
+    /// Register aggregate expression.
+    /// The same aggregate expression appearing multiple times (e.g. `MAX(c), MAX(c) - MIN(c)`) shares a label.
     ///
-    /// Register aggregate expression which is not in select_list
-    #[allow(dead_code)]
+    /// TODO use in HAVING clause
     pub fn register_aggr_expr(&mut self, aggr_expr: AggrExpr) -> AggrExprLabel {
-        let label = self.label_gen.next_aggr();
-        self.aggr_expressions.insert(label, aggr_expr);
-        label
+        if let Some(label) = self.find_aggr_expr(&aggr_expr) {
+            label
+        } else {
+            let label = self.label_gen.next_aggr();
+            self.aggr_expressions.insert(label, aggr_expr);
+            label
+        }
+    }
+
+    /// All the registered aggregate expressions' labels, in registration order.
+    pub fn aggr_labels(&self) -> Vec<AggrExprLabel> {
+        let mut labels = self.aggr_expressions.keys().copied().collect::<Vec<_>>();
+        labels.sort();
+        labels
+    }
+
+    fn find_aggr_expr(&self, aggr_expr: &AggrExpr) -> Option<AggrExprLabel> {
+        self.aggr_expressions
+            .iter()
+            .find_map(|(label, registered)| (registered == aggr_expr).then(|| *label))
     }
 
     /// label -> (internal) value expression + tuple (for ColumnReference) -> SqlValue.
@@ -157,7 +179,39 @@ impl ExprResolver {
             .unwrap_or_else(|| panic!("label {:?} not found", label));
 
         let value_expr_ph2 = value_expr.resolve_colref(tuple)?;
-        value_expr_ph2.eval()
+        value_expr_ph2.eval(&self.config)
+    }
+
+    /// label -> (internal) value expression + aggregated values (for aggregate expressions inside) -> SqlValue.
+    ///
+    /// # Panics
+    ///
+    /// -  `label` is not found
+    ///
+    /// # Failures
+    ///
+    /// - `SpringError::Sql` when:
+    ///   - expression has a column reference outside of aggregate expressions.
+    ///   - somehow failed to eval expression.
+    pub fn eval_value_expr_with_aggr(
+        &self,
+        label: ValueExprLabel,
+        aggregated_and_grouping_values: &AggregatedAndGroupingValues,
+    ) -> Result<SqlValue> {
+        let value_expr = self
+            .value_expressions
+            .get(&label)
+            .cloned()
+            .unwrap_or_else(|| panic!("label {:?} not found", label));
+
+        let value_expr_ph2 = value_expr.resolve_aggr_expr(&|aggr_expr| {
+            let aggr_label = self
+                .find_aggr_expr(aggr_expr)
+                .unwrap_or_else(|| panic!("aggregate expression {:?} not found", aggr_expr));
+            let value = aggregated_and_grouping_values.get_aggregated_value(&aggr_label)?;
+            Ok(value.clone())
+        })?;
+        value_expr_ph2.eval(&self.config)
     }
 
     /// label -> (internal) value expression inside aggr expr + tuple (for ColumnReference) -> SqlValue.
@@ -177,7 +231,31 @@ impl ExprResolver {
         let aggr_expr = self.resolve_aggr_expr(label);
         let value_expr = aggr_expr.aggregated;
         let value_expr_ph2 = value_expr.resolve_colref(tuple)?;
-        value_expr_ph2.eval()
+        value_expr_ph2.eval(&self.config)
+    }
+
+    /// label -> key expression inside aggr expr + tuple (for ColumnReference) -> SqlValue.
+    ///
+    /// _key_ means: ARG_MAX(inner_value_expr, key_value_expr). NULL if the aggregate function has no key.
+    ///
+    /// # Panics
+    ///
+    /// -  `label` is not found
+    ///
+    /// # Failures
+    ///
+    /// - `SpringError::Sql` when:
+    ///   - column reference in expression is not found in `tuple`.
+    ///   - somehow failed to eval expression.
+    pub fn eval_aggr_expr_key(&self, label: AggrExprLabel, tuple: &Tuple) -> Result<SqlValue> {
+        let aggr_expr = self.resolve_aggr_expr(label);
+        match aggr_expr.key {
+            Some(value_expr) => {
+                let value_expr_ph2 = value_expr.resolve_colref(tuple)?;
+                value_expr_ph2.eval(&self.config)
+            }
+            None => Ok(SqlValue::Null),
+        }
     }
 }
 
@@ -185,6 +263,7 @@ impl ExprResolver {
 mod tests {
     use crate::{
         expression::ValueExpr,
+        pipeline::AggregateFunctionParameter,
         stream_engine::{time::SpringTimestamp, RowTime},
     };
 
@@ -209,7 +288,8 @@ mod tests {
             },
         ];
 
-        let (mut resolver, labels_select_list) = ExprResolver::new(select_list);
+        let (mut resolver, labels_select_list) =
+            ExprResolver::new(select_list, SpringSqlConfig::fx_default());
 
         if let &[ExprLabel::Value(value_label0), ExprLabel::Value(value_label1)] =
             &labels_select_list[..]
@@ -258,4 +338,65 @@ mod tests {
             unreachable!()
         }
     }
+
+    #[test]
+    fn test_expr_resolver_with_aggr() {
+        let aggr_expr = |func| AggrExpr {
+            func,
+            aggregated: ValueExpr::factory_colref("s", "c"),
+            key: None,
+        };
+        let max_c = aggr_expr(AggregateFunctionParameter::Max);
+        let min_c = aggr_expr(AggregateFunctionParameter::Min);
+
+        // SELECT MAX(s.c), MAX(s.c) + MIN(s.c)
+        let select_list = vec![
+            SelectFieldSyntax::AggrExpr {
+                aggr_expr: max_c.clone(),
+                alias: None,
+            },
+            SelectFieldSyntax::ValueExpr {
+                value_expr: ValueExpr::factory_add(
+                    ValueExpr::AggrExpr(Box::new(max_c)),
+                    ValueExpr::AggrExpr(Box::new(min_c)),
+                ),
+                alias: None,
+            },
+        ];
+
+        let (resolver, labels_select_list) =
+            ExprResolver::new(select_list, SpringSqlConfig::fx_default());
+
+        let aggr_labels = resolver.aggr_labels();
+        assert_eq!(aggr_labels.len(), 2, "MAX(s.c) is shared");
+
+        if let &[ExprLabel::Aggr(max_label), ExprLabel::Value(value_label)] =
+            &labels_select_list[..]
+        {
+            assert_eq!(aggr_labels[0], max_label);
+            let min_label = aggr_labels[1];
+
+            let aggregated = AggregatedAndGroupingValues::new(
+                vec![
+                    (max_label, SqlValue::factory_integer(10)),
+                    (min_label, SqlValue::factory_integer(3)),
+                ],
+                vec![],
+            );
+            assert_eq!(
+                resolver
+                    .eval_value_expr_with_aggr(value_label, &aggregated)
+                    .unwrap(),
+                SqlValue::factory_integer(13)
+            );
+
+            let empty_tuple = Tuple::new(RowTime::EventTime(SpringTimestamp::fx_ts1()), vec![]);
+            assert!(
+                resolver.eval_value_expr(value_label, &empty_tuple).is_err(),
+                "aggregate expression cannot be evaluated with a tuple"
+            );
+        } else {
+            unreachable!()
+        }
+    }
 }
@@ -23,7 +23,7 @@ impl ExprLabelGenerator {
 #[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
 pub struct ValueExprLabel(u16);
 
-#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
+#[derive(Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug)]
 pub struct AggrExprLabel(u16);
 
 /// Either ValueExprLabel or AggrExprLabel.
@@ -13,7 +13,10 @@ pub use operator::{BinaryOperator, UnaryOperator};
 use anyhow::anyhow;
 
 use crate::{
-    api::error::{Result, SpringError},
+    api::{
+        error::{Result, SpringError},
+        DivisionByZero, SpringSqlConfig,
+    },
     pipeline::{AggregateFunctionParameter, ColumnReference},
     stream_engine::{
         time::{SpringDuration, SpringEventDuration},
@@ -35,35 +38,152 @@ pub enum ValueExpr {
     FunctionCall(FunctionCall<Self>),
 
     ColumnReference(ColumnReference),
+
+    /// Aggregate expression in a value expression (e.g. `MAX(c) - MIN(c)`).
+    /// Resolved with aggregated values after a window closes.
+    AggrExpr(Box<AggrExpr>),
 }
 impl ValueExprType for ValueExpr {}
 
 impl ValueExpr {
+    /// # Failures
+    ///
+    /// - `SpringError::Sql` when:
+    ///   - column reference is not found in `tuple`.
+    ///   - expression contains an aggregate expression.
     pub fn resolve_colref(self, tuple: &Tuple) -> Result<ValueExprPh2> {
+        self.resolve(&|colref| tuple.get_value(colref), &|aggr_expr| {
+            Err(SpringError::Sql(anyhow!(
+                "aggregate expression {:?} is not allowed here",
+                aggr_expr
+            )))
+        })
+    }
+
+    /// Resolves aggregate expressions in this expression into their aggregated values.
+    ///
+    /// # Failures
+    ///
+    /// - `SpringError::Sql` when:
+    ///   - expression contains a column reference outside of aggregate expressions.
+    ///   - `aggr_value` fails.
+    pub fn resolve_aggr_expr(
+        self,
+        aggr_value: &dyn Fn(&AggrExpr) -> Result<SqlValue>,
+    ) -> Result<ValueExprPh2> {
+        self.resolve(
+            &|colref| {
+                Err(SpringError::Sql(anyhow!(
+                    "column reference {:?} must be in GROUP BY clause or inside an aggregate expression",
+                    colref
+                )))
+            },
+            aggr_value,
+        )
+    }
+
+    /// Aggregate expressions in this expression (in left-to-right order).
+    pub fn aggr_exprs(&self) -> Vec<&AggrExpr> {
+        match self {
+            Self::Constant(_) | Self::ColumnReference(_) => vec![],
+            Self::AggrExpr(aggr_expr) => vec![aggr_expr.as_ref()],
+            _ => self
+                .children()
+                .into_iter()
+                .flat_map(|child| child.aggr_exprs())
+                .collect(),
+        }
+    }
+
+    /// Column references in this expression (in left-to-right order). References inside aggregate expressions are not included.
+    pub fn column_references(&self) -> Vec<&ColumnReference> {
+        match self {
+            Self::ColumnReference(colref) => vec![colref],
+            _ => self
+                .children()
+                .into_iter()
+                .flat_map(|child| child.column_references())
+                .collect(),
+        }
+    }
+
+    fn children(&self) -> Vec<&Self> {
+        match self {
+            Self::Constant(_) | Self::ColumnReference(_) | Self::AggrExpr(_) => vec![],
+            Self::UnaryOperator(_, expr) => vec![expr],
+            Self::FunctionCall(function_call) => match function_call {
+                FunctionCall::DurationMillis { duration_millis } => vec![duration_millis],
+                FunctionCall::DurationSecs { duration_secs } => vec![duration_secs],
+                FunctionCall::FloorTime { target, resolution } => vec![target, resolution],
+            },
+            Self::BinaryExpr(binary_expr) => match binary_expr {
+                BinaryExpr::LogicalFunctionVariant(
+                    LogicalFunction::AndVariant { left, right }
+                    | LogicalFunction::OrVariant { left, right },
+                )
+                | BinaryExpr::ComparisonFunctionVariant(
+                    ComparisonFunction::EqualVariant { left, right }
+                    | ComparisonFunction::NotEqualVariant { left, right }
+                    | ComparisonFunction::LessThanVariant { left, right }
+                    | ComparisonFunction::LessThanOrEqualVariant { left, right }
+                    | ComparisonFunction::GreaterThanVariant { left, right }
+                    | ComparisonFunction::GreaterThanOrEqualVariant { left, right },
+                )
+                | BinaryExpr::NumericalFunctionVariant(
+                    NumericalFunction::AddVariant { left, right }
+                    | NumericalFunction::SubVariant { left, right }
+                    | NumericalFunction::MulVariant { left, right }
+                    | NumericalFunction::DivVariant { left, right }
+                    | NumericalFunction::RemVariant { left, right },
+                ) => vec![left, right],
+                BinaryExpr::ComparisonFunctionVariant(ComparisonFunction::BetweenVariant {
+                    target,
+                    low,
+                    high,
+                }) => vec![target, low, high],
+                BinaryExpr::ComparisonFunctionVariant(ComparisonFunction::InVariant {
+                    target,
+                    list,
+                }) => std::iter::once(target.as_ref()).chain(list).collect(),
+            },
+        }
+    }
+
+    /// Resolves leaves (column references and aggregate expressions) into values.
+    fn resolve(
+        self,
+        colref_value: &dyn Fn(&ColumnReference) -> Result<SqlValue>,
+        aggr_value: &dyn Fn(&AggrExpr) -> Result<SqlValue>,
+    ) -> Result<ValueExprPh2> {
         match self {
             Self::Constant(value) => Ok(ValueExprPh2::Constant(value)),
 
             Self::ColumnReference(colref) => {
-                let value = tuple.get_value(&colref)?;
+                let value = colref_value(&colref)?;
+                Ok(ValueExprPh2::Constant(value))
+            }
+
+            Self::AggrExpr(aggr_expr) => {
+                let value = aggr_value(&aggr_expr)?;
                 Ok(ValueExprPh2::Constant(value))
             }
 
             Self::FunctionCall(function_call) => match function_call {
                 FunctionCall::DurationMillis { duration_millis } => {
-                    let duration_millis_ph2 = duration_millis.resolve_colref(tuple)?;
+                    let duration_millis_ph2 = duration_millis.resolve(colref_value, aggr_value)?;
                     Ok(ValueExprPh2::FunctionCall(FunctionCall::DurationMillis {
                         duration_millis: Box::new(duration_millis_ph2),
                     }))
                 }
                 FunctionCall::DurationSecs { duration_secs } => {
-                    let duration_secs_ph2 = duration_secs.resolve_colref(tuple)?;
+                    let duration_secs_ph2 = duration_secs.resolve(colref_value, aggr_value)?;
                     Ok(ValueExprPh2::FunctionCall(FunctionCall::DurationSecs {
                         duration_secs: Box::new(duration_secs_ph2),
                     }))
                 }
                 FunctionCall::FloorTime { target, resolution } => {
-                    let target_ph2 = target.resolve_colref(tuple)?;
-                    let resolution_ph2 = resolution.resolve_colref(tuple)?;
+                    let target_ph2 = target.resolve(colref_value, aggr_value)?;
+                    let resolution_ph2 = resolution.resolve(colref_value, aggr_value)?;
                     Ok(ValueExprPh2::FunctionCall(FunctionCall::FloorTime {
                         target: Box::new(target_ph2),
                         resolution: Box::new(resolution_ph2),
@@ -71,14 +191,14 @@ impl ValueExpr {
                 }
             },
             Self::UnaryOperator(op, expr_ph1) => {
-                let expr_ph2 = expr_ph1.resolve_colref(tuple)?;
+                let expr_ph2 = expr_ph1.resolve(colref_value, aggr_value)?;
                 Ok(ValueExprPh2::UnaryOperator(op, Box::new(expr_ph2)))
             }
             Self::BinaryExpr(bool_expr) => match bool_expr {
                 BinaryExpr::LogicalFunctionVariant(logical_function) => match logical_function {
                     LogicalFunction::AndVariant { left, right } => {
-                        let left_ph2 = left.resolve_colref(tuple)?;
-                        let right_ph2 = right.resolve_colref(tuple)?;
+                        let left_ph2 = left.resolve(colref_value, aggr_value)?;
+                        let right_ph2 = right.resolve(colref_value, aggr_value)?;
                         Ok(ValueExprPh2::BinaryExpr(
                             BinaryExpr::LogicalFunctionVariant(LogicalFunction::AndVariant {
                                 left: Box::new(left_ph2),
@@ -86,31 +206,47 @@ impl ValueExpr {
                             }),
                         ))
                     }
+                    LogicalFunction::OrVariant { left, right } => {
+                        let left_ph2 = left.resolve(colref_value, aggr_value)?;
+                        let right_ph2 = right.resolve(colref_value, aggr_value)?;
+                        Ok(ValueExprPh2::BinaryExpr(
+                            BinaryExpr::LogicalFunctionVariant(LogicalFunction::OrVariant {
+                                left: Box::new(left_ph2),
+                                right: Box::new(right_ph2),
+                            }),
+                        ))
+                    }
                 },
                 BinaryExpr::ComparisonFunctionVariant(comparison_function) => {
-                    match comparison_function {
-                        ComparisonFunction::EqualVariant { left, right } => {
-                            let left_ph2 = left.resolve_colref(tuple)?;
-                            let right_ph2 = right.resolve_colref(tuple)?;
+                    let comparison_function_ph2 = Self::resolve_comparison_function(
+                        comparison_function,
+                        colref_value,
+                        aggr_value,
+                    )?;
+                    Ok(ValueExprPh2::BinaryExpr(
+                        BinaryExpr::ComparisonFunctionVariant(comparison_function_ph2),
+                    ))
+                }
+                BinaryExpr::NumericalFunctionVariant(numerical_function) => {
+                    match numerical_function {
+                        NumericalFunction::AddVariant { left, right } => {
+                            let left_ph2 = left.resolve(colref_value, aggr_value)?;
+                            let right_ph2 = right.resolve(colref_value, aggr_value)?;
                             Ok(ValueExprPh2::BinaryExpr(
-                                BinaryExpr::ComparisonFunctionVariant(
-                                    ComparisonFunction::EqualVariant {
+                                BinaryExpr::NumericalFunctionVariant(
+                                    NumericalFunction::AddVariant {
                                         left: Box::new(left_ph2),
                                         right: Box::new(right_ph2),
                                     },
                                 ),
                             ))
                         }
-                    }
-                }
-                BinaryExpr::NumericalFunctionVariant(numerical_function) => {
-                    match numerical_function {
-                        NumericalFunction::AddVariant { left, right } => {
-                            let left_ph2 = left.resolve_colref(tuple)?;
-                            let right_ph2 = right.resolve_colref(tuple)?;
+                        NumericalFunction::SubVariant { left, right } => {
+                            let left_ph2 = left.resolve(colref_value, aggr_value)?;
+                            let right_ph2 = right.resolve(colref_value, aggr_value)?;
                             Ok(ValueExprPh2::BinaryExpr(
                                 BinaryExpr::NumericalFunctionVariant(
-                                    NumericalFunction::AddVariant {
+                                    NumericalFunction::SubVariant {
                                         left: Box::new(left_ph2),
                                         right: Box::new(right_ph2),
                                     },
@@ -118,8 +254,8 @@ impl ValueExpr {
                             ))
                         }
                         NumericalFunction::MulVariant { left, right } => {
-                            let left_ph2 = left.resolve_colref(tuple)?;
-                            let right_ph2 = right.resolve_colref(tuple)?;
+                            let left_ph2 = left.resolve(colref_value, aggr_value)?;
+                            let right_ph2 = right.resolve(colref_value, aggr_value)?;
                             Ok(ValueExprPh2::BinaryExpr(
                                 BinaryExpr::NumericalFunctionVariant(
                                     NumericalFunction::MulVariant {
@@ -129,6 +265,30 @@ impl ValueExpr {
                                 ),
                             ))
                         }
+                        NumericalFunction::DivVariant { left, right } => {
+                            let left_ph2 = left.resolve(colref_value, aggr_value)?;
+                            let right_ph2 = right.resolve(colref_value, aggr_value)?;
+                            Ok(ValueExprPh2::BinaryExpr(
+                                BinaryExpr::NumericalFunctionVariant(
+                                    NumericalFunction::DivVariant {
+                                        left: Box::new(left_ph2),
+                                        right: Box::new(right_ph2),
+                                    },
+                                ),
+                            ))
+                        }
+                        NumericalFunction::RemVariant { left, right } => {
+                            let left_ph2 = left.resolve(colref_value, aggr_value)?;
+                            let right_ph2 = right.resolve(colref_value, aggr_value)?;
+                            Ok(ValueExprPh2::BinaryExpr(
+                                BinaryExpr::NumericalFunctionVariant(
+                                    NumericalFunction::RemVariant {
+                                        left: Box::new(left_ph2),
+                                        right: Box::new(right_ph2),
+                                    },
+                                ),
+                            ))
+                        }
                     }
                 }
             },
@@ -136,6 +296,74 @@ impl ValueExpr {
     }
 }
 
+impl ValueExpr {
+    fn resolve_comparison_function(
+        comparison_function: ComparisonFunction<Self>,
+        colref_value: &dyn Fn(&ColumnReference) -> Result<SqlValue>,
+        aggr_value: &dyn Fn(&AggrExpr) -> Result<SqlValue>,
+    ) -> Result<ComparisonFunction<ValueExprPh2>> {
+        let resolve = |expr: Box<Self>| -> Result<Box<ValueExprPh2>> {
+            expr.resolve(colref_value, aggr_value).map(Box::new)
+        };
+
+        match comparison_function {
+            ComparisonFunction::EqualVariant { left, right } => {
+                Ok(ComparisonFunction::EqualVariant {
+                    left: resolve(left)?,
+                    right: resolve(right)?,
+                })
+            }
+            ComparisonFunction::NotEqualVariant { left, right } => {
+                Ok(ComparisonFunction::NotEqualVariant {
+                    left: resolve(left)?,
+                    right: resolve(right)?,
+                })
+            }
+            ComparisonFunction::LessThanVariant { left, right } => {
+                Ok(ComparisonFunction::LessThanVariant {
+                    left: resolve(left)?,
+                    right: resolve(right)?,
+                })
+            }
+            ComparisonFunction::LessThanOrEqualVariant { left, right } => {
+                Ok(ComparisonFunction::LessThanOrEqualVariant {
+                    left: resolve(left)?,
+                    right: resolve(right)?,
+                })
+            }
+            ComparisonFunction::GreaterThanVariant { left, right } => {
+                Ok(ComparisonFunction::GreaterThanVariant {
+                    left: resolve(left)?,
+                    right: resolve(right)?,
+                })
+            }
+            ComparisonFunction::GreaterThanOrEqualVariant { left, right } => {
+                Ok(ComparisonFunction::GreaterThanOrEqualVariant {
+                    left: resolve(left)?,
+                    right: resolve(right)?,
+                })
+            }
+            ComparisonFunction::BetweenVariant { target, low, high } => {
+                Ok(ComparisonFunction::BetweenVariant {
+                    target: resolve(target)?,
+                    low: resolve(low)?,
+                    high: resolve(high)?,
+                })
+            }
+            ComparisonFunction::InVariant { target, list } => {
+                let list_ph2 = list
+                    .into_iter()
+                    .map(|expr| expr.resolve(colref_value, aggr_value))
+                    .collect::<Result<Vec<_>>>()?;
+                Ok(ComparisonFunction::InVariant {
+                    target: resolve(target)?,
+                    list: list_ph2,
+                })
+            }
+        }
+    }
+}
+
 /// Value Expression (phase2).
 ///
 /// A value expression phase2 can be evaluated by itself.
@@ -149,83 +377,235 @@ pub enum ValueExprPh2 {
 impl ValueExprType for ValueExprPh2 {}
 
 impl ValueExprPh2 {
-    pub fn eval(self) -> Result<SqlValue> {
+    pub fn eval(self, config: &SpringSqlConfig) -> Result<SqlValue> {
         match self {
             Self::Constant(sql_value) => Ok(sql_value),
             Self::UnaryOperator(uni_op, child) => {
-                let child_sql_value = child.eval()?;
-                match (uni_op, child_sql_value) {
-                    (UnaryOperator::Minus, SqlValue::Null) => Ok(SqlValue::Null),
-                    (UnaryOperator::Minus, SqlValue::NotNull(nn_sql_value)) => {
-                        Ok(SqlValue::NotNull(nn_sql_value.negate()?))
-                    }
-                }
+                let child_sql_value = child.eval(config)?;
+                Self::eval_unary_operator(uni_op, child_sql_value)
             }
             Self::BinaryExpr(bool_expr) => match bool_expr {
                 BinaryExpr::ComparisonFunctionVariant(comparison_function) => {
-                    match comparison_function {
-                        ComparisonFunction::EqualVariant { left, right } => {
-                            let left_sql_value = left.eval()?;
-                            let right_sql_value = right.eval()?;
-                            left_sql_value
-                                .sql_compare(&right_sql_value)
-                                .map(|sql_compare_result| {
-                                    SqlValue::NotNull(NnSqlValue::Boolean(matches!(
-                                        sql_compare_result,
-                                        SqlCompareResult::Eq
-                                    )))
-                                })
-                        }
-                    }
+                    Self::eval_comparison_function(comparison_function, config)
                 }
                 BinaryExpr::LogicalFunctionVariant(logical_function) => match logical_function {
                     LogicalFunction::AndVariant { left, right } => {
-                        let left_sql_value = left.eval()?;
-                        let right_sql_value = right.eval()?;
+                        let left_b = left.eval(config)?.to_nullable_bool()?;
+                        let right_b = right.eval(config)?.to_nullable_bool()?;
+
+                        let b = match (left_b, right_b) {
+                            (Some(false), _) | (_, Some(false)) => Some(false),
+                            (Some(true), Some(true)) => Some(true),
+                            _ => None,
+                        };
+                        Ok(Self::nullable_bool_to_sql_value(b))
+                    }
+                    LogicalFunction::OrVariant { left, right } => {
+                        let left_b = left.eval(config)?.to_nullable_bool()?;
+                        let right_b = right.eval(config)?.to_nullable_bool()?;
 
-                        let b = left_sql_value.to_bool()? && right_sql_value.to_bool()?;
-                        Ok(SqlValue::NotNull(NnSqlValue::Boolean(b)))
+                        let b = match (left_b, right_b) {
+                            (Some(true), _) | (_, Some(true)) => Some(true),
+                            (Some(false), Some(false)) => Some(false),
+                            _ => None,
+                        };
+                        Ok(Self::nullable_bool_to_sql_value(b))
                     }
                 },
                 BinaryExpr::NumericalFunctionVariant(numerical_function) => {
-                    Self::eval_numerical_function(numerical_function)
+                    Self::eval_numerical_function(numerical_function, config)
                 }
             },
-            Self::FunctionCall(function_call) => Self::eval_function_call(function_call),
+            Self::FunctionCall(function_call) => Self::eval_function_call(function_call, config),
+        }
+    }
+    /// NOT and IS [NOT] {TRUE|FALSE} follow three-valued logic. IS [NOT] NULL never results in NULL.
+    fn eval_unary_operator(uni_op: UnaryOperator, child_sql_value: SqlValue) -> Result<SqlValue> {
+        let is = |b: bool| Ok(SqlValue::NotNull(NnSqlValue::Boolean(b)));
+
+        match uni_op {
+            UnaryOperator::Minus => match child_sql_value {
+                SqlValue::Null => Ok(SqlValue::Null),
+                SqlValue::NotNull(nn_sql_value) => Ok(SqlValue::NotNull(nn_sql_value.negate()?)),
+            },
+            UnaryOperator::Not => {
+                let b = child_sql_value.to_nullable_bool()?;
+                Ok(Self::nullable_bool_to_sql_value(b.map(|b| !b)))
+            }
+            UnaryOperator::IsNull => is(matches!(child_sql_value, SqlValue::Null)),
+            UnaryOperator::IsNotNull => is(!matches!(child_sql_value, SqlValue::Null)),
+            UnaryOperator::IsTrue => is(child_sql_value.to_nullable_bool()? == Some(true)),
+            UnaryOperator::IsNotTrue => is(child_sql_value.to_nullable_bool()? != Some(true)),
+            UnaryOperator::IsFalse => is(child_sql_value.to_nullable_bool()? == Some(false)),
+            UnaryOperator::IsNotFalse => is(child_sql_value.to_nullable_bool()? != Some(false)),
+        }
+    }
+
+    fn nullable_bool_to_sql_value(b: Option<bool>) -> SqlValue {
+        match b {
+            Some(b) => SqlValue::NotNull(NnSqlValue::Boolean(b)),
+            None => SqlValue::Null,
         }
     }
-    fn eval_numerical_function(numerical_function: NumericalFunction<Self>) -> Result<SqlValue> {
+
+    /// Comparisons follow three-valued logic: comparing with NULL results in NULL.
+    fn eval_comparison_function(
+        comparison_function: ComparisonFunction<Self>,
+        config: &SpringSqlConfig,
+    ) -> Result<SqlValue> {
+        match comparison_function {
+            ComparisonFunction::EqualVariant { left, right } => {
+                Self::eval_compare(*left, *right, config, |r| matches!(r, SqlCompareResult::Eq))
+            }
+            ComparisonFunction::NotEqualVariant { left, right } => {
+                Self::eval_compare(*left, *right, config, |r| {
+                    !matches!(r, SqlCompareResult::Eq)
+                })
+            }
+            ComparisonFunction::LessThanVariant { left, right } => {
+                Self::eval_compare(*left, *right, config, |r| {
+                    matches!(r, SqlCompareResult::LessThan)
+                })
+            }
+            ComparisonFunction::LessThanOrEqualVariant { left, right } => {
+                Self::eval_compare(*left, *right, config, |r| {
+                    matches!(r, SqlCompareResult::LessThan | SqlCompareResult::Eq)
+                })
+            }
+            ComparisonFunction::GreaterThanVariant { left, right } => {
+                Self::eval_compare(*left, *right, config, |r| {
+                    matches!(r, SqlCompareResult::GreaterThan)
+                })
+            }
+            ComparisonFunction::GreaterThanOrEqualVariant { left, right } => {
+                Self::eval_compare(*left, *right, config, |r| {
+                    matches!(r, SqlCompareResult::GreaterThan | SqlCompareResult::Eq)
+                })
+            }
+            ComparisonFunction::BetweenVariant { target, low, high } => {
+                let target_sql_value = target.eval(config)?;
+                let low_sql_value = low.eval(config)?;
+                let high_sql_value = high.eval(config)?;
+
+                let ge_low = target_sql_value.sql_compare(&low_sql_value)?;
+                let le_high = target_sql_value.sql_compare(&high_sql_value)?;
+                match (ge_low, le_high) {
+                    (SqlCompareResult::LessThan, _) | (_, SqlCompareResult::GreaterThan) => {
+                        Ok(SqlValue::NotNull(NnSqlValue::Boolean(false)))
+                    }
+                    (SqlCompareResult::Null, _) | (_, SqlCompareResult::Null) => Ok(SqlValue::Null),
+                    _ => Ok(SqlValue::NotNull(NnSqlValue::Boolean(true))),
+                }
+            }
+            ComparisonFunction::InVariant { target, list } => {
+                let target_sql_value = target.eval(config)?;
+
+                let mut has_null = false;
+                for expr in list {
+                    match target_sql_value.sql_compare(&expr.eval(config)?)? {
+                        SqlCompareResult::Eq => {
+                            return Ok(SqlValue::NotNull(NnSqlValue::Boolean(true)));
+                        }
+                        SqlCompareResult::Null => has_null = true,
+                        SqlCompareResult::LessThan | SqlCompareResult::GreaterThan => {}
+                    }
+                }
+
+                if has_null {
+                    Ok(SqlValue::Null)
+                } else {
+                    Ok(SqlValue::NotNull(NnSqlValue::Boolean(false)))
+                }
+            }
+        }
+    }
+    fn eval_compare<F>(
+        left: Self,
+        right: Self,
+        config: &SpringSqlConfig,
+        pred: F,
+    ) -> Result<SqlValue>
+    where
+        F: FnOnce(&SqlCompareResult) -> bool,
+    {
+        let left_sql_value = left.eval(config)?;
+        let right_sql_value = right.eval(config)?;
+        left_sql_value
+            .sql_compare(&right_sql_value)
+            .map(|sql_compare_result| match sql_compare_result {
+                SqlCompareResult::Null => SqlValue::Null,
+                _ => SqlValue::NotNull(NnSqlValue::Boolean(pred(&sql_compare_result))),
+            })
+    }
+
+    fn eval_numerical_function(
+        numerical_function: NumericalFunction<Self>,
+        config: &SpringSqlConfig,
+    ) -> Result<SqlValue> {
         match numerical_function {
             NumericalFunction::AddVariant { left, right } => {
-                let left_sql_value = left.eval()?;
-                let right_sql_value = right.eval()?;
+                let left_sql_value = left.eval(config)?;
+                let right_sql_value = right.eval(config)?;
                 left_sql_value + right_sql_value
             }
+            NumericalFunction::SubVariant { left, right } => {
+                let left_sql_value = left.eval(config)?;
+                let right_sql_value = right.eval(config)?;
+                left_sql_value - right_sql_value
+            }
             NumericalFunction::MulVariant { left, right } => {
-                let left_sql_value = left.eval()?;
-                let right_sql_value = right.eval()?;
+                let left_sql_value = left.eval(config)?;
+                let right_sql_value = right.eval(config)?;
                 left_sql_value * right_sql_value
             }
+            NumericalFunction::DivVariant { left, right } => {
+                let left_sql_value = left.eval(config)?;
+                let right_sql_value = right.eval(config)?;
+                Self::handle_division_by_zero(left_sql_value.checked_div(right_sql_value), config)
+            }
+            NumericalFunction::RemVariant { left, right } => {
+                let left_sql_value = left.eval(config)?;
+                let right_sql_value = right.eval(config)?;
+                Self::handle_division_by_zero(left_sql_value.checked_rem(right_sql_value), config)
+            }
+        }
+    }
+    /// `Ok(None)` from `/` and `%` means division by zero.
+    fn handle_division_by_zero(
+        result: Result<Option<SqlValue>>,
+        config: &SpringSqlConfig,
+    ) -> Result<SqlValue> {
+        match (result?, config.division_by_zero) {
+            (Some(sql_value), _) => Ok(sql_value),
+            (None, DivisionByZero::Null) => Ok(SqlValue::Null),
+            (None, DivisionByZero::Error) => Err(SpringError::Sql(anyhow!("division by zero"))),
         }
     }
 
-    fn eval_function_call(function_call: FunctionCall<Self>) -> Result<SqlValue> {
+    fn eval_function_call(
+        function_call: FunctionCall<Self>,
+        config: &SpringSqlConfig,
+    ) -> Result<SqlValue> {
         match function_call {
             FunctionCall::FloorTime { target, resolution } => {
-                Self::eval_function_floor_time(*target, *resolution)
+                Self::eval_function_floor_time(*target, *resolution, config)
             }
             FunctionCall::DurationMillis { duration_millis } => {
-                Self::eval_function_duration_millis(*duration_millis)
+                Self::eval_function_duration_millis(*duration_millis, config)
             }
             FunctionCall::DurationSecs { duration_secs } => {
-                Self::eval_function_duration_secs(*duration_secs)
+                Self::eval_function_duration_secs(*duration_secs, config)
             }
         }
     }
 
-    fn eval_function_floor_time(target: Self, resolution: Self) -> Result<SqlValue> {
-        let target_value = target.eval()?;
-        let resolution_value = resolution.eval()?;
+    fn eval_function_floor_time(
+        target: Self,
+        resolution: Self,
+        config: &SpringSqlConfig,
+    ) -> Result<SqlValue> {
+        let target_value = target.eval(config)?;
+        let resolution_value = resolution.eval(config)?;
 
         match (&target_value, &resolution_value) {
             (
@@ -243,8 +623,11 @@ impl ValueExprPh2 {
         }
     }
 
-    fn eval_function_duration_millis(duration_millis: Self) -> Result<SqlValue> {
-        let duration_value = duration_millis.eval()?;
+    fn eval_function_duration_millis(
+        duration_millis: Self,
+        config: &SpringSqlConfig,
+    ) -> Result<SqlValue> {
+        let duration_value = duration_millis.eval(config)?;
         let duration_millis = duration_value.to_i64()?;
         if duration_millis >= 0 {
             let duration = SpringEventDuration::from_millis(duration_millis as u64);
@@ -256,8 +639,11 @@ impl ValueExprPh2 {
             )))
         }
     }
-    fn eval_function_duration_secs(duration_secs: Self) -> Result<SqlValue> {
-        let duration_value = duration_secs.eval()?;
+    fn eval_function_duration_secs(
+        duration_secs: Self,
+        config: &SpringSqlConfig,
+    ) -> Result<SqlValue> {
+        let duration_value = duration_secs.eval(config)?;
         let duration_secs = duration_value.to_i64()?;
         if duration_secs >= 0 {
             let duration = SpringEventDuration::from_secs(duration_secs as u64);
@@ -272,8 +658,10 @@ impl ValueExprPh2 {
 }
 
 /// Aggregate expression.
-#[derive(Clone, PartialEq, Debug)]
+#[derive(Clone, PartialEq, Hash, Debug)]
 pub struct AggrExpr {
     pub func: AggregateFunctionParameter,
     pub aggregated: ValueExpr,
+    /// Key to compare rows in `ARG_MAX(aggregated, key)` and `ARG_MIN(aggregated, key)`.
+    pub key: Option<ValueExpr>,
 }
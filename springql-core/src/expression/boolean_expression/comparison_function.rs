@@ -3,6 +3,7 @@
 use crate::expression::ValueExprType;
 
 /// Comparison function and its operands
+#[allow(clippy::enum_variant_names)]
 #[derive(Clone, PartialEq, Hash, Debug)]
 pub enum ComparisonFunction<E>
 where
@@ -15,4 +16,62 @@ where
         /// Right operand
         right: Box<E>,
     },
+
+    /// `<>` (or `!=`) operation
+    NotEqualVariant {
+        /// Left operand
+        left: Box<E>,
+        /// Right operand
+        right: Box<E>,
+    },
+
+    /// `<` operation
+    LessThanVariant {
+        /// Left operand
+        left: Box<E>,
+        /// Right operand
+        right: Box<E>,
+    },
+
+    /// `<=` operation
+    LessThanOrEqualVariant {
+        /// Left operand
+        left: Box<E>,
+        /// Right operand
+        right: Box<E>,
+    },
+
+    /// `>` operation
+    GreaterThanVariant {
+        /// Left operand
+        left: Box<E>,
+        /// Right operand
+        right: Box<E>,
+    },
+
+    /// `>=` operation
+    GreaterThanOrEqualVariant {
+        /// Left operand
+        left: Box<E>,
+        /// Right operand
+        right: Box<E>,
+    },
+
+    /// `target BETWEEN low AND high` operation (both ends inclusive)
+    BetweenVariant {
+        /// Value to test
+        target: Box<E>,
+        /// Lower bound
+        low: Box<E>,
+        /// Upper bound
+        high: Box<E>,
+    },
+
+    /// `target IN (list...)` operation
+    InVariant {
+        /// Value to test
+        target: Box<E>,
+        /// Candidates
+        list: Vec<E>,
+    },
 }
@@ -5,6 +5,27 @@
 pub enum UnaryOperator {
     /// -
     Minus,
+
+    /// NOT
+    Not,
+
+    /// IS NULL (postfix)
+    IsNull,
+
+    /// IS NOT NULL (postfix)
+    IsNotNull,
+
+    /// IS TRUE (postfix)
+    IsTrue,
+
+    /// IS NOT TRUE (postfix)
+    IsNotTrue,
+
+    /// IS FALSE (postfix)
+    IsFalse,
+
+    /// IS NOT FALSE (postfix)
+    IsNotFalse,
 }
 
 /// binary operator for an expression
@@ -13,12 +34,39 @@ pub enum BinaryOperator {
     /// =
     Equal,
 
+    /// <> or !=
+    NotEqual,
+
+    /// <
+    LessThan,
+
+    /// <=
+    LessThanOrEqual,
+
+    /// >
+    GreaterThan,
+
+    /// >=
+    GreaterThanOrEqual,
+
     /// +
     Add,
 
+    /// - (binary)
+    Sub,
+
     /// *
     Mul,
 
+    /// /
+    Div,
+
+    /// %
+    Rem,
+
     /// AND
     And,
+
+    /// OR
+    Or,
 }
@@ -18,18 +18,19 @@ pub mod test_support;
 
 pub use field::{ColumnReference, Field};
 pub use name::{
-    AggrAlias, ColumnName, CorrelationAlias, PumpName, QueueName, SinkWriterName, SourceReaderName,
-    StreamName, ValueAlias,
+    AggrAlias, CallbackName, ColumnName, CorrelationAlias, PumpName, QueueName, SinkWriterName,
+    SourceReaderName, StreamName, ValueAlias,
 };
 pub use option::{
-    CANOptions, Http1ClientOptions, HttpMethod, InMemoryQueueOptions, NetClientOptions,
-    NetProtocol, NetServerOptions, Options, OptionsBuilder,
+    CANOptions, CallbackOptions, FileFormat, FileOptions, Http1ClientOptions, HttpMethod,
+    InMemoryQueueOptions, MqttOptions, MqttPayloadFormat, MqttQos, NetClientOptions, NetProtocol,
+    NetServerOptions, Options, OptionsBuilder, ReplaySpeed,
 };
 pub use pipeline_graph::{Edge, PipelineGraph};
 pub use pipeline_version::PipelineVersion;
 pub use pump_model::{
     AggregateFunctionParameter, AggregateParameter, GroupByLabels, JoinParameter, JoinType,
-    PumpInputType, PumpModel, WindowOperationParameter, WindowParameter,
+    PumpInputType, PumpModel, PumpState, WindowOperationParameter, WindowParameter,
 };
 pub use relation::{
     ColumnConstraint, ColumnDataType, ColumnDefinition, F32LooseType, I64LooseType,
@@ -114,6 +115,103 @@ impl Pipeline {
         self.graph.add_sink_writer(sink_writer)
     }
 
+    /// Without `cascade`, refuses to drop a stream which still has pumps, source readers, or sink writers.
+    /// With `cascade`, drops them together with the stream.
+    ///
+    /// # Failure
+    ///
+    /// - `SpringError::Sql` when:
+    ///   - Stream is not registered in pipeline
+    ///   - Stream has dependent objects and `cascade` is false
+    pub fn drop_stream(&mut self, stream: &StreamName, cascade: bool) -> Result<()> {
+        let dependents: Vec<Edge> = self
+            .graph
+            .attached_edges(stream)?
+            .into_iter()
+            .cloned()
+            .collect();
+
+        if !dependents.is_empty() && !cascade {
+            let names = dependents
+                .iter()
+                .map(|edge| match edge {
+                    Edge::Pump { pump_model, .. } => format!("pump {}", pump_model.name()),
+                    Edge::Source(s) => format!("source reader {}", s.name()),
+                    Edge::Sink(s) => format!("sink writer {}", s.name()),
+                })
+                .collect::<Vec<_>>();
+            return Err(SpringError::Sql(anyhow!(
+                r#"cannot drop stream "{}" because other objects depend on it ({}). Use CASCADE to drop them too"#,
+                stream,
+                names.join(", ")
+            )));
+        }
+
+        self.update_version();
+        for edge in dependents {
+            match edge {
+                Edge::Pump { pump_model, .. } => {
+                    // a pump may have several edges to the same stream (self-join, or reading and writing it)
+                    if self.object_names.contains(pump_model.name().as_ref()) {
+                        self.unregister_name(pump_model.name().as_ref());
+                        self.graph.remove_pump(pump_model.name())?;
+                    }
+                }
+                Edge::Source(s) => self.graph.remove_source_reader(s.name())?,
+                Edge::Sink(s) => self.graph.remove_sink_writer(s.name())?,
+            }
+        }
+        self.unregister_name(stream.as_ref());
+        self.graph.remove_stream(stream)
+    }
+
+    /// # Failure
+    ///
+    /// - `SpringError::Sql` when:
+    ///   - Pump is not registered in pipeline
+    pub fn drop_pump(&mut self, pump: &PumpName) -> Result<()> {
+        self.graph.remove_pump(pump)?;
+        self.update_version();
+        self.unregister_name(pump.as_ref());
+        Ok(())
+    }
+
+    /// # Failure
+    ///
+    /// - `SpringError::Sql` when:
+    ///   - Pump is not registered in pipeline
+    pub fn alter_pump_state(&mut self, pump: &PumpName, state: PumpState) -> Result<()> {
+        self.graph.set_pump_state(pump, state)?;
+        self.update_version();
+        Ok(())
+    }
+
+    /// # Failure
+    ///
+    /// - `SpringError::Sql` when:
+    ///   - Source reader is not registered in pipeline
+    pub fn drop_source_reader(&mut self, source_reader: &SourceReaderName) -> Result<()> {
+        self.graph.remove_source_reader(source_reader)?;
+        self.update_version();
+        Ok(())
+    }
+
+    /// # Failure
+    ///
+    /// - `SpringError::Sql` when:
+    ///   - Sink writer is not registered in pipeline
+    pub fn drop_sink_writer(&mut self, sink_writer: &SinkWriterName) -> Result<()> {
+        self.graph.remove_sink_writer(sink_writer)?;
+        self.update_version();
+        Ok(())
+    }
+
+    pub fn all_streams(&self) -> Vec<Arc<StreamModel>> {
+        self.graph.all_streams()
+    }
+    pub fn all_pumps(&self) -> Vec<Arc<PumpModel>> {
+        self.graph.all_pumps()
+    }
     pub fn all_sources(&self) -> Vec<&SourceReaderModel> {
         self.graph.all_sources()
     }
@@ -136,6 +234,10 @@ impl Pipeline {
         }
     }
 
+    fn unregister_name(&mut self, name: &str) {
+        let _ = self.object_names.remove(name);
+    }
+
     fn update_version(&mut self) {
         self.version.up();
     }
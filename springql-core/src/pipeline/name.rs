@@ -22,6 +22,10 @@ pub struct ColumnName(String);
 #[derive(Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug, new)]
 pub struct QueueName(String);
 
+/// Name of a sink callback registered by an application.
+#[derive(Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug, new)]
+pub struct CallbackName(String);
+
 #[derive(Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug, new)]
 pub struct CorrelationAlias(String);
 
@@ -66,6 +70,11 @@ impl Display for QueueName {
         write!(f, "{}", self.0)
     }
 }
+impl Display for CallbackName {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
 impl Display for CorrelationAlias {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(f, "{}", self.0)
@@ -151,6 +160,14 @@ impl SinkWriterName {
     pub fn in_memory_queue_sink() -> Self {
         Self::new("IN_MEMORY_QUEUE_SERVER_SINK".to_string())
     }
+
+    pub fn callback_sink() -> Self {
+        Self::new("CALLBACK_SINK".to_string())
+    }
+
+    pub fn mqtt_sink() -> Self {
+        Self::new("MQTT_SINK".to_string())
+    }
 }
 
 impl MemSize for StreamName {
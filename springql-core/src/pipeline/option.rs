@@ -1,16 +1,22 @@
 // This file is part of https://github.com/SpringQL/SpringQL which is licensed under MIT OR Apache-2.0. See file LICENSE-MIT or LICENSE-APACHE for full license details.
 
+mod callback_options;
 mod can_options;
+mod file_options;
 mod http1_client_options;
 mod in_memory_queue_options;
+mod mqtt_options;
 mod net_client_options;
 mod net_protocol;
 mod net_server_options;
 mod options_builder;
 
+pub use callback_options::CallbackOptions;
 pub use can_options::CANOptions;
+pub use file_options::{FileFormat, FileOptions, ReplaySpeed};
 pub use http1_client_options::{Http1ClientOptions, HttpMethod};
 pub use in_memory_queue_options::InMemoryQueueOptions;
+pub use mqtt_options::{MqttOptions, MqttPayloadFormat, MqttQos};
 pub use net_client_options::NetClientOptions;
 pub use net_protocol::NetProtocol;
 pub use net_server_options::NetServerOptions;
@@ -46,12 +52,63 @@ impl Options {
             })
     }
 
+    /// `Ok(None)` if key is not found in this Options.
+    ///
+    /// # Failure
+    ///
+    /// - `SpringError::InvalidOption` when:
+    ///   - the value is invalid.
+    pub fn get_optional<V, F>(&self, key: &str, value_parser: F) -> Result<Option<V>>
+    where
+        F: FnOnce(&String) -> std::result::Result<V, anyhow::Error>,
+    {
+        self.0
+            .get(key)
+            .map(|value| {
+                value_parser(value).map_err(|e| SpringError::InvalidOption {
+                    key: key.to_string(),
+                    value: value.to_string(),
+                    source: e,
+                })
+            })
+            .transpose()
+    }
+
     pub fn as_key_values(&self) -> Vec<(&str, &str)> {
         self.0
             .iter()
             .map(|(k, v)| (k.as_str(), v.as_str()))
             .collect()
     }
+
+    /// Key-values sorted by key, with values of secret keys (passwords, tokens, authorization headers, ...) replaced.
+    pub fn as_redacted_key_values(&self) -> Vec<(String, String)> {
+        const SECRET_KEY_PARTS: [&str; 6] = [
+            "PASSWORD",
+            "SECRET",
+            "TOKEN",
+            "AUTHORIZATION",
+            "COOKIE",
+            "CREDENTIAL",
+        ];
+        const REDACTED: &str = "********";
+
+        let mut key_values = self
+            .0
+            .iter()
+            .map(|(k, v)| {
+                let upper_key = k.to_uppercase();
+                let v = if SECRET_KEY_PARTS.iter().any(|part| upper_key.contains(part)) {
+                    REDACTED.to_string()
+                } else {
+                    v.clone()
+                };
+                (k.clone(), v)
+            })
+            .collect::<Vec<_>>();
+        key_values.sort();
+        key_values
+    }
 }
 
 impl From<OptionsBuilder> for Options {
@@ -59,3 +116,26 @@ impl From<OptionsBuilder> for Options {
         options_builder.build()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_as_redacted_key_values() {
+        let options = OptionsBuilder::default()
+            .add("REMOTE_PORT", "17890")
+            .add("HEADER_Authorization", "Bearer xxx")
+            .add("PASSWORD", "p@ss")
+            .build();
+
+        assert_eq!(
+            options.as_redacted_key_values(),
+            vec![
+                ("HEADER_Authorization".to_string(), "********".to_string()),
+                ("PASSWORD".to_string(), "********".to_string()),
+                ("REMOTE_PORT".to_string(), "17890".to_string()),
+            ]
+        );
+    }
+}
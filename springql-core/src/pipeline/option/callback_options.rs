@@ -0,0 +1,21 @@
+// This file is part of https://github.com/SpringQL/SpringQL which is licensed under MIT OR Apache-2.0. See file LICENSE-MIT or LICENSE-APACHE for full license details.
+
+use crate::{
+    api::error::{Result, SpringError},
+    pipeline::{name::CallbackName, option::Options},
+};
+
+#[derive(Clone, Eq, PartialEq, Debug)]
+pub struct CallbackOptions {
+    pub callback_name: CallbackName,
+}
+
+impl TryFrom<&Options> for CallbackOptions {
+    type Error = SpringError;
+
+    fn try_from(options: &Options) -> Result<Self> {
+        Ok(Self {
+            callback_name: options.get("NAME", |name| Ok(CallbackName::new(name.to_string())))?,
+        })
+    }
+}
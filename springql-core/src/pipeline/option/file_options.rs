@@ -0,0 +1,169 @@
+// This file is part of https://github.com/SpringQL/SpringQL which is licensed under MIT OR Apache-2.0. See file LICENSE-MIT or LICENSE-APACHE for full license details.
+
+use std::{path::PathBuf, str::FromStr};
+
+use anyhow::{anyhow, Context};
+
+use crate::{
+    api::error::{Result, SpringError},
+    pipeline::option::Options,
+};
+
+/// `FORMAT` option.
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+pub enum FileFormat {
+    /// `'JSONL'`: a JSON document per line.
+    JsonLines,
+    /// `'CSV'`: a header line with column names, and a record per line.
+    Csv,
+}
+
+impl FromStr for FileFormat {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        match s.to_uppercase().as_str() {
+            "JSONL" => Ok(Self::JsonLines),
+            "CSV" => Ok(Self::Csv),
+            _ => Err(anyhow!("FORMAT must be 'JSONL' or 'CSV'")),
+        }
+    }
+}
+
+/// `REPLAY_SPEED` option.
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub enum ReplaySpeed {
+    /// `'MAX'`: rows are read as fast as possible.
+    Max,
+    /// Positive number (e.g. `'1'`, `'10'`, `'0.5'`): rows are paced by their ROWTIME, scaled by this factor (`'1'` for real time).
+    Scaled(f64),
+}
+
+impl FromStr for ReplaySpeed {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        if s.eq_ignore_ascii_case("MAX") {
+            Ok(Self::Max)
+        } else {
+            let speed: f64 = s
+                .parse()
+                .context("REPLAY_SPEED must be 'MAX' or a positive number")?;
+            if speed.is_finite() && speed > 0.0 {
+                Ok(Self::Scaled(speed))
+            } else {
+                Err(anyhow!("REPLAY_SPEED must be 'MAX' or a positive number"))
+            }
+        }
+    }
+}
+
+#[derive(Clone, PartialEq, Debug)]
+pub struct FileOptions {
+    pub path: PathBuf,
+
+    /// Defaults to `'JSONL'`.
+    pub format: FileFormat,
+
+    /// Defaults to `'MAX'`.
+    pub replay_speed: ReplaySpeed,
+
+    /// Wait for lines appended to the file after reaching its end, like `tail -f`. Defaults to `'FALSE'`.
+    pub follow: bool,
+
+    /// Read the file again from the beginning after reaching its end, with ROWTIME shifted by the time span of the file. Defaults to `'FALSE'`.
+    pub loop_replay: bool,
+}
+
+impl TryFrom<&Options> for FileOptions {
+    type Error = SpringError;
+
+    fn try_from(options: &Options) -> Result<Self> {
+        let follow = options
+            .get_optional("FOLLOW", |follow_str| {
+                follow_str
+                    .to_lowercase()
+                    .parse()
+                    .context("FOLLOW must be 'TRUE' or 'FALSE'")
+            })?
+            .unwrap_or(false);
+
+        Ok(Self {
+            path: options.get("PATH", |path| Ok(PathBuf::from(path)))?,
+            format: options
+                .get_optional("FORMAT", |format_str| format_str.parse())?
+                .unwrap_or(FileFormat::JsonLines),
+            replay_speed: options
+                .get_optional("REPLAY_SPEED", |speed_str| speed_str.parse())?
+                .unwrap_or(ReplaySpeed::Max),
+            follow,
+            loop_replay: options
+                .get_optional("LOOP", |loop_str| {
+                    let loop_replay = loop_str
+                        .to_lowercase()
+                        .parse()
+                        .context("LOOP must be 'TRUE' or 'FALSE'")?;
+                    if loop_replay && follow {
+                        Err(anyhow!("LOOP and FOLLOW cannot be 'TRUE' at the same time"))
+                    } else {
+                        Ok(loop_replay)
+                    }
+                })?
+                .unwrap_or(false),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pipeline::OptionsBuilder;
+
+    #[test]
+    fn test_file_options() {
+        let options = OptionsBuilder::default()
+            .add("PATH", "/tmp/trade.csv")
+            .add("FORMAT", "CSV")
+            .add("REPLAY_SPEED", "2.5")
+            .add("LOOP", "TRUE")
+            .build();
+        let options = FileOptions::try_from(&options).unwrap();
+
+        assert_eq!(options.path, PathBuf::from("/tmp/trade.csv"));
+        assert_eq!(options.format, FileFormat::Csv);
+        assert_eq!(options.replay_speed, ReplaySpeed::Scaled(2.5));
+        assert!(!options.follow);
+        assert!(options.loop_replay);
+
+        let options = OptionsBuilder::default().add("PATH", "a.jsonl").build();
+        let options = FileOptions::try_from(&options).unwrap();
+        assert_eq!(options.format, FileFormat::JsonLines);
+        assert_eq!(options.replay_speed, ReplaySpeed::Max);
+    }
+
+    #[test]
+    fn test_file_options_invalid() {
+        for (key, value) in [
+            ("FORMAT", "TSV"),
+            ("REPLAY_SPEED", "0"),
+            ("REPLAY_SPEED", "-1"),
+            ("REPLAY_SPEED", "fast"),
+            ("LOOP", "TRUE"),
+        ] {
+            let options = OptionsBuilder::default()
+                .add("PATH", "a.jsonl")
+                .add("FOLLOW", "TRUE")
+                .add(key, value)
+                .build();
+            assert!(
+                matches!(
+                    FileOptions::try_from(&options).unwrap_err(),
+                    SpringError::InvalidOption { .. }
+                ),
+                "{} = {}",
+                key,
+                value
+            );
+        }
+    }
+}
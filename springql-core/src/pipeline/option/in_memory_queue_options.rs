@@ -1,5 +1,7 @@
 // This file is part of https://github.com/SpringQL/SpringQL which is licensed under MIT OR Apache-2.0. See file LICENSE-MIT or LICENSE-APACHE for full license details.
 
+use anyhow::Context;
+
 use crate::{
     api::error::{Result, SpringError},
     pipeline::{name::QueueName, option::Options},
@@ -8,6 +10,9 @@ use crate::{
 #[derive(Clone, Eq, PartialEq, Debug)]
 pub struct InMemoryQueueOptions {
     pub queue_name: QueueName,
+
+    /// Shared with pipelines using the same queue name with `SHARED 'TRUE'` in the process.
+    pub shared: bool,
 }
 
 impl TryFrom<&Options> for InMemoryQueueOptions {
@@ -16,6 +21,14 @@ impl TryFrom<&Options> for InMemoryQueueOptions {
     fn try_from(options: &Options) -> Result<Self> {
         Ok(Self {
             queue_name: options.get("NAME", |name| Ok(QueueName::new(name.to_string())))?,
+            shared: options
+                .get_optional("SHARED", |shared_str| {
+                    shared_str
+                        .to_lowercase()
+                        .parse()
+                        .context("SHARED must be 'TRUE' or 'FALSE'")
+                })?
+                .unwrap_or(false),
         })
     }
 }
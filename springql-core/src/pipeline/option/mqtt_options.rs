@@ -0,0 +1,255 @@
+// This file is part of https://github.com/SpringQL/SpringQL which is licensed under MIT OR Apache-2.0. See file LICENSE-MIT or LICENSE-APACHE for full license details.
+
+use std::str::FromStr;
+
+use anyhow::{anyhow, Context};
+
+use crate::{
+    api::error::{Result, SpringError},
+    pipeline::{option::Options, ColumnName},
+};
+
+/// `QOS` option.
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+pub enum MqttQos {
+    /// `'0'`
+    AtMostOnce,
+    /// `'1'`
+    AtLeastOnce,
+}
+
+impl FromStr for MqttQos {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        match s {
+            "0" => Ok(Self::AtMostOnce),
+            "1" => Ok(Self::AtLeastOnce),
+            "2" => Err(anyhow!("QoS 2 is not supported")),
+            _ => Err(anyhow!("QOS must be '0' or '1'")),
+        }
+    }
+}
+
+impl From<MqttQos> for u8 {
+    fn from(qos: MqttQos) -> Self {
+        match qos {
+            MqttQos::AtMostOnce => 0,
+            MqttQos::AtLeastOnce => 1,
+        }
+    }
+}
+
+/// `FORMAT` option: how a message payload is mapped to / from stream columns.
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+pub enum MqttPayloadFormat {
+    /// `'JSON'`: a JSON object keyed by column names.
+    Json,
+    /// `'TEXT'`: a UTF-8 string in `PAYLOAD_COLUMN`.
+    Text,
+    /// `'BLOB'`: bytes in `PAYLOAD_COLUMN`.
+    Blob,
+}
+
+impl FromStr for MqttPayloadFormat {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        match s.to_uppercase().as_str() {
+            "JSON" => Ok(Self::Json),
+            "TEXT" => Ok(Self::Text),
+            "BLOB" => Ok(Self::Blob),
+            _ => Err(anyhow!("FORMAT must be 'JSON', 'TEXT' or 'BLOB'")),
+        }
+    }
+}
+
+#[derive(Clone, Eq, PartialEq, Debug)]
+pub struct MqttOptions {
+    pub broker_host: String,
+    /// Defaults to 1883.
+    pub broker_port: u16,
+
+    /// `TOPIC` option, separated by `,`.
+    ///
+    /// Topic filters (`+` and `#` wildcards allowed) for source readers, and a topic name for sink writers.
+    pub topics: Vec<String>,
+
+    /// Defaults to `'0'`.
+    pub qos: MqttQos,
+
+    /// Defaults to a random ID.
+    pub client_id: String,
+    /// Defaults to `'TRUE'`.
+    pub clean_session: bool,
+
+    pub username: Option<String>,
+    pub password: Option<String>,
+
+    /// Defaults to `'JSON'`.
+    pub format: MqttPayloadFormat,
+
+    /// Column to put the topic of a received message into. Defaults to `topic`.
+    pub topic_column: ColumnName,
+    /// Column of the payload in `'TEXT'` and `'BLOB'` formats. Defaults to `payload`.
+    pub payload_column: ColumnName,
+}
+
+impl TryFrom<&Options> for MqttOptions {
+    type Error = SpringError;
+
+    fn try_from(options: &Options) -> Result<Self> {
+        let username = options.get_optional("USERNAME", |username| Ok(username.to_string()))?;
+        let password = options.get_optional("PASSWORD", |password| {
+            if username.is_none() {
+                Err(anyhow!("PASSWORD requires USERNAME"))
+            } else {
+                Ok(password.to_string())
+            }
+        })?;
+
+        Ok(Self {
+            broker_host: options.get("BROKER_HOST", |host| Ok(host.to_string()))?,
+            broker_port: options
+                .get_optional("BROKER_PORT", |port_str| {
+                    port_str.parse().context("invalid broker port")
+                })?
+                .unwrap_or(1883),
+            topics: options.get("TOPIC", |topics_str| Self::parse_topics(topics_str))?,
+            qos: options
+                .get_optional("QOS", |qos_str| qos_str.parse())?
+                .unwrap_or(MqttQos::AtMostOnce),
+            client_id: options
+                .get_optional("CLIENT_ID", |client_id| Ok(client_id.to_string()))?
+                .unwrap_or_else(|| format!("springql-{:016x}", fastrand::u64(..))),
+            clean_session: options
+                .get_optional("CLEAN_SESSION", |clean_session_str| {
+                    clean_session_str
+                        .to_lowercase()
+                        .parse()
+                        .context("CLEAN_SESSION must be 'TRUE' or 'FALSE'")
+                })?
+                .unwrap_or(true),
+            username,
+            password,
+            format: options
+                .get_optional("FORMAT", |format_str| format_str.parse())?
+                .unwrap_or(MqttPayloadFormat::Json),
+            topic_column: options
+                .get_optional("TOPIC_COLUMN", |column| {
+                    Ok(ColumnName::new(column.to_string()))
+                })?
+                .unwrap_or_else(|| ColumnName::new("topic".to_string())),
+            payload_column: options
+                .get_optional("PAYLOAD_COLUMN", |column| {
+                    Ok(ColumnName::new(column.to_string()))
+                })?
+                .unwrap_or_else(|| ColumnName::new("payload".to_string())),
+        })
+    }
+}
+
+impl MqttOptions {
+    /// The only topic name to publish to.
+    ///
+    /// # Failure
+    ///
+    /// - `SpringError::InvalidOption` when:
+    ///   - `TOPIC` has multiple topics or wildcards.
+    pub fn topic_name(&self) -> Result<&str> {
+        match self.topics.as_slice() {
+            [topic] if !topic.contains(&['+', '#'][..]) => Ok(topic),
+            _ => Err(SpringError::InvalidOption {
+                key: "TOPIC".to_string(),
+                value: self.topics.join(","),
+                source: anyhow!("sink writers publish to a single topic without wildcards"),
+            }),
+        }
+    }
+
+    fn parse_topics(topics_str: &str) -> anyhow::Result<Vec<String>> {
+        let topics = topics_str
+            .split(',')
+            .map(|topic| topic.trim().to_string())
+            .collect::<Vec<_>>();
+
+        for topic in &topics {
+            if topic.is_empty() {
+                return Err(anyhow!("empty topic"));
+            }
+            let levels = topic.split('/').collect::<Vec<_>>();
+            for (i, level) in levels.iter().enumerate() {
+                let valid = match *level {
+                    "+" => true,
+                    "#" => i == levels.len() - 1,
+                    _ => !level.contains(&['+', '#'][..]),
+                };
+                if !valid {
+                    return Err(anyhow!(
+                        "wildcards must occupy a whole level ('#' only at the last): {}",
+                        topic
+                    ));
+                }
+            }
+        }
+        Ok(topics)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pipeline::OptionsBuilder;
+
+    #[test]
+    fn test_mqtt_options() {
+        let options = OptionsBuilder::default()
+            .add("BROKER_HOST", "localhost")
+            .add("TOPIC", "vehicle/+/speed, vehicle/#")
+            .add("QOS", "1")
+            .add("CLIENT_ID", "c1")
+            .add("CLEAN_SESSION", "FALSE")
+            .add("USERNAME", "user")
+            .add("PASSWORD", "pass")
+            .add("FORMAT", "TEXT")
+            .build();
+        let options = MqttOptions::try_from(&options).unwrap();
+
+        assert_eq!(options.broker_port, 1883);
+        assert_eq!(options.topics, vec!["vehicle/+/speed", "vehicle/#"]);
+        assert_eq!(options.qos, MqttQos::AtLeastOnce);
+        assert_eq!(options.client_id, "c1");
+        assert!(!options.clean_session);
+        assert_eq!(options.password.as_deref(), Some("pass"));
+        assert_eq!(options.format, MqttPayloadFormat::Text);
+        assert_eq!(options.topic_column, ColumnName::new("topic".to_string()));
+        assert!(options.topic_name().is_err());
+    }
+
+    #[test]
+    fn test_mqtt_options_invalid() {
+        for (key, value) in [
+            ("TOPIC", "a/b#"),
+            ("TOPIC", "a/#/b"),
+            ("TOPIC", "a,,b"),
+            ("QOS", "2"),
+            ("PASSWORD", "pass"),
+            ("FORMAT", "XML"),
+        ] {
+            let options = OptionsBuilder::default()
+                .add("BROKER_HOST", "localhost")
+                .add("TOPIC", "a")
+                .add(key, value)
+                .build();
+            assert!(
+                matches!(
+                    MqttOptions::try_from(&options).unwrap_err(),
+                    SpringError::InvalidOption { .. }
+                ),
+                "{} = {}",
+                key,
+                value
+            );
+        }
+    }
+}
@@ -1,6 +1,6 @@
 // This file is part of https://github.com/SpringQL/SpringQL which is licensed under MIT OR Apache-2.0. See file LICENSE-MIT or LICENSE-APACHE for full license details.
 
-use std::net::IpAddr;
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr};
 
 use anyhow::Context;
 
@@ -21,11 +21,7 @@ impl TryFrom<&Options> for NetClientOptions {
 
     fn try_from(options: &Options) -> Result<Self> {
         Ok(Self {
-            protocol: options.get("PROTOCOL", |protocol_str| {
-                (protocol_str == "TCP")
-                    .then(|| NetProtocol::Tcp)
-                    .context("unsupported protocol")
-            })?,
+            protocol: options.get("PROTOCOL", |protocol_str| protocol_str.parse())?,
             remote_host: options.get("REMOTE_HOST", |remote_host_str| {
                 remote_host_str.parse().context("invalid remote host")
             })?,
@@ -35,3 +31,14 @@ impl TryFrom<&Options> for NetClientOptions {
         })
     }
 }
+
+impl NetClientOptions {
+    /// Address to bind a UDP socket to `REMOTE_HOST`: the unspecified address in the same IP version, with an ephemeral port.
+    pub fn local_bind_addr(&self) -> SocketAddr {
+        let ip = match self.remote_host {
+            IpAddr::V4(_) => IpAddr::V4(Ipv4Addr::UNSPECIFIED),
+            IpAddr::V6(_) => IpAddr::V6(Ipv6Addr::UNSPECIFIED),
+        };
+        SocketAddr::new(ip, 0)
+    }
+}
@@ -1,6 +1,25 @@
 // This file is part of https://github.com/SpringQL/SpringQL which is licensed under MIT OR Apache-2.0. See file LICENSE-MIT or LICENSE-APACHE for full license details.
 
+use std::str::FromStr;
+
+use anyhow::anyhow;
+
 #[derive(Clone, Eq, PartialEq, Debug)]
 pub enum NetProtocol {
     Tcp,
+
+    /// One JSON document per datagram.
+    Udp,
+}
+
+impl FromStr for NetProtocol {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "TCP" => Ok(Self::Tcp),
+            "UDP" => Ok(Self::Udp),
+            _ => Err(anyhow!("unsupported protocol: {}", s)),
+        }
+    }
 }
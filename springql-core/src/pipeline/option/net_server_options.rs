@@ -1,5 +1,7 @@
 // This file is part of https://github.com/SpringQL/SpringQL which is licensed under MIT OR Apache-2.0. See file LICENSE-MIT or LICENSE-APACHE for full license details.
 
+use std::net::{IpAddr, Ipv4Addr};
+
 use anyhow::Context;
 
 use crate::{
@@ -10,6 +12,10 @@ use crate::{
 #[derive(Clone, Eq, PartialEq, Debug)]
 pub struct NetServerOptions {
     pub protocol: NetProtocol,
+
+    /// `BIND_ADDRESS` option. Defaults to `127.0.0.1` (only local clients).
+    pub bind_address: IpAddr,
+
     pub port: u16,
 }
 
@@ -18,11 +24,12 @@ impl TryFrom<&Options> for NetServerOptions {
 
     fn try_from(options: &Options) -> Result<Self> {
         Ok(Self {
-            protocol: options.get("PROTOCOL", |protocol_str| {
-                (protocol_str == "TCP")
-                    .then(|| NetProtocol::Tcp)
-                    .context("unsupported protocol")
-            })?,
+            protocol: options.get("PROTOCOL", |protocol_str| protocol_str.parse())?,
+            bind_address: options
+                .get_optional("BIND_ADDRESS", |bind_address_str| {
+                    bind_address_str.parse().context("invalid bind address")
+                })?
+                .unwrap_or(IpAddr::V4(Ipv4Addr::LOCALHOST)),
             port: options.get("PORT", |remote_port_str| {
                 remote_port_str.parse().context("invalid port")
             })?,
@@ -25,8 +25,11 @@ use petgraph::{
 use crate::{
     api::error::{Result, SpringError},
     pipeline::{
-        name::StreamName, pump_model::PumpModel, sink_writer_model::SinkWriterModel,
-        source_reader_model::SourceReaderModel, stream_model::StreamModel,
+        name::{PumpName, SinkWriterName, SourceReaderName, StreamName},
+        pump_model::{PumpModel, PumpState},
+        sink_writer_model::SinkWriterModel,
+        source_reader_model::SourceReaderModel,
+        stream_model::StreamModel,
     },
 };
 
@@ -81,6 +84,27 @@ impl PipelineGraph {
         upstream_edges.collect()
     }
 
+    pub fn all_streams(&self) -> Vec<Arc<StreamModel>> {
+        self.graph
+            .node_weights()
+            .filter_map(|node| match node {
+                StreamNode::Stream(stream) => Some(stream.clone()),
+                StreamNode::VirtualRoot | StreamNode::VirtualLeaf { .. } => None,
+            })
+            .collect()
+    }
+    /// A pump with 2 or more upstreams appears only once.
+    pub fn all_pumps(&self) -> Vec<Arc<PumpModel>> {
+        let mut pumps: Vec<Arc<PumpModel>> = vec![];
+        for edge in self.graph.edge_weights() {
+            if let Edge::Pump { pump_model, .. } = edge {
+                if !pumps.iter().any(|pump| pump.name() == pump_model.name()) {
+                    pumps.push(pump_model.clone());
+                }
+            }
+        }
+        pumps
+    }
     pub fn all_sources(&self) -> Vec<&SourceReaderModel> {
         self.graph
             .edge_references()
@@ -176,6 +200,150 @@ impl PipelineGraph {
         Ok(())
     }
 
+    /// Edges (pumps, source readers, and sink writers) incoming to or outgoing from a stream.
+    ///
+    /// # Failure
+    ///
+    /// - `SpringError::Sql` when:
+    ///   - Stream is not registered in pipeline
+    pub fn attached_edges(&self, name: &StreamName) -> Result<Vec<&Edge>> {
+        let node = self._find_stream(name)?;
+        let incoming = self
+            .graph
+            .edges_directed(node, petgraph::EdgeDirection::Incoming);
+        let outgoing = self
+            .graph
+            .edges_directed(node, petgraph::EdgeDirection::Outgoing);
+        Ok(incoming.chain(outgoing).map(|e| e.weight()).collect())
+    }
+
+    /// # Failure
+    ///
+    /// - `SpringError::Sql` when:
+    ///   - Stream is not registered in pipeline
+    ///   - Stream still has pumps, source readers, or sink writers
+    pub fn remove_stream(&mut self, name: &StreamName) -> Result<()> {
+        if !self.attached_edges(name)?.is_empty() {
+            return Err(SpringError::Sql(anyhow!(
+                r#"stream "{}" still has pumps, source readers, or sink writers"#,
+                name
+            )));
+        }
+        let node = self._find_stream(name)?;
+        let _ = self.graph.remove_node(node);
+        self.reindex_stream_nodes();
+        Ok(())
+    }
+
+    /// Removes all the edges of a pump.
+    ///
+    /// # Failure
+    ///
+    /// - `SpringError::Sql` when:
+    ///   - Pump is not registered in pipeline
+    pub fn remove_pump(&mut self, name: &PumpName) -> Result<()> {
+        let n_edges = self.graph.edge_count();
+        self.graph.retain_edges(
+            |g, e| !matches!(&g[e], Edge::Pump { pump_model, .. } if pump_model.name() == name),
+        );
+
+        if self.graph.edge_count() == n_edges {
+            Err(SpringError::Sql(anyhow!(
+                r#"pump "{}" does not exist in pipeline"#,
+                name
+            )))
+        } else {
+            Ok(())
+        }
+    }
+
+    /// Replaces the pump model shared by all the edges of a pump with the one in `state`.
+    ///
+    /// # Failure
+    ///
+    /// - `SpringError::Sql` when:
+    ///   - Pump is not registered in pipeline
+    pub fn set_pump_state(&mut self, name: &PumpName, state: PumpState) -> Result<()> {
+        let mut pump = self
+            .graph
+            .edge_weights()
+            .find_map(|edge| match edge {
+                Edge::Pump { pump_model, .. } if pump_model.name() == name => {
+                    Some(pump_model.as_ref().clone())
+                }
+                _ => None,
+            })
+            .ok_or_else(|| {
+                SpringError::Sql(anyhow!(r#"pump "{}" does not exist in pipeline"#, name))
+            })?;
+        pump.set_state(state);
+        let pump = Arc::new(pump);
+
+        self.graph.edge_weights_mut().for_each(|edge| match edge {
+            Edge::Pump { pump_model, .. } if pump_model.name() == name => {
+                *pump_model = pump.clone();
+            }
+            _ => {}
+        });
+        Ok(())
+    }
+
+    /// # Failure
+    ///
+    /// - `SpringError::Sql` when:
+    ///   - Source reader is not registered in pipeline
+    pub fn remove_source_reader(&mut self, name: &SourceReaderName) -> Result<()> {
+        let n_edges = self.graph.edge_count();
+        self.graph
+            .retain_edges(|g, e| !matches!(&g[e], Edge::Source(s) if s.name() == name));
+
+        if self.graph.edge_count() == n_edges {
+            Err(SpringError::Sql(anyhow!(
+                r#"source reader "{}" does not exist in pipeline"#,
+                name
+            )))
+        } else {
+            Ok(())
+        }
+    }
+
+    /// Removes a sink writer edge and its virtual leaf.
+    ///
+    /// # Failure
+    ///
+    /// - `SpringError::Sql` when:
+    ///   - Sink writer is not registered in pipeline
+    pub fn remove_sink_writer(&mut self, name: &SinkWriterName) -> Result<()> {
+        let leaf_node = self
+            .graph
+            .edge_references()
+            .find(|edge| matches!(edge.weight(), Edge::Sink(s) if s.name() == name))
+            .map(|edge| edge.target())
+            .ok_or_else(|| {
+                SpringError::Sql(anyhow!(
+                    r#"sink writer "{}" does not exist in pipeline"#,
+                    name
+                ))
+            })?;
+
+        let _ = self.graph.remove_node(leaf_node);
+        self.reindex_stream_nodes();
+        Ok(())
+    }
+
+    /// `DiGraph::remove_node()` moves the last node into the removed index.
+    fn reindex_stream_nodes(&mut self) {
+        self.stream_nodes = self
+            .graph
+            .node_indices()
+            .filter_map(|idx| match &self.graph[idx] {
+                StreamNode::Stream(stream) => Some((stream.name().clone(), idx)),
+                StreamNode::VirtualRoot => Some((StreamName::virtual_root(), idx)),
+                StreamNode::VirtualLeaf { .. } => None,
+            })
+            .collect();
+    }
+
     /// Just for `From<&PipelineGraph> for TaskGraph`
     pub fn as_petgraph(&self) -> &DiGraph<StreamNode, Edge> {
         &self.graph
@@ -1,10 +1,12 @@
 // This file is part of https://github.com/SpringQL/SpringQL which is licensed under MIT OR Apache-2.0. See file LICENSE-MIT or LICENSE-APACHE for full license details.
 
 mod pump_input_type;
+mod pump_state;
 mod window_operation_parameter;
 mod window_parameter;
 
 pub use pump_input_type::PumpInputType;
+pub use pump_state::PumpState;
 pub use window_operation_parameter::{
     AggregateFunctionParameter, AggregateParameter, GroupByLabels, JoinParameter, JoinType,
     WindowOperationParameter,
@@ -21,6 +23,12 @@ pub struct PumpModel {
     name: PumpName,
     query_plan: QueryPlan,
     insert_plan: InsertPlan,
+
+    /// `CREATE PUMP` statement this pump is created from.
+    sql: String,
+
+    #[new(default)]
+    state: PumpState,
 }
 
 impl PumpModel {
@@ -28,6 +36,14 @@ impl PumpModel {
         &self.name
     }
 
+    pub fn state(&self) -> PumpState {
+        self.state
+    }
+
+    pub fn set_state(&mut self, state: PumpState) {
+        self.state = state;
+    }
+
     pub fn input_type(&self) -> PumpInputType {
         self.query_plan.input_type()
     }
@@ -40,6 +56,10 @@ impl PumpModel {
         &self.insert_plan
     }
 
+    pub fn sql(&self) -> &str {
+        &self.sql
+    }
+
     /// Has more than 1 upstreams on JOIN, for example.
     pub fn upstreams(&self) -> Vec<&StreamName> {
         self.query_plan.upstreams()
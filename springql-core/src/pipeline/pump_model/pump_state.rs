@@ -0,0 +1,16 @@
+// This file is part of https://github.com/SpringQL/SpringQL which is licensed under MIT OR Apache-2.0. See file LICENSE-MIT or LICENSE-APACHE for full license details.
+
+/// A pump is `Started` on creation unless created with `STOPPED`, and switched by `ALTER PUMP ... START / STOP`.
+///
+/// Stopped pumps are not scheduled. Rows arriving at their input queues are kept up to `stopped_queue_capacity` in `[pump]` config, and the rest are dropped.
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
+pub enum PumpState {
+    Started,
+    Stopped,
+}
+
+impl Default for PumpState {
+    fn default() -> Self {
+        Self::Started
+    }
+}
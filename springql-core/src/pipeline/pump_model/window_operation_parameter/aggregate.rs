@@ -1,5 +1,7 @@
 // This file is part of https://github.com/SpringQL/SpringQL which is licensed under MIT OR Apache-2.0. See file LICENSE-MIT or LICENSE-APACHE for full license details.
 
+use ordered_float::OrderedFloat;
+
 use crate::expr_resolver::{AggrExprLabel, ValueExprLabel};
 
 /// [GROUP BY c1, c2, c3...]
@@ -14,23 +16,42 @@ impl GroupByLabels {
     }
 }
 
-/// TODO `support complex expression with aggregations`
-///
 /// ```sql
-/// SELECT group_by, aggr_expr.func(aggr_expr.aggregated)
+/// SELECT group_by, aggr_expr.func(aggr_expr.aggregated), ...
 ///   FROM s
 ///   [GROUP BY group_by]
 ///   SLIDING WINDOW ...;
 /// ```
 #[derive(Clone, PartialEq, Debug, new)]
 pub struct AggregateParameter {
-    // TODO multiple aggr_expr
-    pub aggr_func: AggregateFunctionParameter,
-    pub aggr_expr: AggrExprLabel,
+    /// All the aggregate expressions in a query, including ones inside value expressions (e.g. `MAX(c) - MIN(c)`).
+    pub aggr_exprs: Vec<(AggrExprLabel, AggregateFunctionParameter)>,
     pub group_by: GroupByLabels,
 }
 
-#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
 pub enum AggregateFunctionParameter {
     Avg,
+    /// `COUNT(*)` is represented as `COUNT` of a non-NULL constant.
+    Count,
+    Sum,
+    Min,
+    Max,
+    StddevPop,
+    StddevSamp,
+    VarPop,
+    VarSamp,
+    /// `PERCENTILE_APPROX(c, percentile)`. `percentile` is in [0, 1].
+    PercentileApprox {
+        percentile: OrderedFloat<f64>,
+    },
+    ApproxCountDistinct,
+    /// Value of the row with the earliest ROWTIME.
+    FirstValue,
+    /// Value of the row with the latest ROWTIME.
+    LastValue,
+    /// `ARG_MAX(c, key)`: value of the row with the maximum key. Ties are broken by the earliest ROWTIME.
+    ArgMax,
+    /// `ARG_MIN(c, key)`: value of the row with the minimum key. Ties are broken by the earliest ROWTIME.
+    ArgMin,
 }
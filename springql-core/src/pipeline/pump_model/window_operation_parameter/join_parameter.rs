@@ -20,9 +20,28 @@ pub struct JoinParameter {
     pub right_colrefs: Vec<ColumnReference>,
 
     pub on_expr: ValueExprLabel,
+
+    /// (left, right) value expressions of equalities in `on_expr` (`ON l.c1 = r.c1 AND l.c2 = r.c2 ...`).
+    /// Used to look up matching rows by hash. Empty if `on_expr` has no such equality.
+    pub equi_keys: Vec<(ValueExprLabel, ValueExprLabel)>,
 }
 
 #[derive(Copy, Clone, Eq, PartialEq, Debug)]
 pub enum JoinType {
+    Inner,
     LeftOuter,
+    RightOuter,
+    FullOuter,
+}
+
+impl JoinType {
+    /// Whether left rows without matching right rows are emitted (with NULL right columns).
+    pub fn emits_unmatched_left(&self) -> bool {
+        matches!(self, Self::LeftOuter | Self::FullOuter)
+    }
+
+    /// Whether right rows without matching left rows are emitted (with NULL left columns).
+    pub fn emits_unmatched_right(&self) -> bool {
+        matches!(self, Self::RightOuter | Self::FullOuter)
+    }
 }
@@ -62,4 +62,26 @@ impl WindowParameter {
             WindowParameter::TimedFixedWindow { allowed_delay, .. } => *allowed_delay,
         }
     }
+
+    /// Same window with `allowed_delay` extended by `extra`.
+    pub fn with_extra_allowed_delay(&self, extra: SpringEventDuration) -> Self {
+        match self {
+            WindowParameter::TimedSlidingWindow {
+                length,
+                period,
+                allowed_delay,
+            } => WindowParameter::TimedSlidingWindow {
+                length: *length,
+                period: *period,
+                allowed_delay: *allowed_delay + extra,
+            },
+            WindowParameter::TimedFixedWindow {
+                length,
+                allowed_delay,
+            } => WindowParameter::TimedFixedWindow {
+                length: *length,
+                allowed_delay: *allowed_delay + extra,
+            },
+        }
+    }
 }
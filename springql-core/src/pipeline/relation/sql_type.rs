@@ -1,5 +1,7 @@
 // This file is part of https://github.com/SpringQL/SpringQL which is licensed under MIT OR Apache-2.0. See file LICENSE-MIT or LICENSE-APACHE for full license details.
 
+use std::fmt::Display;
+
 /// SQL type.
 ///
 /// SQL types are hierarchically categorized as follows:
@@ -87,6 +89,38 @@ impl SqlType {
 }
 
 /// Numeric types (comparable).
+impl Display for SqlType {
+    /// Type name in `CREATE STREAM`.
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let s = match self {
+            SqlType::NumericComparable(NumericComparableType::I64Loose(I64LooseType::SmallInt)) => {
+                "SMALLINT"
+            }
+            SqlType::NumericComparable(NumericComparableType::I64Loose(I64LooseType::Integer)) => {
+                "INTEGER"
+            }
+            SqlType::NumericComparable(NumericComparableType::I64Loose(I64LooseType::BigInt)) => {
+                "BIGINT"
+            }
+            SqlType::NumericComparable(NumericComparableType::U64Loose(
+                U64LooseType::UnsignedInteger,
+            )) => "UNSIGNED INTEGER",
+            SqlType::NumericComparable(NumericComparableType::U64Loose(
+                U64LooseType::UnsignedBigInt,
+            )) => "UNSIGNED BIGINT",
+            SqlType::NumericComparable(NumericComparableType::F32Loose(F32LooseType::Float)) => {
+                "FLOAT"
+            }
+            SqlType::StringComparableLoose(StringComparableLoseType::Text) => "TEXT",
+            SqlType::BinaryComparable => "BLOB",
+            SqlType::BooleanComparable => "BOOLEAN",
+            SqlType::TimestampComparable => "TIMESTAMP",
+            SqlType::DurationComparable => "DURATION",
+        };
+        write!(f, "{}", s)
+    }
+}
+
 #[allow(clippy::enum_variant_names)]
 #[derive(Clone, Eq, PartialEq, Hash, Debug)]
 pub enum NumericComparableType {
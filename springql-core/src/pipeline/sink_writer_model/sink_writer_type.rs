@@ -1,5 +1,7 @@
 // This file is part of https://github.com/SpringQL/SpringQL which is licensed under MIT OR Apache-2.0. See file LICENSE-MIT or LICENSE-APACHE for full license details.
 
+use std::fmt::Display;
+
 use crate::pipeline::name::SinkWriterName;
 
 #[derive(Clone, Eq, PartialEq, Debug)]
@@ -7,6 +9,8 @@ pub enum SinkWriterType {
     Net,
     Http1Client,
     InMemoryQueue,
+    Callback,
+    Mqtt,
 }
 
 impl From<&SinkWriterType> for SinkWriterName {
@@ -15,6 +19,22 @@ impl From<&SinkWriterType> for SinkWriterName {
             SinkWriterType::Net => SinkWriterName::net_sink(),
             SinkWriterType::Http1Client => SinkWriterName::http11_sink(),
             SinkWriterType::InMemoryQueue => SinkWriterName::in_memory_queue_sink(),
+            SinkWriterType::Callback => SinkWriterName::callback_sink(),
+            SinkWriterType::Mqtt => SinkWriterName::mqtt_sink(),
         }
     }
 }
+
+impl Display for SinkWriterType {
+    /// Type name in `CREATE SINK WRITER ... TYPE`.
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let s = match self {
+            SinkWriterType::Net => "NET_CLIENT",
+            SinkWriterType::Http1Client => "HTTP1_CLIENT",
+            SinkWriterType::InMemoryQueue => "IN_MEMORY_QUEUE",
+            SinkWriterType::Callback => "CALLBACK",
+            SinkWriterType::Mqtt => "MQTT",
+        };
+        write!(f, "{}", s)
+    }
+}
@@ -1,5 +1,7 @@
 // This file is part of https://github.com/SpringQL/SpringQL which is licensed under MIT OR Apache-2.0. See file LICENSE-MIT or LICENSE-APACHE for full license details.
 
+use std::fmt::Display;
+
 #[allow(clippy::upper_case_acronyms)]
 #[derive(Clone, Eq, PartialEq, Debug)]
 pub enum SourceReaderType {
@@ -7,4 +9,21 @@ pub enum SourceReaderType {
     NetServer,
     CAN,
     InMemoryQueue,
+    Mqtt,
+    File,
+}
+
+impl Display for SourceReaderType {
+    /// Type name in `CREATE SOURCE READER ... TYPE`.
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let s = match self {
+            SourceReaderType::NetClient => "NET_CLIENT",
+            SourceReaderType::NetServer => "NET_SERVER",
+            SourceReaderType::CAN => "CAN",
+            SourceReaderType::InMemoryQueue => "IN_MEMORY_QUEUE",
+            SourceReaderType::Mqtt => "MQTT",
+            SourceReaderType::File => "FILE",
+        };
+        write!(f, "{}", s)
+    }
 }
@@ -1,5 +1,7 @@
 // This file is part of https://github.com/SpringQL/SpringQL which is licensed under MIT OR Apache-2.0. See file LICENSE-MIT or LICENSE-APACHE for full license details.
 
+use ordered_float::OrderedFloat;
+
 use crate::{
     expression::{
         BinaryExpr, ComparisonFunction, LogicalFunction, NumericalFunction, UnaryOperator,
@@ -9,7 +11,10 @@ use crate::{
         field::ColumnReference,
         name::{ColumnName, StreamName},
     },
-    stream_engine::SqlValue,
+    stream_engine::{
+        time::{SpringDuration, SpringEventDuration},
+        NnSqlValue, SqlValue,
+    },
 };
 
 impl StreamName {
@@ -32,6 +37,20 @@ impl ValueExpr {
         Self::Constant(SqlValue::factory_integer(integer))
     }
 
+    pub fn factory_bool(bool_: bool) -> Self {
+        Self::Constant(SqlValue::factory_bool(bool_))
+    }
+
+    pub fn factory_float(float: f32) -> Self {
+        Self::Constant(SqlValue::NotNull(NnSqlValue::Float(OrderedFloat(float))))
+    }
+
+    pub fn factory_duration_secs(secs: u64) -> Self {
+        Self::Constant(SqlValue::NotNull(NnSqlValue::Duration(
+            SpringEventDuration::from_secs(secs),
+        )))
+    }
+
     pub fn factory_uni_op(unary_operator: UnaryOperator, expression: ValueExpr) -> Self {
         Self::UnaryOperator(unary_operator, Box::new(expression))
     }
@@ -40,6 +59,34 @@ impl ValueExpr {
         Self::BinaryExpr(BinaryExpr::factory_eq(left, right))
     }
 
+    pub fn factory_lt(left: ValueExpr, right: ValueExpr) -> Self {
+        Self::BinaryExpr(BinaryExpr::ComparisonFunctionVariant(
+            ComparisonFunction::LessThanVariant {
+                left: Box::new(left),
+                right: Box::new(right),
+            },
+        ))
+    }
+
+    pub fn factory_between(target: ValueExpr, low: ValueExpr, high: ValueExpr) -> Self {
+        Self::BinaryExpr(BinaryExpr::ComparisonFunctionVariant(
+            ComparisonFunction::BetweenVariant {
+                target: Box::new(target),
+                low: Box::new(low),
+                high: Box::new(high),
+            },
+        ))
+    }
+
+    pub fn factory_in(target: ValueExpr, list: Vec<ValueExpr>) -> Self {
+        Self::BinaryExpr(BinaryExpr::ComparisonFunctionVariant(
+            ComparisonFunction::InVariant {
+                target: Box::new(target),
+                list,
+            },
+        ))
+    }
+
     pub fn factory_add(left: ValueExpr, right: ValueExpr) -> Self {
         Self::BinaryExpr(BinaryExpr::factory_add(left, right))
     }
@@ -52,6 +99,15 @@ impl ValueExpr {
             },
         ))
     }
+
+    pub fn factory_or(left: ValueExpr, right: ValueExpr) -> Self {
+        Self::BinaryExpr(BinaryExpr::LogicalFunctionVariant(
+            LogicalFunction::OrVariant {
+                left: Box::new(left),
+                right: Box::new(right),
+            },
+        ))
+    }
 }
 
 impl<E: ValueExprType> BinaryExpr<E> {
@@ -3,7 +3,7 @@
 use std::{net::IpAddr, sync::Arc};
 
 use crate::{
-    api::{SpringConfig, SpringSinkWriterConfig, SpringSourceReaderConfig},
+    api::{SpringConfig, SpringSinkWriterConfig, SpringSourceReaderConfig, SpringSqlConfig},
     pipeline::{
         field::ColumnReference,
         name::{ColumnName, SinkWriterName, SourceReaderName, StreamName},
@@ -18,6 +18,7 @@ use crate::{
 };
 
 impl SpringConfig {
+    /// Config with all the default values (same as `SpringConfig::default()`).
     pub fn fx_default() -> Self {
         Self::new("").unwrap()
     }
@@ -37,6 +38,13 @@ impl SpringSinkWriterConfig {
     }
 }
 
+impl SpringSqlConfig {
+    pub fn fx_default() -> Self {
+        let c = SpringConfig::fx_default();
+        c.sql
+    }
+}
+
 impl Pipeline {
     /// ```text
     /// (0)--a-->[1]
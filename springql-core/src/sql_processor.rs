@@ -10,21 +10,32 @@ pub use sql_parser::{
 };
 
 use crate::{
-    api::error::Result,
+    api::{error::Result, SpringSqlConfig},
     pipeline::{Pipeline, PumpModel, SinkWriterModel, SourceReaderModel, StreamModel},
     sql_processor::query_planner::QueryPlanner,
     stream_engine::command::{AlterPipelineCommand, Command, QueryPlan},
 };
 
-#[derive(Debug, Default)]
-pub struct SqlProcessor(SqlParser);
+#[derive(Debug)]
+pub struct SqlProcessor {
+    parser: SqlParser,
+    config: SpringSqlConfig,
+}
 
 impl SqlProcessor {
+    pub fn new(config: SpringSqlConfig) -> Self {
+        Self {
+            parser: SqlParser::default(),
+            config,
+        }
+    }
+
     /// # Failures
     ///
     /// - `SpringError::Sql` on syntax and semantics error.
     pub fn compile<S: Into<String>>(&self, sql: S, pipeline: &Pipeline) -> Result<Command> {
-        let command = match self.0.parse(sql)? {
+        let sql = sql.into();
+        let command = match self.parser.parse(sql.as_str())? {
             ParseSuccess::CreateSourceStream(source_stream_model) => {
                 self.compile_create_source_stream(source_stream_model, pipeline)?
             }
@@ -41,7 +52,26 @@ impl SqlProcessor {
                 self.compile_create_sink_writer(sink_writer_model, pipeline)?
             }
             ParseSuccess::CreatePump(create_pump) => {
-                self.compile_create_pump(*create_pump, pipeline)?
+                self.compile_create_pump(*create_pump, &sql, pipeline)?
+            }
+            ParseSuccess::DropStream {
+                stream_name,
+                cascade,
+            } => Command::AlterPipeline(AlterPipelineCommand::DropStream {
+                stream_name,
+                cascade,
+            }),
+            ParseSuccess::DropSourceReader(source_reader_name) => {
+                Command::AlterPipeline(AlterPipelineCommand::DropSourceReader(source_reader_name))
+            }
+            ParseSuccess::DropSinkWriter(sink_writer_name) => {
+                Command::AlterPipeline(AlterPipelineCommand::DropSinkWriter(sink_writer_name))
+            }
+            ParseSuccess::DropPump(pump_name) => {
+                Command::AlterPipeline(AlterPipelineCommand::DropPump(pump_name))
+            }
+            ParseSuccess::AlterPump { pump_names, state } => {
+                Command::AlterPipeline(AlterPipelineCommand::AlterPump { pump_names, state })
             }
         };
         Ok(command)
@@ -102,9 +132,20 @@ impl SqlProcessor {
         ))
     }
 
-    fn compile_create_pump(&self, create_pump: CreatePump, pipeline: &Pipeline) -> Result<Command> {
+    fn compile_create_pump(
+        &self,
+        create_pump: CreatePump,
+        sql: &str,
+        pipeline: &Pipeline,
+    ) -> Result<Command> {
         let query_plan = self.compile_select_stream(create_pump.select_stream_syntax, pipeline)?;
-        let pump = PumpModel::new(create_pump.pump_name, query_plan, create_pump.insert_plan);
+        let mut pump = PumpModel::new(
+            create_pump.pump_name,
+            query_plan,
+            create_pump.insert_plan,
+            sql.trim().to_string(),
+        );
+        pump.set_state(create_pump.state);
         Ok(Command::AlterPipeline(AlterPipelineCommand::CreatePump(
             Box::new(pump),
         )))
@@ -115,7 +156,7 @@ impl SqlProcessor {
         select_stream_syntax: SelectStreamSyntax,
         pipeline: &Pipeline,
     ) -> Result<QueryPlan> {
-        let planner = QueryPlanner::new(select_stream_syntax);
+        let planner = QueryPlanner::new(select_stream_syntax, self.config);
         planner.plan(pipeline)
     }
 }
@@ -125,9 +166,9 @@ mod tests {
     use super::*;
     use crate::{
         pipeline::{
-            OptionsBuilder, PipelineVersion, SinkWriterModel, SinkWriterName, SinkWriterType,
-            SourceReaderModel, SourceReaderName, SourceReaderType, StreamModel, StreamName,
-            StreamShape,
+            OptionsBuilder, PipelineVersion, PumpName, PumpState, SinkWriterModel, SinkWriterName,
+            SinkWriterType, SourceReaderModel, SourceReaderName, SourceReaderType, StreamModel,
+            StreamName, StreamShape,
         },
         stream_engine::command::AlterPipelineCommand,
     };
@@ -135,7 +176,7 @@ mod tests {
 
     #[test]
     fn test_create_source_stream() {
-        let processor = SqlProcessor::default();
+        let processor = SqlProcessor::new(SpringSqlConfig::fx_default());
         let pipeline = Pipeline::new(PipelineVersion::new());
 
         let sql = "
@@ -159,7 +200,7 @@ mod tests {
 
     #[test]
     fn test_create_source_reader() {
-        let processor = SqlProcessor::default();
+        let processor = SqlProcessor::new(SpringSqlConfig::fx_default());
         let pipeline = Pipeline::fx_source_only();
 
         let sql = "
@@ -191,7 +232,7 @@ mod tests {
 
     #[test]
     fn test_create_sink_stream() {
-        let processor = SqlProcessor::default();
+        let processor = SqlProcessor::new(SpringSqlConfig::fx_default());
         let pipeline = Pipeline::new(PipelineVersion::new());
 
         let sql = "
@@ -215,7 +256,7 @@ mod tests {
 
     #[test]
     fn test_create_sink_writer() {
-        let processor = SqlProcessor::default();
+        let processor = SqlProcessor::new(SpringSqlConfig::fx_default());
         let pipeline = Pipeline::fx_sink_only();
 
         let sql = "
@@ -241,4 +282,81 @@ mod tests {
             Command::AlterPipeline(AlterPipelineCommand::CreateSinkWriter(expected_sink))
         );
     }
+
+    #[test]
+    fn test_drop_stream() {
+        let processor = SqlProcessor::new(SpringSqlConfig::fx_default());
+        let pipeline = Pipeline::fx_sink_only();
+
+        let command = processor.compile("DROP STREAM sink_1;", &pipeline).unwrap();
+        assert_eq!(
+            command,
+            Command::AlterPipeline(AlterPipelineCommand::DropStream {
+                stream_name: StreamName::new("sink_1".to_string()),
+                cascade: false,
+            })
+        );
+
+        let command = processor
+            .compile("DROP SINK STREAM sink_1 CASCADE;", &pipeline)
+            .unwrap();
+        assert_eq!(
+            command,
+            Command::AlterPipeline(AlterPipelineCommand::DropStream {
+                stream_name: StreamName::new("sink_1".to_string()),
+                cascade: true,
+            })
+        );
+    }
+
+    #[test]
+    fn test_drop_sink_writer() {
+        let processor = SqlProcessor::new(SpringSqlConfig::fx_default());
+        let pipeline = Pipeline::fx_sink_only();
+
+        let command = processor
+            .compile("DROP SINK WRITER tcp_sink_trade;", &pipeline)
+            .unwrap();
+        assert_eq!(
+            command,
+            Command::AlterPipeline(AlterPipelineCommand::DropSinkWriter(SinkWriterName::new(
+                "tcp_sink_trade".to_string()
+            )))
+        );
+    }
+
+    #[test]
+    fn test_alter_pump() {
+        let processor = SqlProcessor::new(SpringSqlConfig::fx_default());
+        let pipeline = Pipeline::fx_sink_only();
+
+        let command = processor
+            .compile("ALTER PUMP pu_1 STOP;", &pipeline)
+            .unwrap();
+        assert_eq!(
+            command,
+            Command::AlterPipeline(AlterPipelineCommand::AlterPump {
+                pump_names: vec![PumpName::new("pu_1".to_string())],
+                state: PumpState::Stopped,
+            })
+        );
+
+        let command = processor
+            .compile("alter pump pu_1, pu_2 start", &pipeline)
+            .unwrap();
+        assert_eq!(
+            command,
+            Command::AlterPipeline(AlterPipelineCommand::AlterPump {
+                pump_names: vec![
+                    PumpName::new("pu_1".to_string()),
+                    PumpName::new("pu_2".to_string())
+                ],
+                state: PumpState::Started,
+            })
+        );
+
+        assert!(processor
+            .compile("ALTER PUMP pu_1 STOPPED;", &pipeline)
+            .is_err());
+    }
 }
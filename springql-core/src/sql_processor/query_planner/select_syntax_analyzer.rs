@@ -3,6 +3,7 @@
 mod field;
 mod from_item;
 mod group_aggregate;
+mod selection;
 mod window;
 
 use crate::sql_processor::sql_parser::SelectStreamSyntax;
@@ -3,7 +3,8 @@
 use crate::{
     api::error::Result,
     expr_resolver::ExprResolver,
-    pipeline::{JoinParameter, Pipeline},
+    expression::{BinaryExpr, ComparisonFunction, LogicalFunction, ValueExpr},
+    pipeline::{ColumnReference, JoinParameter, Pipeline},
     sql_processor::{
         query_planner::SelectSyntaxAnalyzer,
         sql_parser::{FromItemSyntax, SubFromItemSyntax},
@@ -13,48 +14,129 @@ use crate::{
 
 impl SelectSyntaxAnalyzer {
     pub fn join_op(&self, expr_resolver: &mut ExprResolver, pipeline: &Pipeline) -> Result<JoinOp> {
-        match self.select_syntax.from_item.clone() {
+        let (join_op, _) = self.join_op_of(
+            self.select_syntax.from_item.clone(),
+            expr_resolver,
+            pipeline,
+        )?;
+        Ok(join_op)
+    }
+
+    /// Builds a left-deep tree of JoinWindowOp.
+    ///
+    /// Rows joined by a lower JoinWindowOp reach the upper one only after the lower window pane closes.
+    /// So an upper JoinWindowOp allows the delay of one more window length than the lower one not to drop them as late rows.
+    ///
+    /// # Returns
+    ///
+    /// (JoinOp, column references of tuples from the JoinOp)
+    fn join_op_of(
+        &self,
+        from_item: FromItemSyntax,
+        expr_resolver: &mut ExprResolver,
+        pipeline: &Pipeline,
+    ) -> Result<(JoinOp, Vec<ColumnReference>)> {
+        match from_item {
             FromItemSyntax::StreamVariant(sub_from_item) => {
+                let colrefs = pipeline
+                    .get_stream(&sub_from_item.stream_name)?
+                    .column_references();
                 let collect_op = Self::sub_from_item_to_collect_op(sub_from_item);
-                Ok(JoinOp::Collect(collect_op))
+                Ok((JoinOp::Collect(collect_op), colrefs))
             }
             FromItemSyntax::JoinVariant {
-                left: left_sub,
-                right,
+                left,
+                right: right_sub,
                 join_type,
                 on_expr,
             } => {
-                let right_sub = match right.as_ref() {
-                    FromItemSyntax::StreamVariant(sub_from_item) => sub_from_item,
-                    FromItemSyntax::JoinVariant { .. } => unimplemented!("recursive join"),
-                };
+                let (left_op, left_colrefs) = self.join_op_of(*left, expr_resolver, pipeline)?;
 
-                let left_collect_op = Self::sub_from_item_to_collect_op(left_sub.clone());
-                let right_collect_op = Self::sub_from_item_to_collect_op(right_sub.clone());
-
-                let left_colrefs = pipeline
-                    .get_stream(&left_sub.stream_name)?
-                    .column_references();
                 let right_colrefs = pipeline
                     .get_stream(&right_sub.stream_name)?
                     .column_references();
+                let right_collect_op = Self::sub_from_item_to_collect_op(right_sub);
 
+                let equi_keys = Self::equi_join_keys(&on_expr, &left_colrefs, &right_colrefs)
+                    .into_iter()
+                    .map(|(left_key, right_key)| {
+                        (
+                            expr_resolver.register_value_expr(left_key),
+                            expr_resolver.register_value_expr(right_key),
+                        )
+                    })
+                    .collect();
                 let on_expr_label = expr_resolver.register_value_expr(on_expr);
 
-                let join_param =
-                    JoinParameter::new(join_type, left_colrefs, right_colrefs, on_expr_label);
+                let mut colrefs = left_colrefs.clone();
+                colrefs.extend(right_colrefs.iter().cloned());
+
+                let join_param = JoinParameter::new(
+                    join_type,
+                    left_colrefs,
+                    right_colrefs,
+                    on_expr_label,
+                    equi_keys,
+                );
 
-                let window_param = self
-                    .window_parameter()
-                    .expect("JOIN must take window clause");
+                let window_param = match &left_op {
+                    JoinOp::Collect(_) => self
+                        .window_parameter()
+                        .expect("JOIN must take window clause"),
+                    JoinOp::JoinWindow(left_join_window_op) => {
+                        let lower_window_param = &left_join_window_op.window_param;
+                        lower_window_param.with_extra_allowed_delay(lower_window_param.length())
+                    }
+                };
 
-                Ok(JoinOp::JoinWindow(JoinWindowOp {
-                    left: left_collect_op,
+                let join_op = JoinOp::JoinWindow(JoinWindowOp {
+                    left: Box::new(left_op),
                     right: right_collect_op,
                     window_param,
                     join_param,
-                }))
+                });
+                Ok((join_op, colrefs))
+            }
+        }
+    }
+
+    /// Extracts `(left, right)` pairs from equalities connected by AND in ON condition.
+    ///
+    /// An equality is extracted only if one side refers only to left columns and the other only to right columns.
+    /// Other conditions are left as they are (whole ON condition is evaluated after hash lookup anyway).
+    fn equi_join_keys(
+        on_expr: &ValueExpr,
+        left_colrefs: &[ColumnReference],
+        right_colrefs: &[ColumnReference],
+    ) -> Vec<(ValueExpr, ValueExpr)> {
+        let refers_only_to = |expr: &ValueExpr, colrefs: &[ColumnReference]| {
+            let expr_colrefs = expr.column_references();
+            !expr_colrefs.is_empty()
+                && expr.aggr_exprs().is_empty()
+                && expr_colrefs.iter().all(|colref| colrefs.contains(colref))
+        };
+
+        match on_expr {
+            ValueExpr::BinaryExpr(BinaryExpr::LogicalFunctionVariant(
+                LogicalFunction::AndVariant { left, right },
+            )) => {
+                let mut keys = Self::equi_join_keys(left, left_colrefs, right_colrefs);
+                keys.extend(Self::equi_join_keys(right, left_colrefs, right_colrefs));
+                keys
+            }
+            ValueExpr::BinaryExpr(BinaryExpr::ComparisonFunctionVariant(
+                ComparisonFunction::EqualVariant { left, right },
+            )) => {
+                if refers_only_to(left, left_colrefs) && refers_only_to(right, right_colrefs) {
+                    vec![(left.as_ref().clone(), right.as_ref().clone())]
+                } else if refers_only_to(left, right_colrefs) && refers_only_to(right, left_colrefs)
+                {
+                    vec![(right.as_ref().clone(), left.as_ref().clone())]
+                } else {
+                    vec![]
+                }
             }
+            _ => vec![],
         }
     }
 
@@ -1,12 +1,14 @@
 // This file is part of https://github.com/SpringQL/SpringQL which is licensed under MIT OR Apache-2.0. See file LICENSE-MIT or LICENSE-APACHE for full license details.
 
 use crate::{
-    pipeline::{PumpName, SinkWriterModel, SourceReaderModel, StreamModel},
+    pipeline::{
+        PumpName, PumpState, SinkWriterModel, SinkWriterName, SourceReaderModel, SourceReaderName,
+        StreamModel, StreamName,
+    },
     sql_processor::sql_parser::syntax::SelectStreamSyntax,
     stream_engine::command::InsertPlan,
 };
 
-#[allow(clippy::enum_variant_names)]
 #[derive(Clone, PartialEq, Debug)]
 pub enum ParseSuccess {
     CreateSourceStream(StreamModel),
@@ -15,11 +17,23 @@ pub enum ParseSuccess {
     CreateSinkStream(StreamModel),
     CreateSinkWriter(SinkWriterModel),
     CreatePump(Box<CreatePump>),
+    DropStream {
+        stream_name: StreamName,
+        cascade: bool,
+    },
+    DropSourceReader(SourceReaderName),
+    DropSinkWriter(SinkWriterName),
+    DropPump(PumpName),
+    AlterPump {
+        pump_names: Vec<PumpName>,
+        state: PumpState,
+    },
 }
 
 #[derive(Clone, PartialEq, Debug)]
 pub struct CreatePump {
     pub pump_name: PumpName,
+    pub state: PumpState,
     pub select_stream_syntax: SelectStreamSyntax,
     pub insert_plan: InsertPlan,
 }
@@ -18,9 +18,9 @@ use crate::{
     pipeline::{
         AggrAlias, AggregateFunctionParameter, ColumnConstraint, ColumnDataType, ColumnDefinition,
         ColumnName, ColumnReference, CorrelationAlias, JoinType, OptionsBuilder, PumpName,
-        SinkWriterModel, SinkWriterName, SinkWriterType, SourceReaderModel, SourceReaderName,
-        SourceReaderType, SqlType, StreamModel, StreamName, StreamShape, ValueAlias,
-        WindowParameter,
+        PumpState, SinkWriterModel, SinkWriterName, SinkWriterType, SourceReaderModel,
+        SourceReaderName, SourceReaderType, SqlType, StreamModel, StreamName, StreamShape,
+        ValueAlias, WindowParameter,
     },
     sql_processor::sql_parser::{
         parse_success::{CreatePump, ParseSuccess},
@@ -244,9 +244,18 @@ impl PestParserImpl {
         let s = self_as_str(&mut params);
         match s.to_lowercase().as_str() {
             "=" => Ok(BinaryOperator::Equal),
+            "<>" | "!=" => Ok(BinaryOperator::NotEqual),
+            "<" => Ok(BinaryOperator::LessThan),
+            "<=" => Ok(BinaryOperator::LessThanOrEqual),
+            ">" => Ok(BinaryOperator::GreaterThan),
+            ">=" => Ok(BinaryOperator::GreaterThanOrEqual),
             "+" => Ok(BinaryOperator::Add),
+            "-" => Ok(BinaryOperator::Sub),
             "*" => Ok(BinaryOperator::Mul),
+            "/" => Ok(BinaryOperator::Div),
+            "%" => Ok(BinaryOperator::Rem),
             "and" => Ok(BinaryOperator::And),
+            "or" => Ok(BinaryOperator::Or),
             _ => Err(SpringError::Sql(anyhow!(
                 "Does not match any child rule of binary_operator.",
             ))),
@@ -296,6 +305,36 @@ impl PestParserImpl {
             Self::parse_create_pump_command,
             identity,
         )?)
+        .or(try_parse_child(
+            &mut params,
+            Rule::drop_stream_command,
+            Self::parse_drop_stream_command,
+            identity,
+        )?)
+        .or(try_parse_child(
+            &mut params,
+            Rule::drop_source_reader_command,
+            Self::parse_drop_source_reader_command,
+            identity,
+        )?)
+        .or(try_parse_child(
+            &mut params,
+            Rule::drop_sink_writer_command,
+            Self::parse_drop_sink_writer_command,
+            identity,
+        )?)
+        .or(try_parse_child(
+            &mut params,
+            Rule::drop_pump_command,
+            Self::parse_drop_pump_command,
+            identity,
+        )?)
+        .or(try_parse_child(
+            &mut params,
+            Rule::alter_pump_command,
+            Self::parse_alter_pump_command,
+            identity,
+        )?)
         .ok_or_else(|| {
             SpringError::Sql(anyhow!(
                 "Does not match any child rule of command: {}",
@@ -495,6 +534,13 @@ impl PestParserImpl {
             Self::parse_pump_name,
             identity,
         )?;
+        let state = try_parse_child(
+            &mut params,
+            Rule::stopped_pump,
+            |_| Ok(PumpState::Stopped),
+            identity,
+        )?
+        .unwrap_or_default();
         let into_stream = parse_child(
             &mut params,
             Rule::stream_name,
@@ -516,11 +562,116 @@ impl PestParserImpl {
 
         Ok(ParseSuccess::CreatePump(Box::new(CreatePump {
             pump_name,
+            state,
             select_stream_syntax,
             insert_plan: InsertPlan::new(into_stream, insert_column_names),
         })))
     }
 
+    /*
+     * ----------------------------------------------------------------------------
+     * DROP STREAM
+     * ----------------------------------------------------------------------------
+     */
+
+    fn parse_drop_stream_command(mut params: FnParseParams) -> Result<ParseSuccess> {
+        let stream_name = parse_child(
+            &mut params,
+            Rule::stream_name,
+            Self::parse_stream_name,
+            identity,
+        )?;
+        let cascade = try_parse_child(&mut params, Rule::cascade, |_| Ok(()), identity)?.is_some();
+
+        Ok(ParseSuccess::DropStream {
+            stream_name,
+            cascade,
+        })
+    }
+
+    /*
+     * ----------------------------------------------------------------------------
+     * DROP SOURCE READER
+     * ----------------------------------------------------------------------------
+     */
+
+    fn parse_drop_source_reader_command(mut params: FnParseParams) -> Result<ParseSuccess> {
+        let source_reader_name = parse_child(
+            &mut params,
+            Rule::source_reader_name,
+            Self::parse_source_reader_name,
+            identity,
+        )?;
+        Ok(ParseSuccess::DropSourceReader(source_reader_name))
+    }
+
+    /*
+     * ----------------------------------------------------------------------------
+     * DROP SINK WRITER
+     * ----------------------------------------------------------------------------
+     */
+
+    fn parse_drop_sink_writer_command(mut params: FnParseParams) -> Result<ParseSuccess> {
+        let sink_writer_name = parse_child(
+            &mut params,
+            Rule::sink_writer_name,
+            Self::parse_sink_writer_name,
+            identity,
+        )?;
+        Ok(ParseSuccess::DropSinkWriter(sink_writer_name))
+    }
+
+    /*
+     * ----------------------------------------------------------------------------
+     * DROP PUMP
+     * ----------------------------------------------------------------------------
+     */
+
+    fn parse_drop_pump_command(mut params: FnParseParams) -> Result<ParseSuccess> {
+        let pump_name = parse_child(
+            &mut params,
+            Rule::pump_name,
+            Self::parse_pump_name,
+            identity,
+        )?;
+        Ok(ParseSuccess::DropPump(pump_name))
+    }
+
+    /*
+     * ----------------------------------------------------------------------------
+     * ALTER PUMP
+     * ----------------------------------------------------------------------------
+     */
+
+    fn parse_alter_pump_command(mut params: FnParseParams) -> Result<ParseSuccess> {
+        let pump_names = parse_child_seq(
+            &mut params,
+            Rule::pump_name,
+            &Self::parse_pump_name,
+            &identity,
+        )?;
+        let state = try_parse_child(
+            &mut params,
+            Rule::start_pump,
+            |_| Ok(PumpState::Started),
+            identity,
+        )?
+        .or(try_parse_child(
+            &mut params,
+            Rule::stop_pump,
+            |_| Ok(PumpState::Stopped),
+            identity,
+        )?)
+        .ok_or_else(|| {
+            SpringError::Sql(anyhow!(
+                "Does not match any child rule of alter_pump_command: {}",
+                params.sql
+            ))
+        })?;
+
+        Ok(ParseSuccess::AlterPump { pump_names, state })
+    }
+
     /*
      * ----------------------------------------------------------------------------
      * SELECT
@@ -540,6 +691,12 @@ impl PestParserImpl {
             Self::parse_from_item,
             identity,
         )?;
+        let where_clause = try_parse_child(
+            &mut params,
+            Rule::where_clause,
+            Self::parse_where_clause,
+            identity,
+        )?;
         let grouping_elements = try_parse_child(
             &mut params,
             Rule::group_by_clause,
@@ -556,53 +713,34 @@ impl PestParserImpl {
         Ok(SelectStreamSyntax {
             fields,
             from_item,
+            where_clause,
             grouping_elements: grouping_elements.unwrap_or_default(),
             window_clause,
         })
     }
 
+    /// A field which is only an aggregate expression (e.g. `AVG(c) AS a`) is parsed into `SelectFieldSyntax::AggrExpr`.
     fn parse_select_field(mut params: FnParseParams) -> Result<SelectFieldSyntax> {
-        try_parse_child(
+        let value_expr = parse_child(
             &mut params,
             Rule::value_expr,
             Self::parse_value_expr,
             identity,
-        )?
-        .map(|value_expr| {
-            let alias = try_parse_child(
-                &mut params,
-                Rule::value_alias,
-                Self::parse_value_alias,
-                identity,
-            )?;
-            Ok(SelectFieldSyntax::ValueExpr { value_expr, alias })
-        })
-        .transpose()?
-        .or(try_parse_child(
+        )?;
+        let alias = try_parse_child(
             &mut params,
-            Rule::aggr_expr,
-            Self::parse_aggr_expr,
+            Rule::value_alias,
+            Self::parse_value_alias,
             identity,
-        )?
-        .map(|aggr_expr| {
-            let alias = parse_child(
-                &mut params,
-                Rule::aggr_alias,
-                Self::parse_aggr_alias,
-                identity,
-            )?;
-            Ok(SelectFieldSyntax::AggrExpr {
-                aggr_expr,
-                alias: Some(alias),
-            })
-        })
-        .transpose()?)
-        .ok_or_else(|| {
-            SpringError::Sql(anyhow!(
-                "Does not match any child rule of command: {}",
-                params.sql
-            ))
-        })
+        )?;
+
+        match value_expr {
+            ValueExpr::AggrExpr(aggr_expr) => Ok(SelectFieldSyntax::AggrExpr {
+                aggr_expr: *aggr_expr,
+                alias: alias.map(|alias| AggrAlias::new(alias.to_string())),
+            }),
+            value_expr => Ok(SelectFieldSyntax::ValueExpr { value_expr, alias }),
+        }
     }
 
     fn parse_from_item(mut params: FnParseParams) -> Result<FromItemSyntax> {
@@ -613,17 +751,17 @@ impl PestParserImpl {
             identity,
         )?;
 
-        let opt_join = try_parse_child(
+        let mut from_item = FromItemSyntax::StreamVariant(sub_from_item);
+        while let Some(join_type) = try_parse_child(
             &mut params,
             Rule::join_type,
             Self::parse_join_type,
             identity,
-        )?
-        .map(|join_type| {
-            let right_from_item = parse_child(
+        )? {
+            let right_sub_from_item = parse_child(
                 &mut params,
-                Rule::from_item,
-                Self::parse_from_item,
+                Rule::sub_from_item,
+                Self::parse_sub_from_item,
                 identity,
             )?;
             let on_expr = parse_child(
@@ -633,15 +771,15 @@ impl PestParserImpl {
                 identity,
             )?;
 
-            Ok(FromItemSyntax::JoinVariant {
-                left: sub_from_item.clone(),
-                right: Box::new(right_from_item),
+            from_item = FromItemSyntax::JoinVariant {
+                left: Box::new(from_item),
+                right: right_sub_from_item,
                 join_type,
                 on_expr,
-            })
-        });
+            };
+        }
 
-        opt_join.unwrap_or(Ok(FromItemSyntax::StreamVariant(sub_from_item)))
+        Ok(from_item)
     }
 
     fn parse_sub_from_item(mut params: FnParseParams) -> Result<SubFromItemSyntax> {
@@ -661,16 +799,29 @@ impl PestParserImpl {
     }
 
     fn parse_join_type(mut params: FnParseParams) -> Result<JoinType> {
-        let s = self_as_str(&mut params);
-        match s.to_lowercase().as_str() {
+        let s = self_as_str(&mut params)
+            .split_whitespace()
+            .collect::<Vec<_>>()
+            .join(" ")
+            .to_lowercase();
+        match s.as_str() {
+            "inner join" => Ok(JoinType::Inner),
             "left outer join" => Ok(JoinType::LeftOuter),
-            _ => Err(SpringError::Sql(anyhow!(
-                "unknown join type {}",
-                s.to_lowercase()
-            ))),
+            "right outer join" => Ok(JoinType::RightOuter),
+            "full outer join" => Ok(JoinType::FullOuter),
+            _ => Err(SpringError::Sql(anyhow!("unknown join type {}", s))),
         }
     }
 
+    fn parse_where_clause(mut params: FnParseParams) -> Result<ValueExpr> {
+        parse_child(
+            &mut params,
+            Rule::condition,
+            Self::parse_condition,
+            identity,
+        )
+    }
+
     fn parse_group_by_clause(mut params: FnParseParams) -> Result<Vec<GroupingElementSyntax>> {
         parse_child_seq(
             &mut params,
@@ -810,57 +961,288 @@ impl PestParserImpl {
     }
 
     fn parse_value_expr(mut params: FnParseParams) -> Result<ValueExpr> {
-        let expr = parse_child(
+        Self::parse_left_assoc_binary_expr(
             &mut params,
-            Rule::sub_value_expr,
-            Self::parse_sub_value_expr,
+            Rule::and_expr,
+            Self::parse_and_expr,
+            Rule::or_operator,
+        )
+    }
+
+    fn parse_and_expr(mut params: FnParseParams) -> Result<ValueExpr> {
+        Self::parse_left_assoc_binary_expr(
+            &mut params,
+            Rule::not_expr,
+            Self::parse_not_expr,
+            Rule::and_operator,
+        )
+    }
+
+    fn parse_not_expr(mut params: FnParseParams) -> Result<ValueExpr> {
+        let mut n_negations = 0;
+        while try_parse_child(&mut params, Rule::negation, |_| Ok(()), identity)?.is_some() {
+            n_negations += 1;
+        }
+
+        let mut expr = parse_child(
+            &mut params,
+            Rule::predicate_expr,
+            Self::parse_predicate_expr,
+            identity,
+        )?;
+        for _ in 0..n_negations {
+            expr = ValueExpr::UnaryOperator(UnaryOperator::Not, Box::new(expr));
+        }
+        Ok(expr)
+    }
+
+    fn parse_predicate_expr(mut params: FnParseParams) -> Result<ValueExpr> {
+        let left_expr = parse_child(
+            &mut params,
+            Rule::additive_expr,
+            Self::parse_additive_expr,
             identity,
         )?;
 
-        if let Some(bin_op) = try_parse_child(
+        let mut expr = if let Some(bin_op) = try_parse_child(
             &mut params,
-            Rule::binary_operator,
+            Rule::comparison_operator,
             Self::parse_binary_operator,
             identity,
         )? {
             let right_expr = parse_child(
                 &mut params,
-                Rule::value_expr,
-                Self::parse_value_expr,
+                Rule::additive_expr,
+                Self::parse_additive_expr,
                 identity,
             )?;
-
-            match bin_op {
-                BinaryOperator::Equal => Ok(ValueExpr::BinaryExpr(
-                    BinaryExpr::ComparisonFunctionVariant(ComparisonFunction::EqualVariant {
-                        left: Box::new(expr),
-                        right: Box::new(right_expr),
-                    }),
-                )),
-                BinaryOperator::Add => Ok(ValueExpr::BinaryExpr(
-                    BinaryExpr::NumericalFunctionVariant(NumericalFunction::AddVariant {
-                        left: Box::new(expr),
-                        right: Box::new(right_expr),
-                    }),
-                )),
-                BinaryOperator::Mul => Ok(ValueExpr::BinaryExpr(
-                    BinaryExpr::NumericalFunctionVariant(NumericalFunction::MulVariant {
-                        left: Box::new(expr),
-                        right: Box::new(right_expr),
-                    }),
+            Self::binary_expr(bin_op, left_expr, right_expr)
+        } else if let Some((negated, low, high)) = try_parse_child(
+            &mut params,
+            Rule::between_predicate,
+            Self::parse_between_predicate,
+            identity,
+        )? {
+            Self::negate_if(
+                negated,
+                ValueExpr::BinaryExpr(BinaryExpr::ComparisonFunctionVariant(
+                    ComparisonFunction::BetweenVariant {
+                        target: Box::new(left_expr),
+                        low: Box::new(low),
+                        high: Box::new(high),
+                    },
                 )),
-                BinaryOperator::And => Ok(ValueExpr::BinaryExpr(
-                    BinaryExpr::LogicalFunctionVariant(LogicalFunction::AndVariant {
-                        left: Box::new(expr),
-                        right: Box::new(right_expr),
-                    }),
+            )
+        } else if let Some((negated, list)) = try_parse_child(
+            &mut params,
+            Rule::in_predicate,
+            Self::parse_in_predicate,
+            identity,
+        )? {
+            Self::negate_if(
+                negated,
+                ValueExpr::BinaryExpr(BinaryExpr::ComparisonFunctionVariant(
+                    ComparisonFunction::InVariant {
+                        target: Box::new(left_expr),
+                        list,
+                    },
                 )),
+            )
+        } else {
+            left_expr
+        };
+
+        while let Some(is_op) = try_parse_child(
+            &mut params,
+            Rule::is_predicate,
+            Self::parse_is_predicate,
+            identity,
+        )? {
+            expr = ValueExpr::UnaryOperator(is_op, Box::new(expr));
+        }
+        Ok(expr)
+    }
+
+    fn parse_additive_expr(mut params: FnParseParams) -> Result<ValueExpr> {
+        Self::parse_left_assoc_binary_expr(
+            &mut params,
+            Rule::multiplicative_expr,
+            Self::parse_multiplicative_expr,
+            Rule::additive_operator,
+        )
+    }
+
+    fn parse_multiplicative_expr(mut params: FnParseParams) -> Result<ValueExpr> {
+        Self::parse_left_assoc_binary_expr(
+            &mut params,
+            Rule::unary_expr,
+            Self::parse_unary_expr,
+            Rule::multiplicative_operator,
+        )
+    }
+
+    fn parse_unary_expr(mut params: FnParseParams) -> Result<ValueExpr> {
+        let mut uni_ops = Vec::new();
+        while let Some(uni_op) = try_parse_child(
+            &mut params,
+            Rule::unary_operator,
+            Self::parse_unary_operator,
+            identity,
+        )? {
+            uni_ops.push(uni_op);
+        }
+
+        let mut expr = parse_child(
+            &mut params,
+            Rule::sub_value_expr,
+            Self::parse_sub_value_expr,
+            identity,
+        )?;
+        for uni_op in uni_ops.into_iter().rev() {
+            expr = ValueExpr::UnaryOperator(uni_op, Box::new(expr));
+        }
+        Ok(expr)
+    }
+
+    /// Parses `operand (operator operand)*` into left-deep binary expressions.
+    fn parse_left_assoc_binary_expr(
+        params: &mut FnParseParams,
+        operand_rule: Rule,
+        operand_parser: impl Fn(FnParseParams) -> Result<ValueExpr> + Copy,
+        operator_rule: Rule,
+    ) -> Result<ValueExpr> {
+        let mut expr = parse_child(params, operand_rule, operand_parser, identity)?;
+
+        while let Some(bin_op) =
+            try_parse_child(params, operator_rule, Self::parse_binary_operator, identity)?
+        {
+            let right_expr = parse_child(params, operand_rule, operand_parser, identity)?;
+            expr = Self::binary_expr(bin_op, expr, right_expr);
+        }
+        Ok(expr)
+    }
+
+    fn binary_expr(bin_op: BinaryOperator, left: ValueExpr, right: ValueExpr) -> ValueExpr {
+        let (left, right) = (Box::new(left), Box::new(right));
+        let bin_expr = match bin_op {
+            BinaryOperator::Equal => {
+                BinaryExpr::ComparisonFunctionVariant(ComparisonFunction::EqualVariant {
+                    left,
+                    right,
+                })
+            }
+            BinaryOperator::NotEqual => {
+                BinaryExpr::ComparisonFunctionVariant(ComparisonFunction::NotEqualVariant {
+                    left,
+                    right,
+                })
+            }
+            BinaryOperator::LessThan => {
+                BinaryExpr::ComparisonFunctionVariant(ComparisonFunction::LessThanVariant {
+                    left,
+                    right,
+                })
+            }
+            BinaryOperator::LessThanOrEqual => {
+                BinaryExpr::ComparisonFunctionVariant(ComparisonFunction::LessThanOrEqualVariant {
+                    left,
+                    right,
+                })
+            }
+            BinaryOperator::GreaterThan => {
+                BinaryExpr::ComparisonFunctionVariant(ComparisonFunction::GreaterThanVariant {
+                    left,
+                    right,
+                })
+            }
+            BinaryOperator::GreaterThanOrEqual => BinaryExpr::ComparisonFunctionVariant(
+                ComparisonFunction::GreaterThanOrEqualVariant { left, right },
+            ),
+            BinaryOperator::Add => {
+                BinaryExpr::NumericalFunctionVariant(NumericalFunction::AddVariant { left, right })
             }
+            BinaryOperator::Sub => {
+                BinaryExpr::NumericalFunctionVariant(NumericalFunction::SubVariant { left, right })
+            }
+            BinaryOperator::Mul => {
+                BinaryExpr::NumericalFunctionVariant(NumericalFunction::MulVariant { left, right })
+            }
+            BinaryOperator::Div => {
+                BinaryExpr::NumericalFunctionVariant(NumericalFunction::DivVariant { left, right })
+            }
+            BinaryOperator::Rem => {
+                BinaryExpr::NumericalFunctionVariant(NumericalFunction::RemVariant { left, right })
+            }
+            BinaryOperator::And => {
+                BinaryExpr::LogicalFunctionVariant(LogicalFunction::AndVariant { left, right })
+            }
+            BinaryOperator::Or => {
+                BinaryExpr::LogicalFunctionVariant(LogicalFunction::OrVariant { left, right })
+            }
+        };
+        ValueExpr::BinaryExpr(bin_expr)
+    }
+
+    fn negate_if(negated: bool, expr: ValueExpr) -> ValueExpr {
+        if negated {
+            ValueExpr::UnaryOperator(UnaryOperator::Not, Box::new(expr))
         } else {
-            Ok(expr)
+            expr
         }
     }
 
+    fn parse_is_predicate(mut params: FnParseParams) -> Result<UnaryOperator> {
+        let negated = try_parse_child(&mut params, Rule::negation, |_| Ok(()), identity)?.is_some();
+        let target = parse_child(
+            &mut params,
+            Rule::is_predicate_target,
+            |mut params| Ok(self_as_str(&mut params).to_lowercase()),
+            identity,
+        )?;
+
+        match (target.as_str(), negated) {
+            ("null", false) => Ok(UnaryOperator::IsNull),
+            ("null", true) => Ok(UnaryOperator::IsNotNull),
+            ("true", false) => Ok(UnaryOperator::IsTrue),
+            ("true", true) => Ok(UnaryOperator::IsNotTrue),
+            ("false", false) => Ok(UnaryOperator::IsFalse),
+            ("false", true) => Ok(UnaryOperator::IsNotFalse),
+            _ => Err(SpringError::Sql(anyhow!(
+                "Does not match any child rule of is_predicate.",
+            ))),
+        }
+    }
+
+    /// Returns (negated, low, high)
+    fn parse_between_predicate(mut params: FnParseParams) -> Result<(bool, ValueExpr, ValueExpr)> {
+        let negated = try_parse_child(&mut params, Rule::negation, |_| Ok(()), identity)?.is_some();
+        let low = parse_child(
+            &mut params,
+            Rule::additive_expr,
+            Self::parse_additive_expr,
+            identity,
+        )?;
+        let high = parse_child(
+            &mut params,
+            Rule::additive_expr,
+            Self::parse_additive_expr,
+            identity,
+        )?;
+        Ok((negated, low, high))
+    }
+
+    /// Returns (negated, list)
+    fn parse_in_predicate(mut params: FnParseParams) -> Result<(bool, Vec<ValueExpr>)> {
+        let negated = try_parse_child(&mut params, Rule::negation, |_| Ok(()), identity)?.is_some();
+        let list = parse_child_seq(
+            &mut params,
+            Rule::value_expr,
+            &Self::parse_value_expr,
+            &identity,
+        )?;
+        Ok((negated, list))
+    }
+
     fn parse_sub_value_expr(mut params: FnParseParams) -> Result<ValueExpr> {
         try_parse_child(
             &mut params,
@@ -868,35 +1250,30 @@ impl PestParserImpl {
             Self::parse_constant,
             ValueExpr::Constant,
         )?
+        .or(try_parse_child(
+            &mut params,
+            Rule::aggr_expr,
+            Self::parse_aggr_expr,
+            |aggr_expr| ValueExpr::AggrExpr(Box::new(aggr_expr)),
+        )?)
         .or(try_parse_child(
             &mut params,
             Rule::column_reference,
             Self::parse_column_reference,
             ValueExpr::ColumnReference,
         )?)
-        .or({
-            if let Some(uni_op) = try_parse_child(
-                &mut params,
-                Rule::unary_operator,
-                Self::parse_unary_operator,
-                identity,
-            )? {
-                Some(parse_child(
-                    &mut params,
-                    Rule::value_expr,
-                    Self::parse_value_expr,
-                    |expr| ValueExpr::UnaryOperator(uni_op.clone(), Box::new(expr)),
-                )?)
-            } else {
-                None
-            }
-        })
         .or(try_parse_child(
             &mut params,
             Rule::function_call,
             Self::parse_function_call,
             ValueExpr::FunctionCall,
         )?)
+        .or(try_parse_child(
+            &mut params,
+            Rule::value_expr,
+            Self::parse_value_expr,
+            identity,
+        )?)
         .ok_or_else(|| {
             SpringError::Sql(anyhow!("Does not match any child rule of sub_value_expr.",))
         })
@@ -1008,28 +1385,132 @@ impl PestParserImpl {
      */
 
     fn parse_aggr_expr(mut params: FnParseParams) -> Result<AggrExpr> {
-        let func = parse_child(
+        let name = parse_child(
             &mut params,
             Rule::aggregate_name,
             Self::parse_aggregate_name,
             identity,
         )?;
+
+        if try_parse_child(&mut params, Rule::count_all, |_| Ok(()), identity)?.is_some() {
+            return if name == "count" {
+                // COUNT(*) counts rows, which is COUNT of a non-NULL constant.
+                Ok(AggrExpr {
+                    func: AggregateFunctionParameter::Count,
+                    aggregated: ValueExpr::Constant(SqlValue::NotNull(NnSqlValue::Boolean(true))),
+                    key: None,
+                })
+            } else {
+                Err(SpringError::Sql(anyhow!(
+                    "`*` is only allowed in COUNT(*), not in {}",
+                    name
+                )))
+            };
+        }
+
         let aggregated = parse_child(
             &mut params,
             Rule::value_expr,
             &Self::parse_value_expr,
             &identity,
         )?;
-        Ok(AggrExpr { func, aggregated })
+        let mut extra_args = parse_child_seq(
+            &mut params,
+            Rule::value_expr,
+            &Self::parse_value_expr,
+            &identity,
+        )?;
+
+        match name.as_str() {
+            "arg_max" | "arg_min" => {
+                let key = extra_args
+                    .pop()
+                    .filter(|_| extra_args.is_empty())
+                    .ok_or_else(|| {
+                        SpringError::Sql(anyhow!("{} takes a value and a key", name.to_uppercase()))
+                    })?;
+                let func = if name == "arg_max" {
+                    AggregateFunctionParameter::ArgMax
+                } else {
+                    AggregateFunctionParameter::ArgMin
+                };
+                Ok(AggrExpr {
+                    func,
+                    aggregated,
+                    key: Some(key),
+                })
+            }
+            _ => {
+                let func = Self::aggregate_function(&name, extra_args)?;
+                Ok(AggrExpr {
+                    func,
+                    aggregated,
+                    key: None,
+                })
+            }
+        }
     }
 
-    fn parse_aggregate_name(mut params: FnParseParams) -> Result<AggregateFunctionParameter> {
-        let s = self_as_str(&mut params);
-        match s.to_lowercase().as_str() {
-            "avg" => Ok(AggregateFunctionParameter::Avg),
+    /// # Returns
+    ///
+    /// Lower-cased aggregate function name.
+    fn parse_aggregate_name(mut params: FnParseParams) -> Result<String> {
+        Ok(self_as_str(&mut params).to_lowercase())
+    }
+
+    /// `extra_args`: arguments other than the aggregated value expression (the first argument).
+    ///
+    /// ARG_MAX and ARG_MIN are not handled here because their extra argument is a key expression evaluated for each row.
+    fn aggregate_function(
+        name: &str,
+        extra_args: Vec<ValueExpr>,
+    ) -> Result<AggregateFunctionParameter> {
+        let without_extra_args = |func| {
+            if extra_args.is_empty() {
+                Ok(func)
+            } else {
+                Err(SpringError::Sql(anyhow!(
+                    "aggregate function {} takes only 1 argument",
+                    name
+                )))
+            }
+        };
+
+        match name {
+            "avg" => without_extra_args(AggregateFunctionParameter::Avg),
+            "count" => without_extra_args(AggregateFunctionParameter::Count),
+            "max" => without_extra_args(AggregateFunctionParameter::Max),
+            "min" => without_extra_args(AggregateFunctionParameter::Min),
+            "sum" => without_extra_args(AggregateFunctionParameter::Sum),
+            "first_value" => without_extra_args(AggregateFunctionParameter::FirstValue),
+            "last_value" => without_extra_args(AggregateFunctionParameter::LastValue),
+            "stddev_pop" => without_extra_args(AggregateFunctionParameter::StddevPop),
+            "stddev_samp" => without_extra_args(AggregateFunctionParameter::StddevSamp),
+            "var_pop" => without_extra_args(AggregateFunctionParameter::VarPop),
+            "var_samp" => without_extra_args(AggregateFunctionParameter::VarSamp),
+            "approx_count_distinct" => {
+                without_extra_args(AggregateFunctionParameter::ApproxCountDistinct)
+            }
+            "percentile_approx" => {
+                let percentile = match extra_args.as_slice() {
+                    [ValueExpr::Constant(SqlValue::NotNull(percentile))] => {
+                        percentile.to_f64().ok()
+                    }
+                    _ => None,
+                }
+                .filter(|percentile| (0.0..=1.0).contains(percentile))
+                .ok_or_else(|| {
+                    SpringError::Sql(anyhow!(
+                        "PERCENTILE_APPROX takes a value and a constant percentile in [0, 1]"
+                    ))
+                })?;
+                Ok(AggregateFunctionParameter::PercentileApprox {
+                    percentile: OrderedFloat(percentile),
+                })
+            }
             _ => Err(SpringError::Sql(anyhow!(
                 "unknown aggregate function {}",
-                s.to_lowercase()
+                name
             ))),
         }
     }
@@ -1105,8 +1586,11 @@ impl PestParserImpl {
     fn parse_integer_type(mut params: FnParseParams) -> Result<SqlType> {
         let s = self_as_str(&mut params);
         match s.to_ascii_uppercase().as_str() {
+            "SMALLINT" => Ok(SqlType::small_int()),
             "INTEGER" => Ok(SqlType::integer()),
+            "BIGINT" => Ok(SqlType::big_int()),
             "UNSIGNED INTEGER" => Ok(SqlType::unsigned_integer()),
+            "UNSIGNED BIGINT" => Ok(SqlType::unsigned_big_int()),
             x => {
                 eprintln!("Unexpected data type parsed: {}", x);
                 unreachable!();
@@ -1219,6 +1703,8 @@ impl PestParserImpl {
             "NET_SERVER" => Ok(SourceReaderType::NetServer),
             "CAN" => Ok(SourceReaderType::CAN),
             "IN_MEMORY_QUEUE" => Ok(SourceReaderType::InMemoryQueue),
+            "MQTT" => Ok(SourceReaderType::Mqtt),
+            "FILE" => Ok(SourceReaderType::File),
             _ => Err(SpringError::Sql(anyhow!(
                 "Invalid source reader name: {}",
                 typ
@@ -1245,6 +1731,8 @@ impl PestParserImpl {
             "NET_CLIENT" => Ok(SinkWriterType::Net),
             "HTTP1_CLIENT" => Ok(SinkWriterType::Http1Client),
             "IN_MEMORY_QUEUE" => Ok(SinkWriterType::InMemoryQueue),
+            "CALLBACK" => Ok(SinkWriterType::Callback),
+            "MQTT" => Ok(SinkWriterType::Mqtt),
             _ => Err(SpringError::Sql(anyhow!(
                 "Invalid source reader name: {}",
                 typ
@@ -1270,15 +1758,6 @@ impl PestParserImpl {
         )
     }
 
-    fn parse_aggr_alias(mut params: FnParseParams) -> Result<AggrAlias> {
-        parse_child(
-            &mut params,
-            Rule::identifier,
-            Self::parse_identifier,
-            AggrAlias::new,
-        )
-    }
-
     fn parse_correlation(mut params: FnParseParams) -> Result<StreamName> {
         parse_child(
             &mut params,
@@ -22,6 +22,9 @@ pub struct SelectStreamSyntax {
     pub fields: Vec<SelectFieldSyntax>,
     pub from_item: FromItemSyntax,
 
+    /// None when no WHERE clause is supplied.
+    pub where_clause: Option<ValueExpr>,
+
     /// Empty when no GROUP BY clause is supplied.
     pub grouping_elements: Vec<GroupingElementSyntax>,
 
@@ -43,9 +46,10 @@ pub enum SelectFieldSyntax {
 #[derive(Clone, PartialEq, Debug)]
 pub enum FromItemSyntax {
     StreamVariant(SubFromItemSyntax),
+    /// Left-deep: `left` may be another JOIN.
     JoinVariant {
-        left: SubFromItemSyntax,
-        right: Box<FromItemSyntax>,
+        left: Box<FromItemSyntax>,
+        right: SubFromItemSyntax,
 
         join_type: JoinType,
         on_expr: ValueExpr,
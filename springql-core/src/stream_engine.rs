@@ -5,23 +5,33 @@
 pub mod autonomous_executor;
 pub mod command;
 mod in_memory_queue_repository;
+mod sink_callback_repository;
 mod sql_executor;
 pub mod time;
 
-use std::sync::{Arc, Mutex, MutexGuard};
+use std::{
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc, Mutex, MutexGuard,
+    },
+    time::Duration,
+};
 
 use anyhow::anyhow;
 
-pub use crate::stream_engine::autonomous_executor::SpringValue;
+pub use crate::stream_engine::autonomous_executor::{SpringColumnValue, SpringValue};
 pub use autonomous_executor::{NnSqlValue, RowTime, SqlCompareResult, SqlValue, StreamRow, Tuple};
+pub use in_memory_queue_repository::InMemoryQueue;
+pub use sink_callback_repository::{SinkCallback, SinkCallbackErrorHandler};
 
 use crate::{
-    api::{error::Result, SpringConfig, SpringError},
-    pipeline::{Pipeline, QueueName},
+    api::{error::Result, SpringConfig, SpringError, SpringShutdownMode},
+    pipeline::{CallbackName, Pipeline, QueueName},
     stream_engine::{
         autonomous_executor::{AutonomousExecutor, SchemalessRow},
         command::AlterPipelineCommand,
         in_memory_queue_repository::InMemoryQueueRepository,
+        sink_callback_repository::SinkCallbackRepository,
         sql_executor::SqlExecutor,
     },
 };
@@ -51,6 +61,31 @@ impl EngineMutex {
     }
 }
 
+/// Whether a stream engine is shut down, shared with handles used without the engine lock (e.g. `SpringSourceSink`).
+#[derive(Clone, Debug, Default)]
+pub struct ShutdownState(Arc<AtomicBool>);
+
+impl ShutdownState {
+    /// # Failure
+    ///
+    /// - `SpringError::Unavailable` when:
+    ///   - the engine is already shut down.
+    pub fn ensure_running(&self) -> Result<()> {
+        if self.0.load(Ordering::Acquire) {
+            Err(SpringError::Unavailable {
+                resource: "stream engine".to_string(),
+                source: anyhow!("already shut down"),
+            })
+        } else {
+            Ok(())
+        }
+    }
+
+    fn set_shutdown(&self) {
+        self.0.store(true, Ordering::Release);
+    }
+}
+
 /// Stream engine has SQL executor and autonomous executor inside.
 ///
 /// Stream engine has Access Methods.
@@ -59,14 +94,26 @@ impl EngineMutex {
 pub struct StreamEngine {
     sql_executor: SqlExecutor,
     autonomous_executor: AutonomousExecutor,
+    in_memory_queue_repository: Arc<InMemoryQueueRepository>,
+    sink_callback_repository: Arc<SinkCallbackRepository>,
+    shutdown_state: ShutdownState,
 }
 
 impl StreamEngine {
     /// Setup sequence is drawn in a diagram: <https://github.com/SpringQL/SpringQL/issues/100#issuecomment-1101732796>
     pub fn new(config: &SpringConfig) -> Self {
+        let in_memory_queue_repository = Arc::new(InMemoryQueueRepository::default());
+        let sink_callback_repository = Arc::new(SinkCallbackRepository::default());
         Self {
             sql_executor: SqlExecutor::default(),
-            autonomous_executor: AutonomousExecutor::new(config),
+            autonomous_executor: AutonomousExecutor::new(
+                config,
+                in_memory_queue_repository.clone(),
+                sink_callback_repository.clone(),
+            ),
+            in_memory_queue_repository,
+            sink_callback_repository,
+            shutdown_state: ShutdownState::default(),
         }
     }
 
@@ -74,12 +121,27 @@ impl StreamEngine {
         self.sql_executor.current_pipeline()
     }
 
+    /// # Failure
+    ///
+    /// - `SpringError::Unavailable` when:
+    ///   - the engine is already shut down.
     pub fn alter_pipeline(&mut self, command: AlterPipelineCommand) -> Result<()> {
         log::debug!("[StreamEngine] alter_pipeline({:?})", command);
+        self.ensure_running()?;
         let pipeline = self.sql_executor.alter_pipeline(command)?;
         self.autonomous_executor.notify_pipeline_update(pipeline)
     }
 
+    /// Get an in-memory queue to block on it without holding the engine.
+    ///
+    /// # Failure
+    ///
+    /// - `SpringError::Unavailable` when:
+    ///   - queue named `queue_name` does not exist.
+    pub fn in_memory_queue(&self, queue_name: QueueName) -> Result<Arc<InMemoryQueue>> {
+        self.in_memory_queue_repository.get(&queue_name)
+    }
+
     /// # Returns
     ///
     /// - `Ok(Some)` when at least a row is in the queue.
@@ -93,7 +155,7 @@ impl StreamEngine {
         &mut self,
         queue_name: QueueName,
     ) -> Result<Option<SchemalessRow>> {
-        let q = InMemoryQueueRepository::instance().get(&queue_name)?;
+        let q = self.in_memory_queue_repository.get(&queue_name)?;
         let row = q.pop_non_blocking();
         Ok(row)
     }
@@ -102,13 +164,72 @@ impl StreamEngine {
     ///
     /// - `SpringError::Unavailable` when:
     ///   - queue named `queue_name` does not exist.
+    ///   - the queue already reached end-of-stream.
+    ///   - the engine is already shut down.
     pub fn push_in_memory_queue(
         &mut self,
         queue_name: QueueName,
         row: SchemalessRow,
     ) -> Result<()> {
-        let q = InMemoryQueueRepository::instance().get(&queue_name)?;
-        q.push(row);
+        self.ensure_running()?;
+        let q = self.in_memory_queue_repository.get(&queue_name)?;
+        if q.is_ended() {
+            Err(SpringError::Unavailable {
+                resource: queue_name.to_string(),
+                source: anyhow!("queue already reached end-of-stream"),
+            })
+        } else {
+            q.push(row);
+            Ok(())
+        }
+    }
+
+    /// Mark an in-memory source queue as ended. Windows downstream of ended sources emit their open panes.
+    ///
+    /// # Failure
+    ///
+    /// - `SpringError::Unavailable` when:
+    ///   - queue named `queue_name` does not exist.
+    ///   - the engine is already shut down.
+    pub fn end_in_memory_queue(&mut self, queue_name: QueueName) -> Result<()> {
+        self.ensure_running()?;
+        let q = self.in_memory_queue_repository.get(&queue_name)?;
+        q.end();
         Ok(())
     }
+
+    /// Register a callback for `CALLBACK` sink writers. A callback with the same name is replaced.
+    ///
+    /// `on_error` is called when the callback fails.
+    pub fn register_sink_callback(
+        &mut self,
+        callback_name: CallbackName,
+        callback: SinkCallback,
+        on_error: Option<SinkCallbackErrorHandler>,
+    ) {
+        self.sink_callback_repository
+            .register(callback_name, callback, on_error)
+    }
+
+    /// Stop processing the pipeline. Rows already in in-memory sink queues can still be popped after shutdown.
+    ///
+    /// # Failure
+    ///
+    /// - `SpringError::Unavailable` when:
+    ///   - the engine is already shut down.
+    ///   - rows still remain in the pipeline after `timeout` in `Drain` mode.
+    pub fn shutdown(&mut self, mode: SpringShutdownMode, timeout: Duration) -> Result<()> {
+        self.ensure_running()?;
+        self.shutdown_state.set_shutdown();
+        self.autonomous_executor.shutdown(mode, timeout)
+    }
+
+    #[cfg(feature = "async")]
+    pub fn shutdown_state(&self) -> ShutdownState {
+        self.shutdown_state.clone()
+    }
+
+    fn ensure_running(&self) -> Result<()> {
+        self.shutdown_state.ensure_running()
+    }
 }
@@ -20,35 +20,43 @@ mod worker;
 #[cfg(test)]
 pub mod test_support;
 
-pub use row::SpringValue;
 pub use row::{
     ColumnValues, JsonObject, NnSqlValue, RowTime, SchemalessRow, SourceRow, SqlCompareResult,
     SqlValue, SqlValueHashKey, StreamColumns, StreamRow,
 };
+pub use row::{SpringColumnValue, SpringValue};
 pub use task::{
-    NetClientSourceReader, NetServerSourceReader, SinkWriterRepository, SourceReader,
-    SourceReaderRepository, SourceTask, Task, TaskContext, Tuple, Window,
+    AggregatedAndGroupingValues, NetClientSourceReader, NetServerSourceReader,
+    SinkWriterRepository, SourceReader, SourceReaderRepository, SourceTask, Task, TaskContext,
+    Tuple, Window,
 };
 
-use std::sync::Arc;
+use std::{
+    sync::Arc,
+    time::{Duration, Instant},
+};
 
 use crate::{
     api::{
         error::{Result, SpringError},
-        SpringConfig,
+        SpringConfig, SpringShutdownMode,
     },
     pipeline::Pipeline,
-    stream_engine::autonomous_executor::{
-        args::{Coordinators, EventQueues, Locks},
-        event_queue::{BlockingEventQueue, Event, NonBlockingEventQueue},
-        main_job_lock::MainJobLock,
-        memory_state_machine_worker::MemoryStateMachineWorker,
-        performance_monitor_worker::PerformanceMonitorWorker,
-        pipeline_derivatives::PipelineDerivatives,
-        purger_worker::{PurgerWorker, PurgerWorkerThreadArg},
-        repositories::Repositories,
-        task_executor::{TaskExecutor, TaskExecutorLock},
-        worker::{WorkerSetupCoordinator, WorkerStopCoordinator},
+    stream_engine::{
+        autonomous_executor::{
+            args::{Coordinators, EventQueues, Locks},
+            event_queue::{BlockingEventQueue, Event, NonBlockingEventQueue},
+            main_job_lock::MainJobLock,
+            memory_state_machine_worker::MemoryStateMachineWorker,
+            performance_monitor_worker::PerformanceMonitorWorker,
+            pipeline_derivatives::PipelineDerivatives,
+            purger_worker::{PurgerWorker, PurgerWorkerThreadArg},
+            repositories::Repositories,
+            task_executor::{TaskExecutor, TaskExecutorLock},
+            worker::{WorkerSetupCoordinator, WorkerStopCoordinator},
+        },
+        in_memory_queue_repository::InMemoryQueueRepository,
+        sink_callback_repository::SinkCallbackRepository,
     },
 };
 
@@ -64,6 +72,9 @@ pub struct AutonomousExecutor {
     main_job_lock: Arc<MainJobLock>,
     task_executor: TaskExecutor,
 
+    /// Shared with workers. `None` until the first pipeline update.
+    pipeline_derivatives: Option<Arc<PipelineDerivatives>>,
+
     // just holds these ownership
     _memory_state_machine_worker: MemoryStateMachineWorker,
     _performance_monitor_worker: PerformanceMonitorWorker,
@@ -71,8 +82,18 @@ pub struct AutonomousExecutor {
 }
 
 impl AutonomousExecutor {
-    pub fn new(config: &SpringConfig) -> Self {
-        let repos = Arc::new(Repositories::new(config));
+    /// In-memory queues of source readers and sink writers are created in `in_memory_queue_repository`.
+    /// Callback sink writers call callbacks in `sink_callback_repository`.
+    pub fn new(
+        config: &SpringConfig,
+        in_memory_queue_repository: Arc<InMemoryQueueRepository>,
+        sink_callback_repository: Arc<SinkCallbackRepository>,
+    ) -> Self {
+        let repos = Arc::new(Repositories::new(
+            config,
+            in_memory_queue_repository,
+            sink_callback_repository,
+        ));
         let locks = Locks::new(
             Arc::new(MainJobLock::default()),
             Arc::new(TaskExecutorLock::default()),
@@ -121,22 +142,29 @@ impl AutonomousExecutor {
             b_event_queue: event_queues.blocking,
             main_job_lock: locks.main_job_lock,
             task_executor,
+            pipeline_derivatives: None,
             _memory_state_machine_worker: memory_state_machine_worker,
             _performance_monitor_worker: performance_monitor_worker,
             _purger_worker: purger_worker,
         }
     }
 
-    pub fn notify_pipeline_update(&self, pipeline: Pipeline) -> Result<()> {
+    pub fn notify_pipeline_update(&mut self, pipeline: Pipeline) -> Result<()> {
         let main_job_lock = &self.main_job_lock;
         let lock = main_job_lock.main_job_barrier();
 
         let pipeline_derivatives = Arc::new(PipelineDerivatives::new(pipeline));
 
         let task_executor = &self.task_executor;
-        task_executor.cleanup(&lock, pipeline_derivatives.task_graph());
+        let prev_task_graph = self
+            .pipeline_derivatives
+            .as_ref()
+            .map(|pipeline_derivatives| pipeline_derivatives.task_graph());
+        task_executor.cleanup(&lock, prev_task_graph, pipeline_derivatives.task_graph());
         task_executor.update_pipeline(&lock, pipeline_derivatives.clone())?;
 
+        self.pipeline_derivatives = Some(pipeline_derivatives.clone());
+
         let event = Event::UpdatePipeline {
             pipeline_derivatives,
         };
@@ -145,6 +173,33 @@ impl AutonomousExecutor {
         Ok(())
     }
 
+    /// Stop source workers, (in `Drain` mode) process rows remaining in the pipeline, and then stop generic workers.
+    ///
+    /// Sink writers are kept open until the autonomous executor is dropped.
+    ///
+    /// # Failures
+    ///
+    /// - `SpringError::Unavailable` when:
+    ///   - rows still remain in the pipeline after `timeout` in `Drain` mode. Workers are stopped anyway.
+    pub fn shutdown(&mut self, mode: SpringShutdownMode, timeout: Duration) -> Result<()> {
+        let deadline = Instant::now() + timeout;
+        log::info!("[AutonomousExecutor] shutting down ({:?})", mode);
+
+        self.task_executor.stop_source_workers();
+
+        let res = match (mode, &self.pipeline_derivatives) {
+            (SpringShutdownMode::Drain, Some(pipeline_derivatives)) => {
+                self.task_executor.drain(pipeline_derivatives, deadline)
+            }
+            _ => Ok(()),
+        };
+
+        self.task_executor.stop_generic_workers();
+        log::info!("[AutonomousExecutor] task workers stopped");
+
+        res
+    }
+
     /// Workers in autonomous executor may get SpringError but it must continue their work.
     /// This method provides common way, like logging, to handle an error and then continue their work.
     fn handle_error(e: SpringError) {
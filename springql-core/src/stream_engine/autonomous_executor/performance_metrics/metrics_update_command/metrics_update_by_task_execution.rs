@@ -3,7 +3,10 @@
 use std::ops::Add;
 
 use crate::stream_engine::{
-    autonomous_executor::task_graph::{QueueId, RowQueueId, TaskId, WindowQueueId},
+    autonomous_executor::{
+        task::ConnectionEvents,
+        task_graph::{QueueId, RowQueueId, TaskId, WindowQueueId},
+    },
     time::{SpringDuration, WallClockDuration},
 };
 
@@ -27,6 +30,18 @@ impl MetricsUpdateByTaskExecution {
     pub fn updated_task(&self) -> &TaskId {
         &self.task.task_id
     }
+    pub fn task_errors(&self) -> u64 {
+        self.task.n_errors
+    }
+    pub fn task_connection_events(&self) -> ConnectionEvents {
+        self.task.connection_events
+    }
+    pub fn task_accepted_rows(&self) -> u64 {
+        self.task.n_accepted_rows
+    }
+    pub fn task_dropped_rows(&self) -> u64 {
+        self.task.n_dropped_rows
+    }
     pub fn updated_queues(&self) -> Vec<QueueId> {
         self.in_queues
             .iter()
@@ -208,6 +223,42 @@ impl MetricsUpdateByTaskExecution {
 pub struct TaskMetricsUpdateByTask {
     task_id: TaskId,
     execution_time: WallClockDuration,
+
+    /// Errors from foreign sources / sinks (e.g. malformed or oversized input) handled in the task.
+    #[new(default)]
+    n_errors: u64,
+
+    /// Disconnections from / reconnections to foreign sources / sinks.
+    #[new(default)]
+    connection_events: ConnectionEvents,
+
+    /// Rows read from a foreign source.
+    #[new(default)]
+    n_accepted_rows: u64,
+    /// Input from a foreign source dropped because it is malformed or oversized.
+    #[new(default)]
+    n_dropped_rows: u64,
+}
+
+impl TaskMetricsUpdateByTask {
+    pub fn with_errors(self, n_errors: u64) -> Self {
+        Self { n_errors, ..self }
+    }
+
+    pub fn with_connection_events(self, connection_events: ConnectionEvents) -> Self {
+        Self {
+            connection_events,
+            ..self
+        }
+    }
+
+    pub fn with_source_rows(self, n_accepted_rows: u64, n_dropped_rows: u64) -> Self {
+        Self {
+            n_accepted_rows,
+            n_dropped_rows,
+            ..self
+        }
+    }
 }
 
 #[derive(Clone, Eq, PartialEq, Debug)]
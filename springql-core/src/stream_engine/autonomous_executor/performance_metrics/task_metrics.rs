@@ -9,6 +9,11 @@ use crate::stream_engine::autonomous_executor::performance_metrics::{
 pub struct TaskMetrics {
     avg_gain_bytes_per_sec: f32,
     n_executions: u64,
+    n_errors: u64,
+    n_disconnections: u64,
+    n_reconnections: u64,
+    n_accepted_rows: u64,
+    n_dropped_rows: u64,
 }
 
 impl TaskMetrics {
@@ -16,6 +21,13 @@ impl TaskMetrics {
         let n = self.n_executions;
 
         self.n_executions += 1;
+        self.n_errors += command.task_errors();
+
+        let connection_events = command.task_connection_events();
+        self.n_disconnections += connection_events.n_disconnections;
+        self.n_reconnections += connection_events.n_reconnections;
+        self.n_accepted_rows += command.task_accepted_rows();
+        self.n_dropped_rows += command.task_dropped_rows();
         self.avg_gain_bytes_per_sec = next_avg(
             self.avg_gain_bytes_per_sec,
             n,
@@ -26,4 +38,30 @@ impl TaskMetrics {
     pub fn avg_gain_bytes_per_sec(&self) -> f32 {
         self.avg_gain_bytes_per_sec
     }
+
+    /// Total number of errors handled in the task (e.g. malformed rows from a foreign source).
+    pub fn n_errors(&self) -> u64 {
+        self.n_errors
+    }
+
+    /// Total number of lost connections to a foreign source / sink.
+    pub fn n_disconnections(&self) -> u64 {
+        self.n_disconnections
+    }
+
+    /// Total number of connections re-established to a foreign source / sink.
+    pub fn n_reconnections(&self) -> u64 {
+        self.n_reconnections
+    }
+
+    /// Total number of rows read from a foreign source.
+    pub fn n_accepted_rows(&self) -> u64 {
+        self.n_accepted_rows
+    }
+
+    /// Total number of malformed or oversized inputs from a foreign source, which are dropped.
+    /// They are also counted in [n_errors()](Self::n_errors).
+    pub fn n_dropped_rows(&self) -> u64 {
+        self.n_dropped_rows
+    }
 }
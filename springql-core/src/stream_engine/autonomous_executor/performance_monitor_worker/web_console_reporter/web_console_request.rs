@@ -53,6 +53,11 @@ struct TaskRequest {
     id: String,
     type_: String,
     avg_gain_bytes_per_sec: f32,
+    num_errors: u64,
+    num_disconnections: u64,
+    num_reconnections: u64,
+    num_accepted_rows: u64,
+    num_dropped_rows: u64,
 }
 
 impl TaskRequest {
@@ -66,6 +71,11 @@ impl TaskRequest {
             }
             .to_string(),
             avg_gain_bytes_per_sec: metrics.avg_gain_bytes_per_sec(),
+            num_errors: metrics.n_errors(),
+            num_disconnections: metrics.n_disconnections(),
+            num_reconnections: metrics.n_reconnections(),
+            num_accepted_rows: metrics.n_accepted_rows(),
+            num_dropped_rows: metrics.n_dropped_rows(),
         }
     }
 
@@ -75,6 +85,11 @@ impl TaskRequest {
                 "id": self.id.clone(),
                 "type": self.type_.clone(),
                 "avg-gain-bytes-per-sec": self.avg_gain_bytes_per_sec,
+                "num-errors": self.num_errors,
+                "num-disconnections": self.num_disconnections,
+                "num-reconnections": self.num_reconnections,
+                "num-accepted-rows": self.num_accepted_rows,
+                "num-dropped-rows": self.num_dropped_rows,
             }
         )
     }
@@ -35,12 +35,11 @@ impl TaskRepository {
     pub fn purge_windows(&self) {
         for task in self.repo.values() {
             if let Task::Pump(pump_task) = task.as_ref() {
-                if let Some(mut w) = pump_task.get_join_window_mut() {
+                for mut w in pump_task.get_join_windows_mut() {
                     w.borrow_mut().purge()
-                } else if let Some(mut w) = pump_task.get_aggr_window_mut() {
+                }
+                if let Some(mut w) = pump_task.get_aggr_window_mut() {
                     w.borrow_mut().purge()
-                } else {
-                    // nothing to purge
                 }
             }
         }
@@ -29,6 +29,17 @@ impl RowQueue {
             .pop_front()
     }
 
+    pub fn len(&self) -> usize {
+        self.q.lock().expect("mutex in RowQueue is poisoned").len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.q
+            .lock()
+            .expect("mutex in RowQueue is poisoned")
+            .is_empty()
+    }
+
     pub fn purge(&self) {
         self.q
             .lock()
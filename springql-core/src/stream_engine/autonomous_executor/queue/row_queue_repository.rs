@@ -25,12 +25,17 @@ impl RowQueueRepository {
     }
 
     /// Removes all currently existing queues and creates new empty ones.
-    pub fn reset(&self, queue_ids: HashSet<RowQueueId>) {
+    ///
+    /// Queues in both `queue_ids` and `buffered_queue_ids` (input queues of stopped pumps) are kept with their rows.
+    pub fn reset(&self, queue_ids: HashSet<RowQueueId>, buffered_queue_ids: &HashSet<RowQueueId>) {
         let mut repo = self.repo.write();
-        repo.clear();
+        repo.retain(|queue_id, _| {
+            queue_ids.contains(queue_id) && buffered_queue_ids.contains(queue_id)
+        });
 
         queue_ids.into_iter().for_each(|queue_id| {
-            repo.insert(queue_id, Arc::new(RowQueue::default()));
+            repo.entry(queue_id)
+                .or_insert_with(|| Arc::new(RowQueue::default()));
         });
     }
 
@@ -41,3 +46,44 @@ impl RowQueueRepository {
         });
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::stream_engine::autonomous_executor::row::StreamRow;
+
+    #[test]
+    fn test_reset_keeps_buffered_queues() {
+        let q_started = RowQueueId::new("q_started".to_string());
+        let q_stopped = RowQueueId::new("q_stopped".to_string());
+        let q_dropped = RowQueueId::new("q_dropped".to_string());
+
+        let repo = RowQueueRepository::default();
+        repo.reset(
+            vec![q_started.clone(), q_stopped.clone(), q_dropped.clone()]
+                .into_iter()
+                .collect(),
+            &HashSet::new(),
+        );
+        for queue_id in [&q_started, &q_stopped, &q_dropped] {
+            repo.get(queue_id)
+                .put(StreamRow::fx_city_temperature_tokyo());
+        }
+
+        let q_new = RowQueueId::new("q_new".to_string());
+        repo.reset(
+            vec![q_started.clone(), q_stopped.clone(), q_new.clone()]
+                .into_iter()
+                .collect(),
+            &vec![q_stopped.clone(), q_dropped.clone()]
+                .into_iter()
+                .collect(),
+        );
+
+        assert!(repo.get(&q_started).is_empty());
+        assert_eq!(repo.get(&q_stopped).len(), 1);
+        assert!(repo.get(&q_new).is_empty());
+        assert!(!repo.repo.read().contains_key(&q_dropped));
+    }
+}
@@ -29,6 +29,20 @@ impl WindowQueue {
             .pop_front()
     }
 
+    pub fn len(&self) -> usize {
+        self.waiting_q
+            .lock()
+            .expect("mutex in WindowQueue is poisoned")
+            .len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.waiting_q
+            .lock()
+            .expect("mutex in WindowQueue is poisoned")
+            .is_empty()
+    }
+
     pub fn purge(&self) {
         self.waiting_q
             .lock()
@@ -30,12 +30,21 @@ impl WindowQueueRepository {
     }
 
     /// Removes all currently existing queues and creates new empty ones.
-    pub fn reset(&self, queue_ids: HashSet<WindowQueueId>) {
+    ///
+    /// Queues in both `queue_ids` and `buffered_queue_ids` (input queues of stopped pumps) are kept with their rows.
+    pub fn reset(
+        &self,
+        queue_ids: HashSet<WindowQueueId>,
+        buffered_queue_ids: &HashSet<WindowQueueId>,
+    ) {
         let mut repo = self.repo.write();
-        repo.clear();
+        repo.retain(|queue_id, _| {
+            queue_ids.contains(queue_id) && buffered_queue_ids.contains(queue_id)
+        });
 
         queue_ids.into_iter().for_each(|queue_id| {
-            repo.insert(queue_id, Arc::new(WindowQueue::default()));
+            repo.entry(queue_id)
+                .or_insert_with(|| Arc::new(WindowQueue::default()));
         });
     }
 
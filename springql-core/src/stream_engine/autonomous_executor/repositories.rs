@@ -1,10 +1,16 @@
 // This file is part of https://github.com/SpringQL/SpringQL which is licensed under MIT OR Apache-2.0. See file LICENSE-MIT or LICENSE-APACHE for full license details.
 
+use std::sync::Arc;
+
 use crate::{
-    api::SpringConfig,
-    stream_engine::autonomous_executor::{
-        queue::{RowQueueRepository, WindowQueueRepository},
-        task::{SinkWriterRepository, SourceReaderRepository},
+    api::{SpringConfig, SpringPumpConfig},
+    stream_engine::{
+        autonomous_executor::{
+            queue::{RowQueueRepository, WindowQueueRepository},
+            task::{SinkWriterRepository, SourceReaderRepository},
+        },
+        in_memory_queue_repository::InMemoryQueueRepository,
+        sink_callback_repository::SinkCallbackRepository,
     },
 };
 
@@ -15,15 +21,29 @@ pub struct Repositories {
     window_queue_repository: WindowQueueRepository,
     source_reader_repository: SourceReaderRepository,
     sink_writer_repository: SinkWriterRepository,
+
+    pump_config: SpringPumpConfig,
 }
 
 impl Repositories {
-    pub fn new(config: &SpringConfig) -> Self {
+    pub fn new(
+        config: &SpringConfig,
+        in_memory_queue_repository: Arc<InMemoryQueueRepository>,
+        sink_callback_repository: Arc<SinkCallbackRepository>,
+    ) -> Self {
         Self {
             row_queue_repository: RowQueueRepository::default(),
             window_queue_repository: WindowQueueRepository::default(),
-            source_reader_repository: SourceReaderRepository::new(config.source_reader),
-            sink_writer_repository: SinkWriterRepository::new(config.sink_writer),
+            source_reader_repository: SourceReaderRepository::new(
+                config.source_reader,
+                in_memory_queue_repository.clone(),
+            ),
+            sink_writer_repository: SinkWriterRepository::new(
+                config.sink_writer,
+                in_memory_queue_repository,
+                sink_callback_repository,
+            ),
+            pump_config: config.pump,
         }
     }
 
@@ -42,4 +62,8 @@ impl Repositories {
     pub fn sink_writer_repository(&self) -> &SinkWriterRepository {
         &self.sink_writer_repository
     }
+
+    pub fn pump_config(&self) -> &SpringPumpConfig {
+        &self.pump_config
+    }
 }
@@ -11,8 +11,12 @@ mod value;
 
 pub use column::StreamColumns;
 pub use column_values::ColumnValues;
-pub use foreign_row::{CANFrameSourceRow, JsonObject, JsonSourceRow, SourceRow};
+pub use foreign_row::{
+    CANFrameSourceRow, JsonObject, JsonSourceRow, MqttMessageSourceRow, SourceRow,
+};
 pub use rowtime::RowTime;
 pub use schemaless_row::SchemalessRow;
 pub use stream_row::StreamRow;
-pub use value::{NnSqlValue, SpringValue, SqlCompareResult, SqlValue, SqlValueHashKey};
+pub use value::{
+    NnSqlValue, SpringColumnValue, SpringValue, SqlCompareResult, SqlValue, SqlValueHashKey,
+};
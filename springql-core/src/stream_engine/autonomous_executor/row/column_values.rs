@@ -54,6 +54,18 @@ impl ColumnValues {
         Ok(v)
     }
 
+    pub fn column_names(&self) -> impl Iterator<Item = &ColumnName> {
+        self.0.iter().map(|(col, _)| col)
+    }
+
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
     /// # Failure
     ///
     /// - `SpringError::Sql` when:
@@ -4,4 +4,4 @@ mod format;
 mod source_row;
 
 pub use format::JsonObject;
-pub use source_row::{CANFrameSourceRow, JsonSourceRow, SourceRow};
+pub use source_row::{CANFrameSourceRow, JsonSourceRow, MqttMessageSourceRow, SourceRow};
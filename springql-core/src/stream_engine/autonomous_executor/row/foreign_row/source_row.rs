@@ -2,9 +2,11 @@
 
 mod can_frame_source_row;
 mod json_source_row;
+mod mqtt_message_source_row;
 
 pub use can_frame_source_row::CANFrameSourceRow;
 pub use json_source_row::JsonSourceRow;
+pub use mqtt_message_source_row::MqttMessageSourceRow;
 
 use crate::{
     api::{error::Result, SpringError},
@@ -18,6 +20,7 @@ use crate::{
 pub enum SourceRow {
     Json(JsonSourceRow),
     CANFrame(CANFrameSourceRow),
+    Mqtt(MqttMessageSourceRow),
     Raw(SchemalessRow),
 }
 
@@ -39,6 +42,9 @@ impl TryFrom<SourceRow> for SchemalessRow {
         match row {
             SourceRow::Json(json_source_row) => json_source_row.into_schemaless_row(),
             SourceRow::CANFrame(can_frame_source_row) => can_frame_source_row.into_schemaless_row(),
+            SourceRow::Mqtt(mqtt_message_source_row) => {
+                mqtt_message_source_row.into_schemaless_row()
+            }
             SourceRow::Raw(schemaless_row) => Ok(schemaless_row),
         }
     }
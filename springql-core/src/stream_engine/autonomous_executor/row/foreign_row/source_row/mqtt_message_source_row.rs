@@ -0,0 +1,86 @@
+// This file is part of https://github.com/SpringQL/SpringQL which is licensed under MIT OR Apache-2.0. See file LICENSE-MIT or LICENSE-APACHE for full license details.
+
+use anyhow::Context;
+
+use crate::{
+    api::error::{Result, SpringError},
+    pipeline::{ColumnName, MqttPayloadFormat},
+    stream_engine::{
+        autonomous_executor::{
+            row::{foreign_row::format::JsonObject, SchemalessRow},
+            ColumnValues,
+        },
+        NnSqlValue, SqlValue,
+    },
+};
+
+/// Input row from foreign sources (retrieved from SourceReader).
+///
+/// Immediately converted into `Row` on stream-engine boundary.
+#[derive(Clone, Eq, PartialEq, Debug, new)]
+pub struct MqttMessageSourceRow {
+    topic: String,
+    payload: Vec<u8>,
+
+    format: MqttPayloadFormat,
+    topic_column: ColumnName,
+    payload_column: ColumnName,
+}
+
+impl MqttMessageSourceRow {
+    /// # Failure
+    ///
+    /// - `SpringError::InvalidFormat` when:
+    ///   - the payload is not in `format`.
+    pub fn into_schemaless_row(self) -> Result<SchemalessRow> {
+        let column_values = self.into_column_values()?;
+        Ok(SchemalessRow::from(column_values))
+    }
+
+    fn into_column_values(self) -> Result<ColumnValues> {
+        let mut column_values = match self.format {
+            MqttPayloadFormat::Json => {
+                JsonObject::parse(Self::payload_str(&self.payload)?)?.into_column_values()?
+            }
+            MqttPayloadFormat::Text => {
+                let text = Self::payload_str(&self.payload)?.to_string();
+                Self::single_column_values(
+                    self.payload_column,
+                    SqlValue::NotNull(NnSqlValue::Text(text)),
+                )
+            }
+            MqttPayloadFormat::Blob => Self::single_column_values(
+                self.payload_column,
+                SqlValue::NotNull(NnSqlValue::Blob(self.payload)),
+            ),
+        };
+
+        // the topic takes precedence over a payload field of the same name
+        let _ = column_values.remove(&self.topic_column);
+        column_values
+            .insert(
+                self.topic_column,
+                SqlValue::NotNull(NnSqlValue::Text(self.topic)),
+            )
+            .expect("topic column is removed above");
+
+        Ok(column_values)
+    }
+
+    fn payload_str(payload: &[u8]) -> Result<&str> {
+        std::str::from_utf8(payload)
+            .context("MQTT payload is not valid UTF-8")
+            .map_err(|e| SpringError::InvalidFormat {
+                s: String::from_utf8_lossy(payload).to_string(),
+                source: e,
+            })
+    }
+
+    fn single_column_values(column: ColumnName, value: SqlValue) -> ColumnValues {
+        let mut column_values = ColumnValues::default();
+        column_values
+            .insert(column, value)
+            .expect("only one column");
+        column_values
+    }
+}
@@ -45,6 +45,19 @@ impl SchemalessRow {
     pub fn insert(&mut self, k: ColumnName, v: SqlValue) -> Result<()> {
         self.colvals.insert(k, v)
     }
+
+    pub fn column_names(&self) -> impl Iterator<Item = &ColumnName> {
+        self.colvals.column_names()
+    }
+
+    pub fn len(&self) -> usize {
+        self.colvals.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.colvals.is_empty()
+    }
+
     pub fn into_column_values(self) -> ColumnValues {
         self.colvals
     }
@@ -3,5 +3,7 @@
 mod sql_convertible;
 mod sql_value;
 
-pub use crate::stream_engine::autonomous_executor::row::value::sql_convertible::SpringValue;
+pub use crate::stream_engine::autonomous_executor::row::value::sql_convertible::{
+    SpringColumnValue, SpringValue,
+};
 pub use sql_value::{NnSqlValue, SqlCompareResult, SqlValue, SqlValueHashKey};
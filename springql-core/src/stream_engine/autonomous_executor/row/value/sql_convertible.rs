@@ -134,6 +134,44 @@ pub trait SpringValue: ToNnSqlValue + Sized {
     }
 }
 
+/// Rust values which a column value, possibly NULL, can be unpacked into.
+///
+/// - `T: SpringValue` for NOT NULL columns. NULL is an error.
+/// - `Option<T>` for nullable columns. NULL is unpacked into `None`.
+pub trait SpringColumnValue: Sized {
+    /// # Failures
+    ///
+    /// - `SpringError::Sql` when:
+    ///   - the column value is not convertible into this type
+    /// - `SpringError::Null` when:
+    ///   - the column value is NULL and this type is not `Option`
+    fn try_from_sql_value(sql_value: &SqlValue, i_col: usize) -> Result<Self>;
+}
+
+impl<T> SpringColumnValue for T
+where
+    T: SpringValue,
+{
+    fn try_from_sql_value(sql_value: &SqlValue, i_col: usize) -> Result<Self> {
+        match sql_value {
+            SqlValue::Null => Err(SpringError::Null { i_col }),
+            SqlValue::NotNull(nn_sql_value) => nn_sql_value.unpack(),
+        }
+    }
+}
+
+impl<T> SpringColumnValue for Option<T>
+where
+    T: SpringValue,
+{
+    fn try_from_sql_value(sql_value: &SqlValue, _i_col: usize) -> Result<Self> {
+        match sql_value {
+            SqlValue::Null => Ok(None),
+            SqlValue::NotNull(nn_sql_value) => nn_sql_value.unpack().map(Some),
+        }
+    }
+}
+
 /// Rust values which can be packed into NnSqlValue
 pub trait ToNnSqlValue: Sized {
     /// Convert Rust type into strictly-matching SQL type.
@@ -11,7 +11,7 @@ pub use sql_value_hash_key::SqlValueHashKey;
 use std::{
     fmt::Display,
     hash::Hash,
-    ops::{Add, Mul},
+    ops::{Add, Mul, Sub},
 };
 
 use anyhow::anyhow;
@@ -146,6 +146,19 @@ impl SqlValue {
         }
     }
 
+    /// Eval as nullable bool if possible. NULL is evaluated as `None` (UNKNOWN in three-valued logic).
+    ///
+    /// # Failures
+    ///
+    /// - `SpringError::Sql` when:
+    ///   - this SqlValue cannot be evaluated as SQL BOOLEAN
+    pub fn to_nullable_bool(&self) -> Result<Option<bool>> {
+        match self {
+            SqlValue::Null => Ok(None),
+            SqlValue::NotNull(_) => self.to_bool().map(Some),
+        }
+    }
+
     /// Eval as i64 if possible.
     ///
     /// # Failures
@@ -230,6 +243,18 @@ impl Add for SqlValue {
         }
     }
 }
+impl Sub for SqlValue {
+    type Output = Result<Self>;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        match (self, rhs) {
+            (SqlValue::Null, _) | (_, SqlValue::Null) => Ok(SqlValue::Null),
+            (SqlValue::NotNull(lhs_nn), SqlValue::NotNull(rhs_nn)) => {
+                (lhs_nn - rhs_nn).map(SqlValue::NotNull)
+            }
+        }
+    }
+}
 impl Mul for SqlValue {
     type Output = Result<Self>;
 
@@ -243,6 +268,30 @@ impl Mul for SqlValue {
     }
 }
 
+impl SqlValue {
+    /// `Ok(None)` on division by zero.
+    /// NULL if either operand is NULL.
+    pub fn checked_div(self, rhs: Self) -> Result<Option<Self>> {
+        match (self, rhs) {
+            (SqlValue::Null, _) | (_, SqlValue::Null) => Ok(Some(SqlValue::Null)),
+            (SqlValue::NotNull(lhs_nn), SqlValue::NotNull(rhs_nn)) => {
+                lhs_nn.checked_div(rhs_nn).map(|v| v.map(SqlValue::NotNull))
+            }
+        }
+    }
+
+    /// `Ok(None)` on remainder by zero.
+    /// NULL if either operand is NULL.
+    pub fn checked_rem(self, rhs: Self) -> Result<Option<Self>> {
+        match (self, rhs) {
+            (SqlValue::Null, _) | (_, SqlValue::Null) => Ok(Some(SqlValue::Null)),
+            (SqlValue::NotNull(lhs_nn), SqlValue::NotNull(rhs_nn)) => {
+                lhs_nn.checked_rem(rhs_nn).map(|v| v.map(SqlValue::NotNull))
+            }
+        }
+    }
+}
+
 #[cfg(test)]
 impl SqlValue {
     pub fn unwrap(self) -> NnSqlValue {
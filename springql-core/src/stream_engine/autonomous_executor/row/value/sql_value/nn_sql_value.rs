@@ -4,7 +4,7 @@ use std::{
     fmt::Display,
     hash::Hash,
     mem::size_of,
-    ops::{Add, Mul},
+    ops::{Add, Mul, Sub},
 };
 
 use anyhow::anyhow;
@@ -285,11 +285,16 @@ impl NnSqlValue {
                         let (self_f32, other_f32) = (self.unpack::<f32>()?, other.unpack::<f32>()?);
                         Ok(SqlCompareResult::from(self_f32.partial_cmp(&other_f32)))
                     }
-                    _ => Err(SpringError::Sql(anyhow!(
-                        "Cannot compare {:?} and {:?}",
-                        self_n,
-                        other_n
-                    ))),
+                    (NumericComparableType::I64Loose(_), NumericComparableType::U64Loose(_))
+                    | (NumericComparableType::U64Loose(_), NumericComparableType::I64Loose(_)) => {
+                        let (self_i128, other_i128) = (self.to_i128()?, other.to_i128()?);
+                        Ok(SqlCompareResult::from(self_i128.cmp(&other_i128)))
+                    }
+                    // Either side is FLOAT: compare in f64 so that integers up to 2^53 stay exact.
+                    _ => {
+                        let (self_f64, other_f64) = (self.to_f64()?, other.to_f64()?);
+                        Ok(SqlCompareResult::from(self_f64.partial_cmp(&other_f64)))
+                    }
                 }
             }
             (SqlType::StringComparableLoose(self_s), SqlType::StringComparableLoose(other_s)) => {
@@ -312,6 +317,13 @@ impl NnSqlValue {
                 );
                 Ok(SqlCompareResult::from(self_t.cmp(&other_t)))
             }
+            (SqlType::DurationComparable, SqlType::DurationComparable) => {
+                let (self_d, other_d) = (
+                    self.unpack::<SpringEventDuration>()?,
+                    other.unpack::<SpringEventDuration>()?,
+                );
+                Ok(SqlCompareResult::from(self_d.cmp(&other_d)))
+            }
             (_, _) => Err(SpringError::Sql(anyhow!(
                 "`self` and `other` are not in comparable type - self: {:?}, other: {:?}",
                 self,
@@ -320,15 +332,50 @@ impl NnSqlValue {
         }
     }
 
+    /// Widens an integer value for comparison between signed and unsigned types.
+    fn to_i128(&self) -> Result<i128> {
+        match self {
+            NnSqlValue::UnsignedInteger(_) | NnSqlValue::UnsignedBigInt(_) => {
+                self.unpack::<u64>().map(i128::from)
+            }
+            _ => self.unpack::<i64>().map(i128::from),
+        }
+    }
+
+    /// Widens a numeric value into f64 (for comparison between integer and float types, and for AVG).
+    ///
+    /// # Failures
+    ///
+    /// - `SpringError::Sql` when:
+    ///   - inner value is not numeric
+    pub fn to_f64(&self) -> Result<f64> {
+        match self {
+            NnSqlValue::Float(f) => Ok(f64::from(f.into_inner())),
+            NnSqlValue::UnsignedInteger(_) | NnSqlValue::UnsignedBigInt(_) => {
+                self.unpack::<u64>().map(|u| u as f64)
+            }
+            _ => self.unpack::<i64>().map(|i| i as f64),
+        }
+    }
+
     /// # Failures
     ///
     /// - `SpringError::Sql` when:
     ///   - inner value cannot negate
     pub fn negate(self) -> Result<Self> {
         match self {
-            NnSqlValue::SmallInt(v) => Ok(Self::SmallInt(-v)),
-            NnSqlValue::Integer(v) => Ok(Self::Integer(-v)),
-            NnSqlValue::BigInt(v) => Ok(Self::BigInt(-v)),
+            NnSqlValue::SmallInt(v) => v
+                .checked_neg()
+                .map(Self::SmallInt)
+                .ok_or_else(|| SpringError::Sql(anyhow!("integer overflow: -{}", v))),
+            NnSqlValue::Integer(v) => v
+                .checked_neg()
+                .map(Self::Integer)
+                .ok_or_else(|| SpringError::Sql(anyhow!("integer overflow: -{}", v))),
+            NnSqlValue::BigInt(v) => v
+                .checked_neg()
+                .map(Self::BigInt)
+                .ok_or_else(|| SpringError::Sql(anyhow!("integer overflow: -{}", v))),
             NnSqlValue::Float(v) => Ok(Self::Float(-v)),
 
             NnSqlValue::UnsignedInteger(_)
@@ -362,73 +409,166 @@ impl From<NnSqlValue> for serde_json::Value {
     }
 }
 
-impl Add for NnSqlValue {
-    type Output = Result<Self>;
+/// Arithmetic operators on numeric values.
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+enum NumericOp {
+    Add,
+    Sub,
+    Mul,
+    Div,
+    Rem,
+}
+
+impl Display for NumericOp {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let s = match self {
+            NumericOp::Add => "+",
+            NumericOp::Sub => "-",
+            NumericOp::Mul => "*",
+            NumericOp::Div => "/",
+            NumericOp::Rem => "%",
+        };
+        write!(f, "{}", s)
+    }
+}
+
+impl NnSqlValue {
+    /// Integer operands result in BIGINT (or UNSIGNED BIGINT if both are unsigned).
+    /// If either operand is FLOAT, the result is FLOAT.
+    ///
+    /// Division (or remainder) by zero must be excluded by the caller.
+    ///
+    /// # Failures
+    ///
+    /// - `SpringError::Sql` when:
+    ///   - either operand is not numeric.
+    ///   - integer result overflows.
+    fn numeric_op(self, rhs: Self, op: NumericOp) -> Result<Self> {
+        let overflow = || SpringError::Sql(anyhow!("integer overflow: {} {} {}", self, op, rhs));
 
-    fn add(self, rhs: Self) -> Self::Output {
         match (self.sql_type(), rhs.sql_type()) {
             (SqlType::NumericComparable(ref self_n), SqlType::NumericComparable(ref rhs_n)) => {
                 match (self_n, rhs_n) {
                     (NumericComparableType::I64Loose(_), NumericComparableType::I64Loose(_)) => {
                         let (self_i64, rhs_i64) = (self.unpack::<i64>()?, rhs.unpack::<i64>()?);
-                        Ok(Self::BigInt(self_i64 + rhs_i64))
+                        let v = match op {
+                            NumericOp::Add => self_i64.checked_add(rhs_i64),
+                            NumericOp::Sub => self_i64.checked_sub(rhs_i64),
+                            NumericOp::Mul => self_i64.checked_mul(rhs_i64),
+                            NumericOp::Div => self_i64.checked_div(rhs_i64),
+                            NumericOp::Rem => self_i64.checked_rem(rhs_i64),
+                        }
+                        .ok_or_else(overflow)?;
+                        Ok(Self::BigInt(v))
                     }
                     (NumericComparableType::U64Loose(_), NumericComparableType::U64Loose(_)) => {
                         let (self_u64, rhs_u64) = (self.unpack::<u64>()?, rhs.unpack::<u64>()?);
-                        Ok(Self::UnsignedBigInt(self_u64 + rhs_u64))
+                        let v = match op {
+                            NumericOp::Add => self_u64.checked_add(rhs_u64),
+                            NumericOp::Sub => self_u64.checked_sub(rhs_u64),
+                            NumericOp::Mul => self_u64.checked_mul(rhs_u64),
+                            NumericOp::Div => self_u64.checked_div(rhs_u64),
+                            NumericOp::Rem => self_u64.checked_rem(rhs_u64),
+                        }
+                        .ok_or_else(overflow)?;
+                        Ok(Self::UnsignedBigInt(v))
                     }
-                    (NumericComparableType::F32Loose(_), NumericComparableType::F32Loose(_)) => {
-                        let (self_f32, rhs_f32) = (self.unpack::<f32>()?, rhs.unpack::<f32>()?);
-                        Ok(Self::Float(OrderedFloat(self_f32 + rhs_f32)))
+                    (NumericComparableType::I64Loose(_), NumericComparableType::U64Loose(_))
+                    | (NumericComparableType::U64Loose(_), NumericComparableType::I64Loose(_)) => {
+                        let (self_i128, rhs_i128) = (self.to_i128()?, rhs.to_i128()?);
+                        let v = match op {
+                            NumericOp::Add => self_i128.checked_add(rhs_i128),
+                            NumericOp::Sub => self_i128.checked_sub(rhs_i128),
+                            NumericOp::Mul => self_i128.checked_mul(rhs_i128),
+                            NumericOp::Div => self_i128.checked_div(rhs_i128),
+                            NumericOp::Rem => self_i128.checked_rem(rhs_i128),
+                        }
+                        .and_then(|v| i64::try_from(v).ok())
+                        .ok_or_else(overflow)?;
+                        Ok(Self::BigInt(v))
+                    }
+                    // Either side is FLOAT
+                    _ => {
+                        let (self_f64, rhs_f64) = (self.to_f64()?, rhs.to_f64()?);
+                        let v = match op {
+                            NumericOp::Add => self_f64 + rhs_f64,
+                            NumericOp::Sub => self_f64 - rhs_f64,
+                            NumericOp::Mul => self_f64 * rhs_f64,
+                            NumericOp::Div => self_f64 / rhs_f64,
+                            NumericOp::Rem => self_f64 % rhs_f64,
+                        };
+                        Ok(Self::Float(OrderedFloat(v as f32)))
                     }
-                    _ => Err(SpringError::Sql(anyhow!(
-                        "Cannot add {:?} and {:?}",
-                        self_n,
-                        rhs_n
-                    ))),
                 }
             }
             (_, _) => Err(SpringError::Sql(anyhow!(
-                "`self` + `rhs` is undefined - self: {:?}, other: {:?}",
+                "`self` {} `rhs` is undefined - self: {:?}, other: {:?}",
+                op,
                 self,
                 rhs
             ))),
         }
     }
 }
+
+impl Add for NnSqlValue {
+    type Output = Result<Self>;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        self.numeric_op(rhs, NumericOp::Add)
+    }
+}
+impl Sub for NnSqlValue {
+    type Output = Result<Self>;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        self.numeric_op(rhs, NumericOp::Sub)
+    }
+}
 impl Mul for NnSqlValue {
     type Output = Result<Self>;
 
     fn mul(self, rhs: Self) -> Self::Output {
-        match (self.sql_type(), rhs.sql_type()) {
-            (SqlType::NumericComparable(ref self_n), SqlType::NumericComparable(ref rhs_n)) => {
-                match (self_n, rhs_n) {
-                    (NumericComparableType::I64Loose(_), NumericComparableType::I64Loose(_)) => {
-                        let (self_i64, rhs_i64) = (self.unpack::<i64>()?, rhs.unpack::<i64>()?);
-                        Ok(Self::BigInt(self_i64 * rhs_i64))
-                    }
-                    (NumericComparableType::U64Loose(_), NumericComparableType::U64Loose(_)) => {
-                        let (self_u64, rhs_u64) = (self.unpack::<u64>()?, rhs.unpack::<u64>()?);
-                        Ok(Self::UnsignedBigInt(self_u64 * rhs_u64))
-                    }
-                    (NumericComparableType::F32Loose(_), NumericComparableType::F32Loose(_)) => {
-                        let (self_f32, rhs_f32) = (self.unpack::<f32>()?, rhs.unpack::<f32>()?);
-                        Ok(Self::Float(OrderedFloat(self_f32 * rhs_f32)))
-                    }
-                    _ => Err(SpringError::Sql(anyhow!(
-                        "Cannot multiply {:?} by {:?}",
-                        self_n,
-                        rhs_n
-                    ))),
-                }
-            }
-            (_, _) => Err(SpringError::Sql(anyhow!(
-                "`self` + `rhs` is undefined - self: {:?}, other: {:?}",
-                self,
-                rhs
-            ))),
+        self.numeric_op(rhs, NumericOp::Mul)
+    }
+}
+
+impl NnSqlValue {
+    /// `Ok(None)` on division by zero.
+    ///
+    /// # Failures
+    ///
+    /// - `SpringError::Sql` when:
+    ///   - either operand is not numeric.
+    ///   - integer result overflows.
+    pub fn checked_div(self, rhs: Self) -> Result<Option<Self>> {
+        if rhs.is_zero() {
+            Ok(None)
+        } else {
+            self.numeric_op(rhs, NumericOp::Div).map(Some)
+        }
+    }
+
+    /// `Ok(None)` on remainder by zero.
+    ///
+    /// # Failures
+    ///
+    /// - `SpringError::Sql` when:
+    ///   - either operand is not numeric.
+    ///   - integer result overflows.
+    pub fn checked_rem(self, rhs: Self) -> Result<Option<Self>> {
+        if rhs.is_zero() {
+            Ok(None)
+        } else {
+            self.numeric_op(rhs, NumericOp::Rem).map(Some)
         }
     }
+
+    /// `false` for non-numeric values.
+    fn is_zero(&self) -> bool {
+        matches!(self.sql_type(), SqlType::NumericComparable(_))
+            && self.to_f64().map_or(false, |v| v == 0.0)
+    }
 }
 
 #[cfg(test)]
@@ -488,4 +628,68 @@ mod tests {
             b"hello".to_vec()
         );
     }
+
+    #[test]
+    fn test_arithmetic() -> Result<()> {
+        assert_eq!(
+            (NnSqlValue::Integer(7) - NnSqlValue::SmallInt(10))?,
+            NnSqlValue::BigInt(-3)
+        );
+        assert_eq!(
+            NnSqlValue::Integer(7).checked_div(NnSqlValue::Integer(2))?,
+            Some(NnSqlValue::BigInt(3)),
+            "integer division truncates"
+        );
+        assert_eq!(
+            NnSqlValue::Integer(-7).checked_rem(NnSqlValue::Integer(2))?,
+            Some(NnSqlValue::BigInt(-1))
+        );
+        assert_eq!(
+            NnSqlValue::Integer(7).checked_div(NnSqlValue::Float(OrderedFloat(2.0)))?,
+            Some(NnSqlValue::Float(OrderedFloat(3.5)))
+        );
+        assert_eq!(
+            (NnSqlValue::UnsignedInteger(1) - NnSqlValue::Integer(2))?,
+            NnSqlValue::BigInt(-1)
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_division_by_zero() -> Result<()> {
+        assert!(NnSqlValue::Integer(1)
+            .checked_div(NnSqlValue::Integer(0))?
+            .is_none());
+        assert!(NnSqlValue::UnsignedInteger(1)
+            .checked_rem(NnSqlValue::UnsignedInteger(0))?
+            .is_none());
+        assert!(NnSqlValue::Float(OrderedFloat(1.0))
+            .checked_div(NnSqlValue::Integer(0))?
+            .is_none());
+        Ok(())
+    }
+
+    #[test]
+    fn test_integer_overflow() {
+        assert!(matches!(
+            NnSqlValue::BigInt(i64::MAX) + NnSqlValue::BigInt(1),
+            Err(SpringError::Sql(_))
+        ));
+        assert!(matches!(
+            NnSqlValue::BigInt(i64::MIN) * NnSqlValue::BigInt(-1),
+            Err(SpringError::Sql(_))
+        ));
+        assert!(matches!(
+            NnSqlValue::BigInt(i64::MIN).checked_div(NnSqlValue::BigInt(-1)),
+            Err(SpringError::Sql(_))
+        ));
+        assert!(matches!(
+            NnSqlValue::UnsignedBigInt(0) - NnSqlValue::UnsignedBigInt(1),
+            Err(SpringError::Sql(_))
+        ));
+        assert!(matches!(
+            NnSqlValue::SmallInt(i16::MIN).negate(),
+            Err(SpringError::Sql(_))
+        ));
+    }
 }
@@ -8,16 +8,20 @@ mod source_task;
 mod task_context;
 mod window;
 
+mod mqtt_client;
 mod processed_rows;
+mod reconnect;
 
 pub use processed_rows::ProcessedRows;
-pub use sink_task::SinkWriterRepository;
+pub use reconnect::ConnectionEvents;
+pub use sink_task::{SinkWriterContext, SinkWriterRepository};
 pub use source_task::{
-    NetClientSourceReader, NetServerSourceReader, SourceReader, SourceReaderRepository, SourceTask,
+    NetClientSourceReader, NetServerSourceReader, SourceReader, SourceReaderContext,
+    SourceReaderRepository, SourceTask,
 };
 pub use task_context::TaskContext;
 pub use tuple::Tuple;
-pub use window::Window;
+pub use window::{AggregatedAndGroupingValues, Window};
 
 use crate::{
     api::error::Result,
@@ -61,6 +65,15 @@ impl Task {
         }
     }
 
+    /// `true` after the task emitted all its rows, e.g. its source reader reached EOF.
+    pub fn is_end_of_stream(&self) -> bool {
+        match self {
+            Task::Pump(pump_task) => pump_task.is_end_of_stream(),
+            Task::Source(source_task) => source_task.is_end_of_stream(),
+            Task::Sink(_) => false,
+        }
+    }
+
     pub fn run(&self, context: &TaskContext) -> Result<TaskRunResult> {
         match self {
             Task::Pump(pump_task) => pump_task.run(context),
@@ -0,0 +1,575 @@
+// This file is part of https://github.com/SpringQL/SpringQL which is licensed under MIT OR Apache-2.0. See file LICENSE-MIT or LICENSE-APACHE for full license details.
+
+mod packet;
+
+use std::{
+    collections::VecDeque,
+    io::{self, Read, Write},
+    net::{Shutdown, TcpStream, ToSocketAddrs},
+    sync::{
+        mpsc::{self, RecvTimeoutError, TrySendError},
+        Arc, Mutex,
+    },
+    thread,
+    time::{Duration, Instant},
+};
+
+use anyhow::{anyhow, Context};
+
+use self::packet::Packet;
+use crate::{
+    api::{
+        error::{foreign_info::ForeignInfo, Result, SpringError},
+        SpringSinkWriterConfig, SpringSourceReaderConfig,
+    },
+    pipeline::{MqttOptions, MqttQos},
+};
+
+/// How often the receiver thread checks keep alive.
+const POLL_INTERVAL: Duration = Duration::from_millis(100);
+
+/// How often the receiver thread retries queueing an event while the queue is full.
+const QUEUE_FULL_RETRY_INTERVAL: Duration = Duration::from_millis(10);
+
+/// Sink writers only receive acknowledgements, each of which is waited for before the next publish.
+const SINK_QUEUE_CAPACITY: usize = 64;
+
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+pub struct MqttClientConfig {
+    connect_timeout: Duration,
+    keep_alive_sec: u16,
+    queue_capacity: usize,
+}
+
+impl From<&SpringSourceReaderConfig> for MqttClientConfig {
+    fn from(config: &SpringSourceReaderConfig) -> Self {
+        Self {
+            connect_timeout: Duration::from_millis(config.mqtt_connect_timeout_msec as u64),
+            keep_alive_sec: config.mqtt_keep_alive_sec,
+            queue_capacity: config.mqtt_queue_capacity as usize,
+        }
+    }
+}
+
+impl From<&SpringSinkWriterConfig> for MqttClientConfig {
+    fn from(config: &SpringSinkWriterConfig) -> Self {
+        Self {
+            connect_timeout: Duration::from_millis(config.mqtt_connect_timeout_msec as u64),
+            keep_alive_sec: config.mqtt_keep_alive_sec,
+            queue_capacity: SINK_QUEUE_CAPACITY,
+        }
+    }
+}
+
+/// Application message received from a broker.
+#[derive(Clone, Eq, PartialEq, Debug)]
+pub struct MqttMessage {
+    pub topic: String,
+    pub payload: Vec<u8>,
+    /// `Some` in QoS 1. The message must be acknowledged by [MqttClient::ack()].
+    pub packet_id: Option<u16>,
+}
+
+#[derive(Debug)]
+enum MqttEvent {
+    Message(MqttMessage),
+    PubAck(u16),
+    SubAck {
+        packet_id: u16,
+        return_codes: Vec<u8>,
+    },
+    Disconnected(String),
+}
+
+#[derive(Debug)]
+struct Writer {
+    stream: TcpStream,
+    last_write_at: Instant,
+}
+
+impl Writer {
+    fn write(&mut self, packet: &[u8]) -> io::Result<()> {
+        self.stream.write_all(packet)?;
+        self.last_write_at = Instant::now();
+        Ok(())
+    }
+}
+
+/// Minimal MQTT 3.1.1 client (QoS 0 and 1).
+///
+/// A receiver thread reads packets from the broker and keeps the connection alive.
+/// Received messages are queued up to `queue_capacity`; the receiver thread stops reading from the broker while the queue is full,
+/// but still sends PINGREQ so that the broker does not close the connection.
+/// QoS 1 messages are not acknowledged until the user of the client calls `ack()`, so that the broker redelivers messages lost before processed.
+#[derive(Debug)]
+pub struct MqttClient {
+    /// `host:port`
+    broker: String,
+
+    writer: Arc<Mutex<Writer>>,
+    /// Wrapped in Mutex to be Sync.
+    events: Mutex<mpsc::Receiver<MqttEvent>>,
+
+    /// Messages received while waiting for acknowledgements.
+    pending_messages: VecDeque<MqttMessage>,
+
+    last_packet_id: u16,
+}
+
+impl MqttClient {
+    /// Connects to the broker in `options` and waits for its acknowledgement.
+    ///
+    /// # Failure
+    ///
+    /// - `SpringError::ForeignIo` when:
+    ///   - failed to connect to the broker.
+    ///   - the broker refused the connection (e.g. bad username or password).
+    pub fn connect(options: &MqttOptions, config: MqttClientConfig) -> Result<Self> {
+        let broker = format!("{}:{}", options.broker_host, options.broker_port);
+        let foreign_io = |e: anyhow::Error| SpringError::ForeignIo {
+            source: e,
+            foreign_info: ForeignInfo::Mqtt(broker.clone()),
+        };
+
+        let sock_addr = (options.broker_host.as_str(), options.broker_port)
+            .to_socket_addrs()
+            .context("failed to resolve broker address")
+            .and_then(|mut addrs| addrs.next().context("no address for broker host"))
+            .map_err(foreign_io)?;
+        let mut stream = TcpStream::connect_timeout(&sock_addr, config.connect_timeout)
+            .context("failed to connect to broker")
+            .map_err(foreign_io)?;
+        stream
+            .set_read_timeout(Some(config.connect_timeout))
+            .and_then(|_| stream.set_write_timeout(Some(config.connect_timeout)))
+            .and_then(|_| stream.set_nodelay(true))
+            .context("failed to set up connection to broker")
+            .map_err(foreign_io)?;
+
+        let connect = packet::encode_connect(
+            &options.client_id,
+            config.keep_alive_sec,
+            options.clean_session,
+            options.username.as_deref(),
+            options.password.as_deref(),
+        )
+        .context("failed to encode CONNECT")
+        .map_err(foreign_io)?;
+        stream
+            .write_all(&connect)
+            .context("failed to send CONNECT")
+            .map_err(foreign_io)?;
+
+        let mut buf = Vec::new();
+        let (connack, len) = read_packet(&mut stream, &mut buf)
+            .context("failed to receive CONNACK")
+            .map_err(foreign_io)?;
+        buf.drain(..len);
+        match connack {
+            Packet::ConnAck { return_code: 0, .. } => {}
+            Packet::ConnAck { return_code, .. } => {
+                return Err(foreign_io(anyhow!(
+                    "broker refused connection: {}",
+                    connack_reason(return_code)
+                )))
+            }
+            p => return Err(foreign_io(anyhow!("expected CONNACK but got {:?}", p))),
+        }
+
+        stream
+            .set_read_timeout(Some(POLL_INTERVAL))
+            .context("failed to set timeout to broker connection")
+            .map_err(foreign_io)?;
+        let read_stream = stream
+            .try_clone()
+            .context("failed to clone broker connection")
+            .map_err(foreign_io)?;
+
+        let writer = Arc::new(Mutex::new(Writer {
+            stream,
+            last_write_at: Instant::now(),
+        }));
+        let (tx, rx) = mpsc::sync_channel(config.queue_capacity);
+        {
+            let writer = writer.clone();
+            let keep_alive = Duration::from_secs(config.keep_alive_sec as u64);
+            let _ = thread::Builder::new()
+                .name(format!("MqttClient({})", broker))
+                .spawn(move || receive_loop(read_stream, buf, writer, tx, keep_alive));
+        }
+
+        Ok(Self {
+            broker,
+            writer,
+            events: Mutex::new(rx),
+            pending_messages: VecDeque::new(),
+            last_packet_id: 0,
+        })
+    }
+
+    /// # Failure
+    ///
+    /// - `SpringError::ForeignIo` when:
+    ///   - the connection is lost.
+    ///   - the broker rejected any of `topic_filters` or did not acknowledge within `timeout`.
+    ///   - any of `topic_filters` is too long.
+    pub fn subscribe(
+        &mut self,
+        topic_filters: &[String],
+        qos: MqttQos,
+        timeout: Duration,
+    ) -> Result<()> {
+        let packet_id = self.next_packet_id();
+        let subscribe = packet::encode_subscribe(packet_id, topic_filters, qos.into())
+            .context("failed to encode SUBSCRIBE")
+            .map_err(|e| self.foreign_io(e))?;
+        self.write(&subscribe)?;
+
+        let deadline = Instant::now() + timeout;
+        loop {
+            match self.recv_event(deadline)? {
+                Some(MqttEvent::SubAck {
+                    packet_id: acked,
+                    return_codes,
+                }) if acked == packet_id => {
+                    return match return_codes.iter().position(|code| *code == 0x80) {
+                        Some(i) => Err(self.foreign_io(anyhow!(
+                            "broker rejected subscription to {}",
+                            topic_filters.get(i).map_or("?", String::as_str)
+                        ))),
+                        None => Ok(()),
+                    };
+                }
+                Some(MqttEvent::Message(message)) => self.pending_messages.push_back(message),
+                Some(_) => {}
+                None => return Err(self.foreign_io(anyhow!("SUBACK timed out"))),
+            }
+        }
+    }
+
+    /// Publishes `payload`. In QoS 1, waits for the broker's acknowledgement.
+    ///
+    /// # Failure
+    ///
+    /// - `SpringError::ForeignIo` when:
+    ///   - the connection is lost.
+    /// - `SpringError::ForeignSourceTimeout` when:
+    ///   - the broker did not acknowledge within `ack_timeout` in QoS 1.
+    /// - `SpringError::InvalidFormat` when:
+    ///   - `topic` or `payload` is too long to be sent in a message.
+    pub fn publish(
+        &mut self,
+        topic: &str,
+        qos: MqttQos,
+        payload: &[u8],
+        ack_timeout: Duration,
+    ) -> Result<()> {
+        let encode = |packet_id| {
+            packet::encode_publish(topic, packet_id, payload).map_err(|e| {
+                SpringError::InvalidFormat {
+                    s: topic.to_string(),
+                    source: e.context("failed to encode PUBLISH"),
+                }
+            })
+        };
+
+        match qos {
+            MqttQos::AtMostOnce => self.write(&encode(None)?),
+            MqttQos::AtLeastOnce => {
+                let packet_id = self.next_packet_id();
+                self.write(&encode(Some(packet_id))?)?;
+
+                let deadline = Instant::now() + ack_timeout;
+                loop {
+                    match self.recv_event(deadline)? {
+                        Some(MqttEvent::PubAck(acked)) if acked == packet_id => return Ok(()),
+                        Some(MqttEvent::Message(message)) => {
+                            self.pending_messages.push_back(message)
+                        }
+                        Some(_) => {}
+                        None => {
+                            return Err(SpringError::ForeignSourceTimeout {
+                                source: anyhow!("PUBACK timed out"),
+                                foreign_info: ForeignInfo::Mqtt(self.broker.clone()),
+                            })
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    /// Returns a received message, or `None` if no message arrives within `timeout`.
+    ///
+    /// # Failure
+    ///
+    /// - `SpringError::ForeignIo` when:
+    ///   - the connection is lost.
+    pub fn recv(&mut self, timeout: Duration) -> Result<Option<MqttMessage>> {
+        if let Some(message) = self.pending_messages.pop_front() {
+            return Ok(Some(message));
+        }
+
+        let deadline = Instant::now() + timeout;
+        loop {
+            match self.recv_event(deadline)? {
+                Some(MqttEvent::Message(message)) => return Ok(Some(message)),
+                Some(_) => {}
+                None => return Ok(None),
+            }
+        }
+    }
+
+    /// Acknowledges a QoS 1 message received by `recv()`.
+    ///
+    /// # Failure
+    ///
+    /// - `SpringError::ForeignIo` when:
+    ///   - the connection is lost.
+    pub fn ack(&self, packet_id: u16) -> Result<()> {
+        self.write(&packet::encode_puback(packet_id))
+    }
+
+    fn recv_event(&mut self, deadline: Instant) -> Result<Option<MqttEvent>> {
+        let timeout = deadline.saturating_duration_since(Instant::now());
+        let event = self
+            .events
+            .get_mut()
+            .expect("never poisoned")
+            .recv_timeout(timeout);
+        match event {
+            Ok(MqttEvent::Disconnected(reason)) => Err(self.foreign_io(anyhow!(reason))),
+            Ok(event) => Ok(Some(event)),
+            Err(RecvTimeoutError::Timeout) => Ok(None),
+            Err(RecvTimeoutError::Disconnected) => {
+                Err(self.foreign_io(anyhow!("receiver thread has stopped")))
+            }
+        }
+    }
+
+    fn write(&self, packet: &[u8]) -> Result<()> {
+        self.writer
+            .lock()
+            .expect("never poisoned")
+            .write(packet)
+            .context("failed to send packet to broker")
+            .map_err(|e| self.foreign_io(e))
+    }
+
+    fn next_packet_id(&mut self) -> u16 {
+        // packet identifiers must be non-zero
+        self.last_packet_id = self.last_packet_id.checked_add(1).unwrap_or(1);
+        self.last_packet_id
+    }
+
+    fn foreign_io(&self, e: anyhow::Error) -> SpringError {
+        SpringError::ForeignIo {
+            source: e,
+            foreign_info: ForeignInfo::Mqtt(self.broker.clone()),
+        }
+    }
+}
+
+impl Drop for MqttClient {
+    fn drop(&mut self) {
+        if let Ok(mut writer) = self.writer.lock() {
+            let _ = writer.write(&packet::DISCONNECT);
+            // also stops the receiver thread
+            let _ = writer.stream.shutdown(Shutdown::Both);
+        }
+    }
+}
+
+/// Reads from the broker until a whole packet is in `buf`.
+fn read_packet(stream: &mut TcpStream, buf: &mut Vec<u8>) -> anyhow::Result<(Packet, usize)> {
+    let mut chunk = [0u8; 4096];
+    loop {
+        if let Some(packet) = packet::parse(buf)? {
+            return Ok(packet);
+        }
+        match stream.read(&mut chunk)? {
+            0 => return Err(anyhow!("broker closed the connection")),
+            n => buf.extend_from_slice(&chunk[..n]),
+        }
+    }
+}
+
+fn receive_loop(
+    mut stream: TcpStream,
+    mut buf: Vec<u8>,
+    writer: Arc<Mutex<Writer>>,
+    tx: mpsc::SyncSender<MqttEvent>,
+    keep_alive: Duration,
+) {
+    let mut chunk = [0u8; 4096];
+    let mut ping_sent_at: Option<Instant> = None;
+
+    let res: anyhow::Result<()> = (|| loop {
+        while let Some((packet, len)) = packet::parse(&buf)? {
+            buf.drain(..len);
+            let event = match packet {
+                Packet::Publish {
+                    topic,
+                    packet_id,
+                    payload,
+                } => MqttEvent::Message(MqttMessage {
+                    topic,
+                    payload,
+                    packet_id,
+                }),
+                Packet::PubAck(packet_id) => MqttEvent::PubAck(packet_id),
+                Packet::SubAck {
+                    packet_id,
+                    return_codes,
+                } => MqttEvent::SubAck {
+                    packet_id,
+                    return_codes,
+                },
+                Packet::PingResp => {
+                    ping_sent_at = None;
+                    continue;
+                }
+                Packet::ConnAck { .. } => return Err(anyhow!("unexpected CONNACK")),
+            };
+            // waits while the queue is full (backpressure to the broker)
+            if !send_event(&tx, event, &writer, keep_alive, &mut ping_sent_at)? {
+                // client is dropped
+                return Ok(());
+            }
+        }
+
+        match stream.read(&mut chunk) {
+            Ok(0) => return Err(anyhow!("broker closed the connection")),
+            Ok(n) => buf.extend_from_slice(&chunk[..n]),
+            Err(e)
+                if matches!(
+                    e.kind(),
+                    io::ErrorKind::TimedOut | io::ErrorKind::WouldBlock
+                ) => {}
+            Err(e) => return Err(e.into()),
+        }
+
+        if keep_alive > Duration::ZERO {
+            match ping_sent_at {
+                Some(sent_at) if sent_at.elapsed() > keep_alive => {
+                    return Err(anyhow!("broker did not respond to PINGREQ"))
+                }
+                Some(_) => {}
+                None => {
+                    let mut writer = writer.lock().expect("never poisoned");
+                    if writer.last_write_at.elapsed() >= keep_alive / 2 {
+                        writer.write(&packet::PINGREQ)?;
+                        ping_sent_at = Some(Instant::now());
+                    }
+                }
+            }
+        }
+    })();
+
+    if let Err(e) = res {
+        let _ = tx.send(MqttEvent::Disconnected(format!("{:#}", e)));
+    }
+}
+
+/// Queues `event`, sending PINGREQ to the broker while the queue is full.
+///
+/// Returns `false` if the client is dropped.
+fn send_event(
+    tx: &mpsc::SyncSender<MqttEvent>,
+    mut event: MqttEvent,
+    writer: &Mutex<Writer>,
+    keep_alive: Duration,
+    ping_sent_at: &mut Option<Instant>,
+) -> anyhow::Result<bool> {
+    loop {
+        match tx.try_send(event) {
+            Ok(()) => return Ok(true),
+            Err(TrySendError::Disconnected(_)) => return Ok(false),
+            Err(TrySendError::Full(e)) => {
+                event = e;
+
+                // PINGRESP is not read until the queue has room, so only the broker side of keep alive is maintained here.
+                *ping_sent_at = None;
+                if keep_alive > Duration::ZERO {
+                    let mut writer = writer.lock().expect("never poisoned");
+                    if writer.last_write_at.elapsed() >= keep_alive / 2 {
+                        writer.write(&packet::PINGREQ)?;
+                    }
+                }
+                thread::sleep(QUEUE_FULL_RETRY_INTERVAL);
+            }
+        }
+    }
+}
+
+fn connack_reason(return_code: u8) -> &'static str {
+    match return_code {
+        1 => "unacceptable protocol version",
+        2 => "identifier rejected",
+        3 => "server unavailable",
+        4 => "bad user name or password",
+        5 => "not authorized",
+        _ => "unknown return code",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::net::TcpListener;
+
+    use super::*;
+    use crate::pipeline::{ColumnName, MqttPayloadFormat};
+
+    #[test]
+    fn test_keep_alive_while_queue_is_full() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let port = listener.local_addr().unwrap().port();
+
+        let broker = thread::spawn(move || {
+            let (mut stream, _) = listener.accept().unwrap();
+
+            let mut header = [0u8; 2];
+            stream.read_exact(&mut header).unwrap();
+            let mut connect = vec![0u8; header[1] as usize];
+            stream.read_exact(&mut connect).unwrap();
+            stream.write_all(&[0x20, 0x02, 0x00, 0x00]).unwrap();
+
+            // more messages than the queue capacity
+            for _ in 0..3 {
+                stream
+                    .write_all(&packet::encode_publish("t", None, b"{}").unwrap())
+                    .unwrap();
+            }
+
+            stream
+                .set_read_timeout(Some(Duration::from_secs(3)))
+                .unwrap();
+            let mut pingreq = [0u8; 2];
+            stream.read_exact(&mut pingreq).unwrap();
+            pingreq
+        });
+
+        let options = MqttOptions {
+            broker_host: "127.0.0.1".to_string(),
+            broker_port: port,
+            topics: vec!["t".to_string()],
+            qos: MqttQos::AtMostOnce,
+            client_id: "c".to_string(),
+            clean_session: true,
+            username: None,
+            password: None,
+            format: MqttPayloadFormat::Json,
+            topic_column: ColumnName::new("topic".to_string()),
+            payload_column: ColumnName::new("payload".to_string()),
+        };
+        let config = MqttClientConfig {
+            connect_timeout: Duration::from_secs(1),
+            keep_alive_sec: 1,
+            queue_capacity: 1,
+        };
+        let _client = MqttClient::connect(&options, config).unwrap();
+
+        // messages are not received by the client
+        assert_eq!(broker.join().unwrap(), packet::PINGREQ);
+    }
+}
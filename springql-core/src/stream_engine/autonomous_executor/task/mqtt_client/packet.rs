@@ -0,0 +1,332 @@
+// This file is part of https://github.com/SpringQL/SpringQL which is licensed under MIT OR Apache-2.0. See file LICENSE-MIT or LICENSE-APACHE for full license details.
+
+//! Subset of MQTT 3.1.1 control packets used by [MqttClient](super::MqttClient).
+
+use anyhow::{anyhow, bail, ensure, Context};
+
+pub const PINGREQ: [u8; 2] = [0xc0, 0x00];
+pub const DISCONNECT: [u8; 2] = [0xe0, 0x00];
+
+/// Maximum remaining length (variable header and payload) of a packet.
+const MAX_REMAINING_LENGTH: usize = 268_435_455;
+
+/// Control packets sent from a broker to a client.
+#[derive(Clone, Eq, PartialEq, Debug)]
+pub enum Packet {
+    ConnAck {
+        session_present: bool,
+        return_code: u8,
+    },
+    Publish {
+        topic: String,
+        /// `Some` in QoS 1.
+        packet_id: Option<u16>,
+        payload: Vec<u8>,
+    },
+    PubAck(u16),
+    SubAck {
+        packet_id: u16,
+        return_codes: Vec<u8>,
+    },
+    PingResp,
+}
+
+/// # Failure
+///
+/// - `client_id`, `username` or `password` is longer than 65,535 bytes.
+/// - `password` is given without `username` (not allowed in MQTT 3.1.1).
+pub fn encode_connect(
+    client_id: &str,
+    keep_alive_sec: u16,
+    clean_session: bool,
+    username: Option<&str>,
+    password: Option<&str>,
+) -> anyhow::Result<Vec<u8>> {
+    ensure!(
+        username.is_some() || password.is_none(),
+        "password requires user name"
+    );
+
+    let mut flags = 0u8;
+    if clean_session {
+        flags |= 0x02;
+    }
+    if username.is_some() {
+        flags |= 0x80;
+    }
+    if password.is_some() {
+        flags |= 0x40;
+    }
+
+    let mut body = Vec::new();
+    put_bytes(&mut body, b"MQTT")?;
+    body.push(4); // protocol level 3.1.1
+    body.push(flags);
+    body.extend_from_slice(&keep_alive_sec.to_be_bytes());
+    put_bytes(&mut body, client_id.as_bytes()).context("client ID")?;
+    for field in [username, password].iter().flatten() {
+        put_bytes(&mut body, field.as_bytes()).context("user name or password")?;
+    }
+
+    packet(0x10, body)
+}
+
+/// PUBLISH in QoS 1 if `packet_id` is `Some`, otherwise in QoS 0.
+///
+/// # Failure
+///
+/// - `topic` is longer than 65,535 bytes.
+/// - the packet exceeds the maximum remaining length (about 256 MB) because of large `payload`.
+pub fn encode_publish(
+    topic: &str,
+    packet_id: Option<u16>,
+    payload: &[u8],
+) -> anyhow::Result<Vec<u8>> {
+    let mut body = Vec::new();
+    put_bytes(&mut body, topic.as_bytes()).context("topic name")?;
+    let header = match packet_id {
+        Some(packet_id) => {
+            body.extend_from_slice(&packet_id.to_be_bytes());
+            0x32
+        }
+        None => 0x30,
+    };
+    body.extend_from_slice(payload);
+
+    packet(header, body)
+}
+
+pub fn encode_puback(packet_id: u16) -> Vec<u8> {
+    packet(0x40, packet_id.to_be_bytes().to_vec()).expect("PUBACK has 2-byte body")
+}
+
+/// # Failure
+///
+/// - any of `topic_filters` is longer than 65,535 bytes.
+/// - the packet exceeds the maximum remaining length (about 256 MB).
+pub fn encode_subscribe(
+    packet_id: u16,
+    topic_filters: &[String],
+    qos: u8,
+) -> anyhow::Result<Vec<u8>> {
+    let mut body = packet_id.to_be_bytes().to_vec();
+    for topic_filter in topic_filters {
+        put_bytes(&mut body, topic_filter.as_bytes()).context("topic filter")?;
+        body.push(qos);
+    }
+
+    packet(0x82, body)
+}
+
+/// Parses a packet at the head of `buf`.
+///
+/// Returns the packet and its length in bytes, or `None` if `buf` does not contain a whole packet yet.
+pub fn parse(buf: &[u8]) -> anyhow::Result<Option<(Packet, usize)>> {
+    let (remaining_len, header_len) = match parse_remaining_length(buf)? {
+        Some(len) => len,
+        None => return Ok(None),
+    };
+    let packet_len = header_len + remaining_len;
+    if buf.len() < packet_len {
+        return Ok(None);
+    }
+
+    let header = buf[0];
+    let body = &buf[header_len..packet_len];
+
+    let packet = match header >> 4 {
+        2 => {
+            ensure!(body.len() == 2, "malformed CONNACK");
+            Packet::ConnAck {
+                session_present: body[0] & 0x01 != 0,
+                return_code: body[1],
+            }
+        }
+        3 => {
+            let qos = (header >> 1) & 0x03;
+            let (topic, mut rest) = take_bytes(body)?;
+            let topic = String::from_utf8(topic.to_vec())
+                .map_err(|_| anyhow!("topic name is not valid UTF-8"))?;
+            let packet_id = match qos {
+                0 => None,
+                1 | 2 => {
+                    let (packet_id, r) = take_u16(rest)?;
+                    rest = r;
+                    Some(packet_id)
+                }
+                _ => bail!("malformed PUBLISH (QoS 3)"),
+            };
+            Packet::Publish {
+                topic,
+                packet_id,
+                payload: rest.to_vec(),
+            }
+        }
+        4 => Packet::PubAck(take_u16(body)?.0),
+        9 => {
+            let (packet_id, return_codes) = take_u16(body)?;
+            Packet::SubAck {
+                packet_id,
+                return_codes: return_codes.to_vec(),
+            }
+        }
+        13 => Packet::PingResp,
+        t => bail!("unexpected packet type from broker: {}", t),
+    };
+
+    Ok(Some((packet, packet_len)))
+}
+
+fn packet(header: u8, body: Vec<u8>) -> anyhow::Result<Vec<u8>> {
+    let mut packet = vec![header];
+    packet.extend(encode_remaining_length(body.len())?);
+    packet.extend(body);
+    Ok(packet)
+}
+
+/// Variable-length encoding of 1 to 4 bytes.
+fn encode_remaining_length(mut len: usize) -> anyhow::Result<Vec<u8>> {
+    ensure!(
+        len <= MAX_REMAINING_LENGTH,
+        "packet of {} bytes exceeds the maximum remaining length ({} bytes)",
+        len,
+        MAX_REMAINING_LENGTH
+    );
+
+    let mut bytes = Vec::with_capacity(4);
+    loop {
+        let mut byte = (len % 128) as u8;
+        len /= 128;
+        if len > 0 {
+            byte |= 0x80;
+        }
+        bytes.push(byte);
+        if len == 0 {
+            return Ok(bytes);
+        }
+    }
+}
+
+/// Returns (remaining length, fixed header length).
+fn parse_remaining_length(buf: &[u8]) -> anyhow::Result<Option<(usize, usize)>> {
+    let mut len = 0usize;
+    for i in 0..4 {
+        let byte = match buf.get(1 + i) {
+            Some(byte) => *byte,
+            None => return Ok(None),
+        };
+        len += ((byte & 0x7f) as usize) << (7 * i);
+        if byte & 0x80 == 0 {
+            return Ok(Some((len, 2 + i)));
+        }
+    }
+    bail!("malformed remaining length")
+}
+
+/// Puts a length-prefixed field (e.g. UTF-8 string).
+fn put_bytes(buf: &mut Vec<u8>, bytes: &[u8]) -> anyhow::Result<()> {
+    let len = u16::try_from(bytes.len())
+        .map_err(|_| anyhow!("{} bytes is longer than 65,535 bytes", bytes.len()))?;
+    buf.extend_from_slice(&len.to_be_bytes());
+    buf.extend_from_slice(bytes);
+    Ok(())
+}
+
+fn take_u16(buf: &[u8]) -> anyhow::Result<(u16, &[u8])> {
+    ensure!(buf.len() >= 2, "packet too short");
+    Ok((u16::from_be_bytes([buf[0], buf[1]]), &buf[2..]))
+}
+
+fn take_bytes(buf: &[u8]) -> anyhow::Result<(&[u8], &[u8])> {
+    let (len, rest) = take_u16(buf)?;
+    let len = len as usize;
+    ensure!(rest.len() >= len, "packet too short");
+    Ok((&rest[..len], &rest[len..]))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_publish_roundtrip() {
+        let payload = vec![b'x'; 300]; // remaining length takes 2 bytes
+        let mut buf = encode_publish("a/b", Some(7), &payload).unwrap();
+        buf.extend(encode_publish("c", None, b"{}").unwrap());
+
+        let (packet, len) = parse(&buf).unwrap().unwrap();
+        assert_eq!(
+            packet,
+            Packet::Publish {
+                topic: "a/b".to_string(),
+                packet_id: Some(7),
+                payload
+            }
+        );
+        assert_eq!(
+            parse(&buf[len..]).unwrap().unwrap().0,
+            Packet::Publish {
+                topic: "c".to_string(),
+                packet_id: None,
+                payload: b"{}".to_vec()
+            }
+        );
+    }
+
+    #[test]
+    fn test_parse_partial() {
+        assert_eq!(parse(&[]).unwrap(), None);
+        assert_eq!(parse(&[0x20]).unwrap(), None);
+        assert_eq!(parse(&[0x20, 0x02, 0x00]).unwrap(), None);
+        assert_eq!(
+            parse(&[0x20, 0x02, 0x01, 0x04]).unwrap(),
+            Some((
+                Packet::ConnAck {
+                    session_present: true,
+                    return_code: 4
+                },
+                4
+            ))
+        );
+        assert!(parse(&[0xff, 0x00]).is_err());
+    }
+
+    #[test]
+    fn test_encode_connect() {
+        assert_eq!(
+            encode_connect("c", 30, true, Some("u"), Some("p")).unwrap(),
+            vec![
+                0x10, 19, 0, 4, b'M', b'Q', b'T', b'T', 4, 0xc2, 0, 30, 0, 1, b'c', 0, 1, b'u', 0,
+                1, b'p'
+            ]
+        );
+    }
+
+    #[test]
+    fn test_encode_too_long() {
+        let long_topic = "t".repeat(65_536);
+        assert!(encode_publish(&long_topic, None, b"{}").is_err());
+        assert!(encode_publish(&long_topic[1..], None, b"{}").is_ok());
+        assert!(encode_subscribe(1, &[long_topic.clone()], 1).is_err());
+        assert!(encode_connect(&long_topic, 30, true, None, None).is_err());
+        assert!(encode_connect("c", 30, true, Some("u"), Some(&long_topic)).is_err());
+        assert!(encode_connect("c", 30, true, None, Some("p")).is_err());
+    }
+
+    #[test]
+    fn test_remaining_length() {
+        for len in [0, 127, 128, 16_383, 16_384, MAX_REMAINING_LENGTH] {
+            let mut buf = vec![0x30];
+            buf.extend(encode_remaining_length(len).unwrap());
+            assert_eq!(
+                parse_remaining_length(&buf).unwrap(),
+                Some((len, buf.len()))
+            );
+        }
+        assert_eq!(
+            encode_remaining_length(MAX_REMAINING_LENGTH).unwrap(),
+            vec![0xff, 0xff, 0xff, 0x7f]
+        );
+        assert!(encode_remaining_length(MAX_REMAINING_LENGTH + 1).is_err());
+    }
+}
@@ -2,6 +2,7 @@
 
 mod pump_subtask;
 
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
 use std::sync::MutexGuard;
 use std::thread;
 use std::time::Duration;
@@ -12,16 +13,16 @@ use crate::{
     stream_engine::{
         autonomous_executor::{
             performance_metrics::{
-                InQueueMetricsUpdateByTask, MetricsUpdateByTaskExecution,
-                OutQueueMetricsUpdateByTask, TaskMetricsUpdateByTask,
+                InQueueMetricsUpdateByCollect, InQueueMetricsUpdateByTask,
+                MetricsUpdateByTaskExecution, OutQueueMetricsUpdateByTask, TaskMetricsUpdateByTask,
             },
             task::{
-                pump_task::pump_subtask::{InsertSubtask, QuerySubtask},
+                pump_task::pump_subtask::{InsertSubtask, QuerySubtask, QuerySubtaskOut},
                 task_context::TaskContext,
-                window::{AggrWindow, JoinWindow},
+                window::{AggrWindow, FlushMode, JoinWindow},
                 ProcessedRows, TaskRunResult,
             },
-            task_graph::TaskId,
+            task_graph::{QueueId, TaskId},
         },
         time::WallClockStopwatch,
     },
@@ -36,6 +37,28 @@ pub struct PumpTask {
     id: TaskId,
     query_subtask: QuerySubtask,
     insert_subtask: InsertSubtask,
+
+    /// Set after all upstream tasks reach end-of-stream and this task flushes its windows.
+    end_of_stream: AtomicBool,
+
+    /// Runs of this task in progress. Multiple worker threads may run the same task at once.
+    runs_in_flight: AtomicUsize,
+}
+
+/// Counts a run in [PumpTask::runs_in_flight] while alive.
+struct RunInFlight<'a>(&'a AtomicUsize);
+
+impl<'a> RunInFlight<'a> {
+    fn start(runs_in_flight: &'a AtomicUsize) -> Self {
+        runs_in_flight.fetch_add(1, Ordering::SeqCst);
+        Self(runs_in_flight)
+    }
+}
+
+impl Drop for RunInFlight<'_> {
+    fn drop(&mut self) {
+        self.0.fetch_sub(1, Ordering::SeqCst);
+    }
 }
 
 impl PumpTask {
@@ -47,6 +70,8 @@ impl PumpTask {
             id,
             query_subtask,
             insert_subtask,
+            end_of_stream: AtomicBool::new(false),
+            runs_in_flight: AtomicUsize::new(0),
         }
     }
 
@@ -54,10 +79,44 @@ impl PumpTask {
         &self.id
     }
 
+    pub fn is_end_of_stream(&self) -> bool {
+        self.end_of_stream.load(Ordering::Acquire)
+    }
+
     pub fn run(&self, context: &TaskContext) -> Result<TaskRunResult> {
+        let stopwatch = WallClockStopwatch::start();
+        let (processed_rows, in_queue_metrics, out_queues_metrics) = {
+            let _run_in_flight = RunInFlight::start(&self.runs_in_flight);
+            self.run_query_insert(context)?
+        };
+        let execution_time = stopwatch.stop();
+
+        let task_metrics = TaskMetricsUpdateByTask::new(context.task(), execution_time);
+        let metrics = MetricsUpdateByTaskExecution::new(
+            task_metrics,
+            in_queue_metrics.map_or_else(Vec::new, |m| vec![m]),
+            out_queues_metrics,
+        );
+
+        Ok(TaskRunResult {
+            processed_rows,
+            metrics,
+        })
+    }
+
+    /// Closes all open panes in windows and inserts their results into downstream queues.
+    ///
+    /// Rows waiting in input queues are not touched.
+    /// Metrics of the windows and downstream queues are returned as `run()` does.
+    ///
+    /// # Failures
+    ///
+    /// - `SpringError::Sql` when:
+    ///   - failed to evaluate expressions on closing panes.
+    pub fn flush(&self, context: &TaskContext) -> Result<TaskRunResult> {
         let stopwatch = WallClockStopwatch::start();
         let (processed_rows, in_queue_metrics, out_queues_metrics) =
-            self.run_query_insert(context)?;
+            self.flush_insert(FlushMode::KeepWatermark, context)?;
         let execution_time = stopwatch.stop();
 
         let task_metrics = TaskMetricsUpdateByTask::new(context.task(), execution_time);
@@ -82,25 +141,113 @@ impl PumpTask {
         Vec<OutQueueMetricsUpdateByTask>,
     )> {
         if let Some(query_subtask_out) = self.query_subtask.run(context)? {
-            let processed_rows = query_subtask_out.processed_rows();
-            let insert_subtask_out = self
-                .insert_subtask
-                .run(query_subtask_out.values_seq, context);
-            Ok((
-                processed_rows,
-                Some(query_subtask_out.in_queue_metrics_update),
-                insert_subtask_out.out_queues_metrics_update,
-            ))
+            Ok(self.insert(query_subtask_out, context))
+        } else if !self.is_end_of_stream() && self.upstreams_ended(context)? {
+            // Upstream tasks put all their rows before reaching end-of-stream. Check the input queues again.
+            match self.query_subtask.run(context)? {
+                Some(query_subtask_out) => Ok(self.insert(query_subtask_out, context)),
+                // Another run may have taken the last rows and not inserted them yet. The last run ends the stream.
+                None if self.runs_in_flight.load(Ordering::SeqCst) == 1 => self.end_stream(context),
+                None => Ok((ProcessedRows::default(), None, vec![])),
+            }
         } else {
             thread::sleep(WAIT_ON_NO_INPUT);
             Ok((ProcessedRows::default(), None, vec![]))
         }
     }
 
+    fn insert(
+        &self,
+        query_subtask_out: QuerySubtaskOut,
+        context: &TaskContext,
+    ) -> (
+        ProcessedRows,
+        Option<InQueueMetricsUpdateByTask>,
+        Vec<OutQueueMetricsUpdateByTask>,
+    ) {
+        let processed_rows = query_subtask_out.processed_rows();
+        let insert_subtask_out = self
+            .insert_subtask
+            .run(query_subtask_out.values_seq, context);
+        (
+            processed_rows,
+            Some(query_subtask_out.in_queue_metrics_update),
+            insert_subtask_out.out_queues_metrics_update,
+        )
+    }
+
+    fn upstreams_ended(&self, context: &TaskContext) -> Result<bool> {
+        let pipeline_derivatives = context.pipeline_derivatives();
+        let task_graph = pipeline_derivatives.task_graph();
+        for queue_id in task_graph.input_queues(&self.id) {
+            let upstream = pipeline_derivatives.get_task(&task_graph.upstream_task(&queue_id))?;
+            if !upstream.is_end_of_stream() {
+                return Ok(false);
+            }
+        }
+        Ok(true)
+    }
+
+    /// Moves watermarks of windows to +infinity, emits the results of all open panes, and then reaches end-of-stream.
+    fn end_stream(
+        &self,
+        context: &TaskContext,
+    ) -> Result<(
+        ProcessedRows,
+        Option<InQueueMetricsUpdateByTask>,
+        Vec<OutQueueMetricsUpdateByTask>,
+    )> {
+        let out = self.flush_insert(FlushMode::EndOfStream, context)?;
+
+        // downstream tasks may see end-of-stream only after the rows are inserted
+        self.end_of_stream.store(true, Ordering::Release);
+        log::info!("[PumpTask] {} reached end-of-stream", self.id);
+
+        Ok(out)
+    }
+
+    /// Closes all open panes in windows and inserts their results into downstream queues.
+    fn flush_insert(
+        &self,
+        mode: FlushMode,
+        context: &TaskContext,
+    ) -> Result<(
+        ProcessedRows,
+        Option<InQueueMetricsUpdateByTask>,
+        Vec<OutQueueMetricsUpdateByTask>,
+    )> {
+        let (values_seq, window_in_flow) = self.query_subtask.flush(mode)?;
+        let processed_rows = ProcessedRows::new(values_seq.len() as u64);
+        let insert_subtask_out = self.insert_subtask.run(values_seq, context);
+
+        let in_queue_metrics = context
+            .pipeline_derivatives()
+            .task_graph()
+            .input_queues(&self.id)
+            .into_iter()
+            .find_map(|queue_id| match queue_id {
+                QueueId::Window(queue_id) => Some(InQueueMetricsUpdateByTask::new(
+                    InQueueMetricsUpdateByCollect::Window {
+                        queue_id,
+                        waiting_bytes_dispatched: 0,
+                        waiting_rows_dispatched: 0,
+                    },
+                    Some(window_in_flow),
+                )),
+                QueueId::Row(_) => None,
+            });
+
+        Ok((
+            processed_rows,
+            in_queue_metrics,
+            insert_subtask_out.out_queues_metrics_update,
+        ))
+    }
+
     pub fn get_aggr_window_mut(&self) -> Option<MutexGuard<AggrWindow>> {
         self.query_subtask.get_aggr_window_mut()
     }
-    pub fn get_join_window_mut(&self) -> Option<MutexGuard<JoinWindow>> {
-        self.query_subtask.get_join_window_mut()
+    pub fn get_join_windows_mut(&self) -> Vec<MutexGuard<JoinWindow>> {
+        self.query_subtask.get_join_windows_mut()
     }
 }
@@ -4,4 +4,4 @@ mod insert_subtask;
 mod query_subtask;
 
 pub use insert_subtask::{InsertSubtask, InsertSubtaskOut};
-pub use query_subtask::QuerySubtask;
+pub use query_subtask::{QuerySubtask, QuerySubtaskOut};
@@ -4,6 +4,7 @@ mod collect_subtask;
 mod group_aggregate_window_subtask;
 mod join_subtask;
 mod projection_subtask;
+mod selection_subtask;
 
 use std::sync::{Arc, Mutex, MutexGuard};
 
@@ -18,23 +19,22 @@ use crate::{
     pipeline::{ColumnName, StreamModel},
     stream_engine::{
         autonomous_executor::{
-            performance_metrics::{
-                InQueueMetricsUpdateByCollect, InQueueMetricsUpdateByTask, WindowInFlowByWindowTask,
-            },
+            performance_metrics::{InQueueMetricsUpdateByTask, WindowInFlowByWindowTask},
             row::{ColumnValues, StreamColumns, StreamRow},
             task::{
                 pump_task::pump_subtask::query_subtask::{
                     collect_subtask::CollectSubtask,
                     group_aggregate_window_subtask::GroupAggregateWindowSubtask,
                     join_subtask::JoinSubtask, projection_subtask::ProjectionSubtask,
+                    selection_subtask::SelectionSubtask,
                 },
                 task_context::TaskContext,
                 tuple::Tuple,
-                window::{AggrWindow, JoinDir, JoinWindow},
+                window::{AggrWindow, FlushMode, JoinDir, JoinWindow},
                 ProcessedRows,
             },
         },
-        command::{JoinOp, LowerOps, QueryPlan},
+        command::{JoinOp, QueryPlan},
         SqlValue,
     },
 };
@@ -48,16 +48,56 @@ pub struct QuerySubtask {
 
     group_aggr_window_subtask: Option<GroupAggregateWindowSubtask>,
 
-    // TODO recursive JOIN
-    join: Option<(
-        JoinSubtask,
-        CollectSubtask, // right stream
-    )>,
-    left_collect_subtask: CollectSubtask, // left stream
+    join_tree: JoinTreeSubtask,
+
+    selection_subtask: Option<SelectionSubtask>,
 
     rng: Mutex<SmallRng>,
 }
 
+/// Left-deep tree of JOINs. Leaves are collect subtasks.
+#[derive(Debug)]
+enum JoinTreeSubtask {
+    Collect(CollectSubtask),
+    Join {
+        left: Box<JoinTreeSubtask>,
+        join_subtask: JoinSubtask,
+        right_collect_subtask: CollectSubtask,
+    },
+}
+
+impl JoinTreeSubtask {
+    fn from_join_op(join_op: JoinOp) -> Self {
+        match join_op {
+            JoinOp::Collect(collect_op) => {
+                Self::Collect(CollectSubtask::from_collect_op(collect_op))
+            }
+            JoinOp::JoinWindow(join_window_op) => Self::Join {
+                left: Box::new(Self::from_join_op(*join_window_op.left)),
+                join_subtask: JoinSubtask::new(
+                    join_window_op.window_param,
+                    join_window_op.join_param,
+                ),
+                right_collect_subtask: CollectSubtask::from_collect_op(join_window_op.right),
+            },
+        }
+    }
+
+    /// Join windows from the top of the tree.
+    fn join_windows_mut(&self) -> Vec<MutexGuard<JoinWindow>> {
+        match self {
+            Self::Collect(_) => vec![],
+            Self::Join {
+                left, join_subtask, ..
+            } => {
+                let mut windows = vec![join_subtask.get_window_mut()];
+                windows.extend(left.join_windows_mut());
+                windows
+            }
+        }
+    }
+}
+
 #[derive(Clone, Debug, new)]
 pub struct SqlValues(Vec<SqlValue>);
 impl SqlValues {
@@ -118,7 +158,11 @@ impl QuerySubtask {
         let rng =
             Mutex::new(SmallRng::from_rng(rand::thread_rng()).expect("this generally won't fail"));
 
-        let (left_collect_subtask, join) = Self::subtasks_from_lower_ops(plan.lower_ops);
+        let join_tree = JoinTreeSubtask::from_join_op(plan.lower_ops.join);
+        let selection_subtask = plan
+            .lower_ops
+            .selection
+            .map(SelectionSubtask::from_selection_op);
 
         let group_aggr_window_subtask = plan
             .upper_ops
@@ -131,33 +175,11 @@ impl QuerySubtask {
             expr_resolver: plan.expr_resolver,
             projection_subtask,
             group_aggr_window_subtask,
-            left_collect_subtask,
-            join,
+            join_tree,
+            selection_subtask,
             rng,
         }
     }
-    /// (left collect subtask, Option<(join subtask, right collect subtask)>)
-    fn subtasks_from_lower_ops(
-        lower_ops: LowerOps,
-    ) -> (CollectSubtask, Option<(JoinSubtask, CollectSubtask)>) {
-        match lower_ops.join {
-            JoinOp::Collect(collect_op) => {
-                let collect_subtask = CollectSubtask::from_collect_op(collect_op);
-                (collect_subtask, None)
-            }
-            JoinOp::JoinWindow(join_window_op) => {
-                let left_collect_subtask = CollectSubtask::from_collect_op(join_window_op.left);
-                let right_collect_subtask = CollectSubtask::from_collect_op(join_window_op.right);
-                let join_subtask =
-                    JoinSubtask::new(join_window_op.window_param, join_window_op.join_param);
-                (
-                    left_collect_subtask,
-                    Some((join_subtask, right_collect_subtask)),
-                )
-            }
-        }
-    }
-
     /// # Returns
     ///
     /// None when input queue does not exist or is empty.
@@ -166,11 +188,12 @@ impl QuerySubtask {
     ///
     /// TODO
     pub fn run(&self, context: &TaskContext) -> Result<Option<QuerySubtaskOut>> {
-        match self.run_lower_ops(context) {
+        match self.run_lower_ops(context)? {
             None => Ok(None),
             Some((lower_tuples, in_queue_metrics_update_by_task)) => {
+                let selected_tuples = self.run_selection(lower_tuples)?;
                 let (values_seq, in_queue_metrics_update) =
-                    self.run_upper_ops(lower_tuples, in_queue_metrics_update_by_task)?;
+                    self.run_upper_ops(selected_tuples, in_queue_metrics_update_by_task)?;
 
                 Ok(Some(QuerySubtaskOut::new(
                     values_seq,
@@ -180,21 +203,87 @@ impl QuerySubtask {
         }
     }
 
+    /// Drops tuples not satisfying WHERE condition so that they never reach windows nor projection.
+    fn run_selection(&self, tuples: Vec<Tuple>) -> Result<Vec<Tuple>> {
+        match &self.selection_subtask {
+            Some(selection_subtask) => {
+                let mut selected_tuples = Vec::with_capacity(tuples.len());
+                for tuple in tuples {
+                    if selection_subtask.run(&self.expr_resolver, &tuple)? {
+                        selected_tuples.push(tuple);
+                    }
+                }
+                Ok(selected_tuples)
+            }
+            None => Ok(tuples),
+        }
+    }
+
+    /// Closes all open panes in windows and emits their results even if the watermark has not reached their end.
+    /// With `FlushMode::EndOfStream`, watermarks of all windows move to +infinity.
+    ///
+    /// JOIN windows are flushed bottom-up: tuples from a lower JOIN are dispatched to the upper JOIN before the upper one is flushed.
+    /// Then, joined tuples go through selection and an aggregation window, which is flushed at last.
+    pub fn flush(&self, mode: FlushMode) -> Result<(Vec<SqlValues>, WindowInFlowByWindowTask)> {
+        let (joined_tuples, window_in_flow_join) = self.flush_join_tree(&self.join_tree, mode)?;
+        let selected_tuples = self.run_selection(joined_tuples)?;
+        let (mut values_seq, window_in_flow_upper) = self.run_upper_ops_seq(selected_tuples)?;
+
+        let window_in_flow_aggr = match &self.group_aggr_window_subtask {
+            Some(group_aggr_window_subtask) => {
+                let (aggregated_and_grouping_values_seq, window_in_flow) =
+                    group_aggr_window_subtask.flush(&self.expr_resolver, mode)?;
+                for aggregated_and_grouping_values in aggregated_and_grouping_values_seq {
+                    values_seq.push(
+                        self.projection_subtask
+                            .run_with_aggr(&self.expr_resolver, aggregated_and_grouping_values)?,
+                    );
+                }
+                window_in_flow
+            }
+            None => WindowInFlowByWindowTask::zero(),
+        };
+
+        Ok((
+            values_seq,
+            window_in_flow_join + window_in_flow_upper + window_in_flow_aggr,
+        ))
+    }
+
+    fn flush_join_tree(
+        &self,
+        join_tree: &JoinTreeSubtask,
+        mode: FlushMode,
+    ) -> Result<(Vec<Tuple>, WindowInFlowByWindowTask)> {
+        match join_tree {
+            JoinTreeSubtask::Collect(_) => Ok((vec![], WindowInFlowByWindowTask::zero())),
+            JoinTreeSubtask::Join {
+                left, join_subtask, ..
+            } => {
+                let (left_tuples, mut window_in_flow) = self.flush_join_tree(left, mode)?;
+
+                let mut joined_tuples = Vec::new();
+                for tuple in left_tuples {
+                    let (mut out, window_in_flow_join) =
+                        join_subtask.run(&self.expr_resolver, tuple, JoinDir::Left)?;
+                    joined_tuples.append(&mut out);
+                    window_in_flow = window_in_flow + window_in_flow_join;
+                }
+
+                let (mut out, window_in_flow_flush) =
+                    join_subtask.flush(&self.expr_resolver, mode)?;
+                joined_tuples.append(&mut out);
+                Ok((joined_tuples, window_in_flow + window_in_flow_flush))
+            }
+        }
+    }
+
     fn run_upper_ops(
         &self,
         tuples: Vec<Tuple>,
         in_queue_metrics_update_by_lower: InQueueMetricsUpdateByTask,
     ) -> Result<(Vec<SqlValues>, InQueueMetricsUpdateByTask)> {
-        let (values_seq, window_in_flow_upper_total) = tuples.into_iter().fold(
-            Ok((Vec::new(), WindowInFlowByWindowTask::zero())),
-            |res, tuple| {
-                let (mut values_seq_acc, window_in_flow_acc) = res?;
-
-                let (mut values_seq, window_in_flow) = self.run_upper_ops_inner(tuple)?;
-                values_seq_acc.append(&mut values_seq);
-                Ok((values_seq_acc, window_in_flow_acc + window_in_flow))
-            },
-        )?;
+        let (values_seq, window_in_flow_upper_total) = self.run_upper_ops_seq(tuples)?;
         let in_queue_metrics_update_by_task = InQueueMetricsUpdateByTask::new(
             in_queue_metrics_update_by_lower.by_collect,
             Some(window_in_flow_upper_total + in_queue_metrics_update_by_lower.window_in_flow),
@@ -202,19 +291,32 @@ impl QuerySubtask {
 
         Ok((values_seq, in_queue_metrics_update_by_task))
     }
+    fn run_upper_ops_seq(
+        &self,
+        tuples: Vec<Tuple>,
+    ) -> Result<(Vec<SqlValues>, WindowInFlowByWindowTask)> {
+        tuples.into_iter().try_fold(
+            (Vec::new(), WindowInFlowByWindowTask::zero()),
+            |(mut values_seq_acc, window_in_flow_acc), tuple| {
+                let (mut values_seq, window_in_flow) = self.run_upper_ops_inner(tuple)?;
+                values_seq_acc.append(&mut values_seq);
+                Ok((values_seq_acc, window_in_flow_acc + window_in_flow))
+            },
+        )
+    }
     fn run_upper_ops_inner(
         &self,
         tuple: Tuple,
     ) -> Result<(Vec<SqlValues>, WindowInFlowByWindowTask)> {
         if let Some(group_aggr_window_subtask) = &self.group_aggr_window_subtask {
             let (aggregated_and_grouping_values_seq, window_in_flow) =
-                group_aggr_window_subtask.run(&self.expr_resolver, tuple);
+                group_aggr_window_subtask.run(&self.expr_resolver, tuple)?;
 
             let values_seq = aggregated_and_grouping_values_seq
                 .into_iter()
                 .map(|aggregated_and_grouping_values| {
                     self.projection_subtask
-                        .run_with_aggr(aggregated_and_grouping_values)
+                        .run_with_aggr(&self.expr_resolver, aggregated_and_grouping_values)
                 })
                 .collect::<Result<Vec<_>>>()?;
 
@@ -233,45 +335,67 @@ impl QuerySubtask {
     fn run_lower_ops(
         &self,
         context: &TaskContext,
-    ) -> Option<(Vec<Tuple>, InQueueMetricsUpdateByTask)> {
-        match &self.join {
-            Some((join_subtask, right_collect_subtask)) => self.run_join(
-                context,
-                &self.left_collect_subtask,
-                right_collect_subtask,
+    ) -> Result<Option<(Vec<Tuple>, InQueueMetricsUpdateByTask)>> {
+        self.run_join_tree(context, &self.join_tree)
+    }
+
+    fn run_join_tree(
+        &self,
+        context: &TaskContext,
+        join_tree: &JoinTreeSubtask,
+    ) -> Result<Option<(Vec<Tuple>, InQueueMetricsUpdateByTask)>> {
+        match join_tree {
+            JoinTreeSubtask::Collect(collect_subtask) => {
+                Ok(self.run_collect(context, collect_subtask))
+            }
+            JoinTreeSubtask::Join {
+                left,
                 join_subtask,
-            ),
-            None => self
-                .run_left_collect(context)
-                .map(|(tuple, metrics_collect)| {
-                    (
-                        vec![tuple],
-                        InQueueMetricsUpdateByTask::new(
-                            metrics_collect,
-                            None, // single collect subtask does not use window yet
-                        ),
-                    )
-                }),
+                right_collect_subtask,
+            } => self.run_join(context, left, right_collect_subtask, join_subtask),
         }
     }
 
     /// JOIN takes tuples from left or right at a time.
     ///
     /// Left or right is determined randomly and if first candidate does not have tuple to collect, then the other is selected.
+    /// Left may be another JOIN, which gives zero or more joined tuples at a time.
     fn run_join(
         &self,
         context: &TaskContext,
-        left_collect_subtask: &CollectSubtask,
+        left: &JoinTreeSubtask,
         right_collect_subtask: &CollectSubtask,
         join_subtask: &JoinSubtask,
-    ) -> Option<(Vec<Tuple>, InQueueMetricsUpdateByTask)> {
-        self.join_dir_candidates().into_iter().find_map(|dir| {
-            let collect_subtask = match dir {
-                JoinDir::Left => left_collect_subtask,
-                JoinDir::Right => right_collect_subtask,
+    ) -> Result<Option<(Vec<Tuple>, InQueueMetricsUpdateByTask)>> {
+        for dir in self.join_dir_candidates() {
+            let lower = match dir {
+                JoinDir::Left => self.run_join_tree(context, left)?,
+                JoinDir::Right => self.run_collect(context, right_collect_subtask),
             };
-            self.run_join_core(context, collect_subtask, join_subtask, dir)
-        })
+            if let Some((tuples, metrics_lower)) = lower {
+                return self
+                    .run_join_core(join_subtask, tuples, metrics_lower, dir)
+                    .map(Some);
+            }
+        }
+        Ok(None)
+    }
+    fn run_collect(
+        &self,
+        context: &TaskContext,
+        collect_subtask: &CollectSubtask,
+    ) -> Option<(Vec<Tuple>, InQueueMetricsUpdateByTask)> {
+        collect_subtask
+            .run(context)
+            .map(|(tuple, metrics_collect)| {
+                (
+                    vec![tuple],
+                    InQueueMetricsUpdateByTask::new(
+                        metrics_collect,
+                        None, // single collect subtask does not use window yet
+                    ),
+                )
+            })
     }
     fn join_dir_candidates(&self) -> [JoinDir; 2] {
         let first = [JoinDir::Left, JoinDir::Right]
@@ -285,25 +409,21 @@ impl QuerySubtask {
     }
     fn run_join_core(
         &self,
-        context: &TaskContext,
-        collect_subtask: &CollectSubtask,
         join_subtask: &JoinSubtask,
+        tuples: Vec<Tuple>,
+        metrics_lower: InQueueMetricsUpdateByTask,
         join_dir: JoinDir,
-    ) -> Option<(Vec<Tuple>, InQueueMetricsUpdateByTask)> {
-        collect_subtask
-            .run(context)
-            .map(|(tuple, metrics_collect)| {
-                let (tuples, metrics_join) = join_subtask.run(&self.expr_resolver, tuple, join_dir);
-                let metrics = InQueueMetricsUpdateByTask::new(metrics_collect, Some(metrics_join));
-                (tuples, metrics)
-            })
-    }
-
-    fn run_left_collect(
-        &self,
-        context: &TaskContext,
-    ) -> Option<(Tuple, InQueueMetricsUpdateByCollect)> {
-        self.left_collect_subtask.run(context)
+    ) -> Result<(Vec<Tuple>, InQueueMetricsUpdateByTask)> {
+        let mut joined_tuples = Vec::new();
+        let mut window_in_flow = metrics_lower.window_in_flow;
+        for tuple in tuples {
+            let (mut out, metrics_join) = join_subtask.run(&self.expr_resolver, tuple, join_dir)?;
+            joined_tuples.append(&mut out);
+            window_in_flow = window_in_flow + metrics_join;
+        }
+        let metrics =
+            InQueueMetricsUpdateByTask::new(metrics_lower.by_collect, Some(window_in_flow));
+        Ok((joined_tuples, metrics))
     }
 
     pub fn get_aggr_window_mut(&self) -> Option<MutexGuard<AggrWindow>> {
@@ -311,9 +431,7 @@ impl QuerySubtask {
             .as_ref()
             .map(|subtask| subtask.get_window_mut())
     }
-    pub fn get_join_window_mut(&self) -> Option<MutexGuard<JoinWindow>> {
-        self.join
-            .as_ref()
-            .map(|(subtask, _)| subtask.get_window_mut())
+    pub fn get_join_windows_mut(&self) -> Vec<MutexGuard<JoinWindow>> {
+        self.join_tree.join_windows_mut()
     }
 }
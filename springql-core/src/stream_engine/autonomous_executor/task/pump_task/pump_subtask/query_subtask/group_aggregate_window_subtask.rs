@@ -3,13 +3,14 @@
 use std::sync::{Mutex, MutexGuard};
 
 use crate::{
+    api::error::Result,
     expr_resolver::ExprResolver,
     pipeline::{WindowOperationParameter, WindowParameter},
     stream_engine::autonomous_executor::{
         performance_metrics::WindowInFlowByWindowTask,
         task::{
             tuple::Tuple,
-            window::{AggrWindow, AggregatedAndGroupingValues, Window},
+            window::{AggrWindow, AggregatedAndGroupingValues, FlushMode, Window},
         },
     },
 };
@@ -23,16 +24,30 @@ impl GroupAggregateWindowSubtask {
         Self(Mutex::new(window))
     }
 
+    /// # Failures
+    ///
+    /// - `SpringError::Sql` when:
+    ///   - failed to evaluate GROUP BY or aggregate expressions on the tuple.
     pub fn run(
         &self,
         expr_resolver: &ExprResolver,
         tuple: Tuple,
-    ) -> (Vec<AggregatedAndGroupingValues>, WindowInFlowByWindowTask) {
+    ) -> Result<(Vec<AggregatedAndGroupingValues>, WindowInFlowByWindowTask)> {
         self.0
             .lock()
             .expect("another thread accessing to window gets poisoned")
             .dispatch(expr_resolver, tuple, ())
-            .expect("dispatch failed")
+    }
+
+    pub fn flush(
+        &self,
+        expr_resolver: &ExprResolver,
+        mode: FlushMode,
+    ) -> Result<(Vec<AggregatedAndGroupingValues>, WindowInFlowByWindowTask)> {
+        self.0
+            .lock()
+            .expect("another thread accessing to window gets poisoned")
+            .flush(expr_resolver, mode)
     }
 
     pub fn get_window_mut(&self) -> MutexGuard<AggrWindow> {
@@ -3,13 +3,14 @@
 use std::sync::{Mutex, MutexGuard};
 
 use crate::{
+    api::error::Result,
     expr_resolver::ExprResolver,
     pipeline::{JoinParameter, WindowParameter},
     stream_engine::autonomous_executor::{
         performance_metrics::WindowInFlowByWindowTask,
         task::{
             tuple::Tuple,
-            window::{JoinDir, JoinWindow, Window},
+            window::{FlushMode, JoinDir, JoinWindow, Window},
         },
     },
 };
@@ -23,17 +24,35 @@ impl JoinSubtask {
         Self(Mutex::new(window))
     }
 
+    /// # Failures
+    ///
+    /// - `SpringError::Sql` when:
+    ///   - failed to evaluate equi-join keys or ON condition on closing panes.
     pub fn run(
         &self,
         expr_resolver: &ExprResolver,
         tuple: Tuple,
         dir: JoinDir,
-    ) -> (Vec<Tuple>, WindowInFlowByWindowTask) {
+    ) -> Result<(Vec<Tuple>, WindowInFlowByWindowTask)> {
         self.0
             .lock()
             .expect("another thread accessing to window gets poisoned")
             .dispatch(expr_resolver, tuple, dir)
-            .expect("dispatch failed")
+    }
+
+    /// # Failures
+    ///
+    /// - `SpringError::Sql` when:
+    ///   - failed to evaluate equi-join keys or ON condition on closing panes.
+    pub fn flush(
+        &self,
+        expr_resolver: &ExprResolver,
+        mode: FlushMode,
+    ) -> Result<(Vec<Tuple>, WindowInFlowByWindowTask)> {
+        self.0
+            .lock()
+            .expect("another thread accessing to window gets poisoned")
+            .flush(expr_resolver, mode)
     }
 
     pub fn get_window_mut(&self) -> MutexGuard<JoinWindow> {
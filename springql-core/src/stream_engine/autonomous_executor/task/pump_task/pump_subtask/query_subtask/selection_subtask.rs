@@ -0,0 +1,34 @@
+// This file is part of https://github.com/SpringQL/SpringQL which is licensed under MIT OR Apache-2.0. See file LICENSE-MIT or LICENSE-APACHE for full license details.
+
+use crate::{
+    api::error::Result,
+    expr_resolver::{ExprResolver, ValueExprLabel},
+    stream_engine::{autonomous_executor::task::tuple::Tuple, command::SelectionOp},
+};
+
+#[derive(Debug)]
+pub struct SelectionSubtask {
+    where_expr: ValueExprLabel,
+}
+
+impl SelectionSubtask {
+    pub fn from_selection_op(selection_op: SelectionOp) -> Self {
+        Self {
+            where_expr: selection_op.where_expr,
+        }
+    }
+
+    /// # Returns
+    ///
+    /// true if `tuple` satisfies the WHERE condition. NULL condition is evaluated as false.
+    ///
+    /// # Failures
+    ///
+    /// - `SpringError::Sql` when:
+    ///   - the condition cannot be evaluated into BOOLEAN.
+    pub fn run(&self, expr_resolver: &ExprResolver, tuple: &Tuple) -> Result<bool> {
+        expr_resolver
+            .eval_value_expr(self.where_expr, tuple)?
+            .to_bool()
+    }
+}
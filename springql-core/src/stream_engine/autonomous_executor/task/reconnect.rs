@@ -0,0 +1,366 @@
+// This file is part of https://github.com/SpringQL/SpringQL which is licensed under MIT OR Apache-2.0. See file LICENSE-MIT or LICENSE-APACHE for full license details.
+
+use std::{
+    fmt::Display,
+    time::{Duration, Instant},
+};
+
+use anyhow::anyhow;
+
+use crate::api::{
+    error::{foreign_info::ForeignInfo, SpringError},
+    SpringSinkWriterConfig, SpringSourceReaderConfig,
+};
+
+/// How to re-establish a lost connection: exponential backoff with jitter.
+///
+/// Gives up after `reconnect_max_retries` failed attempts in a row.
+/// Sink writers keep retrying by default (every `reconnect_max_backoff_msec` after the backoff reaches it) not to lose rows after a long outage of the foreign sink.
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+pub struct ReconnectPolicy {
+    /// `None` to retry forever.
+    max_retries: Option<u32>,
+    initial_backoff: Duration,
+    max_backoff: Duration,
+    jitter_percent: u32,
+}
+
+impl ReconnectPolicy {
+    /// Wait before the next attempt after `n_failed_attempts` failed attempts in a row.
+    fn backoff(&self, n_failed_attempts: u32) -> Duration {
+        let backoff = self
+            .initial_backoff
+            .saturating_mul(2u32.saturating_pow(n_failed_attempts))
+            .min(self.max_backoff);
+
+        let jitter_percent = self.jitter_percent.min(100);
+        let shorten_percent = fastrand::u32(0..=jitter_percent);
+        backoff * (100 - shorten_percent) / 100
+    }
+}
+
+impl From<&SpringSourceReaderConfig> for ReconnectPolicy {
+    fn from(config: &SpringSourceReaderConfig) -> Self {
+        Self {
+            max_retries: Some(config.reconnect_max_retries),
+            initial_backoff: Duration::from_millis(config.reconnect_initial_backoff_msec as u64),
+            max_backoff: Duration::from_millis(config.reconnect_max_backoff_msec as u64),
+            jitter_percent: config.reconnect_jitter_percent,
+        }
+    }
+}
+
+impl From<&SpringSinkWriterConfig> for ReconnectPolicy {
+    fn from(config: &SpringSinkWriterConfig) -> Self {
+        Self {
+            // 0 to retry forever
+            max_retries: (config.reconnect_max_retries > 0).then(|| config.reconnect_max_retries),
+            initial_backoff: Duration::from_millis(config.reconnect_initial_backoff_msec as u64),
+            max_backoff: Duration::from_millis(config.reconnect_max_backoff_msec as u64),
+            jitter_percent: config.reconnect_jitter_percent,
+        }
+    }
+}
+
+/// Connection state changes of a source reader / sink writer, reported in task metrics.
+#[derive(Copy, Clone, Eq, PartialEq, Debug, Default)]
+pub struct ConnectionEvents {
+    pub n_disconnections: u64,
+    pub n_reconnections: u64,
+}
+
+/// Why [Reconnecting::connection()] does not provide a connection.
+#[derive(Debug)]
+pub enum Unconnected {
+    /// Waiting for the next attempt.
+    Waiting(Duration),
+    /// Gave up reconnecting after `reconnect_max_retries` failed attempts.
+    GaveUp,
+    /// An attempt to reconnect has just failed.
+    Failed(SpringError),
+}
+
+impl Unconnected {
+    pub fn into_error(self, foreign_info: ForeignInfo) -> SpringError {
+        match self {
+            Unconnected::Waiting(wait) => SpringError::ForeignIo {
+                source: anyhow!("disconnected (next reconnection attempt in {:?})", wait),
+                foreign_info,
+            },
+            Unconnected::GaveUp => SpringError::ForeignIo {
+                source: anyhow!("disconnected (gave up reconnecting)"),
+                foreign_info,
+            },
+            Unconnected::Failed(e) => e,
+        }
+    }
+}
+
+#[derive(Debug)]
+enum ConnectionState<C> {
+    Connected(C),
+    Disconnected {
+        n_failed_attempts: u32,
+        next_attempt_at: Instant,
+    },
+    GaveUp,
+}
+
+/// Connection `C` to a foreign host, re-established by [ReconnectPolicy] after it is lost.
+///
+/// Reconnection is attempted on [connection()](Self::connection) calls without blocking for backoff,
+/// so that worker threads are not occupied by a disconnected reader / writer.
+#[derive(Debug)]
+pub struct Reconnecting<C> {
+    /// Used in logs (e.g. `"NetSinkWriter to 127.0.0.1:8080"`).
+    name: String,
+    policy: ReconnectPolicy,
+    state: ConnectionState<C>,
+    events: ConnectionEvents,
+}
+
+impl<C> Reconnecting<C> {
+    pub fn new(name: String, connection: C, policy: ReconnectPolicy) -> Self {
+        Self {
+            name,
+            policy,
+            state: ConnectionState::Connected(connection),
+            events: ConnectionEvents::default(),
+        }
+    }
+
+    /// Returns the connection, attempting to reconnect with `connect` if it is lost and the backoff has passed.
+    pub fn connection<F>(&mut self, connect: F) -> Result<&mut C, Unconnected>
+    where
+        F: FnOnce() -> crate::api::error::Result<C>,
+    {
+        if let ConnectionState::Disconnected {
+            n_failed_attempts,
+            next_attempt_at,
+        } = self.state
+        {
+            let now = Instant::now();
+            if now < next_attempt_at {
+                return Err(Unconnected::Waiting(next_attempt_at - now));
+            }
+
+            match connect() {
+                Ok(connection) => {
+                    log::info!(
+                        "[Reconnecting] {} reconnected after {} failed attempts",
+                        self.name,
+                        n_failed_attempts
+                    );
+                    self.state = ConnectionState::Connected(connection);
+                    self.events.n_reconnections += 1;
+                }
+                Err(e) => {
+                    self.retry_later(n_failed_attempts + 1);
+                    return Err(Unconnected::Failed(e));
+                }
+            }
+        }
+
+        match &mut self.state {
+            ConnectionState::Connected(connection) => Ok(connection),
+            ConnectionState::GaveUp => Err(Unconnected::GaveUp),
+            ConnectionState::Disconnected { .. } => unreachable!("reconnected or returned above"),
+        }
+    }
+
+    /// Drops the connection and schedules reconnection. Does nothing if already disconnected.
+    pub fn disconnect<E: Display>(&mut self, reason: E) {
+        if matches!(self.state, ConnectionState::Connected(_)) {
+            log::warn!("[Reconnecting] {} disconnected: {:#}", self.name, reason);
+            self.events.n_disconnections += 1;
+            self.retry_later(0);
+        }
+    }
+
+    /// `true` after `reconnect_max_retries` failed attempts in a row.
+    pub fn has_given_up(&self) -> bool {
+        matches!(self.state, ConnectionState::GaveUp)
+    }
+
+    /// Connection state changes since the last call.
+    pub fn take_events(&mut self) -> ConnectionEvents {
+        std::mem::take(&mut self.events)
+    }
+
+    fn retry_later(&mut self, n_failed_attempts: u32) {
+        let gives_up = matches!(self.policy.max_retries, Some(max_retries) if n_failed_attempts >= max_retries);
+        self.state = if gives_up {
+            log::warn!(
+                "[Reconnecting] {} gave up reconnecting after {} failed attempts",
+                self.name,
+                n_failed_attempts
+            );
+            ConnectionState::GaveUp
+        } else {
+            let backoff = self.policy.backoff(n_failed_attempts);
+            log::info!(
+                "[Reconnecting] {} will try to reconnect in {:?}",
+                self.name,
+                backoff
+            );
+            ConnectionState::Disconnected {
+                n_failed_attempts,
+                next_attempt_at: Instant::now() + backoff,
+            }
+        };
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::thread;
+
+    use super::*;
+
+    fn policy(max_retries: Option<u32>, jitter_percent: u32) -> ReconnectPolicy {
+        ReconnectPolicy {
+            max_retries,
+            initial_backoff: Duration::from_millis(10),
+            max_backoff: Duration::from_millis(50),
+            jitter_percent,
+        }
+    }
+
+    fn connect_ok() -> crate::api::error::Result<u32> {
+        Ok(2)
+    }
+    fn connect_err() -> crate::api::error::Result<u32> {
+        Err(SpringError::ForeignIo {
+            source: anyhow!("refused"),
+            foreign_info: ForeignInfo::Http("127.0.0.1:80".parse().unwrap()),
+        })
+    }
+
+    /// Waits for the next attempt and returns its result.
+    fn attempt(
+        conn: &mut Reconnecting<u32>,
+        connect: fn() -> crate::api::error::Result<u32>,
+    ) -> Result<u32, Unconnected> {
+        if let Err(Unconnected::Waiting(wait)) = conn.connection(connect) {
+            thread::sleep(wait);
+        }
+        conn.connection(connect).map(|c| *c)
+    }
+
+    #[test]
+    fn test_backoff() {
+        let p = policy(Some(10), 0);
+        assert_eq!(p.backoff(0), Duration::from_millis(10));
+        assert_eq!(p.backoff(1), Duration::from_millis(20));
+        assert_eq!(p.backoff(2), Duration::from_millis(40));
+        assert_eq!(p.backoff(3), Duration::from_millis(50));
+        assert_eq!(p.backoff(100), Duration::from_millis(50));
+
+        let p = policy(Some(10), 20);
+        for _ in 0..100 {
+            let backoff = p.backoff(1);
+            assert!(Duration::from_millis(16) <= backoff && backoff <= Duration::from_millis(20));
+        }
+    }
+
+    #[test]
+    fn test_reconnect() {
+        let mut conn = Reconnecting::new("test".to_string(), 1, policy(Some(3), 0));
+        assert_eq!(*conn.connection(connect_ok).unwrap(), 1);
+
+        conn.disconnect("reset");
+        conn.disconnect("reset again");
+        assert!(matches!(
+            conn.connection(connect_ok),
+            Err(Unconnected::Waiting(_))
+        ));
+        assert!(matches!(
+            attempt(&mut conn, connect_err),
+            Err(Unconnected::Failed(_))
+        ));
+        assert_eq!(attempt(&mut conn, connect_ok).unwrap(), 2);
+        assert!(!conn.has_given_up());
+
+        assert_eq!(
+            conn.take_events(),
+            ConnectionEvents {
+                n_disconnections: 1,
+                n_reconnections: 1
+            }
+        );
+        assert_eq!(conn.take_events(), ConnectionEvents::default());
+    }
+
+    #[test]
+    fn test_give_up() {
+        let mut conn = Reconnecting::new("test".to_string(), 1, policy(Some(2), 0));
+        conn.disconnect("reset");
+
+        assert!(matches!(
+            attempt(&mut conn, connect_err),
+            Err(Unconnected::Failed(_))
+        ));
+        assert!(!conn.has_given_up());
+        assert!(matches!(
+            attempt(&mut conn, connect_err),
+            Err(Unconnected::Failed(_))
+        ));
+        assert!(conn.has_given_up());
+        assert!(matches!(
+            conn.connection(connect_ok),
+            Err(Unconnected::GaveUp)
+        ));
+    }
+
+    #[test]
+    fn test_reconnect_disabled() {
+        let mut conn = Reconnecting::new("test".to_string(), 1, policy(Some(0), 0));
+        conn.disconnect("reset");
+        assert!(conn.has_given_up());
+    }
+
+    #[test]
+    fn test_retry_forever() {
+        let mut conn = Reconnecting::new("test".to_string(), 1, policy(None, 0));
+        conn.disconnect("reset");
+
+        for _ in 0..10 {
+            assert!(matches!(
+                attempt(&mut conn, connect_err),
+                Err(Unconnected::Failed(_))
+            ));
+            assert!(!conn.has_given_up());
+        }
+        assert_eq!(attempt(&mut conn, connect_ok).unwrap(), 2);
+    }
+
+    #[test]
+    fn test_sink_give_up() {
+        let config = SpringSinkWriterConfig {
+            reconnect_initial_backoff_msec: 10,
+            ..SpringSinkWriterConfig::fx_default()
+        };
+        // retries forever by default
+        assert_eq!(ReconnectPolicy::from(&config).max_retries, None);
+
+        let config = SpringSinkWriterConfig {
+            reconnect_max_retries: 2,
+            ..config
+        };
+        let mut conn = Reconnecting::new("test".to_string(), 1, ReconnectPolicy::from(&config));
+        conn.disconnect("reset");
+
+        for _ in 0..2 {
+            assert!(!conn.has_given_up());
+            assert!(matches!(
+                attempt(&mut conn, connect_err),
+                Err(Unconnected::Failed(_))
+            ));
+        }
+        assert!(conn.has_given_up());
+        assert!(matches!(
+            conn.connection(connect_ok),
+            Err(Unconnected::GaveUp)
+        ));
+    }
+}
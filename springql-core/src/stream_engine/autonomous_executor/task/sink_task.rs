@@ -2,7 +2,7 @@
 
 mod sink_writer;
 
-pub use sink_writer::{NetSinkWriter, SinkWriter, SinkWriterRepository};
+pub use sink_writer::{NetSinkWriter, SinkWriter, SinkWriterContext, SinkWriterRepository};
 
 use std::sync::Arc;
 
@@ -18,8 +18,9 @@ use crate::{
             },
             repositories::Repositories,
             row::StreamRow,
-            task::{task_context::TaskContext, ProcessedRows, TaskRunResult},
+            task::{task_context::TaskContext, ConnectionEvents, ProcessedRows, TaskRunResult},
             task_graph::{QueueId, TaskId},
+            AutonomousExecutor,
         },
         time::WallClockStopwatch,
     },
@@ -49,28 +50,29 @@ impl SinkTask {
     pub fn run(&self, context: &TaskContext) -> Result<TaskRunResult> {
         let stopwatch = WallClockStopwatch::start();
 
-        let repos = context.repos();
-
         let opt_in_queue_id = context
             .pipeline_derivatives()
             .task_graph()
             .input_queue(&context.task(), &self.upstream);
 
-        let (processed_rows, in_queues_metrics) = if let Some(in_queue_id) = opt_in_queue_id {
-            if let Some((row, in_queue_metrics)) = self.use_row_from(in_queue_id, repos) {
-                self.emit(row, context)?;
-                (ProcessedRows::new(1), vec![in_queue_metrics])
+        let (processed_rows, in_queues_metrics, n_errors, connection_events) =
+            if let Some(in_queue_id) = opt_in_queue_id {
+                self.emit_from(in_queue_id, context)
             } else {
-                (ProcessedRows::default(), vec![])
-            }
-        } else {
-            (ProcessedRows::default(), vec![])
-        };
+                (
+                    ProcessedRows::default(),
+                    vec![],
+                    0,
+                    ConnectionEvents::default(),
+                )
+            };
 
         let execution_time = stopwatch.stop();
 
         let out_queues_metrics = vec![];
-        let task_metrics = TaskMetricsUpdateByTask::new(context.task(), execution_time);
+        let task_metrics = TaskMetricsUpdateByTask::new(context.task(), execution_time)
+            .with_errors(n_errors)
+            .with_connection_events(connection_events);
         let metrics =
             MetricsUpdateByTaskExecution::new(task_metrics, in_queues_metrics, out_queues_metrics);
         Ok(TaskRunResult {
@@ -107,17 +109,62 @@ impl SinkTask {
         }
     }
 
-    fn emit(&self, row: StreamRow, context: &TaskContext) -> Result<()> {
-        let sink_writer = context
-            .repos()
+    /// Sends a row from `queue_id` if the sink writer is ready. Rows are left in the queue otherwise,
+    /// or dropped one by one as errors if the sink writer gave up reconnecting.
+    ///
+    /// Returns processed rows, metrics of `queue_id`, the number of errors, and connection state changes of the sink writer.
+    fn emit_from(
+        &self,
+        queue_id: QueueId,
+        context: &TaskContext,
+    ) -> (
+        ProcessedRows,
+        Vec<InQueueMetricsUpdateByTask>,
+        u64,
+        ConnectionEvents,
+    ) {
+        let repos = context.repos();
+        let sink_writer = repos
             .sink_writer_repository()
             .get_sink_writer(&self.sink_writer_name);
-
-        sink_writer
+        let mut sink_writer = sink_writer
             .lock()
-            .expect("other worker threads sharing the same sink subtask must not get panic")
-            .send_row(row.into())?;
+            .expect("other worker threads sharing the same sink subtask must not get panic");
+
+        let (processed_rows, in_queues_metrics, res) = match sink_writer.ready() {
+            Ok(true) => {
+                if let Some((row, in_queue_metrics)) = self.use_row_from(queue_id, repos.clone()) {
+                    // sink writers connected to foreign sinks hold the row if they fail to send it
+                    let res = sink_writer.send_row(row.into());
+                    (ProcessedRows::new(1), vec![in_queue_metrics], res)
+                } else {
+                    (ProcessedRows::default(), vec![], Ok(()))
+                }
+            }
+            Ok(false) => (ProcessedRows::default(), vec![], Ok(())),
+            Err(e) if sink_writer.has_given_up() => {
+                // drop a row not to keep rows which are never sent
+                if let Some((_, in_queue_metrics)) = self.use_row_from(queue_id, repos.clone()) {
+                    (ProcessedRows::new(1), vec![in_queue_metrics], Err(e))
+                } else {
+                    (ProcessedRows::default(), vec![], Ok(()))
+                }
+            }
+            Err(e) => (ProcessedRows::default(), vec![], Err(e)),
+        };
 
-        Ok(())
+        let n_errors = match res {
+            Ok(()) => 0,
+            Err(e) => {
+                AutonomousExecutor::handle_error(e);
+                1
+            }
+        };
+        (
+            processed_rows,
+            in_queues_metrics,
+            n_errors,
+            sink_writer.take_connection_events(),
+        )
     }
 }
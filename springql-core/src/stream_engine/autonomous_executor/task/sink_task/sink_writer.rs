@@ -1,28 +1,56 @@
 // This file is part of https://github.com/SpringQL/SpringQL which is licensed under MIT OR Apache-2.0. See file LICENSE-MIT or LICENSE-APACHE for full license details.
 
+mod callback;
+mod http_client;
 mod in_memory_queue;
+mod mqtt;
 mod net;
-mod http_client;
+mod net_udp;
 mod sink_writer_factory;
 mod sink_writer_repository;
 
 pub use net::NetSinkWriter;
 pub use sink_writer_repository::SinkWriterRepository;
 
-use std::fmt::Debug;
+use std::{fmt::Debug, sync::Arc};
 
 use crate::{
     api::{error::Result, SpringSinkWriterConfig},
     pipeline::Options,
-    stream_engine::autonomous_executor::SchemalessRow,
+    stream_engine::{
+        autonomous_executor::{task::ConnectionEvents, SchemalessRow},
+        in_memory_queue_repository::InMemoryQueueRepository,
+        sink_callback_repository::SinkCallbackRepository,
+    },
 };
 
+/// Stream engine resources passed to `SinkWriter::start()`.
+#[derive(Clone, Debug, new)]
+pub struct SinkWriterContext {
+    in_memory_queue_repository: Arc<InMemoryQueueRepository>,
+    sink_callback_repository: Arc<SinkCallbackRepository>,
+}
+
+impl SinkWriterContext {
+    pub fn in_memory_queue_repository(&self) -> &Arc<InMemoryQueueRepository> {
+        &self.in_memory_queue_repository
+    }
+
+    pub fn sink_callback_repository(&self) -> &SinkCallbackRepository {
+        &self.sink_callback_repository
+    }
+}
+
 /// Instance of SinkWriterModel.
 ///
 /// Since agents and servers may live as long as a program lives, sink task cannot hold hold implementations of this trait.
 pub trait SinkWriter: Debug + Sync + Send + 'static {
     /// Blocks until the sink subtask is ready to send SinkRow to foreign sink.
-    fn start(options: &Options, config: &SpringSinkWriterConfig) -> Result<Self>
+    fn start(
+        options: &Options,
+        config: &SpringSinkWriterConfig,
+        context: &SinkWriterContext,
+    ) -> Result<Self>
     where
         Self: Sized;
 
@@ -36,4 +64,29 @@ pub trait SinkWriter: Debug + Sync + Send + 'static {
     /// - `SpringError::Null` when:
     ///   - `row` contains unexpected NULL column.
     fn send_row(&mut self, row: SchemalessRow) -> Result<()>;
+
+    /// `true` if the sink writer can send a row now.
+    ///
+    /// Called before every `send_row()`. A sink writer waiting for reconnection returns `false`, and the sink task leaves rows in its input queue.
+    /// A sink writer holding a row failed to be sent sends it again here first.
+    ///
+    /// # Failure
+    ///
+    /// - `SpringError::ForeignIo` when:
+    ///   - failed to reconnect, or to send the held row again.
+    fn ready(&mut self) -> Result<bool> {
+        Ok(true)
+    }
+
+    /// `true` after the sink writer gave up reconnecting to the foreign sink (`reconnect_max_retries`).
+    ///
+    /// The sink task drops rows from its input queue after that, reporting each of them as an error.
+    fn has_given_up(&self) -> bool {
+        false
+    }
+
+    /// Disconnections from / reconnections to the foreign sink since the last call.
+    fn take_connection_events(&mut self) -> ConnectionEvents {
+        ConnectionEvents::default()
+    }
 }
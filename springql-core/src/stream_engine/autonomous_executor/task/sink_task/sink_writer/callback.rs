@@ -0,0 +1,105 @@
+// This file is part of https://github.com/SpringQL/SpringQL which is licensed under MIT OR Apache-2.0. See file LICENSE-MIT or LICENSE-APACHE for full license details.
+
+use std::{
+    any::Any,
+    fmt::Debug,
+    panic::{self, AssertUnwindSafe},
+    sync::{Arc, Mutex},
+};
+
+use anyhow::anyhow;
+
+use crate::{
+    api::{
+        error::{foreign_info::ForeignInfo, Result, SpringError},
+        SpringSinkWriterConfig,
+    },
+    pipeline::{CallbackName, CallbackOptions, Options},
+    stream_engine::{
+        autonomous_executor::{
+            task::sink_task::sink_writer::{SinkWriter, SinkWriterContext},
+            SchemalessRow,
+        },
+        sink_callback_repository::RegisteredSinkCallback,
+    },
+};
+
+/// Calls a callback registered by an application for each row.
+pub struct CallbackSinkWriter {
+    callback_name: CallbackName,
+    callback: Arc<Mutex<RegisteredSinkCallback>>,
+}
+
+impl Debug for CallbackSinkWriter {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("CallbackSinkWriter")
+            .field("callback_name", &self.callback_name)
+            .finish()
+    }
+}
+
+impl SinkWriter for CallbackSinkWriter {
+    /// # Failure
+    ///
+    /// - `SpringError::Unavailable` when:
+    ///   - callback is not registered yet.
+    /// - `SpringError::InvalidOption`
+    fn start(
+        options: &Options,
+        _config: &SpringSinkWriterConfig,
+        context: &SinkWriterContext,
+    ) -> Result<Self> {
+        let options = CallbackOptions::try_from(options)?;
+        let callback = context
+            .sink_callback_repository()
+            .get(&options.callback_name)?;
+        Ok(Self {
+            callback_name: options.callback_name,
+            callback,
+        })
+    }
+
+    /// # Failure
+    ///
+    /// - `SpringError::ForeignIo` when:
+    ///   - the callback panicked. The error handler is called with the same error, and the callback is called again for the next row.
+    fn send_row(&mut self, row: SchemalessRow) -> Result<()> {
+        let mut registered = self
+            .callback
+            .lock()
+            .expect("another thread sharing the same sink callback got panic");
+
+        // a panic is caught inside the lock, so the mutex is not poisoned
+        let payload = match panic::catch_unwind(AssertUnwindSafe(|| (registered.callback)(row))) {
+            Ok(()) => return Ok(()),
+            Err(payload) => payload,
+        };
+        let message = panic_message(payload.as_ref());
+
+        if let Some(on_error) = &mut registered.on_error {
+            let e = self.panicked(message);
+            // a panic in the error handler is logged by the panic hook and ignored
+            let _ = panic::catch_unwind(AssertUnwindSafe(|| on_error(e)));
+        }
+        Err(self.panicked(message))
+    }
+}
+
+impl CallbackSinkWriter {
+    fn panicked(&self, message: &str) -> SpringError {
+        SpringError::ForeignIo {
+            source: anyhow!("callback panicked: {}", message),
+            foreign_info: ForeignInfo::SinkCallback(self.callback_name.clone()),
+        }
+    }
+}
+
+fn panic_message(payload: &(dyn Any + Send)) -> &str {
+    if let Some(s) = payload.downcast_ref::<&str>() {
+        s
+    } else if let Some(s) = payload.downcast_ref::<String>() {
+        s
+    } else {
+        "(non-string panic payload)"
+    }
+}
@@ -1,25 +1,44 @@
 // This file is part of https://github.com/SpringQL/SpringQL which is licensed under MIT OR Apache-2.0. See file LICENSE-MIT or LICENSE-APACHE for full license details.
 
-use std::{net::SocketAddr, time::Duration};
+use std::{
+    net::{SocketAddr, TcpStream},
+    time::Duration,
+};
 
-use reqwest::{header::HeaderMap, Method, Url};
+use anyhow::Context;
+use reqwest::{blocking::Client, header::HeaderMap, Method, Url};
 
 use crate::{
     api::error::{foreign_info::ForeignInfo, Result, SpringError},
     api::SpringSinkWriterConfig,
     pipeline::{ColumnName, Http1ClientOptions, HttpMethod, Options},
     stream_engine::{
-        autonomous_executor::{task::sink_task::sink_writer::SinkWriter, SchemalessRow},
+        autonomous_executor::{
+            task::{
+                reconnect::{Reconnecting, Unconnected},
+                sink_task::sink_writer::{SinkWriter, SinkWriterContext},
+                ConnectionEvents,
+            },
+            SchemalessRow,
+        },
         SqlValue,
     },
 };
 
+/// Sink writer sending a BLOB column of each row as an HTTP request body.
+///
+/// After a request fails, requests are not sent until the remote host accepts a TCP connection again
+/// following the reconnect policy in [SpringSinkWriterConfig].
+/// Rows are left in the input queue meanwhile, and the failed request is sent again after reconnection.
 #[derive(Debug)]
 pub struct HttpClientSinkWriter {
     foreign_addr: SocketAddr,
 
     timeout: Duration,
     connect_timeout: Duration,
+    client: Reconnecting<Client>,
+    /// Body of a request failed to be sent.
+    unsent: Option<Vec<u8>>,
 
     http_method: Method,
     url: Url,
@@ -35,7 +54,11 @@ impl From<HttpMethod> for Method {
 }
 
 impl SinkWriter for HttpClientSinkWriter {
-    fn start(options: &Options, config: &SpringSinkWriterConfig) -> Result<Self> {
+    fn start(
+        options: &Options,
+        config: &SpringSinkWriterConfig,
+        _context: &SinkWriterContext,
+    ) -> Result<Self> {
         let options = Http1ClientOptions::try_from(options)?;
         let sock_addr = SocketAddr::new(options.remote_host, options.remote_port);
 
@@ -48,12 +71,20 @@ impl SinkWriter for HttpClientSinkWriter {
             HeaderMap::try_from(&options.headers).expect("don't know why this fails");
         let http_body_blob_column = options.blob_body_column;
 
+        let client = Self::build_client(sock_addr, timeout, connect_timeout)?;
+
         log::info!("[HttpClientSinkWriter] Ready to connect {}", sock_addr);
 
         Ok(Self {
             foreign_addr: sock_addr,
             timeout,
             connect_timeout,
+            client: Reconnecting::new(
+                format!("HttpClientSinkWriter to {}", sock_addr),
+                client,
+                config.into(),
+            ),
+            unsent: None,
             http_method,
             url,
             http_headers,
@@ -70,17 +101,83 @@ impl SinkWriter for HttpClientSinkWriter {
             unimplemented!("NULL blob column is not supported yet");
         }
     }
+
+    fn ready(&mut self) -> Result<bool> {
+        let (foreign_addr, timeout, connect_timeout) =
+            (self.foreign_addr, self.timeout, self.connect_timeout);
+
+        match self
+            .client
+            .connection(|| Self::reconnect(foreign_addr, timeout, connect_timeout))
+        {
+            Ok(_) => match self.unsent.take() {
+                Some(body) => self.send_request(body).map(|_| true),
+                None => Ok(true),
+            },
+            Err(Unconnected::Waiting(_)) => Ok(false),
+            Err(unconnected) => Err(unconnected.into_error(ForeignInfo::Http(foreign_addr))),
+        }
+    }
+
+    fn has_given_up(&self) -> bool {
+        self.client.has_given_up()
+    }
+
+    fn take_connection_events(&mut self) -> ConnectionEvents {
+        self.client.take_events()
+    }
 }
 
 impl HttpClientSinkWriter {
+    fn build_client(
+        sock_addr: SocketAddr,
+        timeout: Duration,
+        connect_timeout: Duration,
+    ) -> Result<Client> {
+        Client::builder()
+            .connect_timeout(connect_timeout)
+            .timeout(timeout)
+            .build()
+            .context("failed to create HTTP client")
+            .map_err(|e| SpringError::ForeignIo {
+                source: e,
+                foreign_info: ForeignInfo::Http(sock_addr),
+            })
+    }
+
+    /// Creates a new client after checking if the remote host accepts TCP connections.
+    fn reconnect(
+        sock_addr: SocketAddr,
+        timeout: Duration,
+        connect_timeout: Duration,
+    ) -> Result<Client> {
+        TcpStream::connect_timeout(&sock_addr, connect_timeout)
+            .context("failed to connect to remote host")
+            .map_err(|e| SpringError::ForeignIo {
+                source: e,
+                foreign_info: ForeignInfo::Http(sock_addr),
+            })?;
+        Self::build_client(sock_addr, timeout, connect_timeout)
+    }
+
+    /// Holds `body` if failed to send it.
     fn send_request(&mut self, body: Vec<u8>) -> Result<()> {
+        let (foreign_addr, timeout, connect_timeout) =
+            (self.foreign_addr, self.timeout, self.connect_timeout);
+
+        let client = match self
+            .client
+            .connection(|| Self::reconnect(foreign_addr, timeout, connect_timeout))
+        {
+            Ok(client) => client,
+            Err(unconnected) => {
+                self.unsent = Some(body);
+                return Err(unconnected.into_error(ForeignInfo::Http(foreign_addr)));
+            }
+        };
+
         let req_builder = if self.http_method == Method::POST {
-            reqwest::blocking::Client::builder()
-                .connect_timeout(self.connect_timeout)
-                .timeout(self.timeout)
-                .build()
-                .expect("msg: failed to create reqwest client")
-                .post(self.url.to_string())
+            client.post(self.url.to_string())
         } else {
             unimplemented!("HTTP method {} is not supported yet", self.http_method);
         };
@@ -91,11 +188,15 @@ impl HttpClientSinkWriter {
 
         let _resp = req_builder
             .headers(self.http_headers.clone())
-            .body(body)
+            .body(body.clone())
             .send()
-            .map_err(|e| SpringError::ForeignIo {
-                foreign_info: ForeignInfo::Http(self.foreign_addr),
-                source: e.into(),
+            .map_err(|e| {
+                self.client.disconnect(&e);
+                self.unsent = Some(body);
+                SpringError::ForeignIo {
+                    foreign_info: ForeignInfo::Http(foreign_addr),
+                    source: e.into(),
+                }
             })?;
 
         Ok(())
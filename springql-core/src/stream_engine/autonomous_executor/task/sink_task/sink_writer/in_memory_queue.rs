@@ -1,31 +1,59 @@
 // This file is part of https://github.com/SpringQL/SpringQL which is licensed under MIT OR Apache-2.0. See file LICENSE-MIT or LICENSE-APACHE for full license details.
 
+use std::sync::Arc;
+
 use crate::{
     api::{error::Result, SpringSinkWriterConfig},
     pipeline::{InMemoryQueueOptions, Options, QueueName},
     stream_engine::{
-        autonomous_executor::{task::sink_task::sink_writer::SinkWriter, SchemalessRow},
-        in_memory_queue_repository::InMemoryQueueRepository,
+        autonomous_executor::{
+            task::sink_task::sink_writer::{SinkWriter, SinkWriterContext},
+            SchemalessRow,
+        },
+        in_memory_queue_repository::{InMemoryQueue, InMemoryQueueRepository},
     },
 };
 
 #[derive(Debug)]
-pub struct InMemoryQueueSinkWriter(QueueName);
+pub struct InMemoryQueueSinkWriter {
+    queue_name: QueueName,
+    queue: Arc<InMemoryQueue>,
+    queue_repository: Arc<InMemoryQueueRepository>,
+}
 
 impl SinkWriter for InMemoryQueueSinkWriter {
-    fn start(options: &Options, _config: &SpringSinkWriterConfig) -> Result<Self>
-    where
-        Self: Sized,
-    {
+    /// Creates the queue in the in-memory queue repository.
+    ///
+    /// # Failure
+    ///
+    /// - `SpringError::Sql` when:
+    ///   - queue with the same name already exists in the in-memory queue repository.
+    /// - `SpringError::InvalidOption`
+    fn start(
+        options: &Options,
+        _config: &SpringSinkWriterConfig,
+        context: &SinkWriterContext,
+    ) -> Result<Self> {
         let options = InMemoryQueueOptions::try_from(options)?;
+        let queue_repository = context.in_memory_queue_repository().clone();
         let queue_name = options.queue_name;
-        InMemoryQueueRepository::instance().create(queue_name.clone())?;
-        Ok(Self(queue_name))
+        let queue = queue_repository.create(queue_name.clone(), options.shared)?;
+        Ok(Self {
+            queue_name,
+            queue,
+            queue_repository,
+        })
     }
 
     fn send_row(&mut self, row: SchemalessRow) -> Result<()> {
-        let q = InMemoryQueueRepository::instance().get(&self.0)?;
-        q.push(row);
+        self.queue.push(row);
         Ok(())
     }
 }
+
+impl Drop for InMemoryQueueSinkWriter {
+    /// Frees the queue when the sink writer is dropped from pipeline.
+    fn drop(&mut self) {
+        self.queue_repository.remove(&self.queue_name);
+    }
+}
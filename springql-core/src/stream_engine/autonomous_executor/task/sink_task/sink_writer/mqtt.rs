@@ -0,0 +1,306 @@
+// This file is part of https://github.com/SpringQL/SpringQL which is licensed under MIT OR Apache-2.0. See file LICENSE-MIT or LICENSE-APACHE for full license details.
+
+use std::time::Duration;
+
+use anyhow::anyhow;
+
+use crate::{
+    api::error::{foreign_info::ForeignInfo, Result, SpringError},
+    api::SpringSinkWriterConfig,
+    pipeline::{MqttOptions, MqttPayloadFormat, Options},
+    stream_engine::{
+        autonomous_executor::{
+            row::JsonObject,
+            task::{
+                mqtt_client::{MqttClient, MqttClientConfig},
+                reconnect::{Reconnecting, Unconnected},
+                sink_task::sink_writer::{SinkWriter, SinkWriterContext},
+                ConnectionEvents,
+            },
+            SchemalessRow,
+        },
+        SqlValue,
+    },
+};
+
+/// Sink writer publishing a message per row to `TOPIC` on an MQTT broker.
+///
+/// The payload is a JSON document of the row in `'JSON'` format, or the value of `PAYLOAD_COLUMN` in `'TEXT'` and `'BLOB'` formats.
+///
+/// The connection is re-established following the reconnect policy in [SpringSinkWriterConfig] after it is lost.
+/// Rows are left in the input queue while disconnected, and a message failed to be published is published again after reconnection.
+#[derive(Debug)]
+pub struct MqttSinkWriter {
+    options: MqttOptions,
+    topic: String,
+    config: SpringSinkWriterConfig,
+    client: Reconnecting<MqttClient>,
+    /// Payload failed to be published.
+    unsent: Option<Vec<u8>>,
+}
+
+impl SinkWriter for MqttSinkWriter {
+    /// # Failure
+    ///
+    /// - `SpringError::ForeignIo`
+    /// - `SpringError::InvalidOption`
+    fn start(
+        options: &Options,
+        config: &SpringSinkWriterConfig,
+        _context: &SinkWriterContext,
+    ) -> Result<Self> {
+        let options = MqttOptions::try_from(options)?;
+        let topic = options.topic_name()?.to_string();
+        let client = MqttClient::connect(&options, MqttClientConfig::from(config))?;
+
+        log::info!(
+            "[MqttSinkWriter] Ready to publish {} to {}:{}",
+            topic,
+            options.broker_host,
+            options.broker_port
+        );
+
+        Ok(Self {
+            client: Reconnecting::new(
+                format!(
+                    "MqttSinkWriter to {}:{}",
+                    options.broker_host, options.broker_port
+                ),
+                client,
+                config.into(),
+            ),
+            options,
+            topic,
+            config: *config,
+            unsent: None,
+        })
+    }
+
+    /// # Failure
+    ///
+    /// - `SpringError::ForeignIo` when:
+    ///   - failed to publish the row (the connection is regarded as lost, and the row is held).
+    ///   - disconnected.
+    /// - `SpringError::ForeignSourceTimeout` when:
+    ///   - the broker did not acknowledge the row within timeout in QoS 1 (the row is held).
+    /// - `SpringError::Sql` when:
+    ///   - `PAYLOAD_COLUMN` is missing, NULL or not in `FORMAT`.
+    /// - `SpringError::InvalidFormat` when:
+    ///   - the payload is too large for an MQTT message.
+    fn send_row(&mut self, row: SchemalessRow) -> Result<()> {
+        let payload = self.payload(row)?;
+        self.publish(payload)
+    }
+
+    fn ready(&mut self) -> Result<bool> {
+        let options = &self.options;
+        let config = &self.config;
+        match self
+            .client
+            .connection(|| MqttClient::connect(options, MqttClientConfig::from(config)))
+        {
+            Ok(_) => match self.unsent.take() {
+                Some(payload) => self.publish(payload).map(|_| true),
+                None => Ok(true),
+            },
+            Err(Unconnected::Waiting(_)) => Ok(false),
+            Err(unconnected) => Err(unconnected.into_error(Self::foreign_info(options))),
+        }
+    }
+
+    fn has_given_up(&self) -> bool {
+        self.client.has_given_up()
+    }
+
+    fn take_connection_events(&mut self) -> ConnectionEvents {
+        self.client.take_events()
+    }
+}
+
+impl MqttSinkWriter {
+    /// Holds `payload` if failed to publish it to the broker.
+    fn publish(&mut self, payload: Vec<u8>) -> Result<()> {
+        let options = &self.options;
+        let config = &self.config;
+        let client = match self
+            .client
+            .connection(|| MqttClient::connect(options, MqttClientConfig::from(config)))
+        {
+            Ok(client) => client,
+            Err(unconnected) => {
+                self.unsent = Some(payload);
+                return Err(unconnected.into_error(Self::foreign_info(options)));
+            }
+        };
+
+        log::debug!(
+            "[MqttSinkWriter] Publishing to {}: {}",
+            self.topic,
+            String::from_utf8_lossy(&payload)
+        );
+        let res = client.publish(
+            &self.topic,
+            options.qos,
+            &payload,
+            Duration::from_millis(config.mqtt_ack_timeout_msec as u64),
+        );
+        match &res {
+            Err(e @ SpringError::ForeignIo { .. }) => {
+                self.client.disconnect(e);
+                self.unsent = Some(payload);
+            }
+            Err(SpringError::ForeignSourceTimeout { .. }) => self.unsent = Some(payload),
+            // payload which cannot be encoded is never sent
+            _ => {}
+        }
+        res
+    }
+
+    fn payload(&self, row: SchemalessRow) -> Result<Vec<u8>> {
+        if self.options.format == MqttPayloadFormat::Json {
+            return Ok(JsonObject::from(row).to_string().into_bytes());
+        }
+
+        match row.get_by_column_name(&self.options.payload_column)? {
+            SqlValue::NotNull(value) => match self.options.format {
+                MqttPayloadFormat::Text => value.unpack::<String>().map(String::into_bytes),
+                MqttPayloadFormat::Blob => value.unpack::<Vec<u8>>(),
+                MqttPayloadFormat::Json => unreachable!("returned above"),
+            },
+            SqlValue::Null => Err(SpringError::Sql(anyhow!(
+                "payload column {} is NULL",
+                self.options.payload_column
+            ))),
+        }
+    }
+
+    fn foreign_info(options: &MqttOptions) -> ForeignInfo {
+        ForeignInfo::Mqtt(format!("{}:{}", options.broker_host, options.broker_port))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Instant;
+
+    use springql_foreign_service::mqtt_broker::MqttBroker;
+
+    use super::*;
+    use crate::pipeline::OptionsBuilder;
+
+    const TIMEOUT: Duration = Duration::from_secs(1);
+
+    fn options(broker: &MqttBroker) -> OptionsBuilder {
+        OptionsBuilder::default()
+            .add("BROKER_HOST", broker.host_ip().to_string())
+            .add("BROKER_PORT", broker.port().to_string())
+    }
+
+    #[test]
+    fn test_sink_writer_mqtt_json() {
+        let broker = MqttBroker::start().unwrap();
+        let mut sink_writer = MqttSinkWriter::start(
+            &options(&broker)
+                .add("TOPIC", "city/temperature")
+                .add("QOS", "1")
+                .build(),
+            &SpringSinkWriterConfig::fx_default(),
+            &SinkWriterContext::fx_default(),
+        )
+        .unwrap();
+
+        sink_writer
+            .send_row(SchemalessRow::fx_city_temperature_tokyo())
+            .unwrap();
+        sink_writer
+            .send_row(SchemalessRow::fx_city_temperature_osaka())
+            .unwrap();
+
+        for expected in [
+            JsonObject::fx_city_temperature_tokyo(),
+            JsonObject::fx_city_temperature_osaka(),
+        ] {
+            let (topic, payload) = broker.try_receive(TIMEOUT).unwrap();
+            assert_eq!(topic, "city/temperature");
+            assert_eq!(
+                JsonObject::parse(std::str::from_utf8(&payload).unwrap()).unwrap(),
+                expected
+            );
+        }
+        assert!(broker.try_receive(TIMEOUT).is_none());
+    }
+
+    #[test]
+    fn test_sink_writer_mqtt_text() {
+        let broker = MqttBroker::start().unwrap();
+        let mut sink_writer = MqttSinkWriter::start(
+            &options(&broker)
+                .add("TOPIC", "city")
+                .add("FORMAT", "TEXT")
+                .add("PAYLOAD_COLUMN", "city")
+                .build(),
+            &SpringSinkWriterConfig::fx_default(),
+            &SinkWriterContext::fx_default(),
+        )
+        .unwrap();
+
+        sink_writer
+            .send_row(SchemalessRow::fx_city_temperature_tokyo())
+            .unwrap();
+        assert_eq!(
+            broker.try_receive(TIMEOUT).unwrap(),
+            ("city".to_string(), b"Tokyo".to_vec())
+        );
+    }
+
+    #[test]
+    fn test_sink_writer_mqtt_topic_wildcard() {
+        let broker = MqttBroker::start().unwrap();
+        assert!(matches!(
+            MqttSinkWriter::start(
+                &options(&broker).add("TOPIC", "city/+").build(),
+                &SpringSinkWriterConfig::fx_default(),
+                &SinkWriterContext::fx_default()
+            )
+            .unwrap_err(),
+            SpringError::InvalidOption { .. }
+        ));
+    }
+
+    #[test]
+    fn test_sink_writer_mqtt_reconnect() {
+        let broker = MqttBroker::start().unwrap();
+        let config = SpringSinkWriterConfig {
+            reconnect_initial_backoff_msec: 10,
+            ..SpringSinkWriterConfig::fx_default()
+        };
+        let mut sink_writer = MqttSinkWriter::start(
+            &options(&broker).add("TOPIC", "t").add("QOS", "1").build(),
+            &config,
+            &SinkWriterContext::fx_default(),
+        )
+        .unwrap();
+
+        broker.disconnect_all();
+
+        // the row failed to be published is held, and published again after reconnection
+        let _ = sink_writer.send_row(SchemalessRow::fx_city_temperature_tokyo());
+        let deadline = Instant::now() + Duration::from_secs(10);
+        while !sink_writer.ready().unwrap_or(false) {
+            assert!(Instant::now() < deadline, "sink writer did not reconnect");
+            std::thread::sleep(Duration::from_millis(10));
+        }
+        let (_, payload) = broker.try_receive(TIMEOUT).unwrap();
+        assert_eq!(
+            JsonObject::parse(std::str::from_utf8(&payload).unwrap()).unwrap(),
+            JsonObject::fx_city_temperature_tokyo()
+        );
+        assert_eq!(
+            sink_writer.take_connection_events(),
+            ConnectionEvents {
+                n_disconnections: 1,
+                n_reconnections: 1
+            }
+        );
+    }
+}
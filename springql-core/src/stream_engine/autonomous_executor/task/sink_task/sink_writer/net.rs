@@ -1,7 +1,8 @@
 // This file is part of https://github.com/SpringQL/SpringQL which is licensed under MIT OR Apache-2.0. See file LICENSE-MIT or LICENSE-APACHE for full license details.
 
 use std::{
-    io::{BufWriter, Write},
+    collections::VecDeque,
+    io::{self, BufWriter, Write},
     net::{SocketAddr, TcpStream},
     time::Duration,
 };
@@ -13,21 +14,116 @@ use crate::{
     api::SpringSinkWriterConfig,
     pipeline::{NetClientOptions, Options},
     stream_engine::autonomous_executor::{
-        row::JsonObject, task::sink_task::sink_writer::SinkWriter, SchemalessRow,
+        row::JsonObject,
+        task::{
+            reconnect::{Reconnecting, Unconnected},
+            sink_task::sink_writer::{SinkWriter, SinkWriterContext},
+            ConnectionEvents,
+        },
+        SchemalessRow,
     },
 };
 
+/// Sink writer sending a JSON document per line to `REMOTE_HOST:REMOTE_PORT`.
+///
+/// The connection is re-established following the reconnect policy in [SpringSinkWriterConfig] after it is lost.
+/// Rows are left in the input queue while disconnected, and rows failed to be written are written again after reconnection.
+///
+/// A row written into a connection the remote host has closed (e.g. on restart) is accepted locally, and the connection is found reset only afterwards.
+/// Rows written since the connection was last found alive are therefore written again after reconnection too (they may be duplicated).
 #[derive(Debug)]
 pub struct NetSinkWriter {
     foreign_addr: SocketAddr,
-    tcp_stream_writer: BufWriter<TcpStream>, // TODO UDP
+    config: SpringSinkWriterConfig,
+    tcp_stream_writer: Reconnecting<BufWriter<TcpStream>>,
+    /// Rows to write after reconnection, oldest first.
+    unsent: VecDeque<Vec<u8>>,
+    /// Rows written since the connection was last found alive.
+    unconfirmed: Vec<Vec<u8>>,
 }
 
 impl SinkWriter for NetSinkWriter {
-    fn start(options: &Options, config: &SpringSinkWriterConfig) -> Result<Self> {
+    fn start(
+        options: &Options,
+        config: &SpringSinkWriterConfig,
+        _context: &SinkWriterContext,
+    ) -> Result<Self> {
         let options = NetClientOptions::try_from(options)?;
         let sock_addr = SocketAddr::new(options.remote_host, options.remote_port);
 
+        let tcp_stream_writer = Self::connect(sock_addr, config)?;
+
+        log::info!("[NetSinkWriter] Ready to write into {}", sock_addr);
+
+        Ok(Self {
+            tcp_stream_writer: Reconnecting::new(
+                format!("NetSinkWriter to {}", sock_addr),
+                tcp_stream_writer,
+                config.into(),
+            ),
+            foreign_addr: sock_addr,
+            config: *config,
+            unsent: VecDeque::new(),
+            unconfirmed: Vec::new(),
+        })
+    }
+
+    /// # Failure
+    ///
+    /// - `SpringError::ForeignIo` when:
+    ///   - failed to write the row (the connection is regarded as lost, and the row is held).
+    ///   - disconnected.
+    fn send_row(&mut self, row: SchemalessRow) -> Result<()> {
+        let mut json_s = JsonObject::from(row).to_string();
+        json_s.push('\n');
+
+        log::debug!("[NetSinkWriter] Writing message to remote: {}", json_s);
+        self.write_row(json_s.into_bytes())
+    }
+
+    fn ready(&mut self) -> Result<bool> {
+        let foreign_addr = self.foreign_addr;
+        let config = self.config;
+
+        match self
+            .tcp_stream_writer
+            .connection(|| Self::connect(foreign_addr, &config))
+        {
+            Ok(tcp_stream_writer) => {
+                if Self::is_reset(tcp_stream_writer.get_ref()) {
+                    self.tcp_stream_writer
+                        .disconnect("connection reset by remote host");
+                    self.hold_unconfirmed();
+                    return Ok(false);
+                }
+                self.unconfirmed.clear();
+            }
+            Err(Unconnected::Waiting(_)) => return Ok(false),
+            Err(unconnected) => {
+                return Err(unconnected.into_error(ForeignInfo::GenericTcp(foreign_addr)))
+            }
+        }
+
+        while let Some(content) = self.unsent.pop_front() {
+            self.write_row(content)?;
+        }
+        Ok(true)
+    }
+
+    fn has_given_up(&self) -> bool {
+        self.tcp_stream_writer.has_given_up()
+    }
+
+    fn take_connection_events(&mut self) -> ConnectionEvents {
+        self.tcp_stream_writer.take_events()
+    }
+}
+
+impl NetSinkWriter {
+    fn connect(
+        sock_addr: SocketAddr,
+        config: &SpringSinkWriterConfig,
+    ) -> Result<BufWriter<TcpStream>> {
         let tcp_stream = TcpStream::connect_timeout(
             &sock_addr,
             Duration::from_millis(config.net_connect_timeout_msec as u64),
@@ -47,53 +143,94 @@ impl SinkWriter for NetSinkWriter {
                 foreign_info: ForeignInfo::GenericTcp(sock_addr),
             })?;
 
-        let tcp_stream_writer = BufWriter::new(tcp_stream);
+        Ok(BufWriter::new(tcp_stream))
+    }
 
-        log::info!("[NetSinkWriter] Ready to write into {}", sock_addr);
+    /// `true` if the connection is reset (e.g. the remote host closed it and then received a row).
+    ///
+    /// EOF is not regarded as reset: remote sinks may shut down only their sending side.
+    fn is_reset(tcp_stream: &TcpStream) -> bool {
+        // reading returns EOF rather than the error after the remote host shut down its sending side
+        if !matches!(tcp_stream.take_error(), Ok(None)) {
+            return true;
+        }
 
-        Ok(Self {
-            tcp_stream_writer,
-            foreign_addr: sock_addr,
-        })
+        if tcp_stream.set_nonblocking(true).is_err() {
+            return true;
+        }
+        let reset = match tcp_stream.peek(&mut [0u8; 1]) {
+            Ok(_) => false,
+            Err(e) => e.kind() != io::ErrorKind::WouldBlock,
+        };
+        tcp_stream.set_nonblocking(false).is_err() || reset
     }
 
-    fn send_row(&mut self, row: SchemalessRow) -> Result<()> {
-        let mut json_s = JsonObject::from(row).to_string();
-        json_s.push('\n');
-
-        log::debug!("[NetSinkWriter] Writing message to remote: {}", json_s);
-        self.write_row(json_s.as_bytes())
+    /// Puts unconfirmed rows in front of unsent ones.
+    fn hold_unconfirmed(&mut self) {
+        for content in self.unconfirmed.drain(..).rev() {
+            self.unsent.push_front(content);
+        }
     }
-}
 
-impl NetSinkWriter {
-    fn write_row(&mut self, content: &[u8]) -> Result<()> {
-        self.tcp_stream_writer
-            .write_all(content)
+    /// Holds `content` (and unconfirmed rows) if failed to write it.
+    fn write_row(&mut self, content: Vec<u8>) -> Result<()> {
+        let foreign_addr = self.foreign_addr;
+        let config = self.config;
+
+        let tcp_stream_writer = match self
+            .tcp_stream_writer
+            .connection(|| Self::connect(foreign_addr, &config))
+        {
+            Ok(tcp_stream_writer) => tcp_stream_writer,
+            Err(unconnected) => {
+                self.unsent.push_front(content);
+                self.hold_unconfirmed();
+                return Err(unconnected.into_error(ForeignInfo::GenericTcp(foreign_addr)));
+            }
+        };
+
+        let res = tcp_stream_writer
+            .write_all(&content)
             .with_context(|| {
                 format!(
                     "failed to write row's content to remote sink: {}",
-                    String::from_utf8_lossy(content)
+                    String::from_utf8_lossy(&content)
                 )
             })
-            .map_err(|e| SpringError::ForeignIo {
-                source: e,
-                foreign_info: ForeignInfo::GenericTcp(self.foreign_addr),
-            })?;
-        self.tcp_stream_writer
-            .flush()
-            .with_context(|| "failed to flush row to remote sink")
-            .map_err(|e| SpringError::ForeignIo {
-                source: e,
-                foreign_info: ForeignInfo::GenericTcp(self.foreign_addr),
-            })?;
+            .and_then(|_| {
+                tcp_stream_writer
+                    .flush()
+                    .with_context(|| "failed to flush row to remote sink")
+            });
 
-        Ok(())
+        match res {
+            Ok(()) => {
+                self.unconfirmed.push(content);
+                Ok(())
+            }
+            Err(e) => {
+                self.tcp_stream_writer.disconnect(&e);
+                self.unsent.push_front(content);
+                self.hold_unconfirmed();
+                Err(SpringError::ForeignIo {
+                    source: e,
+                    foreign_info: ForeignInfo::GenericTcp(foreign_addr),
+                })
+            }
+        }
     }
 }
 
 #[cfg(test)]
 mod tests {
+    use std::{
+        io::{BufRead, BufReader},
+        net::TcpListener,
+        sync::mpsc,
+        thread,
+        time::Instant,
+    };
+
     use springql_foreign_service::sink::ForeignSink;
 
     use super::*;
@@ -109,8 +246,12 @@ mod tests {
             .add("REMOTE_PORT", sink.port().to_string())
             .build();
 
-        let mut sink_writer =
-            NetSinkWriter::start(&options, &SpringSinkWriterConfig::fx_default()).unwrap();
+        let mut sink_writer = NetSinkWriter::start(
+            &options,
+            &SpringSinkWriterConfig::fx_default(),
+            &SinkWriterContext::fx_default(),
+        )
+        .unwrap();
 
         sink_writer
             .send_row(SchemalessRow::fx_city_temperature_tokyo())
@@ -137,4 +278,101 @@ mod tests {
         );
         assert!(sink.try_receive(TIMEOUT).is_none());
     }
+
+    #[test]
+    fn test_sink_writer_tcp_reconnect() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let remote_addr = listener.local_addr().unwrap();
+
+        let (tx, rx) = mpsc::channel();
+        let server = thread::spawn(move || {
+            // each connection is closed after receiving a row (remote host restarts)
+            for _ in 0..2 {
+                let (stream, _) = listener.accept().unwrap();
+                let mut line = String::new();
+                BufReader::new(&stream).read_line(&mut line).unwrap();
+                drop(stream);
+                tx.send(JsonObject::parse(&line).unwrap()).unwrap();
+            }
+        });
+
+        let options = OptionsBuilder::default()
+            .add("PROTOCOL", "TCP")
+            .add("REMOTE_HOST", remote_addr.ip().to_string())
+            .add("REMOTE_PORT", remote_addr.port().to_string())
+            .build();
+        let config = SpringSinkWriterConfig {
+            reconnect_initial_backoff_msec: 10,
+            ..SpringSinkWriterConfig::fx_default()
+        };
+        let mut sink_writer =
+            NetSinkWriter::start(&options, &config, &SinkWriterContext::fx_default()).unwrap();
+
+        assert!(sink_writer.ready().unwrap());
+        sink_writer
+            .send_row(SchemalessRow::fx_city_temperature_tokyo())
+            .unwrap();
+        assert_eq!(
+            rx.recv_timeout(Duration::from_secs(1)).unwrap(),
+            JsonObject::fx_city_temperature_tokyo()
+        );
+
+        // the row written into the closed connection is written again after reconnection, as the sink task keeps calling ready()
+        assert!(sink_writer.ready().unwrap());
+        let _ = sink_writer.send_row(SchemalessRow::fx_city_temperature_osaka());
+        let deadline = Instant::now() + Duration::from_secs(10);
+        let received = loop {
+            if let Ok(json) = rx.recv_timeout(Duration::from_millis(10)) {
+                break json;
+            }
+            let _ = sink_writer.ready();
+            assert!(Instant::now() < deadline, "sink writer did not reconnect");
+        };
+        assert_eq!(received, JsonObject::fx_city_temperature_osaka());
+        assert_eq!(
+            sink_writer.take_connection_events(),
+            ConnectionEvents {
+                n_disconnections: 1,
+                n_reconnections: 1
+            }
+        );
+
+        server.join().unwrap();
+    }
+
+    #[test]
+    fn test_sink_writer_tcp_give_up() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let remote_addr = listener.local_addr().unwrap();
+
+        let options = OptionsBuilder::default()
+            .add("PROTOCOL", "TCP")
+            .add("REMOTE_HOST", remote_addr.ip().to_string())
+            .add("REMOTE_PORT", remote_addr.port().to_string())
+            .build();
+        let config = SpringSinkWriterConfig {
+            reconnect_max_retries: 2,
+            reconnect_initial_backoff_msec: 10,
+            ..SpringSinkWriterConfig::fx_default()
+        };
+        let mut sink_writer =
+            NetSinkWriter::start(&options, &config, &SinkWriterContext::fx_default()).unwrap();
+
+        // remote host stops
+        drop(listener.accept().unwrap());
+        drop(listener);
+
+        let deadline = Instant::now() + Duration::from_secs(10);
+        while !sink_writer.has_given_up() {
+            if let Ok(true) = sink_writer.ready() {
+                let _ = sink_writer.send_row(SchemalessRow::fx_city_temperature_tokyo());
+            }
+            assert!(Instant::now() < deadline, "sink writer did not give up");
+            thread::sleep(Duration::from_millis(10));
+        }
+        assert!(matches!(
+            sink_writer.ready().unwrap_err(),
+            SpringError::ForeignIo { .. }
+        ));
+    }
 }
@@ -0,0 +1,165 @@
+// This file is part of https://github.com/SpringQL/SpringQL which is licensed under MIT OR Apache-2.0. See file LICENSE-MIT or LICENSE-APACHE for full license details.
+
+use std::{
+    net::{SocketAddr, UdpSocket},
+    time::Duration,
+};
+
+use anyhow::{anyhow, Context};
+
+use crate::{
+    api::error::{foreign_info::ForeignInfo, Result, SpringError},
+    api::SpringSinkWriterConfig,
+    pipeline::{NetClientOptions, Options},
+    stream_engine::autonomous_executor::{
+        row::JsonObject,
+        task::sink_task::sink_writer::{SinkWriter, SinkWriterContext},
+        SchemalessRow,
+    },
+};
+
+/// Sink writer sending a JSON document per UDP datagram to `REMOTE_HOST:REMOTE_PORT`.
+#[derive(Debug)]
+pub struct NetUdpSinkWriter {
+    foreign_addr: SocketAddr,
+    socket: UdpSocket,
+    max_datagram_bytes: usize,
+}
+
+impl SinkWriter for NetUdpSinkWriter {
+    fn start(
+        options: &Options,
+        config: &SpringSinkWriterConfig,
+        _context: &SinkWriterContext,
+    ) -> Result<Self> {
+        let options = NetClientOptions::try_from(options)?;
+        let sock_addr = SocketAddr::new(options.remote_host, options.remote_port);
+
+        let socket = UdpSocket::bind(options.local_bind_addr())
+            .and_then(|socket| {
+                socket.connect(sock_addr)?;
+                socket.set_write_timeout(Some(Duration::from_millis(
+                    config.net_write_timeout_msec as u64,
+                )))?;
+                Ok(socket)
+            })
+            .context("failed to open UDP socket to remote host")
+            .map_err(|e| SpringError::ForeignIo {
+                source: e,
+                foreign_info: ForeignInfo::GenericUdp(sock_addr),
+            })?;
+
+        log::info!(
+            "[NetUdpSinkWriter] Ready to send datagrams to {}",
+            sock_addr
+        );
+
+        Ok(Self {
+            foreign_addr: sock_addr,
+            socket,
+            max_datagram_bytes: config.net_max_datagram_bytes as usize,
+        })
+    }
+
+    /// # Failure
+    ///
+    /// - `SpringError::ForeignIo` when:
+    ///   - the row encoded into JSON exceeds `net_max_datagram_bytes`.
+    ///   - failed to send a datagram.
+    fn send_row(&mut self, row: SchemalessRow) -> Result<()> {
+        let json_s = JsonObject::from(row).to_string();
+
+        if json_s.len() > self.max_datagram_bytes {
+            return Err(SpringError::ForeignIo {
+                source: anyhow!(
+                    "row exceeds {} bytes (net_max_datagram_bytes): {}",
+                    self.max_datagram_bytes,
+                    json_s
+                ),
+                foreign_info: ForeignInfo::GenericUdp(self.foreign_addr),
+            });
+        }
+
+        log::debug!("[NetUdpSinkWriter] Sending datagram to remote: {}", json_s);
+        self.socket
+            .send(json_s.as_bytes())
+            .with_context(|| format!("failed to send row to remote sink: {}", json_s))
+            .map_err(|e| SpringError::ForeignIo {
+                source: e,
+                foreign_info: ForeignInfo::GenericUdp(self.foreign_addr),
+            })?;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pipeline::OptionsBuilder;
+
+    fn udp_writer(remote: &UdpSocket, max_datagram_bytes: u32) -> NetUdpSinkWriter {
+        let options = OptionsBuilder::default()
+            .add("PROTOCOL", "UDP")
+            .add("REMOTE_HOST", "127.0.0.1")
+            .add(
+                "REMOTE_PORT",
+                remote.local_addr().unwrap().port().to_string(),
+            )
+            .build();
+        let config = SpringSinkWriterConfig {
+            net_max_datagram_bytes: max_datagram_bytes,
+            ..SpringSinkWriterConfig::fx_default()
+        };
+        NetUdpSinkWriter::start(&options, &config, &SinkWriterContext::fx_default()).unwrap()
+    }
+
+    fn recv_json(remote: &UdpSocket) -> JsonObject {
+        let mut buf = [0; 1024];
+        let n = remote.recv(&mut buf).unwrap();
+        JsonObject::parse(std::str::from_utf8(&buf[..n]).unwrap()).unwrap()
+    }
+
+    #[test]
+    fn test_sink_writer_udp() {
+        let remote = UdpSocket::bind("127.0.0.1:0").unwrap();
+        remote
+            .set_read_timeout(Some(Duration::from_secs(1)))
+            .unwrap();
+
+        let mut sink_writer = udp_writer(&remote, 65_507);
+        sink_writer
+            .send_row(SchemalessRow::fx_city_temperature_tokyo())
+            .unwrap();
+        sink_writer
+            .send_row(SchemalessRow::fx_city_temperature_osaka())
+            .unwrap();
+
+        assert_eq!(recv_json(&remote), JsonObject::fx_city_temperature_tokyo());
+        assert_eq!(recv_json(&remote), JsonObject::fx_city_temperature_osaka());
+    }
+
+    #[test]
+    fn test_sink_writer_udp_oversized_row() {
+        let remote = UdpSocket::bind("127.0.0.1:0").unwrap();
+        remote
+            .set_read_timeout(Some(Duration::from_millis(100)))
+            .unwrap();
+
+        let max_datagram_bytes = JsonObject::fx_city_temperature_tokyo().to_string().len() as u32;
+        let mut sink_writer = udp_writer(&remote, max_datagram_bytes);
+
+        assert!(matches!(
+            sink_writer
+                .send_row(SchemalessRow::fx_city_temperature_london())
+                .unwrap_err(),
+            SpringError::ForeignIo { .. }
+        ));
+        sink_writer
+            .send_row(SchemalessRow::fx_city_temperature_tokyo())
+            .unwrap();
+
+        assert_eq!(recv_json(&remote), JsonObject::fx_city_temperature_tokyo());
+        assert!(remote.recv(&mut [0; 1024]).is_err());
+    }
+}
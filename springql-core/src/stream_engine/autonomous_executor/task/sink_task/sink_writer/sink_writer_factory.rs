@@ -2,10 +2,11 @@
 
 use crate::{
     api::{error::Result, SpringSinkWriterConfig},
-    pipeline::{Options, SinkWriterType},
+    pipeline::{NetClientOptions, NetProtocol, Options, SinkWriterType},
     stream_engine::autonomous_executor::task::sink_task::sink_writer::{
-        http_client::HttpClientSinkWriter, in_memory_queue::InMemoryQueueSinkWriter,
-        net::NetSinkWriter, SinkWriter,
+        callback::CallbackSinkWriter, http_client::HttpClientSinkWriter,
+        in_memory_queue::InMemoryQueueSinkWriter, mqtt::MqttSinkWriter, net::NetSinkWriter,
+        net_udp::NetUdpSinkWriter, SinkWriter, SinkWriterContext,
     },
 };
 
@@ -16,20 +17,35 @@ impl SinkWriterFactory {
         sink_writer_type: &SinkWriterType,
         options: &Options,
         config: &SpringSinkWriterConfig,
+        context: &SinkWriterContext,
     ) -> Result<Box<dyn SinkWriter>> {
         match sink_writer_type {
-            SinkWriterType::Net => {
-                let sink_writer = NetSinkWriter::start(options, config)?;
-                Ok(Box::new(sink_writer) as Box<dyn SinkWriter>)
-            }
+            SinkWriterType::Net => match NetClientOptions::try_from(options)?.protocol {
+                NetProtocol::Tcp => {
+                    let sink_writer = NetSinkWriter::start(options, config, context)?;
+                    Ok(Box::new(sink_writer) as Box<dyn SinkWriter>)
+                }
+                NetProtocol::Udp => {
+                    let sink_writer = NetUdpSinkWriter::start(options, config, context)?;
+                    Ok(Box::new(sink_writer) as Box<dyn SinkWriter>)
+                }
+            },
             SinkWriterType::Http1Client => {
-                let sink_writer = HttpClientSinkWriter::start(options, config)?;
+                let sink_writer = HttpClientSinkWriter::start(options, config, context)?;
                 Ok(Box::new(sink_writer) as Box<dyn SinkWriter>)
             }
             SinkWriterType::InMemoryQueue => {
-                let sink = InMemoryQueueSinkWriter::start(options, config)?;
+                let sink = InMemoryQueueSinkWriter::start(options, config, context)?;
+                Ok(Box::new(sink) as Box<dyn SinkWriter>)
+            }
+            SinkWriterType::Callback => {
+                let sink = CallbackSinkWriter::start(options, config, context)?;
                 Ok(Box::new(sink) as Box<dyn SinkWriter>)
             }
+            SinkWriterType::Mqtt => {
+                let sink_writer = MqttSinkWriter::start(options, config, context)?;
+                Ok(Box::new(sink_writer) as Box<dyn SinkWriter>)
+            }
         }
     }
 }
@@ -1,7 +1,7 @@
 // This file is part of https://github.com/SpringQL/SpringQL which is licensed under MIT OR Apache-2.0. See file LICENSE-MIT or LICENSE-APACHE for full license details.
 
 use std::{
-    collections::HashMap,
+    collections::{HashMap, HashSet},
     sync::{Arc, Mutex},
 };
 
@@ -10,8 +10,12 @@ use parking_lot::RwLock;
 use crate::{
     api::{error::Result, SpringSinkWriterConfig},
     pipeline::{SinkWriterModel, SinkWriterName},
-    stream_engine::autonomous_executor::task::sink_task::sink_writer::{
-        sink_writer_factory::SinkWriterFactory, SinkWriter,
+    stream_engine::{
+        autonomous_executor::task::sink_task::sink_writer::{
+            sink_writer_factory::SinkWriterFactory, SinkWriter, SinkWriterContext,
+        },
+        in_memory_queue_repository::InMemoryQueueRepository,
+        sink_callback_repository::SinkCallbackRepository,
     },
 };
 
@@ -19,14 +23,20 @@ use crate::{
 #[derive(Debug)]
 pub struct SinkWriterRepository {
     config: SpringSinkWriterConfig,
+    context: SinkWriterContext,
 
     sinks: RwLock<HashMap<SinkWriterName, Arc<Mutex<Box<dyn SinkWriter>>>>>,
 }
 
 impl SinkWriterRepository {
-    pub fn new(config: SpringSinkWriterConfig) -> Self {
+    pub fn new(
+        config: SpringSinkWriterConfig,
+        in_memory_queue_repository: Arc<InMemoryQueueRepository>,
+        sink_callback_repository: Arc<SinkCallbackRepository>,
+    ) -> Self {
         Self {
             config,
+            context: SinkWriterContext::new(in_memory_queue_repository, sink_callback_repository),
             sinks: RwLock::default(),
         }
     }
@@ -47,6 +57,7 @@ impl SinkWriterRepository {
                 sink_writer.sink_writer_type(),
                 sink_writer.options(),
                 &self.config,
+                &self.context,
             )?;
             let subtask = Arc::new(Mutex::new(subtask as Box<dyn SinkWriter>));
             let _ = sinks.insert(sink_writer.name().clone(), subtask);
@@ -58,6 +69,19 @@ impl SinkWriterRepository {
         }
     }
 
+    /// Drop sink writers whose names are not in `names`.
+    ///
+    /// A dropped sink writer closes its connection when the last task holding it releases it.
+    pub fn retain(&self, names: &HashSet<SinkWriterName>) {
+        self.sinks.write().retain(|name, _| {
+            let keep = names.contains(name);
+            if !keep {
+                log::debug!("[SinkWriterRepository] dropped sink subtask: {}", name);
+            }
+            keep
+        });
+    }
+
     /// # Panics
     ///
     /// `name` is not registered yet
@@ -3,14 +3,18 @@
 mod source_reader;
 
 pub use source_reader::{
-    NetClientSourceReader, NetServerSourceReader, SourceReader, SourceReaderRepository,
+    NetClientSourceReader, NetServerSourceReader, SourceReader, SourceReaderContext,
+    SourceReaderRepository,
 };
 
 use std::fmt::Debug;
-use std::sync::Arc;
+use std::sync::{
+    atomic::{AtomicBool, Ordering},
+    Arc,
+};
 
 use crate::{
-    api::error::Result,
+    api::error::{Result, SpringError},
     mem_size::MemSize,
     pipeline::{SourceReaderModel, SourceReaderName, StreamName},
     stream_engine::{
@@ -33,6 +37,9 @@ pub struct SourceTask {
     id: TaskId,
     source_reader_name: SourceReaderName,
     source_stream_name: StreamName,
+
+    /// Set after the source reader reaches end-of-stream and all rows from it are put into queues.
+    end_of_stream: AtomicBool,
 }
 
 impl SourceTask {
@@ -42,6 +49,7 @@ impl SourceTask {
             id,
             source_reader_name: source_reader.name().clone(),
             source_stream_name: source_reader.dest_source_stream().clone(),
+            end_of_stream: AtomicBool::new(false),
         }
     }
 
@@ -49,24 +57,53 @@ impl SourceTask {
         &self.id
     }
 
+    pub fn is_end_of_stream(&self) -> bool {
+        self.end_of_stream.load(Ordering::Acquire)
+    }
+
     pub fn run(&self, context: &TaskContext) -> Result<TaskRunResult> {
         let stopwatch = WallClockStopwatch::start();
 
-        let (processed_rows, out_queue_metrics_seq) = match self.collect_next(context) {
-            Some(row) => {
-                let out_queue_metrics_seq = context
-                    .output_queues()
-                    .into_iter()
-                    .map(|out_qid| self.put_row_into(out_qid, row.clone(), context)) // remove None metrics
-                    .collect::<Vec<OutQueueMetricsUpdateByTask>>();
-                (ProcessedRows::new(1), out_queue_metrics_seq)
-            }
-            None => (ProcessedRows::default(), vec![]),
-        };
+        let source_reader = context
+            .repos()
+            .source_reader_repository()
+            .get_source_reader(&self.source_reader_name);
+        // Hold the source reader until the row is put into queues so that downstream tasks never see end-of-stream before the last row.
+        let mut source_reader = source_reader
+            .lock()
+            .expect("other worker threads sharing the same subtask must not get panic");
+
+        let (processed_rows, out_queue_metrics_seq, n_errors, n_dropped_rows) =
+            match self.collect_next(context, source_reader.as_mut()) {
+                Ok(Some(row)) => {
+                    let out_queue_metrics_seq = context
+                        .output_queues()
+                        .into_iter()
+                        .map(|out_qid| self.put_row_into(out_qid, row.clone(), context)) // remove None metrics
+                        .collect::<Vec<OutQueueMetricsUpdateByTask>>();
+                    (ProcessedRows::new(1), out_queue_metrics_seq, 0, 0)
+                }
+                Ok(None) => (ProcessedRows::default(), vec![], 0, 0),
+                Err(e) => {
+                    let n_errors = if Self::is_error_counted(&e) { 1 } else { 0 };
+                    let n_dropped_rows = if let SpringError::InvalidFormat { .. } = e {
+                        1
+                    } else {
+                        0
+                    };
+                    AutonomousExecutor::handle_error(e);
+                    (ProcessedRows::default(), vec![], n_errors, n_dropped_rows)
+                }
+            };
+        let connection_events = source_reader.take_connection_events();
+        drop(source_reader);
 
         let execution_time = stopwatch.stop();
 
-        let task_metrics = TaskMetricsUpdateByTask::new(context.task(), execution_time);
+        let task_metrics = TaskMetricsUpdateByTask::new(context.task(), execution_time)
+            .with_errors(n_errors)
+            .with_connection_events(connection_events)
+            .with_source_rows(processed_rows.len(), n_dropped_rows);
         let metrics =
             MetricsUpdateByTaskExecution::new(task_metrics, vec![], out_queue_metrics_seq);
         Ok(TaskRunResult {
@@ -115,11 +152,24 @@ impl SourceTask {
         OutQueueMetricsUpdateByTask::new(queue_id.into(), 1, bytes_put as u64)
     }
 
-    fn collect_next(&self, context: &TaskContext) -> Option<StreamRow> {
-        let source_reader = context
-            .repos()
-            .source_reader_repository()
-            .get_source_reader(&self.source_reader_name);
+    /// # Returns
+    ///
+    /// - `Ok(None)` when no row is available (including timeout and end-of-stream).
+    ///
+    /// # Failure
+    ///
+    /// - Errors from the source reader or in converting its row into the source stream's row.
+    fn collect_next(
+        &self,
+        context: &TaskContext,
+        source_reader: &mut dyn SourceReader,
+    ) -> Result<Option<StreamRow>> {
+        if source_reader.is_end_of_stream() {
+            if !self.end_of_stream.swap(true, Ordering::AcqRel) {
+                log::info!("[SourceTask] {} reached end-of-stream", self.id);
+            }
+            return Ok(None);
+        }
 
         let source_stream = context
             .pipeline_derivatives()
@@ -127,21 +177,28 @@ impl SourceTask {
             .get_stream(&self.source_stream_name)
             .expect("cannot do anything if source stream name is wrong here");
 
-        let mut source_reader = source_reader
-            .lock()
-            .expect("other worker threads sharing the same subtask must not get panic");
         source_reader
             .next_row()
             .and_then(|source_row| {
                 let schemaless_row = SchemalessRow::try_from(source_row)?;
                 StreamRow::from_schemaless_row(schemaless_row, source_stream)
             })
-            .map_or_else(
-                |e| {
-                    AutonomousExecutor::handle_error(e);
-                    None
-                },
-                Some,
-            )
+            .map(Some)
+            .or_else(|e| {
+                // error on reaching end-of-stream is expected
+                if source_reader.is_end_of_stream() {
+                    Ok(None)
+                } else {
+                    Err(e)
+                }
+            })
+    }
+
+    /// Timeouts are not errors in metrics since foreign sources may not always have rows.
+    fn is_error_counted(e: &SpringError) -> bool {
+        !matches!(
+            e,
+            SpringError::ForeignSourceTimeout { .. } | SpringError::InputTimeout { .. }
+        )
     }
 }
@@ -1,9 +1,12 @@
 // This file is part of https://github.com/SpringQL/SpringQL which is licensed under MIT OR Apache-2.0. See file LICENSE-MIT or LICENSE-APACHE for full license details.
 
 mod can;
+mod file;
 mod in_memory_queue;
+mod mqtt;
 mod net_client;
 mod net_server;
+mod net_udp;
 mod source_reader_factory;
 mod source_reader_repository;
 
@@ -12,19 +15,50 @@ pub use net_client::NetClientSourceReader;
 pub use net_server::NetServerSourceReader;
 pub use source_reader_repository::SourceReaderRepository;
 
-use std::fmt::Debug;
+use std::{fmt::Debug, sync::Arc};
 
 use crate::{
-    api::error::Result, api::SpringSourceReaderConfig, pipeline::Options,
-    stream_engine::autonomous_executor::row::SourceRow,
+    api::{error::Result, SpringSourceReaderConfig},
+    pipeline::{Options, SourceReaderType, StreamModel},
+    stream_engine::{
+        autonomous_executor::{row::SourceRow, task::ConnectionEvents},
+        in_memory_queue_repository::InMemoryQueueRepository,
+    },
 };
 
+/// Stream engine resources passed to `SourceReader::start()`.
+#[derive(Clone, Debug, new)]
+pub struct SourceReaderContext {
+    source_reader_type: SourceReaderType,
+    /// Source stream the source reader feeds rows into.
+    dest_stream: Arc<StreamModel>,
+    in_memory_queue_repository: Arc<InMemoryQueueRepository>,
+}
+
+impl SourceReaderContext {
+    pub fn source_reader_type(&self) -> &SourceReaderType {
+        &self.source_reader_type
+    }
+
+    pub fn dest_stream(&self) -> &StreamModel {
+        &self.dest_stream
+    }
+
+    pub fn in_memory_queue_repository(&self) -> &Arc<InMemoryQueueRepository> {
+        &self.in_memory_queue_repository
+    }
+}
+
 /// Instance of SourceReaderModel.
 ///
 /// Since agents and servers may live as long as a program lives, source task cannot hold hold implementations of this trait.
 pub trait SourceReader: Debug + Sync + Send + 'static {
     /// Blocks until the source subtask is ready to provide SourceRow.
-    fn start(options: &Options, config: &SpringSourceReaderConfig) -> Result<Self>
+    fn start(
+        options: &Options,
+        config: &SpringSourceReaderConfig,
+        context: &SourceReaderContext,
+    ) -> Result<Self>
     where
         Self: Sized;
 
@@ -38,4 +72,17 @@ pub trait SourceReader: Debug + Sync + Send + 'static {
     ///   - Failed to parse response from remote source.
     ///   - Unknown foreign error.
     fn next_row(&mut self) -> Result<SourceRow>;
+
+    /// `true` after the foreign source reached its end (e.g. the remote host closed the connection).
+    /// `next_row()` is not called after that.
+    ///
+    /// Windows downstream of sources all reaching end-of-stream emit their open panes.
+    fn is_end_of_stream(&self) -> bool {
+        false
+    }
+
+    /// Disconnections from / reconnections to the foreign source since the last call.
+    fn take_connection_events(&mut self) -> ConnectionEvents {
+        ConnectionEvents::default()
+    }
 }
@@ -11,7 +11,10 @@ use crate::{
         SpringError, SpringSourceReaderConfig,
     },
     pipeline::{CANOptions, Options},
-    stream_engine::autonomous_executor::{row::CANFrameSourceRow, SourceReader, SourceRow},
+    stream_engine::autonomous_executor::{
+        row::CANFrameSourceRow, task::source_task::source_reader::SourceReaderContext,
+        SourceReader, SourceRow,
+    },
 };
 
 /// # Data format
@@ -31,7 +34,11 @@ impl SourceReader for CANSourceReader {
     ///
     /// - `SpringError::ForeignIo`
     /// - `SpringError::InvalidOption`
-    fn start(options: &Options, config: &SpringSourceReaderConfig) -> Result<Self> {
+    fn start(
+        options: &Options,
+        config: &SpringSourceReaderConfig,
+        _context: &SourceReaderContext,
+    ) -> Result<Self> {
         let options = CANOptions::try_from(options)?;
 
         let interface = &options.interface;
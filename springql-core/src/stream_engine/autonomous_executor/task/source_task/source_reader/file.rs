@@ -0,0 +1,699 @@
+// This file is part of https://github.com/SpringQL/SpringQL which is licensed under MIT OR Apache-2.0. See file LICENSE-MIT or LICENSE-APACHE for full license details.
+
+use std::{
+    collections::HashMap,
+    fs::File,
+    io::{self, BufRead, BufReader},
+    str::FromStr,
+    thread,
+    time::{Duration, Instant},
+};
+
+use anyhow::{anyhow, Context};
+
+use crate::{
+    api::error::{foreign_info::ForeignInfo, Result, SpringError},
+    api::SpringSourceReaderConfig,
+    pipeline::{
+        ColumnName, FileFormat, FileOptions, NumericComparableType, Options, ReplaySpeed, SqlType,
+    },
+    stream_engine::{
+        autonomous_executor::{
+            row::{JsonObject, JsonSourceRow, SourceRow},
+            task::source_task::source_reader::{SourceReader, SourceReaderContext},
+        },
+        time::SpringTimestamp,
+    },
+    time::Duration as SpringDuration,
+};
+
+/// How often a FILE source reader in `FOLLOW` mode checks for appended lines.
+const FOLLOW_POLL_INTERVAL: Duration = Duration::from_millis(10);
+
+/// Source reader replaying a JSON Lines or CSV file.
+///
+/// - `REPLAY_SPEED`: rows are read as fast as possible (`'MAX'`), or paced by the ROWTIME column of the source stream.
+/// - `FOLLOW`: waits for lines appended after the end of the file.
+/// - `LOOP`: reads the file again from the beginning after its end.
+///   ROWTIME is shifted forward by the time span of the file (max - min ROWTIME) on each round, so that replayed rows are not too late for windows.
+///
+/// Otherwise, the source reaches end-of-stream at the end of the file.
+///
+/// CSV fields are converted into the data types of the source stream's columns (fields of unknown columns are kept as strings).
+#[derive(Debug)]
+pub struct FileSourceReader {
+    options: FileOptions,
+    /// `Some` if paced by `REPLAY_SPEED` or shifted by `LOOP`.
+    rowtime_column: Option<ColumnName>,
+    column_types: HashMap<ColumnName, SqlType>,
+    read_timeout: Duration,
+
+    reader: BufReader<File>,
+    /// Line not terminated by a newline yet (in `FOLLOW` mode).
+    partial_line: String,
+    /// Column names from the header line of CSV.
+    csv_header: Option<csv::StringRecord>,
+    n_rows_in_round: u64,
+    /// (min, max) ROWTIME in the file (before shifted) seen in the current round.
+    rowtime_range: Option<(SpringTimestamp, SpringTimestamp)>,
+    /// Added to ROWTIME of the rows in the current round (`LOOP`).
+    rowtime_shift: SpringDuration,
+
+    /// Row read from the file but not due yet.
+    pending: Option<(JsonObject, SpringTimestamp)>,
+    /// (ROWTIME of the first row, when the first row was read)
+    pace_origin: Option<(SpringTimestamp, Instant)>,
+
+    end_of_stream: bool,
+}
+
+impl SourceReader for FileSourceReader {
+    /// # Failure
+    ///
+    /// - `SpringError::ForeignIo` when:
+    ///   - failed to open the file.
+    /// - `SpringError::InvalidOption` when:
+    ///   - `REPLAY_SPEED` is a number but the source stream does not have a ROWTIME column.
+    fn start(
+        options: &Options,
+        config: &SpringSourceReaderConfig,
+        context: &SourceReaderContext,
+    ) -> Result<Self> {
+        let options = FileOptions::try_from(options)?;
+        let source_stream = context.dest_stream();
+
+        let stream_rowtime_column = source_stream.shape().event_time();
+        let rowtime_column = match options.replay_speed {
+            ReplaySpeed::Max if options.loop_replay => stream_rowtime_column.cloned(),
+            ReplaySpeed::Max => None,
+            ReplaySpeed::Scaled(speed) => Some(
+                stream_rowtime_column
+                    .ok_or_else(|| SpringError::InvalidOption {
+                        key: "REPLAY_SPEED".to_string(),
+                        value: speed.to_string(),
+                        source: anyhow!("source stream must have a ROWTIME column to pace rows"),
+                    })?
+                    .clone(),
+            ),
+        };
+        let column_types = source_stream
+            .shape()
+            .columns()
+            .iter()
+            .map(|column| {
+                (
+                    column.column_name().clone(),
+                    column.column_data_type().sql_type().clone(),
+                )
+            })
+            .collect();
+
+        let reader = Self::open(&options)?;
+
+        log::info!(
+            "[FileSourceReader] Ready to read {}",
+            options.path.display()
+        );
+
+        Ok(Self {
+            options,
+            rowtime_column,
+            column_types,
+            read_timeout: Duration::from_millis(config.file_read_timeout_msec as u64),
+            reader,
+            partial_line: String::new(),
+            csv_header: None,
+            n_rows_in_round: 0,
+            rowtime_range: None,
+            rowtime_shift: SpringDuration::seconds(0),
+            pending: None,
+            pace_origin: None,
+            end_of_stream: false,
+        })
+    }
+
+    /// # Failure
+    ///
+    /// - `SpringError::ForeignSourceTimeout` when:
+    ///   - the next row is not due yet (`REPLAY_SPEED`).
+    ///   - no line is appended within timeout (`FOLLOW`).
+    ///   - reached the end of the file.
+    /// - `SpringError::ForeignIo` when:
+    ///   - failed to read the file.
+    /// - `SpringError::InvalidFormat` when:
+    ///   - a line is not in `FORMAT` (the line is skipped).
+    ///   - a CSV field cannot be converted into the data type of its column (the line is skipped).
+    ///   - a line does not have a valid ROWTIME column (`REPLAY_SPEED` or `LOOP`).
+    fn next_row(&mut self) -> Result<SourceRow> {
+        let deadline = Instant::now() + self.read_timeout;
+
+        loop {
+            if self.end_of_stream {
+                return Err(self.timeout("reached the end of the file"));
+            }
+
+            let (json, rowtime) = match self.pending.take() {
+                Some(pending) => pending,
+                None => match self.read_row()? {
+                    Some(json) => match self.rowtime_column.clone() {
+                        Some(rowtime_column) => {
+                            let (json, rowtime) = self.shift_rowtime(json, &rowtime_column)?;
+                            if !self.is_paced() {
+                                return Ok(Self::source_row(json));
+                            }
+                            (json, rowtime)
+                        }
+                        None => return Ok(Self::source_row(json)),
+                    },
+                    None => {
+                        self.on_end_of_file(deadline)?;
+                        continue;
+                    }
+                },
+            };
+
+            let due = self.due_at(rowtime);
+            let now = Instant::now();
+            if due > now {
+                if due > deadline {
+                    self.pending = Some((json, rowtime));
+                    thread::sleep(deadline.saturating_duration_since(now));
+                    return Err(self.timeout("next row is not due yet"));
+                }
+                thread::sleep(due - now);
+            }
+            return Ok(Self::source_row(json));
+        }
+    }
+
+    fn is_end_of_stream(&self) -> bool {
+        self.end_of_stream
+    }
+}
+
+impl FileSourceReader {
+    fn open(options: &FileOptions) -> Result<BufReader<File>> {
+        File::open(&options.path)
+            .context("failed to open file")
+            .map(BufReader::new)
+            .map_err(|e| SpringError::ForeignIo {
+                source: e,
+                foreign_info: ForeignInfo::File(options.path.display().to_string()),
+            })
+    }
+
+    fn on_end_of_file(&mut self, deadline: Instant) -> Result<()> {
+        if self.options.follow {
+            let now = Instant::now();
+            if now >= deadline {
+                return Err(self.timeout("no line appended"));
+            }
+            thread::sleep(FOLLOW_POLL_INTERVAL.min(deadline - now));
+        } else if self.options.loop_replay {
+            if self.n_rows_in_round == 0 {
+                return Err(self.timeout("no row in the file"));
+            }
+            log::debug!(
+                "[FileSourceReader] Reading {} again",
+                self.options.path.display()
+            );
+            self.reader = Self::open(&self.options)?;
+            self.partial_line.clear();
+            self.csv_header = None;
+            self.n_rows_in_round = 0;
+            if let Some((min_rowtime, max_rowtime)) = self.rowtime_range.take() {
+                self.rowtime_shift = self.rowtime_shift + (max_rowtime - min_rowtime);
+            }
+            self.pace_origin = None;
+        } else {
+            log::info!(
+                "[FileSourceReader] Reached the end of {}",
+                self.options.path.display()
+            );
+            self.end_of_stream = true;
+        }
+        Ok(())
+    }
+
+    /// Returns `None` at the end of the file.
+    fn read_row(&mut self) -> Result<Option<JsonObject>> {
+        while let Some(line) = self.read_line()? {
+            let json = match self.options.format {
+                FileFormat::JsonLines => JsonObject::parse(&line)?,
+                FileFormat::Csv => match &self.csv_header {
+                    Some(header) => self.parse_csv_record(header, &line)?,
+                    None => {
+                        self.csv_header = Some(Self::parse_csv_line(&line)?);
+                        continue;
+                    }
+                },
+            };
+            self.n_rows_in_round += 1;
+            return Ok(Some(json));
+        }
+        Ok(None)
+    }
+
+    /// Returns a non-empty line without the newline, or `None` at the end of the file.
+    fn read_line(&mut self) -> Result<Option<String>> {
+        loop {
+            let n_bytes = self.reader.read_line(&mut self.partial_line).map_err(|e| {
+                if e.kind() == io::ErrorKind::InvalidData {
+                    SpringError::InvalidFormat {
+                        s: "(line not in UTF-8)".to_string(),
+                        source: e.into(),
+                    }
+                } else {
+                    SpringError::ForeignIo {
+                        source: e.into(),
+                        foreign_info: ForeignInfo::File(self.options.path.display().to_string()),
+                    }
+                }
+            })?;
+
+            let complete = self.partial_line.ends_with('\n')
+                || (n_bytes == 0 && !self.options.follow && !self.partial_line.is_empty());
+            if complete {
+                let line = std::mem::take(&mut self.partial_line);
+                let line = line.trim_end_matches(&['\r', '\n'][..]);
+                if !line.trim().is_empty() {
+                    return Ok(Some(line.to_string()));
+                }
+            } else if n_bytes == 0 {
+                return Ok(None);
+            }
+        }
+    }
+
+    fn parse_csv_line(line: &str) -> Result<csv::StringRecord> {
+        csv::ReaderBuilder::new()
+            .has_headers(false)
+            .from_reader(line.as_bytes())
+            .records()
+            .next()
+            .context("empty CSV line")
+            .and_then(|record| record.context("failed to parse CSV line"))
+            .map_err(|e| SpringError::InvalidFormat {
+                s: line.to_string(),
+                source: e,
+            })
+    }
+
+    /// Empty fields are NULL.
+    fn parse_csv_record(&self, header: &csv::StringRecord, line: &str) -> Result<JsonObject> {
+        let record = Self::parse_csv_line(line)?;
+        if record.len() != header.len() {
+            return Err(SpringError::InvalidFormat {
+                s: line.to_string(),
+                source: anyhow!(
+                    "{} fields in a CSV record while the header has {}",
+                    record.len(),
+                    header.len()
+                ),
+            });
+        }
+
+        let object = header
+            .iter()
+            .zip(record.iter())
+            .map(|(column, field)| {
+                let sql_type = self.column_types.get(&ColumnName::new(column.to_string()));
+                Self::parse_csv_field(field, sql_type)
+                    .map(|value| (column.to_string(), value))
+                    .with_context(|| format!("invalid field for column {}", column))
+                    .map_err(|e| SpringError::InvalidFormat {
+                        s: line.to_string(),
+                        source: e,
+                    })
+            })
+            .collect::<Result<serde_json::Map<_, _>>>()?;
+        Ok(JsonObject::new(serde_json::Value::Object(object)))
+    }
+
+    /// Numeric and boolean fields are parsed as `sql_type`. Other fields (including those of unknown columns) are strings.
+    fn parse_csv_field(
+        field: &str,
+        sql_type: Option<&SqlType>,
+    ) -> anyhow::Result<serde_json::Value> {
+        if field.is_empty() {
+            return Ok(serde_json::Value::Null);
+        }
+
+        let value = match sql_type {
+            Some(SqlType::NumericComparable(NumericComparableType::I64Loose(_))) => {
+                serde_json::Value::from(field.parse::<i64>()?)
+            }
+            Some(SqlType::NumericComparable(NumericComparableType::U64Loose(_))) => {
+                serde_json::Value::from(field.parse::<u64>()?)
+            }
+            Some(SqlType::NumericComparable(NumericComparableType::F32Loose(_))) => {
+                serde_json::Number::from_f64(field.parse::<f64>()?)
+                    .map(serde_json::Value::Number)
+                    .context("not a finite number")?
+            }
+            Some(SqlType::BooleanComparable) => {
+                serde_json::Value::Bool(field.to_lowercase().parse::<bool>()?)
+            }
+            _ => serde_json::Value::String(field.to_string()),
+        };
+        Ok(value)
+    }
+
+    /// Adds `rowtime_shift` to the ROWTIME column of `json`.
+    ///
+    /// # Returns
+    ///
+    /// The shifted row and its ROWTIME.
+    fn shift_rowtime(
+        &mut self,
+        json: JsonObject,
+        rowtime_column: &ColumnName,
+    ) -> Result<(JsonObject, SpringTimestamp)> {
+        let rowtime = Self::rowtime(&json, rowtime_column)?;
+        let (min_rowtime, max_rowtime) = self.rowtime_range.get_or_insert((rowtime, rowtime));
+        *min_rowtime = (*min_rowtime).min(rowtime);
+        *max_rowtime = (*max_rowtime).max(rowtime);
+
+        if self.rowtime_shift.num_nanoseconds() == 0 {
+            Ok((json, rowtime))
+        } else {
+            let shifted = rowtime + self.rowtime_shift;
+            let mut value = serde_json::Value::from(json);
+            value[rowtime_column.as_ref()] = serde_json::Value::String(shifted.to_string());
+            Ok((JsonObject::new(value), shifted))
+        }
+    }
+
+    fn rowtime(json: &JsonObject, rowtime_column: &ColumnName) -> Result<SpringTimestamp> {
+        let value = serde_json::Value::from(json.clone());
+        value
+            .get(rowtime_column.as_ref())
+            .and_then(serde_json::Value::as_str)
+            .with_context(|| format!("ROWTIME column {} must be a string", rowtime_column))
+            .map_err(|e| SpringError::InvalidFormat {
+                s: value.to_string(),
+                source: e,
+            })
+            .and_then(SpringTimestamp::from_str)
+    }
+
+    fn is_paced(&self) -> bool {
+        matches!(self.options.replay_speed, ReplaySpeed::Scaled(_))
+    }
+
+    /// When a row with `rowtime` should be read, relative to the first row.
+    ///
+    /// Rows older than the first row are due immediately.
+    fn due_at(&mut self, rowtime: SpringTimestamp) -> Instant {
+        let speed = match self.options.replay_speed {
+            ReplaySpeed::Scaled(speed) => speed,
+            ReplaySpeed::Max => unreachable!("only paced rows have ROWTIME"),
+        };
+        let (first_rowtime, first_read_at) = *self
+            .pace_origin
+            .get_or_insert_with(|| (rowtime, Instant::now()));
+
+        let elapsed = (rowtime - first_rowtime).to_std().unwrap_or_default();
+        first_read_at + elapsed.div_f64(speed)
+    }
+
+    fn source_row(json: JsonObject) -> SourceRow {
+        SourceRow::Json(JsonSourceRow::from_json(json))
+    }
+
+    fn timeout(&self, reason: &'static str) -> SpringError {
+        SpringError::ForeignSourceTimeout {
+            source: anyhow!(reason),
+            foreign_info: ForeignInfo::File(self.options.path.display().to_string()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{io::Write, sync::Arc};
+
+    use tempfile::NamedTempFile;
+
+    use super::*;
+    use crate::{
+        pipeline::{
+            ColumnDataType, ColumnDefinition, OptionsBuilder, SourceReaderType, StreamModel,
+            StreamName, StreamShape,
+        },
+        stream_engine::{
+            autonomous_executor::SchemalessRow, in_memory_queue_repository::InMemoryQueueRepository,
+        },
+    };
+
+    /// `ts` (ROWTIME), `n` INTEGER, `city` TEXT, `code` TEXT and `temperature` FLOAT.
+    fn source_stream() -> StreamModel {
+        let columns = [
+            ("n", SqlType::integer()),
+            ("city", SqlType::text()),
+            ("code", SqlType::text()),
+            ("temperature", SqlType::float()),
+        ]
+        .into_iter()
+        .map(|(name, sql_type)| {
+            ColumnDefinition::new(
+                ColumnDataType::new(ColumnName::new(name.to_string()), sql_type, true),
+                vec![],
+            )
+        });
+        let shape = StreamShape::new(
+            std::iter::once(ColumnDefinition::fx_timestamp())
+                .chain(columns)
+                .collect(),
+        )
+        .unwrap();
+        StreamModel::new(StreamName::factory("source_file"), shape)
+    }
+
+    fn context(source_stream: StreamModel) -> SourceReaderContext {
+        SourceReaderContext::new(
+            SourceReaderType::File,
+            Arc::new(source_stream),
+            Arc::new(InMemoryQueueRepository::default()),
+        )
+    }
+
+    fn file_with(content: &str) -> NamedTempFile {
+        let mut file = NamedTempFile::new().unwrap();
+        file.write_all(content.as_bytes()).unwrap();
+        file.flush().unwrap();
+        file
+    }
+
+    fn start(file: &NamedTempFile, options: &[(&str, &str)]) -> FileSourceReader {
+        let options = options
+            .iter()
+            .fold(
+                OptionsBuilder::default().add("PATH", file.path().to_str().unwrap()),
+                |builder, (key, value)| builder.add(*key, *value),
+            )
+            .build();
+        FileSourceReader::start(
+            &options,
+            &SpringSourceReaderConfig::fx_default(),
+            &context(source_stream()),
+        )
+        .unwrap()
+    }
+
+    fn json_row(json: serde_json::Value) -> SourceRow {
+        SourceRow::Json(JsonSourceRow::from_json(JsonObject::new(json)))
+    }
+
+    fn assert_end_of_stream(reader: &mut FileSourceReader) {
+        assert!(matches!(
+            reader.next_row().unwrap_err(),
+            SpringError::ForeignSourceTimeout { .. }
+        ));
+        assert!(reader.is_end_of_stream());
+    }
+
+    #[test]
+    fn test_source_file_jsonl() {
+        let file = file_with("{\"n\": 1}\n\n{ broken json\n{\"n\": 2}");
+        let mut reader = start(&file, &[]);
+
+        assert_eq!(
+            reader.next_row().unwrap(),
+            json_row(serde_json::json!({"n": 1}))
+        );
+        assert!(matches!(
+            reader.next_row().unwrap_err(),
+            SpringError::InvalidFormat { .. }
+        ));
+        assert_eq!(
+            reader.next_row().unwrap(),
+            json_row(serde_json::json!({"n": 2}))
+        );
+        assert_end_of_stream(&mut reader);
+    }
+
+    #[test]
+    fn test_source_file_csv() {
+        let file = file_with("ts,city,code,temperature,unknown\r\n2022-01-01 13:00:00.000000000,\"Tokyo, Japan\",007,21.5,1\n2022-01-01 13:00:01.000000000,,1e3,-3,x\n2022-01-01 13:00:02.000000000,Osaka,,warm,\n");
+        let mut reader = start(&file, &[("FORMAT", "CSV")]);
+
+        // converted by the column types, not by guessing from the fields
+        assert_eq!(
+            reader.next_row().unwrap(),
+            json_row(serde_json::json!({
+                "ts": "2022-01-01 13:00:00.000000000",
+                "city": "Tokyo, Japan",
+                "code": "007",
+                "temperature": 21.5,
+                "unknown": "1"
+            }))
+        );
+        assert_eq!(
+            reader.next_row().unwrap(),
+            json_row(serde_json::json!({
+                "ts": "2022-01-01 13:00:01.000000000",
+                "city": null,
+                "code": "1e3",
+                "temperature": -3.0,
+                "unknown": "x"
+            }))
+        );
+        assert!(matches!(
+            reader.next_row().unwrap_err(),
+            SpringError::InvalidFormat { .. }
+        ));
+        assert_end_of_stream(&mut reader);
+    }
+
+    #[test]
+    fn test_source_file_replay_speed() {
+        let file = file_with(
+            r#"{"ts": "2022-01-01 13:00:00.000000000", "n": 1}
+{"ts": "2022-01-01 13:00:00.500000000", "n": 2}
+{"ts": "2022-01-01 12:00:00.000000000", "n": 3}
+"#,
+        );
+        let mut reader = start(&file, &[("REPLAY_SPEED", "2")]);
+
+        let started_at = Instant::now();
+        let mut n_timeouts = 0;
+        let mut ns = Vec::new();
+        while ns.len() < 3 {
+            match reader.next_row() {
+                Ok(row) => ns.push(
+                    SchemalessRow::try_from(row)
+                        .unwrap()
+                        .get_by_column_name(&ColumnName::new("n".to_string()))
+                        .unwrap()
+                        .clone(),
+                ),
+                Err(SpringError::ForeignSourceTimeout { .. }) => n_timeouts += 1,
+                Err(e) => panic!("{:?}", e),
+            }
+        }
+        // 0.5 sec in ROWTIME = 0.25 sec in replay (the 3rd row is older than the 1st and read immediately)
+        let elapsed = started_at.elapsed();
+        assert!(
+            Duration::from_millis(250) <= elapsed && elapsed < Duration::from_millis(1000),
+            "{:?}",
+            elapsed
+        );
+        assert!(n_timeouts >= 1); // file_read_timeout_msec = 100
+        assert_eq!(ns.len(), 3);
+        assert_end_of_stream(&mut reader);
+    }
+
+    #[test]
+    fn test_source_file_replay_speed_requires_rowtime() {
+        let file = file_with("");
+        let options = OptionsBuilder::default()
+            .add("PATH", file.path().to_str().unwrap())
+            .add("REPLAY_SPEED", "1")
+            .build();
+        let no_rowtime_stream = StreamModel::new(
+            StreamName::factory("source_file"),
+            StreamShape::fx_no_promoted_rowtime(),
+        );
+        assert!(matches!(
+            FileSourceReader::start(
+                &options,
+                &SpringSourceReaderConfig::fx_default(),
+                &context(no_rowtime_stream)
+            )
+            .unwrap_err(),
+            SpringError::InvalidOption { .. }
+        ));
+    }
+
+    #[test]
+    fn test_source_file_follow() {
+        let mut file = file_with("{\"n\": 1}\n{\"n\":");
+        let mut reader = start(&file, &[("FOLLOW", "TRUE")]);
+
+        assert_eq!(
+            reader.next_row().unwrap(),
+            json_row(serde_json::json!({"n": 1}))
+        );
+        // incomplete line is not read until terminated
+        assert!(matches!(
+            reader.next_row().unwrap_err(),
+            SpringError::ForeignSourceTimeout { .. }
+        ));
+        assert!(!reader.is_end_of_stream());
+
+        file.write_all(b" 2}\n").unwrap();
+        file.flush().unwrap();
+        assert_eq!(
+            reader.next_row().unwrap(),
+            json_row(serde_json::json!({"n": 2}))
+        );
+        assert!(!reader.is_end_of_stream());
+    }
+
+    #[test]
+    fn test_source_file_loop() {
+        let file =
+            file_with("ts,n\n2022-01-01 13:00:00.000000000,1\n2022-01-01 13:00:10.000000000,2\n");
+        let mut reader = start(&file, &[("FORMAT", "CSV"), ("LOOP", "TRUE")]);
+
+        // ROWTIME is shifted by 10 sec on each round
+        for (ts, n) in [
+            ("2022-01-01 13:00:00.000000000", 1),
+            ("2022-01-01 13:00:10.000000000", 2),
+            ("2022-01-01 13:00:10.000000000", 1),
+            ("2022-01-01 13:00:20.000000000", 2),
+            ("2022-01-01 13:00:20.000000000", 1),
+        ] {
+            assert_eq!(
+                reader.next_row().unwrap(),
+                json_row(serde_json::json!({ "ts": ts, "n": n }))
+            );
+        }
+        assert!(!reader.is_end_of_stream());
+
+        let empty_file = file_with("ts,n\n");
+        let mut reader = start(&empty_file, &[("FORMAT", "CSV"), ("LOOP", "TRUE")]);
+        assert!(matches!(
+            reader.next_row().unwrap_err(),
+            SpringError::ForeignSourceTimeout { .. }
+        ));
+        assert!(!reader.is_end_of_stream());
+    }
+
+    #[test]
+    fn test_source_file_not_found() {
+        let options = OptionsBuilder::default()
+            .add("PATH", "/nonexistent/file.jsonl")
+            .build();
+        assert!(matches!(
+            FileSourceReader::start(
+                &options,
+                &SpringSourceReaderConfig::fx_default(),
+                &context(source_stream())
+            )
+            .unwrap_err(),
+            SpringError::ForeignIo { .. }
+        ));
+    }
+}
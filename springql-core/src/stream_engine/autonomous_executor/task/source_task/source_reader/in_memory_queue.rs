@@ -1,31 +1,53 @@
 // This file is part of https://github.com/SpringQL/SpringQL which is licensed under MIT OR Apache-2.0. See file LICENSE-MIT or LICENSE-APACHE for full license details.
 
+use std::sync::Arc;
+
 use anyhow::anyhow;
 
 use crate::{
-    api::error::{foreign_info::ForeignInfo, Result, SpringError},
-    api::SpringSourceReaderConfig,
+    api::{
+        error::{foreign_info::ForeignInfo, Result, SpringError},
+        SpringSourceReaderConfig,
+    },
     pipeline::{InMemoryQueueOptions, Options, QueueName},
     stream_engine::{
-        autonomous_executor::{row::SourceRow, task::source_task::source_reader::SourceReader},
-        in_memory_queue_repository::InMemoryQueueRepository,
+        autonomous_executor::{
+            row::SourceRow,
+            task::source_task::source_reader::{SourceReader, SourceReaderContext},
+        },
+        in_memory_queue_repository::{InMemoryQueue, InMemoryQueueRepository},
     },
 };
 
 #[derive(Debug)]
-pub struct InMemoryQueueSourceReader(QueueName);
+pub struct InMemoryQueueSourceReader {
+    queue_name: QueueName,
+    queue: Arc<InMemoryQueue>,
+    queue_repository: Arc<InMemoryQueueRepository>,
+}
 
 impl SourceReader for InMemoryQueueSourceReader {
+    /// Creates the queue in the in-memory queue repository.
+    ///
     /// # Failure
     ///
-    /// - `SpringError::Unavailable` when:
-    ///   - queue name provided from the option is invalid
+    /// - `SpringError::Sql` when:
+    ///   - queue with the same name already exists in the in-memory queue repository.
     /// - `SpringError::InvalidOption`
-    fn start(options: &Options, _config: &SpringSourceReaderConfig) -> Result<Self> {
+    fn start(
+        options: &Options,
+        _config: &SpringSourceReaderConfig,
+        context: &SourceReaderContext,
+    ) -> Result<Self> {
         let options = InMemoryQueueOptions::try_from(options)?;
+        let queue_repository = context.in_memory_queue_repository().clone();
         let queue_name = options.queue_name;
-        InMemoryQueueRepository::instance().create(queue_name.clone())?;
-        Ok(Self(queue_name))
+        let queue = queue_repository.create(queue_name.clone(), options.shared)?;
+        Ok(Self {
+            queue_name,
+            queue,
+            queue_repository,
+        })
     }
 
     /// # Failure
@@ -33,15 +55,24 @@ impl SourceReader for InMemoryQueueSourceReader {
     /// - `SpringError::ForeignSourceTimeout` when:
     ///   - queue does not have any row (does not wait a bit)
     fn next_row(&mut self) -> Result<SourceRow> {
-        let q = InMemoryQueueRepository::instance().get(&self.0)?;
-
-        if let Some(row) = q.pop_non_blocking() {
+        if let Some(row) = self.queue.pop_non_blocking() {
             Ok(SourceRow::Raw(row))
         } else {
             Err(SpringError::ForeignSourceTimeout {
                 source: anyhow!("queue is empty"),
-                foreign_info: ForeignInfo::InMemoryQueue(self.0.clone()),
+                foreign_info: ForeignInfo::InMemoryQueue(self.queue_name.clone()),
             })
         }
     }
+
+    fn is_end_of_stream(&self) -> bool {
+        self.queue.is_end_of_stream()
+    }
+}
+
+impl Drop for InMemoryQueueSourceReader {
+    /// Frees the queue when the source reader is dropped from pipeline.
+    fn drop(&mut self) {
+        self.queue_repository.remove(&self.queue_name);
+    }
 }
@@ -0,0 +1,350 @@
+// This file is part of https://github.com/SpringQL/SpringQL which is licensed under MIT OR Apache-2.0. See file LICENSE-MIT or LICENSE-APACHE for full license details.
+
+use std::time::Duration;
+
+use anyhow::anyhow;
+
+use crate::{
+    api::error::{foreign_info::ForeignInfo, Result, SpringError},
+    api::SpringSourceReaderConfig,
+    pipeline::{MqttOptions, Options},
+    stream_engine::autonomous_executor::{
+        row::{MqttMessageSourceRow, SourceRow},
+        task::{
+            mqtt_client::{MqttClient, MqttClientConfig},
+            reconnect::{Reconnecting, Unconnected},
+            source_task::source_reader::{SourceReader, SourceReaderContext},
+            ConnectionEvents,
+        },
+    },
+};
+
+/// Source reader subscribing to `TOPIC` filters on an MQTT broker.
+///
+/// Each message becomes a row: the payload is mapped to columns by `FORMAT`, and the topic is put into `TOPIC_COLUMN`.
+///
+/// The connection is re-established (and the topics are subscribed again) following the reconnect policy in [SpringSourceReaderConfig] after it is lost,
+/// and the source reaches end-of-stream when reconnection is given up.
+///
+/// A QoS 1 message is acknowledged in the next `next_row()` call, after the source task has put the row into the pipeline.
+#[derive(Debug)]
+pub struct MqttSourceReader {
+    options: MqttOptions,
+    config: SpringSourceReaderConfig,
+    client: Reconnecting<MqttClient>,
+    /// Packet ID of the QoS 1 message returned by the last `next_row()`.
+    unacked: Option<u16>,
+}
+
+impl SourceReader for MqttSourceReader {
+    /// # Failure
+    ///
+    /// - `SpringError::ForeignIo`
+    /// - `SpringError::InvalidOption`
+    fn start(
+        options: &Options,
+        config: &SpringSourceReaderConfig,
+        _context: &SourceReaderContext,
+    ) -> Result<Self> {
+        let options = MqttOptions::try_from(options)?;
+        let client = Self::connect(&options, config)?;
+
+        log::info!(
+            "[MqttSourceReader] Ready to read {:?} from {}:{}",
+            options.topics,
+            options.broker_host,
+            options.broker_port
+        );
+
+        Ok(Self {
+            client: Reconnecting::new(
+                format!(
+                    "MqttSourceReader from {}:{}",
+                    options.broker_host, options.broker_port
+                ),
+                client,
+                config.into(),
+            ),
+            options,
+            config: *config,
+            unacked: None,
+        })
+    }
+
+    /// # Failure
+    ///
+    /// - `SpringError::ForeignSourceTimeout` when:
+    ///   - no message arrives within timeout.
+    ///   - waiting for the next reconnection attempt.
+    /// - `SpringError::ForeignIo` when:
+    ///   - the connection is lost.
+    ///   - failed to reconnect.
+    fn next_row(&mut self) -> Result<SourceRow> {
+        let options = &self.options;
+        let config = &self.config;
+
+        let client = self
+            .client
+            .connection(|| Self::connect(options, config))
+            .map_err(|unconnected| match unconnected {
+                Unconnected::Waiting(_) => SpringError::ForeignSourceTimeout {
+                    source: anyhow!("waiting for reconnection"),
+                    foreign_info: Self::foreign_info(options),
+                },
+                _ => unconnected.into_error(Self::foreign_info(options)),
+            })?;
+
+        let res = match self.unacked.take() {
+            Some(packet_id) => client.ack(packet_id),
+            None => Ok(()),
+        }
+        .and_then(|_| client.recv(Duration::from_millis(config.mqtt_read_timeout_msec as u64)));
+        match res {
+            Ok(Some(message)) => {
+                self.unacked = message.packet_id;
+                log::debug!(
+                    "[MqttSourceReader] received from {}: {}",
+                    message.topic,
+                    String::from_utf8_lossy(&message.payload)
+                );
+                Ok(SourceRow::Mqtt(MqttMessageSourceRow::new(
+                    message.topic,
+                    message.payload,
+                    options.format,
+                    options.topic_column.clone(),
+                    options.payload_column.clone(),
+                )))
+            }
+            Ok(None) => Err(SpringError::ForeignSourceTimeout {
+                source: anyhow!("no message within timeout"),
+                foreign_info: Self::foreign_info(options),
+            }),
+            Err(e) => {
+                // unacknowledged messages are redelivered by the broker in a new connection (if the session is kept)
+                self.client.disconnect(&e);
+                Err(e)
+            }
+        }
+    }
+
+    fn is_end_of_stream(&self) -> bool {
+        self.client.has_given_up()
+    }
+
+    fn take_connection_events(&mut self) -> ConnectionEvents {
+        self.client.take_events()
+    }
+}
+
+impl MqttSourceReader {
+    fn connect(options: &MqttOptions, config: &SpringSourceReaderConfig) -> Result<MqttClient> {
+        let mut client = MqttClient::connect(options, MqttClientConfig::from(config))?;
+        client.subscribe(
+            &options.topics,
+            options.qos,
+            Duration::from_millis(config.mqtt_connect_timeout_msec as u64),
+        )?;
+        Ok(client)
+    }
+
+    fn foreign_info(options: &MqttOptions) -> ForeignInfo {
+        ForeignInfo::Mqtt(format!("{}:{}", options.broker_host, options.broker_port))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Instant;
+
+    use springql_foreign_service::mqtt_broker::MqttBroker;
+
+    use super::*;
+    use crate::{
+        pipeline::{ColumnName, OptionsBuilder, SourceReaderType},
+        stream_engine::{
+            autonomous_executor::{row::JsonObject, SchemalessRow},
+            SqlValue,
+        },
+    };
+
+    fn options(broker: &MqttBroker) -> OptionsBuilder {
+        OptionsBuilder::default()
+            .add("BROKER_HOST", broker.host_ip().to_string())
+            .add("BROKER_PORT", broker.port().to_string())
+    }
+
+    fn text(row: &SchemalessRow, column: &str) -> String {
+        match row
+            .get_by_column_name(&ColumnName::new(column.to_string()))
+            .unwrap()
+        {
+            SqlValue::NotNull(v) => v.unpack().unwrap(),
+            SqlValue::Null => panic!("{} is NULL", column),
+        }
+    }
+
+    fn next_row(reader: &mut MqttSourceReader) -> SchemalessRow {
+        let deadline = Instant::now() + Duration::from_secs(10);
+        loop {
+            match reader.next_row() {
+                Ok(row) => return SchemalessRow::try_from(row).unwrap(),
+                Err(e) => assert!(Instant::now() < deadline, "no row: {:?}", e),
+            }
+        }
+    }
+
+    #[test]
+    fn test_source_mqtt_json() {
+        let broker = MqttBroker::start().unwrap();
+        let options = options(&broker)
+            .add("TOPIC", "city/+/temperature, alert/#")
+            .add("QOS", "1")
+            .add("TOPIC_COLUMN", "city_topic")
+            .build();
+        let mut reader = MqttSourceReader::start(
+            &options,
+            &SpringSourceReaderConfig::fx_default(),
+            &SourceReaderContext::fx_trade(SourceReaderType::Mqtt),
+        )
+        .unwrap();
+
+        let json = JsonObject::fx_city_temperature_tokyo().to_string();
+        broker.publish("city/tokyo/temperature", json.as_bytes(), 1);
+        broker.publish("city/tokyo/humidity", json.as_bytes(), 1); // not subscribed
+        broker.publish("alert/tokyo/heat", json.as_bytes(), 0);
+
+        let row = next_row(&mut reader);
+        assert_eq!(text(&row, "city_topic"), "city/tokyo/temperature");
+        assert_eq!(text(&row, "city"), "Tokyo");
+        assert_eq!(
+            text(&next_row(&mut reader), "city_topic"),
+            "alert/tokyo/heat"
+        );
+        assert!(matches!(
+            reader.next_row().unwrap_err(),
+            SpringError::ForeignSourceTimeout { .. }
+        ));
+    }
+
+    #[test]
+    fn test_source_mqtt_text_and_blob() {
+        let broker = MqttBroker::start().unwrap();
+        let mut text_reader = MqttSourceReader::start(
+            &options(&broker)
+                .add("TOPIC", "log")
+                .add("FORMAT", "TEXT")
+                .build(),
+            &SpringSourceReaderConfig::fx_default(),
+            &SourceReaderContext::fx_trade(SourceReaderType::Mqtt),
+        )
+        .unwrap();
+        let mut blob_reader = MqttSourceReader::start(
+            &options(&broker)
+                .add("TOPIC", "log")
+                .add("FORMAT", "BLOB")
+                .add("PAYLOAD_COLUMN", "data")
+                .build(),
+            &SpringSourceReaderConfig::fx_default(),
+            &SourceReaderContext::fx_trade(SourceReaderType::Mqtt),
+        )
+        .unwrap();
+
+        broker.publish("log", b"hello", 0);
+        broker.publish("log", &[0xff], 0);
+
+        let row = next_row(&mut text_reader);
+        assert_eq!(text(&row, "payload"), "hello");
+        assert_eq!(text(&row, "topic"), "log");
+        let invalid_row = loop {
+            if let Ok(row) = text_reader.next_row() {
+                break row;
+            }
+        };
+        assert!(matches!(
+            SchemalessRow::try_from(invalid_row).unwrap_err(),
+            SpringError::InvalidFormat { .. }
+        ));
+
+        let row = next_row(&mut blob_reader);
+        match row
+            .get_by_column_name(&ColumnName::new("data".to_string()))
+            .unwrap()
+        {
+            SqlValue::NotNull(v) => assert_eq!(v.unpack::<Vec<u8>>().unwrap(), b"hello"),
+            SqlValue::Null => panic!("data is NULL"),
+        }
+    }
+
+    #[test]
+    fn test_source_mqtt_credentials() {
+        let broker = MqttBroker::start_with_credentials("user", "pass").unwrap();
+
+        let bad_options = options(&broker)
+            .add("TOPIC", "t")
+            .add("USERNAME", "user")
+            .add("PASSWORD", "wrong")
+            .build();
+        assert!(matches!(
+            MqttSourceReader::start(
+                &bad_options,
+                &SpringSourceReaderConfig::fx_default(),
+                &SourceReaderContext::fx_trade(SourceReaderType::Mqtt)
+            )
+            .unwrap_err(),
+            SpringError::ForeignIo { .. }
+        ));
+
+        let good_options = options(&broker)
+            .add("TOPIC", "t")
+            .add("USERNAME", "user")
+            .add("PASSWORD", "pass")
+            .build();
+        assert!(MqttSourceReader::start(
+            &good_options,
+            &SpringSourceReaderConfig::fx_default(),
+            &SourceReaderContext::fx_trade(SourceReaderType::Mqtt)
+        )
+        .is_ok());
+    }
+
+    #[test]
+    fn test_source_mqtt_reconnect() {
+        let broker = MqttBroker::start().unwrap();
+        let config = SpringSourceReaderConfig {
+            reconnect_initial_backoff_msec: 10,
+            ..SpringSourceReaderConfig::fx_default()
+        };
+        let mut reader = MqttSourceReader::start(
+            &options(&broker).add("TOPIC", "t").build(),
+            &config,
+            &SourceReaderContext::fx_trade(SourceReaderType::Mqtt),
+        )
+        .unwrap();
+
+        broker.disconnect_all();
+
+        // subscribed again after reconnection
+        let deadline = Instant::now() + Duration::from_secs(10);
+        let row = loop {
+            broker.publish(
+                "t",
+                JsonObject::fx_city_temperature_tokyo()
+                    .to_string()
+                    .as_bytes(),
+                0,
+            );
+            if let Ok(row) = reader.next_row() {
+                break SchemalessRow::try_from(row).unwrap();
+            }
+            assert!(Instant::now() < deadline, "source reader did not reconnect");
+        };
+        assert_eq!(text(&row, "city"), "Tokyo");
+        assert_eq!(
+            reader.take_connection_events(),
+            ConnectionEvents {
+                n_disconnections: 1,
+                n_reconnections: 1
+            }
+        );
+    }
+}
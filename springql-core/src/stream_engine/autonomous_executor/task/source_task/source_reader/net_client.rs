@@ -6,21 +6,31 @@ use std::{
     time::Duration,
 };
 
-use anyhow::Context;
+use anyhow::{anyhow, Context};
 
 use crate::{
     api::error::{foreign_info::ForeignInfo, Result, SpringError},
     api::SpringSourceReaderConfig,
     pipeline::{NetClientOptions, Options},
     stream_engine::autonomous_executor::{
-        row::SourceRow, task::source_task::source_reader::SourceReader,
+        row::SourceRow,
+        task::{
+            reconnect::{Reconnecting, Unconnected},
+            source_task::source_reader::{SourceReader, SourceReaderContext},
+            ConnectionEvents,
+        },
     },
 };
 
+/// Source reader connecting to `REMOTE_HOST:REMOTE_PORT` and reading a JSON document per line.
+///
+/// The connection is re-established following the reconnect policy in [SpringSourceReaderConfig] after it is lost
+/// (including the remote host closing it), and the source reaches end-of-stream when reconnection is given up.
 #[derive(Debug)]
 pub struct NetClientSourceReader {
     foreign_addr: SocketAddr,
-    tcp_stream_reader: BufReader<TcpStream>, // TODO UDP
+    config: SpringSourceReaderConfig,
+    tcp_stream_reader: Reconnecting<BufReader<TcpStream>>,
 }
 
 impl SourceReader for NetClientSourceReader {
@@ -28,10 +38,94 @@ impl SourceReader for NetClientSourceReader {
     ///
     /// - `SpringError::ForeignIo`
     /// - `SpringError::InvalidOption`
-    fn start(options: &Options, config: &SpringSourceReaderConfig) -> Result<Self> {
+    fn start(
+        options: &Options,
+        config: &SpringSourceReaderConfig,
+        _context: &SourceReaderContext,
+    ) -> Result<Self> {
         let options = NetClientOptions::try_from(options)?;
         let sock_addr = SocketAddr::new(options.remote_host, options.remote_port);
 
+        let tcp_stream_reader = Self::connect(sock_addr, config)?;
+
+        log::info!("[NetSourceReader] Ready to read from {}", sock_addr);
+
+        Ok(Self {
+            tcp_stream_reader: Reconnecting::new(
+                format!("NetClientSourceReader from {}", sock_addr),
+                tcp_stream_reader,
+                config.into(),
+            ),
+            foreign_addr: sock_addr,
+            config: *config,
+        })
+    }
+
+    /// # Failure
+    ///
+    /// - `SpringError::ForeignSourceTimeout` when:
+    ///   - no row arrives within timeout.
+    ///   - waiting for the next reconnection attempt.
+    /// - `SpringError::ForeignIo` when:
+    ///   - the connection is lost (including the remote host closing it).
+    ///   - failed to reconnect.
+    fn next_row(&mut self) -> Result<SourceRow> {
+        let foreign_addr = self.foreign_addr;
+        let config = self.config;
+
+        let tcp_stream_reader = self
+            .tcp_stream_reader
+            .connection(|| Self::connect(foreign_addr, &config))
+            .map_err(|unconnected| match unconnected {
+                Unconnected::Waiting(_) => SpringError::ForeignSourceTimeout {
+                    source: anyhow!("waiting for reconnection"),
+                    foreign_info: ForeignInfo::GenericTcp(foreign_addr),
+                },
+                _ => unconnected.into_error(ForeignInfo::GenericTcp(foreign_addr)),
+            })?;
+
+        let mut json_s = String::new();
+        match tcp_stream_reader.read_line(&mut json_s) {
+            Ok(0) => {
+                let e = anyhow!("remote host closed the connection");
+                self.tcp_stream_reader.disconnect(&e);
+                Err(SpringError::ForeignIo {
+                    source: e,
+                    foreign_info: ForeignInfo::GenericTcp(foreign_addr),
+                })
+            }
+            Ok(_) => self.parse_resp(&json_s),
+            Err(io_err) => {
+                if let io::ErrorKind::TimedOut | io::ErrorKind::WouldBlock = io_err.kind() {
+                    Err(SpringError::ForeignSourceTimeout {
+                        source: anyhow::Error::from(io_err),
+                        foreign_info: ForeignInfo::GenericTcp(foreign_addr),
+                    })
+                } else {
+                    self.tcp_stream_reader.disconnect(&io_err);
+                    Err(SpringError::ForeignIo {
+                        source: anyhow::Error::from(io_err),
+                        foreign_info: ForeignInfo::GenericTcp(foreign_addr),
+                    })
+                }
+            }
+        }
+    }
+
+    fn is_end_of_stream(&self) -> bool {
+        self.tcp_stream_reader.has_given_up()
+    }
+
+    fn take_connection_events(&mut self) -> ConnectionEvents {
+        self.tcp_stream_reader.take_events()
+    }
+}
+
+impl NetClientSourceReader {
+    fn connect(
+        sock_addr: SocketAddr,
+        config: &SpringSourceReaderConfig,
+    ) -> Result<BufReader<TcpStream>> {
         let tcp_stream = TcpStream::connect_timeout(
             &sock_addr,
             Duration::from_millis(config.net_connect_timeout_msec as u64),
@@ -51,55 +145,24 @@ impl SourceReader for NetClientSourceReader {
                 foreign_info: ForeignInfo::GenericTcp(sock_addr),
             })?;
 
-        let tcp_stream_reader = BufReader::new(tcp_stream);
-
-        log::info!("[NetSourceReader] Ready to read from {}", sock_addr);
-
-        Ok(Self {
-            tcp_stream_reader,
-            foreign_addr: sock_addr,
-        })
-    }
-
-    fn next_row(&mut self) -> Result<SourceRow> {
-        let mut json_s = String::new();
-
-        self.tcp_stream_reader
-            .read_line(&mut json_s)
-            .map_err(|io_err| {
-                if let io::ErrorKind::TimedOut | io::ErrorKind::WouldBlock = io_err.kind() {
-                    SpringError::ForeignSourceTimeout {
-                        source: anyhow::Error::from(io_err),
-                        foreign_info: ForeignInfo::GenericTcp(self.foreign_addr),
-                    }
-                } else {
-                    SpringError::ForeignIo {
-                        source: anyhow::Error::from(io_err),
-                        foreign_info: ForeignInfo::GenericTcp(self.foreign_addr),
-                    }
-                }
-            })?;
-
-        self.parse_resp(&json_s)
+        Ok(BufReader::new(tcp_stream))
     }
-}
 
-impl NetClientSourceReader {
     // TODO other formats than JSON
     fn parse_resp(&self, json_s: &str) -> Result<SourceRow> {
         SourceRow::from_json(json_s)
     }
 }
 
-impl NetClientSourceReader {}
-
 #[cfg(test)]
 mod tests {
+    use std::{io::Write, net::TcpListener, thread, time::Instant};
+
     use springql_foreign_service::source::ForeignSource;
     use springql_foreign_service::source::ForeignSourceInput;
 
     use super::*;
-    use crate::pipeline::OptionsBuilder;
+    use crate::pipeline::{OptionsBuilder, SourceReaderType};
     use crate::stream_engine::autonomous_executor::row::JsonObject;
     use crate::stream_engine::autonomous_executor::row::JsonSourceRow;
 
@@ -123,8 +186,11 @@ mod tests {
             serde_json::Value::from(j1.clone()),
         ]));
 
-        let mut subtask =
-            NetClientSourceReader::start(&options, &SpringSourceReaderConfig::fx_default())?;
+        let mut subtask = NetClientSourceReader::start(
+            &options,
+            &SpringSourceReaderConfig::fx_default(),
+            &SourceReaderContext::fx_trade(SourceReaderType::NetClient),
+        )?;
 
         assert_eq!(
             subtask.next_row()?,
@@ -145,4 +211,111 @@ mod tests {
 
         Ok(())
     }
+
+    fn reconnecting_reader(
+        remote_addr: SocketAddr,
+        reconnect_max_retries: u32,
+    ) -> NetClientSourceReader {
+        let options = OptionsBuilder::default()
+            .add("PROTOCOL", "TCP")
+            .add("REMOTE_HOST", remote_addr.ip().to_string())
+            .add("REMOTE_PORT", remote_addr.port().to_string())
+            .build();
+        let config = SpringSourceReaderConfig {
+            reconnect_max_retries,
+            reconnect_initial_backoff_msec: 10,
+            ..SpringSourceReaderConfig::fx_default()
+        };
+        NetClientSourceReader::start(
+            &options,
+            &config,
+            &SourceReaderContext::fx_trade(SourceReaderType::NetClient),
+        )
+        .unwrap()
+    }
+
+    fn write_line(stream: &mut TcpStream, json: JsonObject) {
+        stream
+            .write_all(format!("{}\n", json.to_string()).as_bytes())
+            .unwrap();
+    }
+
+    /// Skips errors (disconnection, timeouts, ...) until a row arrives or the reader reaches end-of-stream.
+    fn next_row_skipping_errors(reader: &mut NetClientSourceReader) -> Option<SourceRow> {
+        let deadline = Instant::now() + Duration::from_secs(10);
+        while Instant::now() < deadline {
+            if reader.is_end_of_stream() {
+                return None;
+            }
+            if let Ok(row) = reader.next_row() {
+                return Some(row);
+            }
+        }
+        panic!("neither row nor end-of-stream within timeout")
+    }
+
+    #[test]
+    fn test_source_tcp_reconnect() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let remote_addr = listener.local_addr().unwrap();
+
+        let server = thread::spawn(move || {
+            let (mut stream, _) = listener.accept().unwrap();
+            write_line(&mut stream, JsonObject::fx_city_temperature_tokyo());
+            drop(stream); // remote host restarts
+
+            let (mut stream, _) = listener.accept().unwrap();
+            write_line(&mut stream, JsonObject::fx_city_temperature_osaka());
+            stream
+        });
+
+        let mut reader = reconnecting_reader(remote_addr, 5);
+
+        assert_eq!(
+            next_row_skipping_errors(&mut reader),
+            Some(SourceRow::Json(JsonSourceRow::fx_city_temperature_tokyo()))
+        );
+        assert_eq!(
+            next_row_skipping_errors(&mut reader),
+            Some(SourceRow::Json(JsonSourceRow::fx_city_temperature_osaka()))
+        );
+        assert!(!reader.is_end_of_stream());
+        assert_eq!(
+            reader.take_connection_events(),
+            ConnectionEvents {
+                n_disconnections: 1,
+                n_reconnections: 1
+            }
+        );
+
+        let _stream = server.join().unwrap();
+    }
+
+    #[test]
+    fn test_source_tcp_give_up_reconnect() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let remote_addr = listener.local_addr().unwrap();
+
+        let server = thread::spawn(move || {
+            let (mut stream, _) = listener.accept().unwrap();
+            write_line(&mut stream, JsonObject::fx_city_temperature_tokyo());
+            // remote host closes the connection and stops listening
+        });
+
+        let mut reader = reconnecting_reader(remote_addr, 2);
+        server.join().unwrap();
+
+        assert_eq!(
+            next_row_skipping_errors(&mut reader),
+            Some(SourceRow::Json(JsonSourceRow::fx_city_temperature_tokyo()))
+        );
+        assert_eq!(next_row_skipping_errors(&mut reader), None);
+        assert_eq!(
+            reader.take_connection_events(),
+            ConnectionEvents {
+                n_disconnections: 1,
+                n_reconnections: 0
+            }
+        );
+    }
 }
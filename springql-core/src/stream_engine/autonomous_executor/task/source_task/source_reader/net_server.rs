@@ -1,61 +1,93 @@
 // This file is part of https://github.com/SpringQL/SpringQL which is licensed under MIT OR Apache-2.0. See file LICENSE-MIT or LICENSE-APACHE for full license details.
 
 use std::{
-    io::{BufRead, BufReader},
+    io::{self, BufRead, BufReader},
     net::{Shutdown, SocketAddr, TcpListener, TcpStream},
-    sync::{mpsc, Mutex, MutexGuard},
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        mpsc, Arc, Mutex, MutexGuard,
+    },
     thread,
     time::Duration,
 };
 
+use anyhow::{anyhow, Context};
+
 use crate::{
     api::error::{foreign_info::ForeignInfo, Result, SpringError},
     api::SpringSourceReaderConfig,
-    pipeline::{NetProtocol, NetServerOptions, Options},
+    pipeline::{NetServerOptions, Options},
     stream_engine::autonomous_executor::{
-        row::{JsonObject, JsonSourceRow, SourceRow},
-        task::source_task::source_reader::SourceReader,
+        row::SourceRow,
+        task::source_task::source_reader::{SourceReader, SourceReaderContext},
     },
 };
 
+/// Interval to check if the source reader is dropped while waiting for clients.
+const ACCEPT_POLL_INTERVAL: Duration = Duration::from_millis(100);
+
+/// Source reader accepting TCP clients, each sending a JSON document per line.
+///
+/// Each client is served by its own thread, and rows from all clients are sent to a bounded channel.
+/// While the channel is full, clients are not read (backpressure by TCP flow control).
+///
+/// Malformed lines and lines longer than `net_server_max_line_bytes` are logged and skipped.
+/// They are also sent to the channel as errors to be counted in task metrics.
 #[derive(Debug)]
 pub struct NetServerSourceReader {
     my_addr: SocketAddr,
 
-    /// FIXME this source reader does not scale
-    rx: Mutex<mpsc::Receiver<serde_json::Value>>,
+    rx: Mutex<mpsc::Receiver<Result<SourceRow>>>,
 
     timeout: Duration,
+
+    /// Set on drop to stop the accepting thread and connection threads.
+    stopped: Arc<AtomicBool>,
 }
 
 impl SourceReader for NetServerSourceReader {
     /// # Failure
     ///
-    /// - `SpringError::ForeignIo`
+    /// - `SpringError::ForeignIo` when:
+    ///   - failed to bind `BIND_ADDRESS:PORT`.
     /// - `SpringError::InvalidOption`
-    fn start(options: &Options, config: &SpringSourceReaderConfig) -> Result<Self> {
+    fn start(
+        options: &Options,
+        config: &SpringSourceReaderConfig,
+        _context: &SourceReaderContext,
+    ) -> Result<Self> {
         let options = NetServerOptions::try_from(options)?;
-        assert!(
-            matches!(options.protocol, NetProtocol::Tcp),
-            "unsupported protocol"
-        );
+        let bind_addr = SocketAddr::new(options.bind_address, options.port);
 
-        let listener = TcpListener::bind(("127.0.0.1", options.port)).unwrap();
-        let my_addr = listener.local_addr().unwrap();
+        let (listener, my_addr) = TcpListener::bind(bind_addr)
+            .and_then(|listener| {
+                listener.set_nonblocking(true)?;
+                let my_addr = listener.local_addr()?;
+                Ok((listener, my_addr))
+            })
+            .context("failed to listen")
+            .map_err(|e| SpringError::ForeignIo {
+                source: e,
+                foreign_info: ForeignInfo::GenericTcp(bind_addr),
+            })?;
 
-        let (tx, rx) = mpsc::channel();
+        let (tx, rx) = mpsc::sync_channel(config.net_server_queue_capacity as usize);
 
         let timeout = Duration::from_millis(config.net_read_timeout_msec as u64);
+        let max_line_bytes = config.net_server_max_line_bytes as usize;
+        let stopped = Arc::new(AtomicBool::new(false));
 
-        let _ = thread::Builder::new()
-            .name("NetServerSourceReader".into())
-            .spawn(move || {
-                for stream in listener.incoming() {
-                    let stream = stream.unwrap();
-                    stream.shutdown(Shutdown::Write).unwrap();
-                    Self::stream_handler(stream, tx.clone());
-                }
-            });
+        {
+            let stopped = stopped.clone();
+            thread::Builder::new()
+                .name("NetServerSourceReader".into())
+                .spawn(move || Self::accept_loop(listener, tx, timeout, max_line_bytes, stopped))
+                .context("failed to spawn a thread to accept clients")
+                .map_err(|e| SpringError::ForeignIo {
+                    source: e,
+                    foreign_info: ForeignInfo::GenericTcp(my_addr),
+                })?;
+        }
 
         log::info!(
             "[NetServerSourceReader] Ready to accept rows at {}",
@@ -66,77 +98,234 @@ impl SourceReader for NetServerSourceReader {
             my_addr,
             rx: Mutex::new(rx),
             timeout,
+            stopped,
         })
     }
 
+    /// # Failure
+    ///
+    /// - `SpringError::ForeignSourceTimeout` when:
+    ///   - no row arrives within timeout.
+    /// - `SpringError::InvalidFormat` when:
+    ///   - a client sent a malformed line.
+    ///   - a client sent a line longer than `net_server_max_line_bytes`.
     fn next_row(&mut self) -> Result<SourceRow> {
         let rx = self.rx();
 
-        rx.try_recv()
-            .or_else(|_| {
-                thread::sleep(self.timeout);
-                rx.try_recv()
-            })
-            .map(|json| {
-                let json_obj = JsonObject::new(json);
-                SourceRow::Json(JsonSourceRow::from_json(json_obj))
-            })
+        rx.recv_timeout(self.timeout)
             .map_err(|e| SpringError::ForeignSourceTimeout {
                 source: anyhow::Error::from(e),
                 foreign_info: ForeignInfo::GenericTcp(self.my_addr),
-            })
+            })?
     }
 }
 
 impl NetServerSourceReader {
-    fn rx(&self) -> MutexGuard<mpsc::Receiver<serde_json::Value>> {
+    fn rx(&self) -> MutexGuard<mpsc::Receiver<Result<SourceRow>>> {
         self.rx.lock().expect("failed to lock mutex")
     }
 
-    fn stream_handler(stream: TcpStream, tx: mpsc::Sender<serde_json::Value>) {
-        log::info!(
-            "[NetServerSourceReader] Connection from {}",
-            stream.peer_addr().unwrap()
-        );
+    fn accept_loop(
+        listener: TcpListener,
+        tx: mpsc::SyncSender<Result<SourceRow>>,
+        read_timeout: Duration,
+        max_line_bytes: usize,
+        stopped: Arc<AtomicBool>,
+    ) {
+        while !stopped.load(Ordering::Acquire) {
+            match listener.accept() {
+                Ok((stream, peer_addr)) => {
+                    let tx = tx.clone();
+                    let stopped = stopped.clone();
+                    let spawned = thread::Builder::new()
+                        .name(format!("NetServerSourceReader-{}", peer_addr))
+                        .spawn(move || {
+                            Self::stream_handler(
+                                stream,
+                                peer_addr,
+                                tx,
+                                read_timeout,
+                                max_line_bytes,
+                                stopped,
+                            )
+                        });
+                    if let Err(e) = spawned {
+                        log::warn!(
+                            "[NetServerSourceReader] failed to spawn a thread for {}: {:?}",
+                            peer_addr,
+                            e
+                        );
+                    }
+                }
+                Err(e) if e.kind() == io::ErrorKind::WouldBlock => {
+                    thread::sleep(ACCEPT_POLL_INTERVAL)
+                }
+                Err(e) => log::warn!("[NetServerSourceReader] failed to accept: {:?}", e),
+            }
+        }
+        log::info!("[NetServerSourceReader] Stop accepting clients.");
+    }
 
-        let mut tcp_reader = BufReader::new(stream);
+    fn stream_handler(
+        stream: TcpStream,
+        peer_addr: SocketAddr,
+        tx: mpsc::SyncSender<Result<SourceRow>>,
+        read_timeout: Duration,
+        max_line_bytes: usize,
+        stopped: Arc<AtomicBool>,
+    ) {
+        log::info!("[NetServerSourceReader] Connection from {}", peer_addr);
 
-        loop {
-            let mut buf_read = String::new();
-            loop {
-                log::info!("[NetServerSourceReader] waiting for next row message...");
+        let setup = stream
+            .set_nonblocking(false)
+            .and_then(|_| stream.set_read_timeout(Some(read_timeout)));
+        if let Err(e) = setup {
+            log::warn!(
+                "[NetServerSourceReader] failed to set up connection from {}: {:?}",
+                peer_addr,
+                e
+            );
+            return;
+        }
+        let _ = stream.shutdown(Shutdown::Write);
 
-                let n = tcp_reader
-                    .read_line(&mut buf_read)
-                    .expect("failed to read from the socket");
+        let mut tcp_reader = BufReader::new(stream);
+        // bytes read before a timeout are kept in `line`
+        let mut line = Vec::new();
+        // set while skipping the rest of a line longer than `max_line_bytes`
+        let mut oversized = false;
+        let (mut n_accepted, mut n_dropped) = (0u64, 0u64);
+        let mut eof = false;
 
-                if n == 0 {
-                    log::info!("[NetServerSourceReader] Got EOF. Stop stream_handler.");
-                    return;
+        while !eof && !stopped.load(Ordering::Acquire) {
+            let (n_consumed, end_of_line) = match tcp_reader.fill_buf() {
+                Ok([]) => {
+                    log::info!("[NetServerSourceReader] Got EOF from {}.", peer_addr);
+                    eof = true;
+                    // the last line may lack a line break
+                    (0, !line.is_empty() || oversized)
+                }
+                Ok(buf) => {
+                    let (content, end_of_line) = match buf.iter().position(|b| *b == b'\n') {
+                        Some(i) => (&buf[..i], true),
+                        None => (buf, false),
+                    };
+                    if !oversized {
+                        if line.len() + content.len() > max_line_bytes {
+                            oversized = true;
+                            line.clear();
+                        } else {
+                            line.extend_from_slice(content);
+                        }
+                    }
+                    (content.len() + end_of_line as usize, end_of_line)
+                }
+                Err(e)
+                    if matches!(
+                        e.kind(),
+                        io::ErrorKind::WouldBlock | io::ErrorKind::TimedOut
+                    ) =>
+                {
+                    continue
+                }
+                Err(e) => {
+                    log::warn!(
+                        "[NetServerSourceReader] failed to read from {}: {:?}",
+                        peer_addr,
+                        e
+                    );
+                    break;
                 }
+            };
+            tcp_reader.consume(n_consumed);
+            if !end_of_line {
+                continue;
+            }
 
-                log::info!("[NetServerSourceReader] read: {}", buf_read);
+            let row = if oversized {
+                Err(SpringError::InvalidFormat {
+                    s: String::new(),
+                    source: anyhow!(
+                        "line from {} exceeds {} bytes (net_server_max_line_bytes)",
+                        peer_addr,
+                        max_line_bytes
+                    ),
+                })
+            } else {
+                Self::parse_line(&line)
+            };
+            line.clear();
+            oversized = false;
 
-                let received_json: serde_json::Value = buf_read.parse().unwrap();
-                tx.send(received_json).unwrap();
+            match &row {
+                Ok(_) => n_accepted += 1,
+                Err(e) => {
+                    n_dropped += 1;
+                    log::warn!(
+                        "[NetServerSourceReader] dropped a line from {}: {:?}",
+                        peer_addr,
+                        e
+                    );
+                }
+            }
 
-                buf_read.clear();
+            // blocks while the channel is full
+            if tx.send(row).is_err() {
+                // source reader is dropped
+                break;
             }
         }
+
+        log::info!(
+            "[NetServerSourceReader] Connection from {} closed: {} rows accepted, {} lines dropped",
+            peer_addr,
+            n_accepted,
+            n_dropped
+        );
+    }
+
+    /// # Failure
+    ///
+    /// - `SpringError::InvalidFormat` when:
+    ///   - `line` is not a JSON document.
+    fn parse_line(line: &[u8]) -> Result<SourceRow> {
+        let json_s = std::str::from_utf8(line)
+            .context("line is not valid UTF-8")
+            .map_err(|e| SpringError::InvalidFormat {
+                s: String::from_utf8_lossy(line).to_string(),
+                source: e,
+            })?;
+        if json_s.trim().is_empty() {
+            return Err(SpringError::InvalidFormat {
+                s: json_s.to_string(),
+                source: anyhow!("empty line"),
+            });
+        }
+        SourceRow::from_json(json_s)
+    }
+}
+
+impl Drop for NetServerSourceReader {
+    fn drop(&mut self) {
+        self.stopped.store(true, Ordering::Release);
     }
 }
 
 #[cfg(test)]
 mod tests {
+    use std::{io::Write, time::Instant};
+
     use super::*;
     use crate::{
         api::SpringSinkWriterConfig,
-        pipeline::OptionsBuilder,
+        pipeline::{OptionsBuilder, SourceReaderType},
         stream_engine::autonomous_executor::{
-            task::sink_task::{NetSinkWriter, SinkWriter},
+            row::{JsonObject, JsonSourceRow},
+            task::sink_task::{NetSinkWriter, SinkWriter, SinkWriterContext},
             SchemalessRow,
         },
     };
+
     fn ephemeral_port() -> u16 {
         let addr = TcpListener::bind("127.0.0.1:0").unwrap();
         addr.local_addr().unwrap().port()
@@ -148,18 +337,41 @@ mod tests {
             .add("REMOTE_HOST", "127.0.0.1")
             .add("REMOTE_PORT", remote_port.to_string())
             .build();
-        NetSinkWriter::start(&options, &SpringSinkWriterConfig::fx_default()).unwrap()
+        NetSinkWriter::start(
+            &options,
+            &SpringSinkWriterConfig::fx_default(),
+            &SinkWriterContext::fx_default(),
+        )
+        .unwrap()
     }
 
-    #[test]
-    fn test_source_tcp() -> crate::api::error::Result<()> {
-        let port = ephemeral_port();
+    fn tcp_server(port: u16) -> Result<NetServerSourceReader> {
         let options = OptionsBuilder::default()
             .add("PROTOCOL", "TCP")
             .add("PORT", port.to_string())
             .build();
-        let mut reader =
-            NetServerSourceReader::start(&options, &SpringSourceReaderConfig::fx_default())?;
+        NetServerSourceReader::start(
+            &options,
+            &SpringSourceReaderConfig::fx_default(),
+            &SourceReaderContext::fx_trade(SourceReaderType::NetServer),
+        )
+    }
+
+    /// Skips timeouts until a row or an error arrives.
+    fn next_row_or_error(reader: &mut NetServerSourceReader) -> Result<SourceRow> {
+        let deadline = Instant::now() + Duration::from_secs(10);
+        loop {
+            match reader.next_row() {
+                Err(SpringError::ForeignSourceTimeout { .. }) if Instant::now() < deadline => {}
+                res => return res,
+            }
+        }
+    }
+
+    #[test]
+    fn test_source_tcp() -> crate::api::error::Result<()> {
+        let port = ephemeral_port();
+        let mut reader = tcp_server(port)?;
 
         let mut writer = tcp_writer(port);
 
@@ -174,7 +386,7 @@ mod tests {
             .unwrap();
 
         assert_eq!(
-            reader.next_row()?,
+            next_row_or_error(&mut reader)?,
             SourceRow::Json(JsonSourceRow::fx_city_temperature_tokyo())
         );
         assert_eq!(
@@ -192,4 +404,144 @@ mod tests {
 
         Ok(())
     }
+
+    #[test]
+    fn test_source_tcp_malformed_lines_skipped() -> crate::api::error::Result<()> {
+        let port = ephemeral_port();
+        let mut reader = tcp_server(port)?;
+
+        let mut client = TcpStream::connect(("127.0.0.1", port)).unwrap();
+        client.write_all(b"{ broken json\n").unwrap();
+        client.write_all(&[0xff, 0xfe, b'\n']).unwrap();
+        client
+            .write_all(
+                format!("{}\n", JsonObject::fx_city_temperature_tokyo().to_string()).as_bytes(),
+            )
+            .unwrap();
+
+        for _ in 0..2 {
+            assert!(matches!(
+                next_row_or_error(&mut reader).unwrap_err(),
+                SpringError::InvalidFormat { .. }
+            ));
+        }
+        assert_eq!(
+            reader.next_row()?,
+            SourceRow::Json(JsonSourceRow::fx_city_temperature_tokyo())
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_source_tcp_oversized_lines_skipped() -> crate::api::error::Result<()> {
+        let port = ephemeral_port();
+        let options = OptionsBuilder::default()
+            .add("PROTOCOL", "TCP")
+            .add("PORT", port.to_string())
+            .build();
+        let config = SpringSourceReaderConfig {
+            net_server_max_line_bytes: 256,
+            ..SpringSourceReaderConfig::fx_default()
+        };
+        let mut reader = NetServerSourceReader::start(
+            &options,
+            &config,
+            &SourceReaderContext::fx_trade(SourceReaderType::NetServer),
+        )?;
+
+        let tokyo = JsonObject::fx_city_temperature_tokyo().to_string();
+        let mut client = TcpStream::connect(("127.0.0.1", port)).unwrap();
+        client.write_all(&[b' '; 10_000]).unwrap();
+        client.write_all(b"\n").unwrap();
+        // the last line without line break
+        client
+            .write_all(format!("{}\n{}", tokyo, tokyo).as_bytes())
+            .unwrap();
+        client.shutdown(Shutdown::Write).unwrap();
+
+        assert!(matches!(
+            next_row_or_error(&mut reader).unwrap_err(),
+            SpringError::InvalidFormat { .. }
+        ));
+        for _ in 0..2 {
+            assert_eq!(
+                next_row_or_error(&mut reader)?,
+                SourceRow::Json(JsonSourceRow::fx_city_temperature_tokyo())
+            );
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_source_tcp_multiple_clients() -> crate::api::error::Result<()> {
+        let port = ephemeral_port();
+        let mut reader = tcp_server(port)?;
+
+        // both clients keep their connections open
+        let mut writer1 = tcp_writer(port);
+        let mut writer2 = tcp_writer(port);
+        writer2
+            .send_row(SchemalessRow::fx_city_temperature_osaka())
+            .unwrap();
+        writer1
+            .send_row(SchemalessRow::fx_city_temperature_tokyo())
+            .unwrap();
+
+        let mut rows = vec![
+            next_row_or_error(&mut reader)?,
+            next_row_or_error(&mut reader)?,
+        ];
+        rows.sort_by_key(|row| format!("{:?}", row));
+        let mut expected = vec![
+            SourceRow::Json(JsonSourceRow::fx_city_temperature_tokyo()),
+            SourceRow::Json(JsonSourceRow::fx_city_temperature_osaka()),
+        ];
+        expected.sort_by_key(|row| format!("{:?}", row));
+        assert_eq!(rows, expected);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_source_tcp_bind_error() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let port = listener.local_addr().unwrap().port();
+
+        assert!(matches!(
+            tcp_server(port).unwrap_err(),
+            SpringError::ForeignIo { .. }
+        ));
+
+        let options = OptionsBuilder::default()
+            .add("PROTOCOL", "TCP")
+            .add("BIND_ADDRESS", "not an address")
+            .add("PORT", "0")
+            .build();
+        assert!(matches!(
+            NetServerSourceReader::start(
+                &options,
+                &SpringSourceReaderConfig::fx_default(),
+                &SourceReaderContext::fx_trade(SourceReaderType::NetServer)
+            )
+            .unwrap_err(),
+            SpringError::InvalidOption { .. }
+        ));
+    }
+
+    #[test]
+    fn test_source_tcp_port_released_on_drop() -> crate::api::error::Result<()> {
+        let port = ephemeral_port();
+        let reader = tcp_server(port)?;
+        drop(reader);
+
+        let deadline = Instant::now() + Duration::from_secs(10);
+        while TcpListener::bind(("127.0.0.1", port)).is_err() {
+            assert!(Instant::now() < deadline, "port is not released");
+            thread::sleep(ACCEPT_POLL_INTERVAL);
+        }
+
+        Ok(())
+    }
 }
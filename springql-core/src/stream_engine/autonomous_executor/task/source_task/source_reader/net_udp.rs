@@ -0,0 +1,305 @@
+// This file is part of https://github.com/SpringQL/SpringQL which is licensed under MIT OR Apache-2.0. See file LICENSE-MIT or LICENSE-APACHE for full license details.
+
+use std::{
+    io,
+    net::{IpAddr, Ipv4Addr, SocketAddr, UdpSocket},
+    time::Duration,
+};
+
+use anyhow::{anyhow, Context};
+
+use crate::{
+    api::error::{foreign_info::ForeignInfo, Result, SpringError},
+    api::SpringSourceReaderConfig,
+    pipeline::{NetClientOptions, NetServerOptions, Options, SourceReaderType},
+    stream_engine::autonomous_executor::{
+        row::SourceRow,
+        task::source_task::source_reader::{SourceReader, SourceReaderContext},
+    },
+};
+
+/// Source reader receiving a JSON document per UDP datagram.
+///
+/// - `NET_CLIENT`: binds an ephemeral port and receives datagrams only from `REMOTE_HOST:REMOTE_PORT`.
+/// - `NET_SERVER`: binds `BIND_ADDRESS:PORT` and receives datagrams from any host.
+#[derive(Debug)]
+pub struct NetUdpSourceReader {
+    my_addr: SocketAddr,
+    socket: UdpSocket,
+
+    max_datagram_bytes: usize,
+
+    /// 1 byte larger than `max_datagram_bytes` to detect oversized datagrams (truncated by OS).
+    buf: Vec<u8>,
+}
+
+impl SourceReader for NetUdpSourceReader {
+    /// Starts in `NET_CLIENT` or `NET_SERVER` mode following the source reader type.
+    ///
+    /// # Failure
+    ///
+    /// - `SpringError::ForeignIo`
+    /// - `SpringError::InvalidOption`
+    fn start(
+        options: &Options,
+        config: &SpringSourceReaderConfig,
+        context: &SourceReaderContext,
+    ) -> Result<Self> {
+        match context.source_reader_type() {
+            SourceReaderType::NetServer => Self::start_server(options, config),
+            _ => Self::start_client(options, config),
+        }
+    }
+
+    /// # Failure
+    ///
+    /// - `SpringError::ForeignSourceTimeout` when:
+    ///   - no datagram arrives within timeout.
+    /// - `SpringError::ForeignIo` when:
+    ///   - a datagram exceeds `net_max_datagram_bytes`.
+    /// - `SpringError::InvalidFormat` when:
+    ///   - a datagram is not a JSON document.
+    fn next_row(&mut self) -> Result<SourceRow> {
+        let (n_bytes, peer_addr) = self.socket.recv_from(&mut self.buf).map_err(|io_err| {
+            if let io::ErrorKind::TimedOut | io::ErrorKind::WouldBlock = io_err.kind() {
+                SpringError::ForeignSourceTimeout {
+                    source: anyhow::Error::from(io_err),
+                    foreign_info: ForeignInfo::GenericUdp(self.my_addr),
+                }
+            } else {
+                SpringError::ForeignIo {
+                    source: anyhow::Error::from(io_err),
+                    foreign_info: ForeignInfo::GenericUdp(self.my_addr),
+                }
+            }
+        })?;
+
+        if n_bytes > self.max_datagram_bytes {
+            return Err(SpringError::ForeignIo {
+                source: anyhow!(
+                    "datagram from {} exceeds {} bytes (net_max_datagram_bytes)",
+                    peer_addr,
+                    self.max_datagram_bytes
+                ),
+                foreign_info: ForeignInfo::GenericUdp(self.my_addr),
+            });
+        }
+
+        let datagram = &self.buf[..n_bytes];
+        let json_s = std::str::from_utf8(datagram)
+            .context("datagram is not valid UTF-8")
+            .map_err(|e| SpringError::InvalidFormat {
+                s: String::from_utf8_lossy(datagram).to_string(),
+                source: e,
+            })?;
+        log::debug!(
+            "[NetUdpSourceReader] received from {}: {}",
+            peer_addr,
+            json_s
+        );
+
+        SourceRow::from_json(json_s)
+    }
+}
+
+impl NetUdpSourceReader {
+    fn start_client(options: &Options, config: &SpringSourceReaderConfig) -> Result<Self> {
+        let options = NetClientOptions::try_from(options)?;
+        let remote_addr = SocketAddr::new(options.remote_host, options.remote_port);
+
+        let socket = Self::bind(options.local_bind_addr())?;
+        socket
+            .connect(remote_addr)
+            .context("failed to connect to remote host")
+            .map_err(|e| SpringError::ForeignIo {
+                source: e,
+                foreign_info: ForeignInfo::GenericUdp(remote_addr),
+            })?;
+
+        Self::new(socket, config)
+    }
+
+    fn start_server(options: &Options, config: &SpringSourceReaderConfig) -> Result<Self> {
+        let options = NetServerOptions::try_from(options)?;
+        let socket = Self::bind(SocketAddr::new(options.bind_address, options.port))?;
+        Self::new(socket, config)
+    }
+
+    fn bind(addr: SocketAddr) -> Result<UdpSocket> {
+        UdpSocket::bind(addr)
+            .context("failed to bind UDP socket")
+            .map_err(|e| SpringError::ForeignIo {
+                source: e,
+                foreign_info: ForeignInfo::GenericUdp(addr),
+            })
+    }
+
+    fn new(socket: UdpSocket, config: &SpringSourceReaderConfig) -> Result<Self> {
+        let my_addr = socket
+            .local_addr()
+            .context("failed to get local address of UDP socket")
+            .map_err(|e| SpringError::ForeignIo {
+                source: e,
+                foreign_info: ForeignInfo::GenericUdp(SocketAddr::new(
+                    IpAddr::V4(Ipv4Addr::UNSPECIFIED),
+                    0,
+                )),
+            })?;
+        socket
+            .set_read_timeout(Some(Duration::from_millis(
+                config.net_read_timeout_msec as u64,
+            )))
+            .context("failed to set timeout to UDP socket")
+            .map_err(|e| SpringError::ForeignIo {
+                source: e,
+                foreign_info: ForeignInfo::GenericUdp(my_addr),
+            })?;
+
+        let max_datagram_bytes = config.net_max_datagram_bytes as usize;
+
+        log::info!(
+            "[NetUdpSourceReader] Ready to receive datagrams at {}",
+            my_addr
+        );
+
+        Ok(Self {
+            my_addr,
+            socket,
+            max_datagram_bytes,
+            buf: vec![0; max_datagram_bytes + 1],
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        pipeline::OptionsBuilder,
+        stream_engine::autonomous_executor::row::{JsonObject, JsonSourceRow},
+    };
+
+    fn udp_server(max_datagram_bytes: u32) -> NetUdpSourceReader {
+        let options = OptionsBuilder::default()
+            .add("PROTOCOL", "UDP")
+            .add("PORT", "0")
+            .build();
+        let config = SpringSourceReaderConfig {
+            net_max_datagram_bytes: max_datagram_bytes,
+            ..SpringSourceReaderConfig::fx_default()
+        };
+        NetUdpSourceReader::start(
+            &options,
+            &config,
+            &SourceReaderContext::fx_trade(SourceReaderType::NetServer),
+        )
+        .unwrap()
+    }
+
+    fn send_to(reader: &NetUdpSourceReader, datagram: &[u8]) {
+        let socket = UdpSocket::bind("127.0.0.1:0").unwrap();
+        socket.send_to(datagram, reader.my_addr).unwrap();
+    }
+
+    #[test]
+    fn test_source_udp_server() {
+        let mut reader = udp_server(65_507);
+
+        for json in [
+            JsonObject::fx_city_temperature_tokyo(),
+            JsonObject::fx_city_temperature_osaka(),
+        ] {
+            send_to(&reader, json.to_string().as_bytes());
+        }
+
+        assert_eq!(
+            reader.next_row().unwrap(),
+            SourceRow::Json(JsonSourceRow::fx_city_temperature_tokyo())
+        );
+        assert_eq!(
+            reader.next_row().unwrap(),
+            SourceRow::Json(JsonSourceRow::fx_city_temperature_osaka())
+        );
+        assert!(matches!(
+            reader.next_row().unwrap_err(),
+            SpringError::ForeignSourceTimeout { .. }
+        ));
+    }
+
+    #[test]
+    fn test_source_udp_server_invalid_datagrams() {
+        let json = JsonObject::fx_city_temperature_tokyo().to_string();
+        let mut reader = udp_server(json.len() as u32);
+
+        send_to(&reader, format!("{} ", json).as_bytes());
+        send_to(&reader, b"{ broken json");
+        send_to(&reader, &[0xff, 0xfe]);
+        send_to(&reader, json.as_bytes());
+
+        assert!(matches!(
+            reader.next_row().unwrap_err(),
+            SpringError::ForeignIo { .. }
+        ));
+        assert!(matches!(
+            reader.next_row().unwrap_err(),
+            SpringError::InvalidFormat { .. }
+        ));
+        assert!(matches!(
+            reader.next_row().unwrap_err(),
+            SpringError::InvalidFormat { .. }
+        ));
+        assert_eq!(
+            reader.next_row().unwrap(),
+            SourceRow::Json(JsonSourceRow::fx_city_temperature_tokyo())
+        );
+    }
+
+    #[test]
+    fn test_source_udp_client() {
+        let remote = UdpSocket::bind("127.0.0.1:0").unwrap();
+        let other = UdpSocket::bind("127.0.0.1:0").unwrap();
+
+        let options = OptionsBuilder::default()
+            .add("PROTOCOL", "UDP")
+            .add("REMOTE_HOST", "127.0.0.1")
+            .add(
+                "REMOTE_PORT",
+                remote.local_addr().unwrap().port().to_string(),
+            )
+            .build();
+        let mut reader = NetUdpSourceReader::start(
+            &options,
+            &SpringSourceReaderConfig::fx_default(),
+            &SourceReaderContext::fx_trade(SourceReaderType::NetClient),
+        )
+        .unwrap();
+        let reader_addr = SocketAddr::new(IpAddr::V4(Ipv4Addr::LOCALHOST), reader.my_addr.port());
+
+        // datagrams from other hosts are ignored
+        other
+            .send_to(
+                JsonObject::fx_city_temperature_osaka()
+                    .to_string()
+                    .as_bytes(),
+                reader_addr,
+            )
+            .unwrap();
+        remote
+            .send_to(
+                JsonObject::fx_city_temperature_tokyo()
+                    .to_string()
+                    .as_bytes(),
+                reader_addr,
+            )
+            .unwrap();
+
+        assert_eq!(
+            reader.next_row().unwrap(),
+            SourceRow::Json(JsonSourceRow::fx_city_temperature_tokyo())
+        );
+        assert!(matches!(
+            reader.next_row().unwrap_err(),
+            SpringError::ForeignSourceTimeout { .. }
+        ));
+    }
+}
@@ -2,10 +2,11 @@
 
 use crate::{
     api::{error::Result, SpringSourceReaderConfig},
-    pipeline::{Options, SourceReaderType},
+    pipeline::{NetClientOptions, NetProtocol, NetServerOptions, Options, SourceReaderType},
     stream_engine::autonomous_executor::task::source_task::source_reader::{
-        can::CANSourceReader, net_client::NetClientSourceReader, net_server::NetServerSourceReader,
-        InMemoryQueueSourceReader, SourceReader,
+        can::CANSourceReader, file::FileSourceReader, mqtt::MqttSourceReader,
+        net_client::NetClientSourceReader, net_server::NetServerSourceReader,
+        net_udp::NetUdpSourceReader, InMemoryQueueSourceReader, SourceReader, SourceReaderContext,
     },
 };
 
@@ -13,21 +14,39 @@ pub struct SourceReaderFactory;
 
 impl SourceReaderFactory {
     pub fn source(
-        source_reader_type: &SourceReaderType,
         options: &Options,
         config: &SpringSourceReaderConfig,
+        context: &SourceReaderContext,
     ) -> Result<Box<dyn SourceReader>> {
-        match source_reader_type {
-            SourceReaderType::NetClient => {
-                Ok(Box::new(NetClientSourceReader::start(options, config)?))
+        match context.source_reader_type() {
+            SourceReaderType::NetClient => match NetClientOptions::try_from(options)?.protocol {
+                NetProtocol::Tcp => Ok(Box::new(NetClientSourceReader::start(
+                    options, config, context,
+                )?)),
+                NetProtocol::Udp => Ok(Box::new(NetUdpSourceReader::start(
+                    options, config, context,
+                )?)),
+            },
+            SourceReaderType::NetServer => match NetServerOptions::try_from(options)?.protocol {
+                NetProtocol::Tcp => Ok(Box::new(NetServerSourceReader::start(
+                    options, config, context,
+                )?)),
+                NetProtocol::Udp => Ok(Box::new(NetUdpSourceReader::start(
+                    options, config, context,
+                )?)),
+            },
+            SourceReaderType::CAN => {
+                Ok(Box::new(CANSourceReader::start(options, config, context)?))
             }
-            SourceReaderType::NetServer => {
-                Ok(Box::new(NetServerSourceReader::start(options, config)?))
+            SourceReaderType::Mqtt => {
+                Ok(Box::new(MqttSourceReader::start(options, config, context)?))
             }
-            SourceReaderType::CAN => Ok(Box::new(CANSourceReader::start(options, config)?)),
-            SourceReaderType::InMemoryQueue => {
-                Ok(Box::new(InMemoryQueueSourceReader::start(options, config)?))
+            SourceReaderType::File => {
+                Ok(Box::new(FileSourceReader::start(options, config, context)?))
             }
+            SourceReaderType::InMemoryQueue => Ok(Box::new(InMemoryQueueSourceReader::start(
+                options, config, context,
+            )?)),
         }
     }
 }
@@ -1,7 +1,7 @@
 // This file is part of https://github.com/SpringQL/SpringQL which is licensed under MIT OR Apache-2.0. See file LICENSE-MIT or LICENSE-APACHE for full license details.
 
 use std::{
-    collections::HashMap,
+    collections::{HashMap, HashSet},
     sync::{Arc, Mutex},
 };
 
@@ -9,9 +9,12 @@ use parking_lot::RwLock;
 
 use crate::{
     api::{error::Result, SpringSourceReaderConfig},
-    pipeline::{SourceReaderModel, SourceReaderName},
-    stream_engine::autonomous_executor::task::source_task::source_reader::{
-        source_reader_factory::SourceReaderFactory, SourceReader,
+    pipeline::{SourceReaderModel, SourceReaderName, StreamModel},
+    stream_engine::{
+        autonomous_executor::task::source_task::source_reader::{
+            source_reader_factory::SourceReaderFactory, SourceReader, SourceReaderContext,
+        },
+        in_memory_queue_repository::InMemoryQueueRepository,
     },
 };
 
@@ -19,35 +22,48 @@ use crate::{
 #[derive(Debug)]
 pub struct SourceReaderRepository {
     config: SpringSourceReaderConfig,
+    in_memory_queue_repository: Arc<InMemoryQueueRepository>,
 
     sources: RwLock<HashMap<SourceReaderName, Arc<Mutex<Box<dyn SourceReader>>>>>,
 }
 
 impl SourceReaderRepository {
-    pub fn new(config: SpringSourceReaderConfig) -> Self {
+    pub fn new(
+        config: SpringSourceReaderConfig,
+        in_memory_queue_repository: Arc<InMemoryQueueRepository>,
+    ) -> Self {
         Self {
             config,
+            in_memory_queue_repository,
             sources: RwLock::default(),
         }
     }
 
     /// Do nothing if a source reader with the same name already exists.
     ///
+    /// `dest_stream`: the source stream of `source_reader`.
+    ///
     /// # Failures
     ///
     /// - `SpringError::ForeignIo` when:
     ///   - failed to start subtask.
-    pub fn register(&self, source_reader: &SourceReaderModel) -> Result<()> {
+    pub fn register(
+        &self,
+        source_reader: &SourceReaderModel,
+        dest_stream: Arc<StreamModel>,
+    ) -> Result<()> {
         let mut sources = self.sources.write();
 
         if sources.get(source_reader.name()).is_some() {
             Ok(())
         } else {
-            let subtask = SourceReaderFactory::source(
-                source_reader.source_reader_type(),
-                source_reader.options(),
-                &self.config,
-            )?;
+            let context = SourceReaderContext::new(
+                source_reader.source_reader_type().clone(),
+                dest_stream,
+                self.in_memory_queue_repository.clone(),
+            );
+            let subtask =
+                SourceReaderFactory::source(source_reader.options(), &self.config, &context)?;
             let subtask = Arc::new(Mutex::new(subtask as Box<dyn SourceReader>));
             let _ = sources.insert(source_reader.name().clone(), subtask);
             log::debug!(
@@ -58,6 +74,19 @@ impl SourceReaderRepository {
         }
     }
 
+    /// Drop source readers whose names are not in `names`.
+    ///
+    /// A dropped source reader closes its connection when the last task holding it releases it.
+    pub fn retain(&self, names: &HashSet<SourceReaderName>) {
+        self.sources.write().retain(|name, _| {
+            let keep = names.contains(name);
+            if !keep {
+                log::debug!("[SourceReaderRepository] dropped source subtask: {}", name);
+            }
+            keep
+        });
+    }
+
     /// # Panics
     ///
     /// `name` is not registered yet
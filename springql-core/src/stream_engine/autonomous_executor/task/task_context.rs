@@ -40,9 +40,25 @@ impl TaskContext {
         self.pipeline_derivatives.clone()
     }
 
+    /// Input queues of stopped pumps holding `stopped_queue_capacity` rows or more are excluded. Rows for them are dropped.
     pub fn output_queues(&self) -> Vec<QueueId> {
         let task_graph = self.pipeline_derivatives.task_graph();
-        task_graph.output_queues(&self.task)
+        task_graph
+            .output_queues(&self.task)
+            .into_iter()
+            .filter(|queue_id| {
+                !task_graph.is_stopped(&task_graph.downstream_task(queue_id))
+                    || self.queue_len(queue_id)
+                        < self.repos.pump_config().stopped_queue_capacity as usize
+            })
+            .collect()
+    }
+
+    fn queue_len(&self, queue_id: &QueueId) -> usize {
+        match queue_id {
+            QueueId::Row(queue_id) => self.repos.row_queue_repository().get(queue_id).len(),
+            QueueId::Window(queue_id) => self.repos.window_queue_repository().get(queue_id).len(),
+        }
     }
 
     pub fn repos(&self) -> Arc<Repositories> {
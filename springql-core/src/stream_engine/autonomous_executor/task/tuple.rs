@@ -111,6 +111,7 @@ impl Tuple {
 #[cfg(test)]
 mod tests {
     use crate::{
+        api::SpringSqlConfig,
         expression::{UnaryOperator, ValueExpr},
         stream_engine::time::SpringTimestamp,
     };
@@ -149,6 +150,75 @@ mod tests {
             TestDatum::new(
                 ValueExpr::factory_eq(ValueExpr::factory_null(), ValueExpr::factory_null()),
                 Tuple::fx_trade_oracle(),
+                SqlValue::Null,
+            ),
+            TestDatum::new(
+                ValueExpr::factory_lt(ValueExpr::factory_integer(1), ValueExpr::factory_null()),
+                Tuple::fx_trade_oracle(),
+                SqlValue::Null,
+            ),
+            TestDatum::new(
+                ValueExpr::factory_lt(ValueExpr::factory_integer(1), ValueExpr::factory_float(1.5)),
+                Tuple::fx_trade_oracle(),
+                SqlValue::factory_bool(true),
+            ),
+            TestDatum::new(
+                ValueExpr::factory_lt(
+                    ValueExpr::factory_duration_secs(10),
+                    ValueExpr::factory_duration_secs(9),
+                ),
+                Tuple::fx_trade_oracle(),
+                SqlValue::factory_bool(false),
+            ),
+            TestDatum::new(
+                ValueExpr::factory_between(
+                    ValueExpr::ColumnReference(ColumnReference::factory("trade", "amount")),
+                    ValueExpr::factory_integer(100),
+                    ValueExpr::factory_integer(200),
+                ),
+                Tuple::factory_trade(SpringTimestamp::fx_ts1(), "ORCL", 200),
+                SqlValue::factory_bool(true),
+            ),
+            TestDatum::new(
+                ValueExpr::factory_between(
+                    ValueExpr::factory_integer(300),
+                    ValueExpr::factory_null(),
+                    ValueExpr::factory_integer(200),
+                ),
+                Tuple::fx_trade_oracle(),
+                SqlValue::factory_bool(false),
+            ),
+            TestDatum::new(
+                ValueExpr::factory_between(
+                    ValueExpr::factory_integer(100),
+                    ValueExpr::factory_null(),
+                    ValueExpr::factory_integer(200),
+                ),
+                Tuple::fx_trade_oracle(),
+                SqlValue::Null,
+            ),
+            TestDatum::new(
+                ValueExpr::factory_in(
+                    ValueExpr::factory_integer(2),
+                    vec![ValueExpr::factory_null(), ValueExpr::factory_integer(2)],
+                ),
+                Tuple::fx_trade_oracle(),
+                SqlValue::factory_bool(true),
+            ),
+            TestDatum::new(
+                ValueExpr::factory_in(
+                    ValueExpr::factory_integer(2),
+                    vec![ValueExpr::factory_null(), ValueExpr::factory_integer(3)],
+                ),
+                Tuple::fx_trade_oracle(),
+                SqlValue::Null,
+            ),
+            TestDatum::new(
+                ValueExpr::factory_in(
+                    ValueExpr::factory_integer(2),
+                    vec![ValueExpr::factory_integer(1), ValueExpr::factory_integer(3)],
+                ),
+                Tuple::fx_trade_oracle(),
                 SqlValue::factory_bool(false),
             ),
             TestDatum::new(
@@ -195,12 +265,80 @@ mod tests {
                 Tuple::fx_trade_oracle(),
                 SqlValue::factory_bool(false),
             ),
+            // three-valued logic
+            TestDatum::new(
+                ValueExpr::factory_and(ValueExpr::factory_null(), ValueExpr::factory_bool(false)),
+                Tuple::fx_trade_oracle(),
+                SqlValue::factory_bool(false),
+            ),
+            TestDatum::new(
+                ValueExpr::factory_and(ValueExpr::factory_null(), ValueExpr::factory_bool(true)),
+                Tuple::fx_trade_oracle(),
+                SqlValue::Null,
+            ),
+            TestDatum::new(
+                ValueExpr::factory_or(ValueExpr::factory_bool(true), ValueExpr::factory_null()),
+                Tuple::fx_trade_oracle(),
+                SqlValue::factory_bool(true),
+            ),
+            TestDatum::new(
+                ValueExpr::factory_or(ValueExpr::factory_bool(false), ValueExpr::factory_null()),
+                Tuple::fx_trade_oracle(),
+                SqlValue::Null,
+            ),
+            TestDatum::new(
+                ValueExpr::factory_or(
+                    ValueExpr::factory_bool(false),
+                    ValueExpr::factory_bool(false),
+                ),
+                Tuple::fx_trade_oracle(),
+                SqlValue::factory_bool(false),
+            ),
+            TestDatum::new(
+                ValueExpr::factory_uni_op(UnaryOperator::Not, ValueExpr::factory_null()),
+                Tuple::fx_trade_oracle(),
+                SqlValue::Null,
+            ),
+            TestDatum::new(
+                ValueExpr::factory_uni_op(UnaryOperator::Not, ValueExpr::factory_bool(false)),
+                Tuple::fx_trade_oracle(),
+                SqlValue::factory_bool(true),
+            ),
+            TestDatum::new(
+                ValueExpr::factory_uni_op(UnaryOperator::IsNull, ValueExpr::factory_null()),
+                Tuple::fx_trade_oracle(),
+                SqlValue::factory_bool(true),
+            ),
+            TestDatum::new(
+                ValueExpr::factory_uni_op(UnaryOperator::IsNotNull, ValueExpr::factory_null()),
+                Tuple::fx_trade_oracle(),
+                SqlValue::factory_bool(false),
+            ),
+            TestDatum::new(
+                ValueExpr::factory_uni_op(UnaryOperator::IsTrue, ValueExpr::factory_null()),
+                Tuple::fx_trade_oracle(),
+                SqlValue::factory_bool(false),
+            ),
+            TestDatum::new(
+                ValueExpr::factory_uni_op(UnaryOperator::IsNotFalse, ValueExpr::factory_null()),
+                Tuple::fx_trade_oracle(),
+                SqlValue::factory_bool(true),
+            ),
+            TestDatum::new(
+                ValueExpr::factory_uni_op(UnaryOperator::IsFalse, ValueExpr::factory_bool(false)),
+                Tuple::fx_trade_oracle(),
+                SqlValue::factory_bool(true),
+            ),
         ];
 
         for t in test_data {
             let expr_ph2 = t.in_expr.resolve_colref(&t.tuple).unwrap();
-            let sql_value = expr_ph2.eval().unwrap();
-            assert_eq!(sql_value, t.expected_sql_value);
+            let sql_value = expr_ph2.eval(&SpringSqlConfig::fx_default()).unwrap();
+            match t.expected_sql_value {
+                // NULL != NULL in SqlValue's PartialEq
+                SqlValue::Null => assert!(matches!(sql_value, SqlValue::Null)),
+                SqlValue::NotNull(_) => assert_eq!(sql_value, t.expected_sql_value),
+            }
         }
     }
 }
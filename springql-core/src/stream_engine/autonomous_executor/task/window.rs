@@ -23,6 +23,15 @@ use crate::{
 
 type Success<T> = (Vec<T>, WindowInFlowByWindowTask);
 
+/// How to close panes which the watermark has not reached yet.
+#[derive(Clone, Copy, Eq, PartialEq, Debug)]
+pub enum FlushMode {
+    /// Close all panes and keep the watermark. Tuples later than the watermark still open new panes.
+    KeepWatermark,
+    /// Move the watermark to +infinity and close all panes. Tuples after this are too late.
+    EndOfStream,
+}
+
 pub trait Window {
     type Pane: Pane;
 
@@ -35,6 +44,11 @@ pub trait Window {
     fn purge(&mut self);
 
     /// A task dispatches a tuple from waiting queue.
+    ///
+    /// # Failures
+    ///
+    /// - `SpringError::Sql` when:
+    ///   - failed to evaluate expressions on the tuple in a pane or on closing panes.
     fn dispatch(
         &mut self,
         expr_resolver: &ExprResolver,
@@ -50,28 +64,50 @@ pub trait Window {
             self.watermark_mut().update(rowtime);
             let wm = *self.watermark();
 
-            let window_in_flow_dispatch = self
-                .panes_mut()
-                .panes_to_dispatch(rowtime)?
-                .map(|pane| pane.dispatch(expr_resolver, &tuple, arg.clone()))
-                .fold(WindowInFlowByWindowTask::zero(), |acc, window_in_flow| {
-                    acc + window_in_flow
-                });
-
-            let (out, window_in_flow_close) = self
-                .panes_mut()
-                .remove_panes_to_close(&wm)
-                .into_iter()
-                .fold(
-                    (Vec::new(), WindowInFlowByWindowTask::zero()),
-                    |(mut out_acc, window_in_flow_acc), pane| {
-                        let (mut out_seq, window_in_flow) = pane.close(expr_resolver);
-                        out_acc.append(&mut out_seq);
-                        (out_acc, window_in_flow_acc + window_in_flow)
-                    },
-                );
+            let window_in_flow_dispatch = self.panes_mut().panes_to_dispatch(rowtime)?.try_fold(
+                WindowInFlowByWindowTask::zero(),
+                |acc, pane| {
+                    pane.dispatch(expr_resolver, &tuple, arg.clone())
+                        .map(|window_in_flow| acc + window_in_flow)
+                },
+            )?;
+
+            let panes_to_close = self.panes_mut().remove_panes_to_close(&wm);
+            let (out, window_in_flow_close) = close_panes(expr_resolver, panes_to_close)?;
 
             Ok((out, window_in_flow_dispatch + window_in_flow_close))
         }
     }
+
+    /// Close all panes even if the watermark has not reached their end, to emit their results on shutdown or end-of-stream.
+    ///
+    /// # Failures
+    ///
+    /// - `SpringError::Sql` when:
+    ///   - failed to evaluate expressions on closing panes.
+    fn flush(
+        &mut self,
+        expr_resolver: &ExprResolver,
+        mode: FlushMode,
+    ) -> Result<Success<<Self::Pane as Pane>::CloseOut>, SpringError> {
+        if mode == FlushMode::EndOfStream {
+            self.watermark_mut().end_of_stream();
+        }
+        let panes_to_close = self.panes_mut().remove_all_panes();
+        close_panes(expr_resolver, panes_to_close)
+    }
+}
+
+fn close_panes<P: Pane>(
+    expr_resolver: &ExprResolver,
+    panes: Vec<P>,
+) -> Result<Success<P::CloseOut>, SpringError> {
+    panes.into_iter().try_fold(
+        (Vec::new(), WindowInFlowByWindowTask::zero()),
+        |(mut out_acc, window_in_flow_acc), pane| {
+            let (mut out_seq, window_in_flow) = pane.close(expr_resolver)?;
+            out_acc.append(&mut out_seq);
+            Ok((out_acc, window_in_flow_acc + window_in_flow))
+        },
+    )
 }
@@ -108,16 +108,18 @@ mod tests {
     use std::str::FromStr;
 
     use crate::{
+        api::{DivisionByZero, SpringSqlConfig},
         expr_resolver::{ExprLabel, ExprResolver},
-        expression::{AggrExpr, ValueExpr},
+        expression::{AggrExpr, BinaryExpr, NumericalFunction, ValueExpr},
         pipeline::{
             AggrAlias, AggregateFunctionParameter, AggregateParameter, ColumnName, GroupByLabels,
             StreamName,
         },
         sql_processor::SelectFieldSyntax,
         stream_engine::{
-            autonomous_executor::task::tuple::Tuple,
+            autonomous_executor::task::{tuple::Tuple, window::FlushMode},
             time::{SpringDuration, SpringEventDuration, SpringTimestamp},
+            NnSqlValue,
         },
     };
 
@@ -181,6 +183,7 @@ mod tests {
                 StreamName::fx_trade().as_ref(),
                 ColumnName::fx_amount().as_ref(),
             ),
+            key: None,
         };
 
         let select_list = vec![
@@ -194,7 +197,7 @@ mod tests {
             },
         ];
 
-        let (expr_resolver, labels) = ExprResolver::new(select_list);
+        let (expr_resolver, labels) = ExprResolver::new(select_list, SpringSqlConfig::fx_default());
         match &labels[..] {
             &[ExprLabel::Value(group_by_label), ExprLabel::Aggr(aggr_label)] => {
                 let mut window = AggrWindow::new(
@@ -204,8 +207,7 @@ mod tests {
                         allowed_delay: SpringEventDuration::from_secs(1),
                     },
                     WindowOperationParameter::Aggregate(AggregateParameter {
-                        aggr_func: AggregateFunctionParameter::Avg,
-                        aggr_expr: aggr_label,
+                        aggr_exprs: vec![(aggr_label, AggregateFunctionParameter::Avg)],
                         group_by: GroupByLabels::new(vec![group_by_label]),
                     }),
                 );
@@ -429,6 +431,7 @@ mod tests {
                 StreamName::fx_trade().as_ref(),
                 ColumnName::fx_amount().as_ref(),
             ),
+            key: None,
         };
 
         let select_list = vec![
@@ -442,7 +445,7 @@ mod tests {
             },
         ];
 
-        let (expr_resolver, labels) = ExprResolver::new(select_list);
+        let (expr_resolver, labels) = ExprResolver::new(select_list, SpringSqlConfig::fx_default());
         match &labels[..] {
             &[ExprLabel::Value(group_by_label), ExprLabel::Aggr(aggr_label)] => {
                 let mut window = AggrWindow::new(
@@ -451,8 +454,7 @@ mod tests {
                         allowed_delay: SpringEventDuration::from_secs(1),
                     },
                     WindowOperationParameter::Aggregate(AggregateParameter {
-                        aggr_func: AggregateFunctionParameter::Avg,
-                        aggr_expr: aggr_label,
+                        aggr_exprs: vec![(aggr_label, AggregateFunctionParameter::Avg)],
                         group_by: GroupByLabels::new(vec![group_by_label]),
                     }),
                 );
@@ -621,4 +623,222 @@ mod tests {
             _ => unreachable!(),
         }
     }
+
+    #[test]
+    fn test_flush() {
+        setup_test_logger();
+
+        // SELECT ticker, AVG(amount) AS avg_amount
+        //   FROM trade
+        //   FIXED WINDOW duration_secs(10), duration_secs(0)
+        //   GROUP BY ticker;
+
+        let ticker_expr = ValueExpr::factory_colref(
+            StreamName::fx_trade().as_ref(),
+            ColumnName::fx_ticker().as_ref(),
+        );
+        let avg_amount_expr = AggrExpr {
+            func: AggregateFunctionParameter::Avg,
+            aggregated: ValueExpr::factory_colref(
+                StreamName::fx_trade().as_ref(),
+                ColumnName::fx_amount().as_ref(),
+            ),
+            key: None,
+        };
+
+        let select_list = vec![
+            SelectFieldSyntax::ValueExpr {
+                value_expr: ticker_expr,
+                alias: None,
+            },
+            SelectFieldSyntax::AggrExpr {
+                aggr_expr: avg_amount_expr,
+                alias: Some(AggrAlias::new("avg_amount".to_string())),
+            },
+        ];
+
+        let (expr_resolver, labels) = ExprResolver::new(select_list, SpringSqlConfig::fx_default());
+        match &labels[..] {
+            &[ExprLabel::Value(group_by_label), ExprLabel::Aggr(aggr_label)] => {
+                let mut window = AggrWindow::new(
+                    WindowParameter::TimedSlidingWindow {
+                        length: SpringEventDuration::from_secs(10),
+                        period: SpringEventDuration::from_secs(10),
+                        allowed_delay: SpringEventDuration::from_secs(0),
+                    },
+                    WindowOperationParameter::Aggregate(AggregateParameter {
+                        aggr_exprs: vec![(aggr_label, AggregateFunctionParameter::Avg)],
+                        group_by: GroupByLabels::new(vec![group_by_label]),
+                    }),
+                );
+
+                // [:00, :10): ("ORCL", 100), ("ORCL", 300)
+                for amount in [100, 300] {
+                    let (out, _) = window
+                        .dispatch(
+                            &expr_resolver,
+                            Tuple::factory_trade(
+                                SpringTimestamp::from_str("2020-01-01 00:00:01.000000000").unwrap(),
+                                "ORCL",
+                                amount,
+                            ),
+                            (),
+                        )
+                        .unwrap();
+                    assert!(out.is_empty());
+                }
+
+                // [:00, :10): -> "ORCL" AVG = 200
+                let (out, _) = window
+                    .flush(&expr_resolver, FlushMode::KeepWatermark)
+                    .unwrap();
+                assert_eq!(out.len(), 1);
+                t_expect(
+                    aggr_label,
+                    group_by_label,
+                    out.get(0).cloned().unwrap(),
+                    "ORCL",
+                    200,
+                );
+
+                let (out, _) = window
+                    .flush(&expr_resolver, FlushMode::EndOfStream)
+                    .unwrap();
+                assert!(out.is_empty());
+
+                // too late after end-of-stream
+                let (out, window_in_flow) = window
+                    .dispatch(
+                        &expr_resolver,
+                        Tuple::factory_trade(
+                            SpringTimestamp::from_str("2020-01-01 00:00:11.000000000").unwrap(),
+                            "ORCL",
+                            100,
+                        ),
+                        (),
+                    )
+                    .unwrap();
+                assert!(out.is_empty());
+                assert_eq!(window_in_flow.window_gain_bytes_states, 0);
+                let (out, _) = window
+                    .flush(&expr_resolver, FlushMode::EndOfStream)
+                    .unwrap();
+                assert!(out.is_empty());
+            }
+            _ => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn test_aggregate_argument_error() {
+        setup_test_logger();
+
+        // SELECT SUM(amount / 0) AS sum_amount
+        //   FROM trade
+        //   FIXED WINDOW duration_secs(10), duration_secs(0);
+        // with `division_by_zero = "error"`
+
+        let sum_amount_expr = AggrExpr {
+            func: AggregateFunctionParameter::Sum,
+            aggregated: ValueExpr::BinaryExpr(BinaryExpr::NumericalFunctionVariant(
+                NumericalFunction::DivVariant {
+                    left: Box::new(ValueExpr::factory_colref(
+                        StreamName::fx_trade().as_ref(),
+                        ColumnName::fx_amount().as_ref(),
+                    )),
+                    right: Box::new(ValueExpr::factory_integer(0)),
+                },
+            )),
+            key: None,
+        };
+
+        let select_list = vec![SelectFieldSyntax::AggrExpr {
+            aggr_expr: sum_amount_expr,
+            alias: Some(AggrAlias::new("sum_amount".to_string())),
+        }];
+
+        let sql_config = SpringSqlConfig {
+            division_by_zero: DivisionByZero::Error,
+        };
+        let (expr_resolver, labels) = ExprResolver::new(select_list, sql_config);
+        match &labels[..] {
+            &[ExprLabel::Aggr(aggr_label)] => {
+                let mut window = AggrWindow::new(
+                    WindowParameter::TimedSlidingWindow {
+                        length: SpringEventDuration::from_secs(10),
+                        period: SpringEventDuration::from_secs(10),
+                        allowed_delay: SpringEventDuration::from_secs(0),
+                    },
+                    WindowOperationParameter::Aggregate(AggregateParameter {
+                        aggr_exprs: vec![(aggr_label, AggregateFunctionParameter::Sum)],
+                        group_by: GroupByLabels::new(vec![]),
+                    }),
+                );
+
+                let res = window.dispatch(
+                    &expr_resolver,
+                    Tuple::factory_trade(
+                        SpringTimestamp::from_str("2020-01-01 00:00:01.000000000").unwrap(),
+                        "ORCL",
+                        100,
+                    ),
+                    (),
+                );
+                assert!(matches!(res.unwrap_err(), SpringError::Sql(_)));
+            }
+            _ => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn test_sum_overflow_error() {
+        setup_test_logger();
+
+        // SELECT SUM(9223372036854775807) AS sum_max
+        //   FROM trade
+        //   FIXED WINDOW duration_secs(10), duration_secs(0);
+
+        let sum_max_expr = AggrExpr {
+            func: AggregateFunctionParameter::Sum,
+            aggregated: ValueExpr::Constant(SqlValue::NotNull(NnSqlValue::BigInt(i64::MAX))),
+            key: None,
+        };
+
+        let select_list = vec![SelectFieldSyntax::AggrExpr {
+            aggr_expr: sum_max_expr,
+            alias: Some(AggrAlias::new("sum_max".to_string())),
+        }];
+
+        let (expr_resolver, labels) = ExprResolver::new(select_list, SpringSqlConfig::fx_default());
+        match &labels[..] {
+            &[ExprLabel::Aggr(aggr_label)] => {
+                let mut window = AggrWindow::new(
+                    WindowParameter::TimedSlidingWindow {
+                        length: SpringEventDuration::from_secs(10),
+                        period: SpringEventDuration::from_secs(10),
+                        allowed_delay: SpringEventDuration::from_secs(0),
+                    },
+                    WindowOperationParameter::Aggregate(AggregateParameter {
+                        aggr_exprs: vec![(aggr_label, AggregateFunctionParameter::Sum)],
+                        group_by: GroupByLabels::new(vec![]),
+                    }),
+                );
+
+                let tuple = || {
+                    Tuple::factory_trade(
+                        SpringTimestamp::from_str("2020-01-01 00:00:01.000000000").unwrap(),
+                        "ORCL",
+                        100,
+                    )
+                };
+
+                let (out, _) = window.dispatch(&expr_resolver, tuple(), ()).unwrap();
+                assert!(out.is_empty());
+
+                let res = window.dispatch(&expr_resolver, tuple(), ());
+                assert!(matches!(res.unwrap_err(), SpringError::Sql(_)));
+            }
+            _ => unreachable!(),
+        }
+    }
 }
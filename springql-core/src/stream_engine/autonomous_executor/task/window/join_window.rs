@@ -56,8 +56,9 @@ mod tests {
     use springql_test_logger::setup_test_logger;
 
     use crate::{
+        api::{DivisionByZero, SpringError, SpringSqlConfig},
         expr_resolver::ExprResolver,
-        expression::ValueExpr,
+        expression::{BinaryExpr, NumericalFunction, ValueExpr},
         pipeline::{ColumnName, ColumnReference, JoinParameter, JoinType, StreamName},
         sql_processor::SelectFieldSyntax,
         stream_engine::{
@@ -149,7 +150,7 @@ mod tests {
             },
         ];
 
-        let (mut expr_resolver, _) = ExprResolver::new(select_list);
+        let (mut expr_resolver, _) = ExprResolver::new(select_list, SpringSqlConfig::fx_default());
 
         let on_expr_label = expr_resolver.register_value_expr(on_expr);
 
@@ -171,6 +172,7 @@ mod tests {
                     ColumnReference::fx_city_temperature_temperature(),
                 ],
                 on_expr: on_expr_label,
+                equi_keys: vec![],
             },
         );
 
@@ -311,4 +313,201 @@ mod tests {
         assert!(window_in_flow.window_gain_bytes_rows < 0);
         assert_eq!(window_in_flow.window_gain_bytes_states, 0);
     }
+
+    /// `ON trade.ticker = city_temperature.city AND city_temperature.temperature < trade.amount`
+    fn join_by_type(join_type: JoinType) -> Vec<(Option<i32>, Option<i32>)> {
+        let colref_expr = |colref: ColumnReference| ValueExpr::ColumnReference(colref);
+
+        let on_expr = ValueExpr::factory_and(
+            ValueExpr::factory_eq(
+                colref_expr(ColumnReference::fx_trade_ticker()),
+                colref_expr(ColumnReference::fx_city_temperature_city()),
+            ),
+            ValueExpr::factory_lt(
+                colref_expr(ColumnReference::fx_city_temperature_temperature()),
+                colref_expr(ColumnReference::fx_trade_amount()),
+            ),
+        );
+
+        let (mut expr_resolver, _) = ExprResolver::new(vec![], SpringSqlConfig::fx_default());
+        let left_key_label =
+            expr_resolver.register_value_expr(colref_expr(ColumnReference::fx_trade_ticker()));
+        let right_key_label = expr_resolver
+            .register_value_expr(colref_expr(ColumnReference::fx_city_temperature_city()));
+        let on_expr_label = expr_resolver.register_value_expr(on_expr);
+
+        let mut window = JoinWindow::new(
+            WindowParameter::TimedFixedWindow {
+                length: SpringEventDuration::from_secs(10),
+                allowed_delay: SpringEventDuration::from_secs(0),
+            },
+            JoinParameter {
+                join_type,
+                left_colrefs: vec![
+                    ColumnReference::fx_trade_timestamp(),
+                    ColumnReference::fx_trade_ticker(),
+                    ColumnReference::fx_trade_amount(),
+                ],
+                right_colrefs: vec![
+                    ColumnReference::fx_city_temperature_timestamp(),
+                    ColumnReference::fx_city_temperature_city(),
+                    ColumnReference::fx_city_temperature_temperature(),
+                ],
+                on_expr: on_expr_label,
+                equi_keys: vec![(left_key_label, right_key_label)],
+            },
+        );
+
+        let ts = SpringTimestamp::from_str("2020-01-01 00:00:00.000000000").unwrap();
+        let tuples = vec![
+            (Tuple::factory_trade(ts, "a", 100), JoinDir::Left),
+            (Tuple::factory_trade(ts, "b", 200), JoinDir::Left),
+            (Tuple::factory_trade(ts, "c", 300), JoinDir::Left),
+            (Tuple::factory_city_temperature(ts, "a", 10), JoinDir::Right),
+            (
+                Tuple::factory_city_temperature(ts, "a", 1000),
+                JoinDir::Right,
+            ),
+            (Tuple::factory_city_temperature(ts, "b", 20), JoinDir::Right),
+            (Tuple::factory_city_temperature(ts, "d", 40), JoinDir::Right),
+        ];
+        for (tuple, dir) in tuples {
+            let (out, _) = window.dispatch(&expr_resolver, tuple, dir).unwrap();
+            assert!(out.is_empty());
+        }
+
+        // closes [:00, :10)
+        let (out, window_in_flow) = window
+            .dispatch(
+                &expr_resolver,
+                Tuple::factory_trade(
+                    SpringTimestamp::from_str("2020-01-01 00:00:10.000000000").unwrap(),
+                    "z",
+                    0,
+                ),
+                JoinDir::Left,
+            )
+            .unwrap();
+        assert!(window_in_flow.window_gain_bytes_rows < 0);
+
+        out.into_iter()
+            .map(|tuple| {
+                let get_i32 = |colref: ColumnReference| match tuple.get_value(&colref).unwrap() {
+                    SqlValue::Null => None,
+                    SqlValue::NotNull(v) => Some(v.unpack::<i32>().unwrap()),
+                };
+                (
+                    get_i32(ColumnReference::fx_trade_amount()),
+                    get_i32(ColumnReference::fx_city_temperature_temperature()),
+                )
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_timed_fixed_window_join_types() {
+        setup_test_logger();
+
+        assert_eq!(
+            join_by_type(JoinType::Inner),
+            vec![(Some(100), Some(10)), (Some(200), Some(20))]
+        );
+        assert_eq!(
+            join_by_type(JoinType::LeftOuter),
+            vec![
+                (Some(100), Some(10)),
+                (Some(200), Some(20)),
+                (Some(300), None)
+            ]
+        );
+        assert_eq!(
+            join_by_type(JoinType::RightOuter),
+            vec![
+                (Some(100), Some(10)),
+                (Some(200), Some(20)),
+                (None, Some(1000)),
+                (None, Some(40))
+            ]
+        );
+        assert_eq!(
+            join_by_type(JoinType::FullOuter),
+            vec![
+                (Some(100), Some(10)),
+                (Some(200), Some(20)),
+                (Some(300), None),
+                (None, Some(1000)),
+                (None, Some(40))
+            ]
+        );
+    }
+
+    #[test]
+    fn test_join_key_error() {
+        setup_test_logger();
+
+        // ON trade.amount / 0 = city_temperature.temperature
+        // with `division_by_zero = "error"`
+
+        let colref_expr = |colref: ColumnReference| ValueExpr::ColumnReference(colref);
+        let left_key_expr = ValueExpr::BinaryExpr(BinaryExpr::NumericalFunctionVariant(
+            NumericalFunction::DivVariant {
+                left: Box::new(colref_expr(ColumnReference::fx_trade_amount())),
+                right: Box::new(ValueExpr::factory_integer(0)),
+            },
+        ));
+        let right_key_expr = colref_expr(ColumnReference::fx_city_temperature_temperature());
+        let on_expr = ValueExpr::factory_eq(left_key_expr.clone(), right_key_expr.clone());
+
+        let sql_config = SpringSqlConfig {
+            division_by_zero: DivisionByZero::Error,
+        };
+        let (mut expr_resolver, _) = ExprResolver::new(vec![], sql_config);
+        let left_key_label = expr_resolver.register_value_expr(left_key_expr);
+        let right_key_label = expr_resolver.register_value_expr(right_key_expr);
+        let on_expr_label = expr_resolver.register_value_expr(on_expr);
+
+        let mut window = JoinWindow::new(
+            WindowParameter::TimedFixedWindow {
+                length: SpringEventDuration::from_secs(10),
+                allowed_delay: SpringEventDuration::from_secs(0),
+            },
+            JoinParameter {
+                join_type: JoinType::Inner,
+                left_colrefs: vec![
+                    ColumnReference::fx_trade_timestamp(),
+                    ColumnReference::fx_trade_ticker(),
+                    ColumnReference::fx_trade_amount(),
+                ],
+                right_colrefs: vec![
+                    ColumnReference::fx_city_temperature_timestamp(),
+                    ColumnReference::fx_city_temperature_city(),
+                    ColumnReference::fx_city_temperature_temperature(),
+                ],
+                on_expr: on_expr_label,
+                equi_keys: vec![(left_key_label, right_key_label)],
+            },
+        );
+
+        let ts = SpringTimestamp::from_str("2020-01-01 00:00:00.000000000").unwrap();
+        let tuples = vec![
+            (Tuple::factory_trade(ts, "a", 100), JoinDir::Left),
+            (Tuple::factory_city_temperature(ts, "a", 10), JoinDir::Right),
+        ];
+        for (tuple, dir) in tuples {
+            let (out, _) = window.dispatch(&expr_resolver, tuple, dir).unwrap();
+            assert!(out.is_empty());
+        }
+
+        // closes [:00, :10)
+        let res = window.dispatch(
+            &expr_resolver,
+            Tuple::factory_trade(
+                SpringTimestamp::from_str("2020-01-01 00:00:10.000000000").unwrap(),
+                "z",
+                0,
+            ),
+            JoinDir::Left,
+        );
+        assert!(matches!(res.unwrap_err(), SpringError::Sql(_)));
+    }
 }
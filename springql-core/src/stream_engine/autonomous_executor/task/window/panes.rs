@@ -75,6 +75,11 @@ where
         panes_to_close
     }
 
+    /// Remove all panes regardless of watermark, in `Pane::open_at` order.
+    pub fn remove_all_panes(&mut self) -> Vec<P> {
+        std::mem::take(&mut self.panes)
+    }
+
     pub fn purge(&mut self) {
         self.panes.clear()
     }
@@ -145,6 +150,7 @@ mod tests {
     use std::str::FromStr;
 
     use crate::{
+        api::SpringSqlConfig,
         expr_resolver::{ExprLabel, ExprResolver},
         expression::{AggrExpr, ValueExpr},
         pipeline::{AggregateFunctionParameter, AggregateParameter, GroupByLabels},
@@ -162,6 +168,7 @@ mod tests {
         let aggr_expr = AggrExpr {
             func: AggregateFunctionParameter::Avg,
             aggregated: ValueExpr::factory_colref("dontcare", "dontcare"),
+            key: None,
         };
         let group_by_expr = ValueExpr::factory_colref("dontcare", "dontcare");
 
@@ -169,18 +176,21 @@ mod tests {
             aggr_expr,
             alias: None,
         }];
-        let (mut expr_resolver, labels) = ExprResolver::new(select_list);
+        let (mut expr_resolver, labels) =
+            ExprResolver::new(select_list, SpringSqlConfig::fx_default());
 
         let group_by_labels =
             GroupByLabels::new(vec![expr_resolver.register_value_expr(group_by_expr)]);
 
         WindowOperationParameter::Aggregate(AggregateParameter {
-            aggr_func: AggregateFunctionParameter::Avg,
-            aggr_expr: if let ExprLabel::Aggr(l) = labels[0] {
-                l
-            } else {
-                unreachable!()
-            },
+            aggr_exprs: vec![(
+                if let ExprLabel::Aggr(l) = labels[0] {
+                    l
+                } else {
+                    unreachable!()
+                },
+                AggregateFunctionParameter::Avg,
+            )],
             group_by: group_by_labels,
         })
     }
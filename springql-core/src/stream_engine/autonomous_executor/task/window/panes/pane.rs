@@ -7,6 +7,7 @@ pub use aggregate_pane::{AggrPane, AggrPaneInner, GroupByValues};
 pub use join_pane::{JoinDir, JoinPane};
 
 use crate::{
+    api::error::Result,
     expr_resolver::ExprResolver,
     pipeline::WindowOperationParameter,
     stream_engine::{
@@ -39,13 +40,23 @@ pub trait Pane {
         self.close_at() <= watermark.as_timestamp()
     }
 
+    /// # Failures
+    ///
+    /// - `SpringError::Sql` when:
+    ///   - failed to evaluate expressions on the tuple.
     fn dispatch(
         &mut self,
         expr_resolver: &ExprResolver,
         tuple: &Tuple,
         arg: Self::DispatchArg,
-    ) -> WindowInFlowByWindowTask;
-
-    fn close(self, expr_resolver: &ExprResolver)
-        -> (Vec<Self::CloseOut>, WindowInFlowByWindowTask);
+    ) -> Result<WindowInFlowByWindowTask>;
+
+    /// # Failures
+    ///
+    /// - `SpringError::Sql` when:
+    ///   - failed to evaluate expressions on tuples in the pane.
+    fn close(
+        self,
+        expr_resolver: &ExprResolver,
+    ) -> Result<(Vec<Self::CloseOut>, WindowInFlowByWindowTask)>;
 }
@@ -4,14 +4,11 @@ mod aggregate_state;
 
 use std::collections::HashMap;
 
-use ordered_float::OrderedFloat;
-
 use crate::{
     api::error::Result,
     expr_resolver::ExprResolver,
-    pipeline::{
-        AggregateFunctionParameter, AggregateParameter, GroupByLabels, WindowOperationParameter,
-    },
+    mem_size::MemSize,
+    pipeline::{AggregateParameter, GroupByLabels, WindowOperationParameter},
     stream_engine::{
         autonomous_executor::{
             performance_metrics::WindowInFlowByWindowTask,
@@ -19,7 +16,7 @@ use crate::{
                 tuple::Tuple,
                 window::{
                     aggregate::AggregatedAndGroupingValues,
-                    panes::pane::{aggregate_pane::aggregate_state::AvgState, Pane},
+                    panes::pane::{aggregate_pane::aggregate_state::AggrState, Pane},
                 },
             },
         },
@@ -51,10 +48,8 @@ impl Pane for AggrPane {
         op_param: WindowOperationParameter,
     ) -> Self {
         if let WindowOperationParameter::Aggregate(aggregate_parameter) = op_param {
-            let inner = match aggregate_parameter.aggr_func {
-                AggregateFunctionParameter::Avg => AggrPaneInner::Avg {
-                    states: HashMap::new(),
-                },
+            let inner = AggrPaneInner {
+                states: HashMap::new(),
             };
 
             Self {
@@ -81,80 +76,98 @@ impl Pane for AggrPane {
         expr_resolver: &ExprResolver,
         tuple: &Tuple,
         _arg: (),
-    ) -> WindowInFlowByWindowTask {
+    ) -> Result<WindowInFlowByWindowTask> {
         let group_by_values = GroupByValues::from_group_by_labels(
             self.aggregate_parameter.group_by.clone(),
             expr_resolver,
             tuple,
-        )
-        .expect("TODO handle Result");
-
-        let aggregated_value = expr_resolver
-            .eval_aggr_expr_inner(self.aggregate_parameter.aggr_expr, tuple)
-            .expect("TODO Result");
-        let aggregated_value = if let SqlValue::NotNull(v) = aggregated_value {
-            v
-        } else {
-            unimplemented!("aggregation with NULL value is not supported")
-        };
-
-        match &mut self.inner {
-            AggrPaneInner::Avg { states } => {
-                let state = states
-                    .entry(group_by_values)
-                    .or_insert_with(AvgState::default);
-
-                state.next(
-                    aggregated_value
-                        .unpack::<f32>()
-                        .expect("only f32 is supported currently"),
-                );
-
-                WindowInFlowByWindowTask::zero() // state in AVG is constant
-            }
+        )?;
+
+        let aggr_exprs = &self.aggregate_parameter.aggr_exprs;
+        let mut states_size_before = 0;
+        let states = self
+            .inner
+            .states
+            .entry(group_by_values)
+            .and_modify(|states| states_size_before = states_mem_size(states))
+            .or_insert_with(|| {
+                aggr_exprs
+                    .iter()
+                    .map(|(_, aggr_func)| AggrState::new(*aggr_func))
+                    .collect()
+            });
+
+        for ((aggr_label, _), state) in aggr_exprs.iter().zip(states.iter_mut()) {
+            let aggregated_value = expr_resolver.eval_aggr_expr_inner(*aggr_label, tuple)?;
+            let key_value = expr_resolver.eval_aggr_expr_key(*aggr_label, tuple)?;
+
+            // NULL is ignored by most aggregate functions (but the group is still emitted).
+            state.next(aggregated_value, tuple.rowtime().as_timestamp(), key_value)?;
         }
+
+        // sketches (e.g. PERCENTILE_APPROX) may grow
+        let states_size_after = states_mem_size(states);
+        Ok(WindowInFlowByWindowTask::new(
+            states_size_after as i64 - states_size_before as i64,
+            0,
+        ))
     }
 
     fn close(
         self,
         _expr_resolver: &ExprResolver,
-    ) -> (Vec<Self::CloseOut>, WindowInFlowByWindowTask) {
-        let aggr_label = self.aggregate_parameter.aggr_expr;
+    ) -> Result<(Vec<Self::CloseOut>, WindowInFlowByWindowTask)> {
+        let aggr_labels = self
+            .aggregate_parameter
+            .aggr_exprs
+            .iter()
+            .map(|(aggr_label, _)| *aggr_label)
+            .collect::<Vec<_>>();
         let group_by_labels = self.aggregate_parameter.group_by;
+        let states_size = self
+            .inner
+            .states
+            .values()
+            .map(|states| states_mem_size(states))
+            .sum::<usize>();
+
+        let aggregated_and_grouping_values_seq = self
+            .inner
+            .states
+            .into_iter()
+            .map(|(group_by_values, states)| {
+                let aggr_values = aggr_labels
+                    .iter()
+                    .cloned()
+                    .zip(states.into_iter().map(AggrState::finalize))
+                    .collect();
 
-        match self.inner {
-            AggrPaneInner::Avg { states } => {
-                let aggregated_and_grouping_values_seq = states
-                    .into_iter()
-                    .map(|(group_by_values, state)| {
-                        let aggr_value =
-                            SqlValue::NotNull(NnSqlValue::Float(OrderedFloat(state.finalize())));
-
-                        let group_bys = group_by_labels
-                            .as_labels()
-                            .iter()
-                            .cloned()
-                            .zip(group_by_values.into_sql_values())
-                            .collect();
-
-                        AggregatedAndGroupingValues::new(vec![(aggr_label, aggr_value)], group_bys)
-                    })
+                let group_bys = group_by_labels
+                    .as_labels()
+                    .iter()
+                    .cloned()
+                    .zip(group_by_values.into_sql_values())
                     .collect();
 
-                (
-                    aggregated_and_grouping_values_seq,
-                    WindowInFlowByWindowTask::zero(),
-                )
-            }
-        }
+                AggregatedAndGroupingValues::new(aggr_values, group_bys)
+            })
+            .collect();
+
+        Ok((
+            aggregated_and_grouping_values_seq,
+            WindowInFlowByWindowTask::new(-(states_size as i64), 0),
+        ))
     }
 }
 
 #[derive(Debug)]
-pub enum AggrPaneInner {
-    Avg {
-        states: HashMap<GroupByValues, AvgState>,
-    },
+pub struct AggrPaneInner {
+    /// States are in the same order as `AggregateParameter::aggr_exprs`.
+    states: HashMap<GroupByValues, Vec<AggrState>>,
+}
+
+fn states_mem_size(states: &[AggrState]) -> usize {
+    states.iter().map(MemSize::mem_size).sum()
 }
 
 #[derive(Clone, PartialEq, Eq, Hash, Debug)]
@@ -1,32 +1,758 @@
 // This file is part of https://github.com/SpringQL/SpringQL which is licensed under MIT OR Apache-2.0. See file LICENSE-MIT or LICENSE-APACHE for full license details.
 
-// TODO more generic avg
+use std::{
+    collections::hash_map::DefaultHasher,
+    hash::{Hash, Hasher},
+    mem::size_of,
+};
+
+use ordered_float::OrderedFloat;
+
+use crate::{
+    api::error::Result,
+    mem_size::MemSize,
+    pipeline::AggregateFunctionParameter,
+    stream_engine::{time::SpringTimestamp, NnSqlValue, SqlCompareResult, SqlValue},
+};
+
+/// Intermediate state of an aggregate function for a group in a pane.
+///
+/// NULL values are ignored by aggregate functions except for FIRST_VALUE, LAST_VALUE, ARG_MAX and ARG_MIN, which return NULL if the chosen row has NULL value.
+#[derive(Debug)]
+pub enum AggrState {
+    NullIgnoring(NullIgnoringAggrState),
+    RowChoosing(RowChoosingAggrState),
+}
+
+impl AggrState {
+    pub fn new(aggr_func: AggregateFunctionParameter) -> Self {
+        match aggr_func {
+            AggregateFunctionParameter::Avg => {
+                Self::NullIgnoring(NullIgnoringAggrState::Avg(AvgState::default()))
+            }
+            AggregateFunctionParameter::Count => {
+                Self::NullIgnoring(NullIgnoringAggrState::Count(CountState::default()))
+            }
+            AggregateFunctionParameter::Sum => {
+                Self::NullIgnoring(NullIgnoringAggrState::Sum(SumState::default()))
+            }
+            AggregateFunctionParameter::Min => {
+                Self::NullIgnoring(NullIgnoringAggrState::Min(MinState::default()))
+            }
+            AggregateFunctionParameter::Max => {
+                Self::NullIgnoring(NullIgnoringAggrState::Max(MaxState::default()))
+            }
+            AggregateFunctionParameter::StddevPop => {
+                Self::NullIgnoring(NullIgnoringAggrState::StddevPop(VarianceState::default()))
+            }
+            AggregateFunctionParameter::StddevSamp => {
+                Self::NullIgnoring(NullIgnoringAggrState::StddevSamp(VarianceState::default()))
+            }
+            AggregateFunctionParameter::VarPop => {
+                Self::NullIgnoring(NullIgnoringAggrState::VarPop(VarianceState::default()))
+            }
+            AggregateFunctionParameter::VarSamp => {
+                Self::NullIgnoring(NullIgnoringAggrState::VarSamp(VarianceState::default()))
+            }
+            AggregateFunctionParameter::PercentileApprox { percentile } => {
+                Self::NullIgnoring(NullIgnoringAggrState::PercentileApprox(
+                    PercentileApproxState::new(percentile.into_inner()),
+                ))
+            }
+            AggregateFunctionParameter::ApproxCountDistinct => Self::NullIgnoring(
+                NullIgnoringAggrState::ApproxCountDistinct(ApproxCountDistinctState::default()),
+            ),
+            AggregateFunctionParameter::FirstValue => {
+                Self::RowChoosing(RowChoosingAggrState::FirstValue(FirstValueState::default()))
+            }
+            AggregateFunctionParameter::LastValue => {
+                Self::RowChoosing(RowChoosingAggrState::LastValue(LastValueState::default()))
+            }
+            AggregateFunctionParameter::ArgMax => {
+                Self::RowChoosing(RowChoosingAggrState::ArgMax(ArgMaxState::default()))
+            }
+            AggregateFunctionParameter::ArgMin => {
+                Self::RowChoosing(RowChoosingAggrState::ArgMin(ArgMinState::default()))
+            }
+        }
+    }
+
+    /// `rowtime` and `key` are used by FIRST_VALUE, LAST_VALUE, ARG_MAX and ARG_MIN (`key` is NULL for other functions).
+    ///
+    /// # Failures
+    ///
+    /// - `SpringError::Sql` when:
+    ///   - `next_val` has a type which the aggregate function cannot handle.
+    ///   - SUM overflows.
+    ///   - `key` is not comparable to the current key.
+    pub fn next(
+        &mut self,
+        next_val: SqlValue,
+        rowtime: SpringTimestamp,
+        key: SqlValue,
+    ) -> Result<()> {
+        match self {
+            AggrState::NullIgnoring(state) => {
+                if let SqlValue::NotNull(next_val) = next_val {
+                    state.next(next_val)?;
+                }
+            }
+            AggrState::RowChoosing(state) => state.next(next_val, rowtime, key)?,
+        }
+        Ok(())
+    }
+
+    /// Aggregated value. NULL if no value other than NULL has been fed (except for COUNT, which returns 0).
+    ///
+    /// FIRST_VALUE, LAST_VALUE, ARG_MAX and ARG_MIN return the value of the chosen row, which may be NULL.
+    pub fn finalize(self) -> SqlValue {
+        match self {
+            AggrState::NullIgnoring(state) => {
+                state.finalize().map_or(SqlValue::Null, SqlValue::NotNull)
+            }
+            AggrState::RowChoosing(state) => state.finalize(),
+        }
+    }
+}
+
+/// Only variable-size parts (kept values and sketches) are counted.
+/// Fixed-size states (e.g. AVG's) are regarded as constant in window memory usage.
+impl MemSize for AggrState {
+    fn mem_size(&self) -> usize {
+        match self {
+            AggrState::NullIgnoring(state) => state.mem_size(),
+            AggrState::RowChoosing(state) => state.mem_size(),
+        }
+    }
+}
+
+/// Aggregate functions fed only with non-NULL values.
+#[derive(Debug)]
+pub enum NullIgnoringAggrState {
+    Avg(AvgState),
+    Count(CountState),
+    Sum(SumState),
+    Min(MinState),
+    Max(MaxState),
+    StddevPop(VarianceState),
+    StddevSamp(VarianceState),
+    VarPop(VarianceState),
+    VarSamp(VarianceState),
+    PercentileApprox(PercentileApproxState),
+    ApproxCountDistinct(ApproxCountDistinctState),
+}
+
+impl NullIgnoringAggrState {
+    /// # Failures
+    ///
+    /// - `SpringError::Sql` when:
+    ///   - `next_val` has a type which the aggregate function cannot handle.
+    ///   - SUM overflows.
+    fn next(&mut self, next_val: NnSqlValue) -> Result<()> {
+        match self {
+            Self::Avg(state) => state.next(next_val.to_f64()?),
+            Self::Count(state) => state.next(),
+            Self::Sum(state) => state.next(next_val)?,
+            Self::Min(state) => state.next(next_val)?,
+            Self::Max(state) => state.next(next_val)?,
+            Self::StddevPop(state)
+            | Self::StddevSamp(state)
+            | Self::VarPop(state)
+            | Self::VarSamp(state) => state.next(next_val.to_f64()?),
+            Self::PercentileApprox(state) => state.next(next_val.to_f64()?),
+            Self::ApproxCountDistinct(state) => state.next(&next_val),
+        }
+        Ok(())
+    }
+
+    fn finalize(self) -> Option<NnSqlValue> {
+        match self {
+            Self::Avg(state) => state
+                .finalize()
+                .map(|avg| NnSqlValue::Float(OrderedFloat(avg as f32))),
+            Self::Count(state) => Some(NnSqlValue::BigInt(state.finalize())),
+            Self::Sum(state) => state.finalize(),
+            Self::Min(state) => state.finalize(),
+            Self::Max(state) => state.finalize(),
+            Self::StddevPop(state) => state.var_pop().map(|v| float(v.sqrt())),
+            Self::StddevSamp(state) => state.var_samp().map(|v| float(v.sqrt())),
+            Self::VarPop(state) => state.var_pop().map(float),
+            Self::VarSamp(state) => state.var_samp().map(float),
+            Self::PercentileApprox(state) => state.finalize().map(float),
+            Self::ApproxCountDistinct(state) => Some(NnSqlValue::BigInt(state.finalize())),
+        }
+    }
+}
+
+impl MemSize for NullIgnoringAggrState {
+    fn mem_size(&self) -> usize {
+        match self {
+            Self::Avg(_)
+            | Self::Count(_)
+            | Self::StddevPop(_)
+            | Self::StddevSamp(_)
+            | Self::VarPop(_)
+            | Self::VarSamp(_) => 0,
+            Self::Sum(state) => state.mem_size(),
+            Self::Min(state) => state.mem_size(),
+            Self::Max(state) => state.mem_size(),
+            Self::PercentileApprox(state) => state.mem_size(),
+            Self::ApproxCountDistinct(state) => state.mem_size(),
+        }
+    }
+}
+
+/// Aggregate functions choosing a row, fed with NULL values too.
+#[derive(Debug)]
+pub enum RowChoosingAggrState {
+    FirstValue(FirstValueState),
+    LastValue(LastValueState),
+    ArgMax(ArgMaxState),
+    ArgMin(ArgMinState),
+}
+
+impl RowChoosingAggrState {
+    /// # Failures
+    ///
+    /// - `SpringError::Sql` when:
+    ///   - `key` is not comparable to the current key.
+    fn next(&mut self, next_val: SqlValue, rowtime: SpringTimestamp, key: SqlValue) -> Result<()> {
+        match self {
+            Self::FirstValue(state) => state.next(next_val, rowtime),
+            Self::LastValue(state) => state.next(next_val, rowtime),
+            Self::ArgMax(state) => state.next(next_val, rowtime, key)?,
+            Self::ArgMin(state) => state.next(next_val, rowtime, key)?,
+        }
+        Ok(())
+    }
+
+    fn finalize(self) -> SqlValue {
+        match self {
+            Self::FirstValue(state) => state.finalize(),
+            Self::LastValue(state) => state.finalize(),
+            Self::ArgMax(state) => state.finalize(),
+            Self::ArgMin(state) => state.finalize(),
+        }
+    }
+}
+
+impl MemSize for RowChoosingAggrState {
+    fn mem_size(&self) -> usize {
+        match self {
+            Self::FirstValue(state) => state.mem_size(),
+            Self::LastValue(state) => state.mem_size(),
+            Self::ArgMax(state) => state.mem_size(),
+            Self::ArgMin(state) => state.mem_size(),
+        }
+    }
+}
+
+fn float(v: f64) -> NnSqlValue {
+    NnSqlValue::Float(OrderedFloat(v as f32))
+}
+
 #[derive(Debug, Default)]
 pub struct AvgState {
-    current_avg: f32,
+    current_avg: f64,
     current_n: u64,
 }
 
 impl AvgState {
-    pub fn next<V>(&mut self, next_val: V)
-    where
-        V: Into<f32>,
-    {
-        let next_val: f32 = next_val.into();
+    pub fn next(&mut self, next_val: f64) {
         let next_n = self.current_n + 1;
 
-        self.current_avg =
-            self.current_avg + (next_val - self.current_avg) * (1.0 / (next_n as f32));
+        self.current_avg += (next_val - self.current_avg) / (next_n as f64);
         self.current_n = next_n;
     }
 
-    pub fn finalize(self) -> f32 {
-        self.current_avg.round()
+    pub fn finalize(self) -> Option<f64> {
+        (self.current_n > 0).then(|| self.current_avg)
+    }
+}
+
+#[derive(Debug, Default)]
+pub struct CountState {
+    current_n: i64,
+}
+
+impl CountState {
+    pub fn next(&mut self) {
+        self.current_n += 1;
+    }
+
+    pub fn finalize(self) -> i64 {
+        self.current_n
+    }
+}
+
+/// Integer sum is kept as integer (BIGINT or UNSIGNED BIGINT), and float sum is kept as FLOAT.
+#[derive(Debug, Default)]
+pub struct SumState {
+    current_sum: Option<NnSqlValue>,
+}
+
+impl SumState {
+    /// # Failures
+    ///
+    /// - `SpringError::Sql` when:
+    ///   - `next_val` is not numeric.
+    ///   - integer sum overflows. The current sum is kept.
+    pub fn next(&mut self, next_val: NnSqlValue) -> Result<()> {
+        let next_sum = match &self.current_sum {
+            Some(current_sum) => (current_sum.clone() + next_val)?,
+            // validates `next_val` is numeric and widens it
+            None => (NnSqlValue::SmallInt(0) + next_val)?,
+        };
+        self.current_sum = Some(next_sum);
+        Ok(())
+    }
+
+    pub fn finalize(self) -> Option<NnSqlValue> {
+        self.current_sum
+    }
+}
+
+impl MemSize for SumState {
+    fn mem_size(&self) -> usize {
+        self.current_sum.as_ref().map_or(0, MemSize::mem_size)
+    }
+}
+
+#[derive(Debug, Default)]
+pub struct MinState {
+    current_min: Option<NnSqlValue>,
+}
+
+impl MinState {
+    /// # Failures
+    ///
+    /// - `SpringError::Sql` when:
+    ///   - `next_val` is not comparable to current minimum.
+    pub fn next(&mut self, next_val: NnSqlValue) -> Result<()> {
+        match &self.current_min {
+            Some(current_min)
+                if next_val.sql_compare(current_min)? != SqlCompareResult::LessThan => {}
+            _ => self.current_min = Some(next_val),
+        }
+        Ok(())
+    }
+
+    pub fn finalize(self) -> Option<NnSqlValue> {
+        self.current_min
+    }
+}
+
+impl MemSize for MinState {
+    fn mem_size(&self) -> usize {
+        self.current_min.as_ref().map_or(0, MemSize::mem_size)
+    }
+}
+
+#[derive(Debug, Default)]
+pub struct MaxState {
+    current_max: Option<NnSqlValue>,
+}
+
+impl MaxState {
+    /// # Failures
+    ///
+    /// - `SpringError::Sql` when:
+    ///   - `next_val` is not comparable to current maximum.
+    pub fn next(&mut self, next_val: NnSqlValue) -> Result<()> {
+        match &self.current_max {
+            Some(current_max)
+                if next_val.sql_compare(current_max)? != SqlCompareResult::GreaterThan => {}
+            _ => self.current_max = Some(next_val),
+        }
+        Ok(())
+    }
+
+    pub fn finalize(self) -> Option<NnSqlValue> {
+        self.current_max
+    }
+}
+
+impl MemSize for MaxState {
+    fn mem_size(&self) -> usize {
+        self.current_max.as_ref().map_or(0, MemSize::mem_size)
+    }
+}
+
+/// Welford's online algorithm for variance.
+#[derive(Debug, Default)]
+pub struct VarianceState {
+    current_n: u64,
+    current_mean: f64,
+    /// Sum of squared differences from the current mean.
+    current_m2: f64,
+}
+
+impl VarianceState {
+    pub fn next(&mut self, next_val: f64) {
+        self.current_n += 1;
+        let delta = next_val - self.current_mean;
+        self.current_mean += delta / (self.current_n as f64);
+        self.current_m2 += delta * (next_val - self.current_mean);
+    }
+
+    /// Population variance. None if no value.
+    pub fn var_pop(&self) -> Option<f64> {
+        (self.current_n > 0).then(|| self.current_m2 / (self.current_n as f64))
+    }
+
+    /// Sample variance. None if less than 2 values.
+    pub fn var_samp(&self) -> Option<f64> {
+        (self.current_n > 1).then(|| self.current_m2 / ((self.current_n - 1) as f64))
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+struct Centroid {
+    mean: f64,
+    weight: f64,
+}
+
+/// Merging t-digest for approximate percentiles.
+///
+/// Memory is bounded by `T_DIGEST_COMPRESSION` (number of centroids) and `T_DIGEST_BUFFER_SIZE` (unmerged values).
+/// Centroids near both ends of the distribution are kept small so that extreme percentiles (e.g. 0.99) are accurate.
+#[derive(Debug)]
+pub struct PercentileApproxState {
+    percentile: f64,
+
+    /// Sorted by mean.
+    centroids: Vec<Centroid>,
+    buffer: Vec<f64>,
+
+    total_weight: f64,
+    min: f64,
+    max: f64,
+}
+
+impl PercentileApproxState {
+    const T_DIGEST_COMPRESSION: f64 = 100.0;
+    const T_DIGEST_BUFFER_SIZE: usize = 500;
+
+    /// `percentile` must be in [0, 1].
+    pub fn new(percentile: f64) -> Self {
+        Self {
+            percentile,
+            centroids: Vec::new(),
+            buffer: Vec::new(),
+            total_weight: 0.0,
+            min: f64::INFINITY,
+            max: f64::NEG_INFINITY,
+        }
+    }
+
+    pub fn next(&mut self, next_val: f64) {
+        if next_val.is_nan() {
+            return;
+        }
+
+        self.min = self.min.min(next_val);
+        self.max = self.max.max(next_val);
+        self.buffer.push(next_val);
+        if self.buffer.len() >= Self::T_DIGEST_BUFFER_SIZE {
+            self.compress();
+        }
+    }
+
+    /// None if no value.
+    pub fn finalize(mut self) -> Option<f64> {
+        self.compress();
+        self.quantile(self.percentile)
+    }
+
+    fn compress(&mut self) {
+        if self.buffer.is_empty() {
+            return;
+        }
+
+        let mut all = self
+            .buffer
+            .drain(..)
+            .map(|v| Centroid {
+                mean: v,
+                weight: 1.0,
+            })
+            .chain(self.centroids.drain(..))
+            .collect::<Vec<_>>();
+        all.sort_by(|a, b| a.mean.partial_cmp(&b.mean).expect("NaN is not in t-digest"));
+
+        let total_weight = all.iter().map(|c| c.weight).sum::<f64>();
+        let mut merged = Vec::new();
+        let mut weight_so_far = 0.0;
+        let mut iter = all.into_iter();
+        let mut current = iter.next().expect("buffer is not empty");
+
+        for c in iter {
+            let proposed_weight = current.weight + c.weight;
+            let q = (weight_so_far + proposed_weight / 2.0) / total_weight;
+            let max_weight = 4.0 * total_weight * q * (1.0 - q) / Self::T_DIGEST_COMPRESSION;
+
+            if proposed_weight <= max_weight.max(1.0) {
+                current.mean += (c.mean - current.mean) * c.weight / proposed_weight;
+                current.weight = proposed_weight;
+            } else {
+                weight_so_far += current.weight;
+                merged.push(current);
+                current = c;
+            }
+        }
+        merged.push(current);
+
+        self.centroids = merged;
+        self.total_weight = total_weight;
+    }
+
+    /// Interpolates between centroid centers (and min / max at both ends).
+    fn quantile(&self, q: f64) -> Option<f64> {
+        if self.centroids.is_empty() {
+            return None;
+        }
+
+        let rank = q * self.total_weight;
+        let interpolate = |(r0, v0): (f64, f64), (r1, v1): (f64, f64)| {
+            if r1 > r0 {
+                v0 + (rank - r0) / (r1 - r0) * (v1 - v0)
+            } else {
+                v0
+            }
+        };
+
+        let mut prev = (0.0, self.min);
+        let mut weight_so_far = 0.0;
+        for c in &self.centroids {
+            let center = (weight_so_far + c.weight / 2.0, c.mean);
+            if rank < center.0 {
+                return Some(interpolate(prev, center));
+            }
+            prev = center;
+            weight_so_far += c.weight;
+        }
+        Some(interpolate(prev, (self.total_weight, self.max)))
+    }
+}
+
+impl MemSize for PercentileApproxState {
+    fn mem_size(&self) -> usize {
+        self.centroids.capacity() * size_of::<Centroid>()
+            + self.buffer.capacity() * size_of::<f64>()
+    }
+}
+
+/// HyperLogLog for approximate distinct count.
+///
+/// Uses 2^12 registers (4 KiB), whose standard error is about 1.6%.
+#[derive(Debug)]
+pub struct ApproxCountDistinctState {
+    registers: Vec<u8>,
+}
+
+impl Default for ApproxCountDistinctState {
+    fn default() -> Self {
+        Self {
+            registers: vec![0; 1 << Self::PRECISION],
+        }
+    }
+}
+
+impl ApproxCountDistinctState {
+    const PRECISION: u32 = 12;
+
+    /// Loosely-typed equal values (e.g. `42 SMALLINT` and `42 INTEGER`) are counted as the same value.
+    pub fn next(&mut self, next_val: &NnSqlValue) {
+        let mut hasher = DefaultHasher::new();
+        next_val.hash(&mut hasher);
+        let hash = hasher.finish();
+
+        let idx = (hash >> (64 - Self::PRECISION)) as usize;
+        let rest = hash << Self::PRECISION;
+        let rank = (rest.leading_zeros() + 1).min(64 - Self::PRECISION + 1) as u8;
+
+        if self.registers[idx] < rank {
+            self.registers[idx] = rank;
+        }
+    }
+
+    pub fn finalize(self) -> i64 {
+        let m = self.registers.len() as f64;
+        let alpha = 0.7213 / (1.0 + 1.079 / m);
+        let sum = self
+            .registers
+            .iter()
+            .map(|r| 2f64.powi(-(*r as i32)))
+            .sum::<f64>();
+        let estimate = alpha * m * m / sum;
+
+        let zeros = self.registers.iter().filter(|r| **r == 0).count();
+        let estimate = if estimate <= 2.5 * m && zeros > 0 {
+            // linear counting for small cardinalities
+            m * (m / zeros as f64).ln()
+        } else {
+            estimate
+        };
+        estimate.round() as i64
+    }
+}
+
+impl MemSize for ApproxCountDistinctState {
+    fn mem_size(&self) -> usize {
+        self.registers.capacity()
+    }
+}
+
+/// Value of the row with the earliest ROWTIME. The first-fed row wins among rows with the same ROWTIME.
+///
+/// NULL is returned if the value of the row is NULL.
+#[derive(Debug, Default)]
+pub struct FirstValueState {
+    current: Option<(SpringTimestamp, SqlValue)>,
+}
+
+impl FirstValueState {
+    pub fn next(&mut self, next_val: SqlValue, rowtime: SpringTimestamp) {
+        match &self.current {
+            Some((current_rowtime, _)) if *current_rowtime <= rowtime => {}
+            _ => self.current = Some((rowtime, next_val)),
+        }
+    }
+
+    pub fn finalize(self) -> SqlValue {
+        self.current.map_or(SqlValue::Null, |(_, v)| v)
+    }
+}
+
+impl MemSize for FirstValueState {
+    fn mem_size(&self) -> usize {
+        self.current.as_ref().map_or(0, |(_, v)| v.mem_size())
+    }
+}
+
+/// Value of the row with the latest ROWTIME. The last-fed row wins among rows with the same ROWTIME.
+///
+/// NULL is returned if the value of the row is NULL.
+#[derive(Debug, Default)]
+pub struct LastValueState {
+    current: Option<(SpringTimestamp, SqlValue)>,
+}
+
+impl LastValueState {
+    pub fn next(&mut self, next_val: SqlValue, rowtime: SpringTimestamp) {
+        match &self.current {
+            Some((current_rowtime, _)) if *current_rowtime > rowtime => {}
+            _ => self.current = Some((rowtime, next_val)),
+        }
+    }
+
+    pub fn finalize(self) -> SqlValue {
+        self.current.map_or(SqlValue::Null, |(_, v)| v)
+    }
+}
+
+impl MemSize for LastValueState {
+    fn mem_size(&self) -> usize {
+        self.current.as_ref().map_or(0, |(_, v)| v.mem_size())
+    }
+}
+
+/// Value of the row with the maximum key. Ties of the key are broken by the earliest ROWTIME, and then by the first-fed row.
+///
+/// Rows with NULL key are ignored. NULL is returned if the value of the row is NULL.
+#[derive(Debug, Default)]
+pub struct ArgMaxState {
+    /// (key, rowtime, value)
+    current: Option<(NnSqlValue, SpringTimestamp, SqlValue)>,
+}
+
+impl ArgMaxState {
+    /// # Failures
+    ///
+    /// - `SpringError::Sql` when:
+    ///   - `key` is not comparable to current maximum key.
+    pub fn next(
+        &mut self,
+        next_val: SqlValue,
+        rowtime: SpringTimestamp,
+        key: SqlValue,
+    ) -> Result<()> {
+        if let SqlValue::NotNull(key) = key {
+            if let Some((current_key, current_rowtime, _)) = &self.current {
+                match key.sql_compare(current_key)? {
+                    SqlCompareResult::GreaterThan => {}
+                    SqlCompareResult::Eq if rowtime < *current_rowtime => {}
+                    _ => return Ok(()),
+                }
+            }
+            self.current = Some((key, rowtime, next_val));
+        }
+        Ok(())
+    }
+
+    pub fn finalize(self) -> SqlValue {
+        self.current.map_or(SqlValue::Null, |(_, _, v)| v)
+    }
+}
+
+impl MemSize for ArgMaxState {
+    fn mem_size(&self) -> usize {
+        self.current
+            .as_ref()
+            .map_or(0, |(k, _, v)| k.mem_size() + v.mem_size())
+    }
+}
+
+/// Value of the row with the minimum key. Ties of the key are broken by the earliest ROWTIME, and then by the first-fed row.
+///
+/// Rows with NULL key are ignored. NULL is returned if the value of the row is NULL.
+#[derive(Debug, Default)]
+pub struct ArgMinState {
+    /// (key, rowtime, value)
+    current: Option<(NnSqlValue, SpringTimestamp, SqlValue)>,
+}
+
+impl ArgMinState {
+    /// # Failures
+    ///
+    /// - `SpringError::Sql` when:
+    ///   - `key` is not comparable to current minimum key.
+    pub fn next(
+        &mut self,
+        next_val: SqlValue,
+        rowtime: SpringTimestamp,
+        key: SqlValue,
+    ) -> Result<()> {
+        if let SqlValue::NotNull(key) = key {
+            if let Some((current_key, current_rowtime, _)) = &self.current {
+                match key.sql_compare(current_key)? {
+                    SqlCompareResult::LessThan => {}
+                    SqlCompareResult::Eq if rowtime < *current_rowtime => {}
+                    _ => return Ok(()),
+                }
+            }
+            self.current = Some((key, rowtime, next_val));
+        }
+        Ok(())
+    }
+
+    pub fn finalize(self) -> SqlValue {
+        self.current.map_or(SqlValue::Null, |(_, _, v)| v)
+    }
+}
+
+impl MemSize for ArgMinState {
+    fn mem_size(&self) -> usize {
+        self.current
+            .as_ref()
+            .map_or(0, |(k, _, v)| k.mem_size() + v.mem_size())
     }
 }
 
 #[cfg(test)]
 mod tests {
+    use std::str::FromStr;
+
     use super::*;
 
     #[test]
@@ -34,7 +760,331 @@ mod tests {
         let mut state = AvgState::default();
         state.next(100.);
         state.next(400.);
-        state.next(100.);
-        assert_eq!(state.finalize().round() as i32, 200);
+        state.next(103.);
+        assert_eq!(state.finalize(), Some(201.));
+
+        assert_eq!(AvgState::default().finalize(), None);
+    }
+
+    #[test]
+    fn test_count_state() {
+        let mut state = CountState::default();
+        state.next();
+        state.next();
+        assert_eq!(state.finalize(), 2);
+
+        assert_eq!(CountState::default().finalize(), 0);
+    }
+
+    #[test]
+    fn test_sum_state() -> Result<()> {
+        let mut state = SumState::default();
+        state.next(NnSqlValue::Integer(1))?;
+        state.next(NnSqlValue::SmallInt(2))?;
+        assert_eq!(state.finalize(), Some(NnSqlValue::BigInt(3)));
+
+        let mut state = SumState::default();
+        state.next(NnSqlValue::Float(OrderedFloat(1.5)))?;
+        state.next(NnSqlValue::Integer(1))?;
+        assert_eq!(state.finalize(), Some(NnSqlValue::Float(OrderedFloat(2.5))));
+
+        let mut state = SumState::default();
+        state.next(NnSqlValue::BigInt(i64::MAX))?;
+        assert!(state.next(NnSqlValue::Integer(1)).is_err());
+        assert_eq!(state.finalize(), Some(NnSqlValue::BigInt(i64::MAX)));
+
+        let mut state = SumState::default();
+        assert!(state.next(NnSqlValue::Text("a".to_string())).is_err());
+
+        assert_eq!(SumState::default().finalize(), None);
+        Ok(())
+    }
+
+    #[test]
+    fn test_min_max_state() -> Result<()> {
+        let values = || {
+            vec![
+                NnSqlValue::Text("b".to_string()),
+                NnSqlValue::Text("a".to_string()),
+                NnSqlValue::Text("c".to_string()),
+            ]
+        };
+
+        let mut min = MinState::default();
+        let mut max = MaxState::default();
+        for v in values() {
+            min.next(v.clone())?;
+            max.next(v)?;
+        }
+        assert_eq!(min.finalize(), Some(NnSqlValue::Text("a".to_string())));
+        assert_eq!(max.finalize(), Some(NnSqlValue::Text("c".to_string())));
+
+        let ts = |s: &str| NnSqlValue::Timestamp(SpringTimestamp::from_str(s).unwrap());
+        let mut min = MinState::default();
+        let mut max = MaxState::default();
+        for v in [
+            ts("2020-01-01 00:00:01.000000000"),
+            ts("2020-01-01 00:00:00.000000000"),
+            ts("2020-01-01 00:00:02.000000000"),
+        ] {
+            min.next(v.clone())?;
+            max.next(v)?;
+        }
+        assert_eq!(min.finalize(), Some(ts("2020-01-01 00:00:00.000000000")));
+        assert_eq!(max.finalize(), Some(ts("2020-01-01 00:00:02.000000000")));
+
+        let mut min = MinState::default();
+        min.next(NnSqlValue::Integer(1))?;
+        assert!(min.next(NnSqlValue::Text("a".to_string())).is_err());
+
+        assert_eq!(MaxState::default().finalize(), None);
+        Ok(())
+    }
+
+    #[test]
+    fn test_variance_state() {
+        let mut state = VarianceState::default();
+        assert_eq!(state.var_pop(), None);
+
+        state.next(2.);
+        assert_eq!(state.var_pop(), Some(0.));
+        assert_eq!(state.var_samp(), None);
+
+        for v in [4., 4., 4., 5., 5., 7., 9.] {
+            state.next(v);
+        }
+        assert_eq!(state.var_pop(), Some(4.));
+        assert!((state.var_samp().unwrap() - 32. / 7.).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_percentile_approx_state() {
+        let mut state = PercentileApproxState::new(0.95);
+        assert_eq!(PercentileApproxState::new(0.5).finalize(), None);
+
+        // 1..=10000 in a scrambled order
+        for i in 0..10000u64 {
+            state.next(((i * 7919) % 10000 + 1) as f64);
+        }
+        assert!(
+            state.centroids.len() + state.buffer.len() < 1000,
+            "memory is bounded"
+        );
+
+        let p95 = state.finalize().unwrap();
+        assert!((p95 - 9500.).abs() < 9500. * 0.01, "p95 = {}", p95);
+
+        let mut state = PercentileApproxState::new(0.5);
+        state.next(42.);
+        assert_eq!(state.finalize(), Some(42.));
+    }
+
+    #[test]
+    fn test_approx_count_distinct_state() {
+        assert_eq!(ApproxCountDistinctState::default().finalize(), 0);
+
+        let mut state = ApproxCountDistinctState::default();
+        for i in 0..100000 {
+            state.next(&NnSqlValue::Integer(i % 20000));
+        }
+        state.next(&NnSqlValue::SmallInt(1));
+        state.next(&NnSqlValue::BigInt(2));
+
+        let n = state.finalize();
+        assert!((n - 20000).abs() < 20000 * 5 / 100, "n = {}", n);
+    }
+
+    #[test]
+    fn test_first_last_value_state() {
+        let ts = |s: &str| SpringTimestamp::from_str(s).unwrap();
+        let rows = [
+            ("2020-01-01 00:00:01.000000000", 1),
+            ("2020-01-01 00:00:00.000000000", 0),
+            ("2020-01-01 00:00:02.000000000", 2),
+            ("2020-01-01 00:00:02.000000000", 3),
+            ("2020-01-01 00:00:00.000000000", 4),
+        ];
+
+        let mut first = FirstValueState::default();
+        let mut last = LastValueState::default();
+        for (rowtime, v) in rows {
+            first.next(SqlValue::NotNull(NnSqlValue::Integer(v)), ts(rowtime));
+            last.next(SqlValue::NotNull(NnSqlValue::Integer(v)), ts(rowtime));
+        }
+        assert_eq!(first.finalize(), SqlValue::NotNull(NnSqlValue::Integer(0)));
+        assert_eq!(last.finalize(), SqlValue::NotNull(NnSqlValue::Integer(3)));
+
+        assert!(matches!(
+            FirstValueState::default().finalize(),
+            SqlValue::Null
+        ));
+        assert!(matches!(
+            LastValueState::default().finalize(),
+            SqlValue::Null
+        ));
+    }
+
+    #[test]
+    fn test_first_last_value_state_null() {
+        let ts = |s: &str| SpringTimestamp::from_str(s).unwrap();
+        let rows = [
+            ("2020-01-01 00:00:00.000000000", SqlValue::Null),
+            (
+                "2020-01-01 00:00:01.000000000",
+                SqlValue::NotNull(NnSqlValue::Integer(1)),
+            ),
+            ("2020-01-01 00:00:02.000000000", SqlValue::Null),
+        ];
+
+        let mut first = FirstValueState::default();
+        let mut last = LastValueState::default();
+        for (rowtime, v) in rows {
+            first.next(v.clone(), ts(rowtime));
+            last.next(v, ts(rowtime));
+        }
+        // NULL of the earliest / latest row is not skipped
+        assert!(matches!(first.finalize(), SqlValue::Null));
+        assert!(matches!(last.finalize(), SqlValue::Null));
+    }
+
+    #[test]
+    fn test_arg_max_min_state() -> Result<()> {
+        let rows = [
+            ("a", SqlValue::NotNull(NnSqlValue::Integer(20))),
+            ("b", SqlValue::Null),
+            ("c", SqlValue::NotNull(NnSqlValue::Integer(10))),
+            ("d", SqlValue::NotNull(NnSqlValue::Integer(30))),
+            ("e", SqlValue::NotNull(NnSqlValue::Integer(10))),
+            ("f", SqlValue::NotNull(NnSqlValue::Integer(30))),
+        ];
+
+        let text = |v: &str| SqlValue::NotNull(NnSqlValue::Text(v.to_string()));
+        let rowtime = SpringTimestamp::from_str("2020-01-01 00:00:00.000000000").unwrap();
+
+        let mut arg_max = ArgMaxState::default();
+        let mut arg_min = ArgMinState::default();
+        for (v, key) in rows {
+            arg_max.next(text(v), rowtime, key.clone())?;
+            arg_min.next(text(v), rowtime, key)?;
+        }
+        assert_eq!(arg_max.finalize(), text("d"));
+        assert_eq!(arg_min.finalize(), text("c"));
+
+        let mut arg_max = ArgMaxState::default();
+        arg_max.next(
+            SqlValue::NotNull(NnSqlValue::Integer(1)),
+            rowtime,
+            SqlValue::Null,
+        )?;
+        assert!(matches!(arg_max.finalize(), SqlValue::Null));
+
+        let mut arg_min = ArgMinState::default();
+        arg_min.next(
+            SqlValue::NotNull(NnSqlValue::Integer(1)),
+            rowtime,
+            SqlValue::NotNull(NnSqlValue::Integer(1)),
+        )?;
+        assert!(arg_min
+            .next(
+                SqlValue::NotNull(NnSqlValue::Integer(2)),
+                rowtime,
+                SqlValue::NotNull(NnSqlValue::Text("a".to_string()))
+            )
+            .is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_arg_max_min_state_tie() -> Result<()> {
+        let ts = |s: &str| SpringTimestamp::from_str(s).unwrap();
+        let key = SqlValue::NotNull(NnSqlValue::Integer(10));
+        let rows = [
+            ("2020-01-01 00:00:01.000000000", 1),
+            ("2020-01-01 00:00:00.000000000", 0),
+            ("2020-01-01 00:00:02.000000000", 2),
+            ("2020-01-01 00:00:00.000000000", 3),
+        ];
+
+        let mut arg_max = ArgMaxState::default();
+        let mut arg_min = ArgMinState::default();
+        for (rowtime, v) in rows {
+            let v = SqlValue::NotNull(NnSqlValue::Integer(v));
+            arg_max.next(v.clone(), ts(rowtime), key.clone())?;
+            arg_min.next(v, ts(rowtime), key.clone())?;
+        }
+        // the earliest ROWTIME wins regardless of arrival order, then the first-fed row
+        assert_eq!(
+            arg_max.finalize(),
+            SqlValue::NotNull(NnSqlValue::Integer(0))
+        );
+        assert_eq!(
+            arg_min.finalize(),
+            SqlValue::NotNull(NnSqlValue::Integer(0))
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_arg_max_min_state_null() -> Result<()> {
+        let key = |k: i32| SqlValue::NotNull(NnSqlValue::Integer(k));
+        let rows = [
+            (SqlValue::NotNull(NnSqlValue::Integer(1)), key(20)),
+            (SqlValue::Null, key(30)),
+            (SqlValue::Null, key(10)),
+            (SqlValue::NotNull(NnSqlValue::Integer(4)), SqlValue::Null),
+        ];
+
+        let rowtime = SpringTimestamp::from_str("2020-01-01 00:00:00.000000000").unwrap();
+
+        let mut arg_max = ArgMaxState::default();
+        let mut arg_min = ArgMinState::default();
+        for (v, key) in rows {
+            arg_max.next(v.clone(), rowtime, key.clone())?;
+            arg_min.next(v, rowtime, key)?;
+        }
+        // NULL of the row with the max / min key is not skipped, while the row with NULL key is
+        assert!(matches!(arg_max.finalize(), SqlValue::Null));
+        assert!(matches!(arg_min.finalize(), SqlValue::Null));
+        Ok(())
+    }
+
+    #[test]
+    fn test_aggr_state_null() -> Result<()> {
+        let rowtime = SpringTimestamp::from_str("2020-01-01 00:00:00.000000000").unwrap();
+        let values = [SqlValue::Null, SqlValue::NotNull(NnSqlValue::Integer(1))];
+
+        let mut sum = AggrState::new(AggregateFunctionParameter::Sum);
+        let mut count = AggrState::new(AggregateFunctionParameter::Count);
+        let mut first = AggrState::new(AggregateFunctionParameter::FirstValue);
+        for v in values {
+            sum.next(v.clone(), rowtime, SqlValue::Null)?;
+            count.next(v.clone(), rowtime, SqlValue::Null)?;
+            first.next(v, rowtime, SqlValue::Null)?;
+        }
+        assert_eq!(sum.finalize(), SqlValue::NotNull(NnSqlValue::BigInt(1)));
+        assert_eq!(count.finalize(), SqlValue::NotNull(NnSqlValue::BigInt(1)));
+        assert!(matches!(first.finalize(), SqlValue::Null));
+        Ok(())
+    }
+
+    #[test]
+    fn test_aggr_state_mem_size() {
+        let mut state = AggrState::new(AggregateFunctionParameter::PercentileApprox {
+            percentile: OrderedFloat(0.5),
+        });
+        let size0 = state.mem_size();
+        for i in 0..100 {
+            state
+                .next(
+                    SqlValue::NotNull(NnSqlValue::Integer(i)),
+                    SpringTimestamp::from_str("2020-01-01 00:00:00.000000000").unwrap(),
+                    SqlValue::Null,
+                )
+                .unwrap();
+        }
+        assert!(state.mem_size() > size0, "sketch size is reported");
+
+        let state = AggrState::new(AggregateFunctionParameter::ApproxCountDistinct);
+        assert!(state.mem_size() >= 1 << 12);
     }
 }
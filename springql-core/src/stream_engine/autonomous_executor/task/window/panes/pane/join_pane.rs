@@ -1,18 +1,21 @@
 // This file is part of https://github.com/SpringQL/SpringQL which is licensed under MIT OR Apache-2.0. See file LICENSE-MIT or LICENSE-APACHE for full license details.
 
-use std::str::FromStr;
+use std::{collections::HashMap, str::FromStr};
+
+use ordered_float::OrderedFloat;
 
 use crate::{
-    expr_resolver::ExprResolver,
+    api::error::Result,
+    expr_resolver::{ExprResolver, ValueExprLabel},
     mem_size::MemSize,
-    pipeline::{Field, JoinParameter, JoinType, WindowOperationParameter},
+    pipeline::{ColumnReference, Field, JoinParameter, WindowOperationParameter},
     stream_engine::{
         autonomous_executor::{
             performance_metrics::WindowInFlowByWindowTask,
             task::{tuple::Tuple, window::panes::pane::Pane},
         },
         time::SpringTimestamp,
-        RowTime, SqlValue,
+        NnSqlValue, RowTime, SqlValue,
     },
 };
 
@@ -74,62 +77,162 @@ impl Pane for JoinPane {
         _expr_resolver: &ExprResolver,
         tuple: &Tuple,
         dir: JoinDir,
-    ) -> WindowInFlowByWindowTask {
+    ) -> Result<WindowInFlowByWindowTask> {
         match dir {
             JoinDir::Left => self.left_tuples.push(tuple.clone()),
             JoinDir::Right => self.right_tuples.push(tuple.clone()),
         }
-        WindowInFlowByWindowTask::new(0, tuple.mem_size() as i64)
+        Ok(WindowInFlowByWindowTask::new(0, tuple.mem_size() as i64))
     }
 
     fn close(
         self,
         expr_resolver: &ExprResolver,
-    ) -> (Vec<Self::CloseOut>, WindowInFlowByWindowTask) {
-        match self.join_parameter.join_type {
-            JoinType::LeftOuter => self.left_outer_join(expr_resolver),
+    ) -> Result<(Vec<Self::CloseOut>, WindowInFlowByWindowTask)> {
+        self.join(expr_resolver)
+    }
+}
+
+/// Hash key of a value in equi-join conditions.
+///
+/// Numeric values are widened into f64 so that integers and floats equal in `=` (e.g. `1 = 1.0`) share a bucket.
+/// Values in a bucket may still differ (e.g. large integers losing precision), so ON condition is evaluated for each candidate.
+#[derive(Eq, PartialEq, Hash, Debug)]
+enum JoinKeyValue {
+    Numeric(OrderedFloat<f64>),
+    Other(NnSqlValue),
+}
+
+impl From<NnSqlValue> for JoinKeyValue {
+    fn from(v: NnSqlValue) -> Self {
+        match v {
+            NnSqlValue::SmallInt(_)
+            | NnSqlValue::Integer(_)
+            | NnSqlValue::BigInt(_)
+            | NnSqlValue::UnsignedInteger(_)
+            | NnSqlValue::UnsignedBigInt(_)
+            | NnSqlValue::Float(_) => {
+                Self::Numeric(OrderedFloat(v.to_f64().expect("numeric value")))
+            }
+            _ => Self::Other(v),
         }
     }
 }
 
 impl JoinPane {
-    fn left_outer_join(
-        self,
-        expr_resolver: &ExprResolver,
-    ) -> (Vec<Tuple>, WindowInFlowByWindowTask) {
+    /// Joins left and right tuples in this pane.
+    ///
+    /// Candidate right tuples for a left tuple are looked up by hash of `equi_keys` (or all right tuples if no equi-join key),
+    /// and then ON condition is evaluated for each candidate.
+    /// Unmatched left tuples come after their (non-existent) matches and unmatched right tuples come last.
+    ///
+    /// # Failures
+    ///
+    /// - `SpringError::Sql` when:
+    ///   - failed to evaluate equi-join keys or ON condition.
+    fn join(self, expr_resolver: &ExprResolver) -> Result<(Vec<Tuple>, WindowInFlowByWindowTask)> {
         let window_in_flow = self.calc_window_in_flow_on_close();
 
+        let join_type = self.join_parameter.join_type;
         let null_right = self.null_right_tuple();
 
-        // using Nested Loop Join.
+        let right_index = self.right_index(expr_resolver)?;
+        let mut right_matched = vec![false; self.right_tuples.len()];
+
         let mut res_tuples = Vec::new();
-        for left_tuple in self.left_tuples {
-            let mut joined_to_the_left = vec![];
+        for left_tuple in &self.left_tuples {
+            let candidates = match &right_index {
+                None => (0..self.right_tuples.len()).collect(),
+                Some(index) => self
+                    .eval_keys(
+                        expr_resolver,
+                        self.join_parameter.equi_keys.iter().map(|(l, _)| *l),
+                        left_tuple,
+                    )?
+                    .and_then(|keys| index.get(&keys))
+                    .cloned()
+                    .unwrap_or_default(),
+            };
 
-            for right_tuple in &self.right_tuples {
+            let mut left_matched = false;
+            for right_idx in candidates {
                 // TODO less clone. ExprResolver takes two tuples to resolve ColumnReference?
-                let joined_tuple = left_tuple.clone().join(right_tuple.clone());
+                let joined_tuple = left_tuple
+                    .clone()
+                    .join(self.right_tuples[right_idx].clone());
 
                 let on_bool = expr_resolver
-                    .eval_value_expr(self.join_parameter.on_expr, &joined_tuple)
-                    .expect("TODO Result")
-                    .to_bool()
-                    .expect("TODO Result");
+                    .eval_value_expr(self.join_parameter.on_expr, &joined_tuple)?
+                    .to_bool()?;
 
                 if on_bool {
-                    joined_to_the_left.push(joined_tuple);
+                    res_tuples.push(joined_tuple);
+                    left_matched = true;
+                    right_matched[right_idx] = true;
                 }
             }
 
-            if joined_to_the_left.is_empty() {
-                let joined_tuple = left_tuple.join(null_right.clone());
-                joined_to_the_left.push(joined_tuple);
+            if !left_matched && join_type.emits_unmatched_left() {
+                res_tuples.push(left_tuple.clone().join(null_right.clone()));
             }
+        }
+
+        if join_type.emits_unmatched_right() {
+            for (right_tuple, matched) in self.right_tuples.iter().zip(right_matched) {
+                if !matched {
+                    let null_left = self.null_left_tuple(right_tuple.rowtime());
+                    res_tuples.push(null_left.join(right_tuple.clone()));
+                }
+            }
+        }
 
-            res_tuples.extend(joined_to_the_left);
+        Ok((res_tuples, window_in_flow))
+    }
+
+    /// Hash index of right tuples by equi-join keys. None if no equi-join key (nested loop join).
+    ///
+    /// Right tuples with NULL in keys are not indexed (they never match).
+    fn right_index(
+        &self,
+        expr_resolver: &ExprResolver,
+    ) -> Result<Option<HashMap<Vec<JoinKeyValue>, Vec<usize>>>> {
+        if self.join_parameter.equi_keys.is_empty() {
+            return Ok(None);
         }
 
-        (res_tuples, window_in_flow)
+        let mut index: HashMap<Vec<JoinKeyValue>, Vec<usize>> = HashMap::new();
+        for (idx, right_tuple) in self.right_tuples.iter().enumerate() {
+            if let Some(keys) = self.eval_keys(
+                expr_resolver,
+                self.join_parameter.equi_keys.iter().map(|(_, r)| *r),
+                right_tuple,
+            )? {
+                index.entry(keys).or_default().push(idx);
+            }
+        }
+        Ok(Some(index))
+    }
+
+    /// None if any key is NULL.
+    ///
+    /// # Failures
+    ///
+    /// - `SpringError::Sql` when:
+    ///   - failed to evaluate a key on `tuple`.
+    fn eval_keys(
+        &self,
+        expr_resolver: &ExprResolver,
+        key_labels: impl Iterator<Item = ValueExprLabel>,
+        tuple: &Tuple,
+    ) -> Result<Option<Vec<JoinKeyValue>>> {
+        let mut keys = Vec::new();
+        for label in key_labels {
+            match expr_resolver.eval_value_expr(label, tuple)? {
+                SqlValue::Null => return Ok(None),
+                SqlValue::NotNull(v) => keys.push(JoinKeyValue::from(v)),
+            }
+        }
+        Ok(Some(keys))
     }
 
     fn calc_window_in_flow_on_close(&self) -> WindowInFlowByWindowTask {
@@ -148,9 +251,16 @@ impl JoinPane {
         let rowtime =
             RowTime::EventTime(SpringTimestamp::from_str("1970-01-01 00:00:00.000000000").unwrap());
 
-        let fields = self
-            .join_parameter
-            .right_colrefs
+        Self::null_tuple(rowtime, &self.join_parameter.right_colrefs)
+    }
+
+    /// Joined tuple uses left rowtime, so `rowtime` should be the right tuple's one.
+    fn null_left_tuple(&self, rowtime: RowTime) -> Tuple {
+        Self::null_tuple(rowtime, &self.join_parameter.left_colrefs)
+    }
+
+    fn null_tuple(rowtime: RowTime, colrefs: &[ColumnReference]) -> Tuple {
+        let fields = colrefs
             .iter()
             .map(|colref| Field::new(colref.clone(), SqlValue::Null))
             .collect();
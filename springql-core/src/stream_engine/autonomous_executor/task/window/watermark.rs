@@ -3,7 +3,7 @@
 use std::cmp::max;
 
 use crate::stream_engine::time::{
-    SpringDuration, SpringEventDuration, SpringTimestamp, MIN_TIMESTAMP,
+    SpringDuration, SpringEventDuration, SpringTimestamp, MAX_TIMESTAMP, MIN_TIMESTAMP,
 };
 
 /// A watermark is held by each window.
@@ -32,4 +32,9 @@ impl Watermark {
     pub fn update(&mut self, rowtime: SpringTimestamp) {
         self.max_rowtime = max(rowtime, self.max_rowtime);
     }
+
+    /// Moves the watermark to (almost) +infinity. Any tuple after this is too late.
+    pub fn end_of_stream(&mut self) {
+        self.max_rowtime = MAX_TIMESTAMP;
+    }
 }
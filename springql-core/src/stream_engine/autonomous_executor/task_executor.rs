@@ -10,22 +10,38 @@ pub use task_executor_lock::{
     TaskExecutionBarrierGuard, TaskExecutionLockGuard, TaskExecutorLock, TaskExecutorLockToken,
 };
 
-use std::sync::Arc;
+use std::{
+    collections::HashSet,
+    sync::Arc,
+    thread,
+    time::{Duration, Instant},
+};
+
+use anyhow::anyhow;
 
 use crate::{
-    api::{error::Result, SpringConfig},
+    api::{
+        error::{Result, SpringError},
+        SpringConfig,
+    },
     stream_engine::autonomous_executor::{
         args::{Coordinators, EventQueues, Locks},
+        event_queue::{Event, NonBlockingEventQueue},
         main_job_lock::MainJobBarrierGuard,
+        performance_metrics::MetricsUpdateByTaskExecutionOrPurge,
         pipeline_derivatives::PipelineDerivatives,
         repositories::Repositories,
+        task::{ProcessedRows, Task, TaskContext},
         task_executor::{
             generic_worker_pool::GenericWorkerPool, source_worker_pool::SourceWorkerPool,
         },
-        task_graph::TaskGraph,
+        task_graph::{QueueId, TaskGraph},
+        AutonomousExecutor,
     },
 };
 
+const DRAIN_POLL_INTERVAL: Duration = Duration::from_millis(10);
+
 /// Task executor executes task graph's dataflow by internal worker threads.
 /// Source tasks are scheduled by SourceScheduler and other tasks are scheduled by FlowEfficientScheduler (in Moderate state) or MemoryReducingScheduler (in Severe state).
 ///
@@ -33,9 +49,13 @@ use crate::{
 #[derive(Debug)]
 pub struct TaskExecutor {
     repos: Arc<Repositories>,
+    task_executor_lock: Arc<TaskExecutorLock>,
+    event_queue: Arc<NonBlockingEventQueue>,
 
-    _generic_worker_pool: GenericWorkerPool,
-    _source_worker_pool: SourceWorkerPool,
+    /// `None` after workers are stopped.
+    generic_worker_pool: Option<GenericWorkerPool>,
+    /// `None` after workers are stopped.
+    source_worker_pool: Option<SourceWorkerPool>,
 }
 
 impl TaskExecutor {
@@ -48,21 +68,23 @@ impl TaskExecutor {
     ) -> Self {
         Self {
             repos: repos.clone(),
+            task_executor_lock: locks.task_executor_lock.clone(),
+            event_queue: event_queues.non_blocking.clone(),
 
-            _generic_worker_pool: GenericWorkerPool::new(
+            generic_worker_pool: Some(GenericWorkerPool::new(
                 &config.worker,
                 locks.clone(),
                 event_queues.clone(),
                 coordinators.clone(),
                 repos.clone(),
-            ),
-            _source_worker_pool: SourceWorkerPool::new(
+            )),
+            source_worker_pool: Some(SourceWorkerPool::new(
                 &config.worker,
                 locks,
                 event_queues,
                 coordinators,
                 repos,
-            ),
+            )),
         }
     }
 
@@ -73,13 +95,30 @@ impl TaskExecutor {
         pipeline_derivatives: Arc<PipelineDerivatives>,
     ) -> Result<()> {
         let pipeline = pipeline_derivatives.pipeline();
+
+        self.repos.source_reader_repository().retain(
+            &pipeline
+                .all_sources()
+                .into_iter()
+                .map(|source_reader| source_reader.name().clone())
+                .collect(),
+        );
+        self.repos.sink_writer_repository().retain(
+            &pipeline
+                .all_sinks()
+                .into_iter()
+                .map(|sink_writer| sink_writer.name().clone())
+                .collect(),
+        );
+
         pipeline
             .all_sources()
             .into_iter()
             .try_for_each(|source_reader| {
+                let dest_stream = pipeline.get_stream(source_reader.dest_source_stream())?;
                 self.repos
                     .source_reader_repository()
-                    .register(source_reader)
+                    .register(source_reader, dest_stream)
             })?;
         pipeline
             .all_sinks()
@@ -92,14 +131,156 @@ impl TaskExecutor {
     }
 
     /// Stop all source tasks and executes pump tasks and sink tasks to finish all rows remaining in queues.
-    pub fn cleanup(&self, _lock_guard: &MainJobBarrierGuard, task_graph: &TaskGraph) {
+    ///
+    /// Rows buffered for pumps stopped in `prev_task_graph` are kept if the queues remain in `task_graph`.
+    pub fn cleanup(
+        &self,
+        _lock_guard: &MainJobBarrierGuard,
+        prev_task_graph: Option<&TaskGraph>,
+        task_graph: &TaskGraph,
+    ) {
         // TODO do not just remove rows in queues. Do the things in doc comment.
 
+        let mut buffered_row_queues = HashSet::new();
+        let mut buffered_window_queues = HashSet::new();
+        for queue_id in prev_task_graph
+            .map(TaskGraph::stopped_input_queues)
+            .unwrap_or_default()
+        {
+            match queue_id {
+                QueueId::Row(queue_id) => {
+                    let _ = buffered_row_queues.insert(queue_id);
+                }
+                QueueId::Window(queue_id) => {
+                    let _ = buffered_window_queues.insert(queue_id);
+                }
+            }
+        }
+
+        self.repos.row_queue_repository().reset(
+            task_graph.row_queues().into_iter().collect(),
+            &buffered_row_queues,
+        );
+        self.repos.window_queue_repository().reset(
+            task_graph.window_queues().into_iter().collect(),
+            &buffered_window_queues,
+        );
+    }
+
+    /// Stop source workers and close source readers so that no more rows come into the pipeline.
+    ///
+    /// Returns after all source workers finish their current task.
+    pub fn stop_source_workers(&mut self) {
+        self.source_worker_pool = None;
         self.repos
-            .row_queue_repository()
-            .reset(task_graph.row_queues().into_iter().collect());
-        self.repos
-            .window_queue_repository()
-            .reset(task_graph.window_queues().into_iter().collect());
+            .source_reader_repository()
+            .retain(&HashSet::new());
+    }
+
+    /// Stop generic workers. Rows remaining in queues and windows are no longer processed.
+    ///
+    /// Sink writers are kept open so that rows already in in-memory queues can be popped.
+    pub fn stop_generic_workers(&mut self) {
+        self.generic_worker_pool = None;
+    }
+
+    /// Executes pump tasks and sink tasks until all rows remaining in queues and windows reach sinks.
+    ///
+    /// After all rows in queues are processed, open panes in windows are closed even if the watermark has not reached their end.
+    /// Rows buffered for stopped pumps are not processed and left in their queues.
+    /// Source workers must be stopped in advance by `stop_source_workers()`.
+    ///
+    /// # Failures
+    ///
+    /// - `SpringError::Unavailable` when:
+    ///   - rows still remain in queues or windows at `deadline`.
+    pub fn drain(
+        &self,
+        pipeline_derivatives: &Arc<PipelineDerivatives>,
+        deadline: Instant,
+    ) -> Result<()> {
+        loop {
+            {
+                // no task is running while the barrier is held
+                let _lock = self.task_executor_lock.task_execution_barrier();
+
+                if self.started_queues_empty(pipeline_derivatives.task_graph())
+                    && self.flush_windows(pipeline_derivatives)?.is_empty()
+                {
+                    log::info!("[TaskExecutor] all rows are drained");
+                    return Ok(());
+                }
+            }
+
+            if Instant::now() >= deadline {
+                return Err(SpringError::Unavailable {
+                    resource: "pipeline drain".to_string(),
+                    source: anyhow!("rows still remain in queues or windows at the deadline"),
+                });
+            }
+            thread::sleep(DRAIN_POLL_INTERVAL);
+        }
+    }
+
+    /// `true` if no row is waiting in any queue of started tasks.
+    fn started_queues_empty(&self, task_graph: &TaskGraph) -> bool {
+        let is_started =
+            |queue_id: QueueId| !task_graph.is_stopped(&task_graph.downstream_task(&queue_id));
+
+        task_graph.row_queues().into_iter().all(|queue_id| {
+            !is_started(queue_id.clone().into())
+                || self.repos.row_queue_repository().get(&queue_id).is_empty()
+        }) && task_graph.window_queues().into_iter().all(|queue_id| {
+            !is_started(queue_id.clone().into())
+                || self
+                    .repos
+                    .window_queue_repository()
+                    .get(&queue_id)
+                    .is_empty()
+        })
+    }
+
+    /// Flush windows of all started pump tasks into their downstream queues.
+    ///
+    /// Rows from a flushed window may reach downstream windows, which are flushed in the next call.
+    /// Metrics are published and errors are handled in the same way as task execution by workers.
+    fn flush_windows(
+        &self,
+        pipeline_derivatives: &Arc<PipelineDerivatives>,
+    ) -> Result<ProcessedRows> {
+        let task_graph = pipeline_derivatives.task_graph();
+        task_graph
+            .tasks()
+            .into_iter()
+            .filter(|task_id| task_id.is_window_task() && !task_graph.is_stopped(task_id))
+            .try_fold(ProcessedRows::default(), |acc, task_id| {
+                let task = pipeline_derivatives.get_task(&task_id)?;
+                if let Task::Pump(pump_task) = task.as_ref() {
+                    let context = TaskContext::new(
+                        task_id.clone(),
+                        pipeline_derivatives.clone(),
+                        self.repos.clone(),
+                    );
+                    let processed_rows = pump_task
+                        .flush(&context)
+                        .map(|run_result| {
+                            self.event_queue.publish(Event::IncrementalUpdateMetrics {
+                                metrics_update_by_task_execution_or_purge: Arc::new(
+                                    MetricsUpdateByTaskExecutionOrPurge::TaskExecution(
+                                        run_result.metrics,
+                                    ),
+                                ),
+                            });
+                            run_result.processed_rows
+                        })
+                        .unwrap_or_else(|e| {
+                            AutonomousExecutor::handle_error(e);
+                            ProcessedRows::default()
+                        });
+                    Ok(acc + processed_rows)
+                } else {
+                    Ok(acc)
+                }
+            })
     }
 }
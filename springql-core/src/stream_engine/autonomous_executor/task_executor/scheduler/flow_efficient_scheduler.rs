@@ -69,6 +69,7 @@ impl Generator {
         graph
             .downstream_tasks(&self.task_id)
             .into_iter()
+            .filter(|task_id| !graph.is_stopped(task_id))
             .map(|collector_task_id| Collector {
                 task_id: collector_task_id,
             })
@@ -155,7 +156,10 @@ impl FlowEfficientScheduler {
 
     fn collector_to_stoppers_dfs(&self, collector: &Collector, graph: &TaskGraph) -> Vec<TaskId> {
         fn to_stoppers_dfs(current_task: &TaskId, graph: &TaskGraph) -> Vec<TaskId> {
-            if current_task.is_window_task() {
+            if graph.is_stopped(current_task) {
+                // stopped task is not executed and rows do not flow beyond it
+                vec![]
+            } else if current_task.is_window_task() {
                 // window task is a stopper
                 vec![current_task.clone()]
             } else {
@@ -182,6 +186,7 @@ impl FlowEfficientScheduler {
             .iter()
             .cloned()
             .chain(graph.window_tasks())
+            .filter(|task_id| !graph.is_stopped(task_id))
             .map(|task_id| Generator { task_id })
             .collect()
     }
@@ -79,6 +79,7 @@ impl MemoryReducingScheduler {
         let tasks = tasks.difference(&source_tasks); // source tasks are scheduled by SourceScheduler
 
         let mut profiles = tasks
+            .filter(|task| !graph.is_stopped(task))
             .map(|task| self.task_profile(task, graph, metrics))
             .collect::<Vec<_>>();
         profiles.sort_by(|a, b| a.loss.partial_cmp(&b.loss).expect("loss cannot be NaN"));
@@ -17,12 +17,12 @@ mod edge_ref;
 pub use queue_id::{QueueId, RowQueueId, WindowQueueId};
 pub use task_id::TaskId;
 
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 
 use petgraph::graph::{DiGraph, NodeIndex};
 
 use crate::{
-    pipeline::{Edge, Pipeline, PipelineVersion, StreamName},
+    pipeline::{Edge, Pipeline, PipelineVersion, PumpState, StreamName},
     stream_engine::autonomous_executor::task_graph::edge_ref::MyEdgeRef,
 };
 
@@ -40,6 +40,9 @@ pub struct TaskGraph {
     g: DiGraph<TaskId, QueueIdWithUpstream>,
     task_id_node_map: HashMap<TaskId, NodeIndex>,
     queue_id_edge_map: HashMap<QueueId, MyEdgeRef>,
+
+    /// Pump tasks created with `STOPPED` or stopped by `ALTER PUMP ... STOP`. They stay in the graph but are not scheduled.
+    stopped_tasks: HashSet<TaskId>,
 }
 
 impl TaskGraph {
@@ -49,6 +52,7 @@ impl TaskGraph {
             g: DiGraph::default(),
             task_id_node_map: HashMap::default(),
             queue_id_edge_map: HashMap::default(),
+            stopped_tasks: HashSet::default(),
         }
     }
 
@@ -116,6 +120,18 @@ impl TaskGraph {
             .collect()
     }
 
+    pub fn is_stopped(&self, task_id: &TaskId) -> bool {
+        self.stopped_tasks.contains(task_id)
+    }
+
+    /// Input queues of stopped tasks, where rows are buffered until the tasks are started.
+    pub fn stopped_input_queues(&self) -> Vec<QueueId> {
+        self.stopped_tasks
+            .iter()
+            .flat_map(|task_id| self.input_queues(task_id))
+            .collect()
+    }
+
     pub fn tasks(&self) -> Vec<TaskId> {
         self.g.node_weights().cloned().collect()
     }
@@ -167,6 +183,10 @@ impl TaskGraph {
         let _ = self.task_id_node_map.insert(task_id, i);
     }
 
+    pub fn stop_task(&mut self, task_id: TaskId) {
+        let _ = self.stopped_tasks.insert(task_id);
+    }
+
     /// # Panics
     ///
     /// `source` or `target` task is not added in the graph.
@@ -210,7 +230,13 @@ impl From<&Pipeline> for TaskGraph {
         pipeline_petgraph.edge_weights().for_each(|edge| {
             let task_id = TaskId::from(edge);
             // duplicate task id on JOIN pump task (but it's ok)
-            task_graph.add_task(task_id);
+            task_graph.add_task(task_id.clone());
+
+            if let Edge::Pump { pump_model, .. } = edge {
+                if pump_model.state() == PumpState::Stopped {
+                    task_graph.stop_task(task_id);
+                }
+            }
         });
 
         // Add all queues.
@@ -6,11 +6,13 @@ use springql_foreign_service::source::{ForeignSource, ForeignSourceInput};
 
 use crate::{
     api::SpringSourceReaderConfig,
-    pipeline::{ColumnName, OptionsBuilder, StreamModel, StreamName, StreamShape},
+    pipeline::{
+        ColumnName, OptionsBuilder, SourceReaderType, StreamModel, StreamName, StreamShape,
+    },
     stream_engine::{
         autonomous_executor::{
             row::{ColumnValues, NnSqlValue, SqlValue, StreamColumns, StreamRow},
-            task::{NetClientSourceReader, SourceReader, Tuple},
+            task::{NetClientSourceReader, SourceReader, SourceReaderContext, Tuple},
         },
         time::SpringTimestamp,
     },
@@ -47,7 +49,12 @@ impl NetClientSourceReader {
             .build();
 
         source.start(input);
-        NetClientSourceReader::start(&options, &SpringSourceReaderConfig::fx_default()).unwrap()
+        NetClientSourceReader::start(
+            &options,
+            &SpringSourceReaderConfig::fx_default(),
+            &SourceReaderContext::fx_trade(SourceReaderType::NetClient),
+        )
+        .unwrap()
     }
 }
 
@@ -1,12 +1,13 @@
 // This file is part of https://github.com/SpringQL/SpringQL which is licensed under MIT OR Apache-2.0. See file LICENSE-MIT or LICENSE-APACHE for full license details.
 
-use std::net::IpAddr;
+use std::{net::IpAddr, sync::Arc};
 
 use serde_json::json;
 
 use crate::{
     pipeline::{
-        PipelineVersion, PumpInputType, SinkWriterModel, SourceReaderModel, StreamModel, StreamName,
+        PipelineVersion, PumpInputType, SinkWriterModel, SourceReaderModel, SourceReaderType,
+        StreamModel, StreamName,
     },
     stream_engine::{
         autonomous_executor::performance_metrics::{
@@ -21,12 +22,14 @@ use crate::{
                 OutQueueMetricsUpdateByTask, TaskMetricsUpdateByTask, WindowInFlowByWindowTask,
             },
             row::{JsonObject, JsonSourceRow, SchemalessRow, StreamColumns, StreamRow},
-            task::Tuple,
+            task::{SinkWriterContext, SourceReaderContext, Tuple},
             task_graph::{
                 QueueId, QueueIdWithUpstream, RowQueueId, TaskGraph, TaskId, WindowQueueId,
             },
         },
         command::AlterPipelineCommand,
+        in_memory_queue_repository::InMemoryQueueRepository,
+        sink_callback_repository::SinkCallbackRepository,
         time::{SpringDuration, WallClockDuration},
     },
 };
@@ -230,6 +233,26 @@ impl AlterPipelineCommand {
     }
 }
 
+impl SourceReaderContext {
+    /// Context of a source reader feeding rows into the trade stream.
+    pub fn fx_trade(source_reader_type: SourceReaderType) -> Self {
+        Self::new(
+            source_reader_type,
+            Arc::new(StreamModel::fx_trade()),
+            Arc::new(InMemoryQueueRepository::default()),
+        )
+    }
+}
+
+impl SinkWriterContext {
+    pub fn fx_default() -> Self {
+        Self::new(
+            Arc::new(InMemoryQueueRepository::default()),
+            Arc::new(SinkCallbackRepository::default()),
+        )
+    }
+}
+
 impl TaskGraph {
     pub fn fx_split_join() -> Self {
         let mut g = TaskGraph::new(PipelineVersion::new());
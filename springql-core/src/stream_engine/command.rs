@@ -8,7 +8,7 @@ pub use crate::stream_engine::command::alter_pipeline_command::AlterPipelineComm
 pub use insert_plan::InsertPlan;
 pub use query_plan::{
     CollectOp, GroupAggregateWindowOp, JoinOp, JoinWindowOp, LowerOps, ProjectionOp, QueryPlan,
-    UpperOps,
+    SelectionOp, UpperOps,
 };
 
 #[derive(Clone, PartialEq, Debug)]
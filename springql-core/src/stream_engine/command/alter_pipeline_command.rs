@@ -1,8 +1,10 @@
 // This file is part of https://github.com/SpringQL/SpringQL which is licensed under MIT OR Apache-2.0. See file LICENSE-MIT or LICENSE-APACHE for full license details.
 
-use crate::pipeline::{PumpModel, SinkWriterModel, SourceReaderModel, StreamModel};
+use crate::pipeline::{
+    PumpModel, PumpName, PumpState, SinkWriterModel, SinkWriterName, SourceReaderModel,
+    SourceReaderName, StreamModel, StreamName,
+};
 
-#[allow(clippy::enum_variant_names)]
 #[derive(Clone, PartialEq, Debug)]
 pub enum AlterPipelineCommand {
     CreateSourceStream(StreamModel),
@@ -11,4 +13,19 @@ pub enum AlterPipelineCommand {
     CreateSinkStream(StreamModel),
     CreateSinkWriter(SinkWriterModel),
     CreatePump(Box<PumpModel>),
+
+    /// With `cascade`, pumps, source readers and sink writers attached to the stream are dropped together.
+    DropStream {
+        stream_name: StreamName,
+        cascade: bool,
+    },
+    DropSourceReader(SourceReaderName),
+    DropSinkWriter(SinkWriterName),
+    DropPump(PumpName),
+
+    /// `ALTER PUMP ... START` or `ALTER PUMP ... STOP`. All the pumps are switched in a pipeline update.
+    AlterPump {
+        pump_names: Vec<PumpName>,
+        state: PumpState,
+    },
 }
@@ -3,7 +3,8 @@
 mod query_plan_operation;
 
 pub use query_plan_operation::{
-    CollectOp, GroupAggregateWindowOp, JoinOp, JoinWindowOp, LowerOps, ProjectionOp, UpperOps,
+    CollectOp, GroupAggregateWindowOp, JoinOp, JoinWindowOp, LowerOps, ProjectionOp, SelectionOp,
+    UpperOps,
 };
 
 use crate::{
@@ -31,11 +32,6 @@ impl QueryPlan {
     }
 
     pub fn upstreams(&self) -> Vec<&StreamName> {
-        match &self.lower_ops.join {
-            JoinOp::Collect(collect) => vec![&collect.stream],
-            JoinOp::JoinWindow(JoinWindowOp { left, right, .. }) => {
-                vec![&left.stream, &right.stream]
-            }
-        }
+        self.lower_ops.join.upstreams()
     }
 }
@@ -1,7 +1,7 @@
 // This file is part of https://github.com/SpringQL/SpringQL which is licensed under MIT OR Apache-2.0. See file LICENSE-MIT or LICENSE-APACHE for full license details.
 
 use crate::{
-    expr_resolver::ExprLabel,
+    expr_resolver::{ExprLabel, ValueExprLabel},
     pipeline::{JoinParameter, StreamName, WindowOperationParameter, WindowParameter},
 };
 
@@ -19,6 +19,7 @@ impl UpperOps {
 #[derive(Clone, PartialEq, Debug)]
 pub struct LowerOps {
     pub join: JoinOp,
+    pub selection: Option<SelectionOp>,
 }
 impl LowerOps {
     pub fn has_window(&self) -> bool {
@@ -31,6 +32,12 @@ pub struct ProjectionOp {
     pub expr_labels: Vec<ExprLabel>,
 }
 
+/// WHERE clause. Applied to tuples from collect (or join) before they reach upper operations.
+#[derive(Clone, PartialEq, Debug)]
+pub struct SelectionOp {
+    pub where_expr: ValueExprLabel,
+}
+
 #[derive(Clone, PartialEq, Debug)]
 pub struct GroupAggregateWindowOp {
     pub window_param: WindowParameter,
@@ -42,16 +49,29 @@ pub struct CollectOp {
     pub stream: StreamName,
 }
 
-/// TODO recursive join
+/// Left-deep tree of JOINs (`(a JOIN b) JOIN c`).
 #[derive(Clone, PartialEq, Debug)]
 pub enum JoinOp {
     Collect(CollectOp),
     JoinWindow(JoinWindowOp),
 }
+impl JoinOp {
+    /// Streams to collect from, in left-to-right order.
+    pub fn upstreams(&self) -> Vec<&StreamName> {
+        match self {
+            JoinOp::Collect(collect) => vec![&collect.stream],
+            JoinOp::JoinWindow(JoinWindowOp { left, right, .. }) => {
+                let mut upstreams = left.upstreams();
+                upstreams.push(&right.stream);
+                upstreams
+            }
+        }
+    }
+}
 
 #[derive(Clone, PartialEq, Debug)]
 pub struct JoinWindowOp {
-    pub left: CollectOp,
+    pub left: Box<JoinOp>,
     pub right: CollectOp,
 
     pub window_param: WindowParameter,
@@ -2,9 +2,11 @@
 
 mod in_memory_queue;
 
+pub use in_memory_queue::InMemoryQueue;
+
 use std::{
-    collections::HashMap,
-    sync::{Arc, Mutex, MutexGuard},
+    collections::{hash_map::Entry, HashMap},
+    sync::{Arc, Mutex, MutexGuard, Weak},
 };
 
 use anyhow::anyhow;
@@ -13,27 +15,24 @@ use once_cell::sync::Lazy;
 use crate::{
     api::error::{Result, SpringError},
     pipeline::QueueName,
-    stream_engine::in_memory_queue_repository::in_memory_queue::InMemoryQueue,
 };
 
-static INSTANCE: Lazy<Arc<InMemoryQueueRepository>> =
-    Lazy::new(|| Arc::new(InMemoryQueueRepository::empty()));
+/// Shared queues alive in this process. A shared queue is freed when no repository holds it.
+///
+/// Also locked while a shared queue is acquired or released, so that a queue being released by the last holder is never revived.
+static SHARED_QUEUES: Lazy<Mutex<HashMap<QueueName, Weak<InMemoryQueue>>>> =
+    Lazy::new(|| Mutex::new(HashMap::new()));
 
-/// Singleton
-#[derive(Debug)]
+/// In-memory queues visible from a stream engine.
+///
+/// Each stream engine has its own repository, so pipelines using the same queue name do not interfere.
+/// Queues created with `shared` flag are also visible from other stream engines in the process, to connect pipelines.
+#[derive(Debug, Default)]
 pub struct InMemoryQueueRepository(
     Mutex<HashMap<QueueName, Arc<InMemoryQueue>>>, // TODO faster (lock-free?) queue
 );
 
 impl InMemoryQueueRepository {
-    fn empty() -> Self {
-        Self(Mutex::new(HashMap::new()))
-    }
-
-    pub fn instance() -> Arc<Self> {
-        INSTANCE.clone()
-    }
-
     /// # Failure
     ///
     /// - `SpringError::Unavailable` when:
@@ -48,28 +47,131 @@ impl InMemoryQueueRepository {
             })
     }
 
+    /// Create a queue. A `shared` queue is the same instance as the shared queue with the same name in other repositories, if any.
+    ///
+    /// A shared queue removed from all the repositories is not reused even if its handles (e.g. a sink stream) are still alive. A new queue is created instead.
+    ///
     /// # Failure
     ///
     /// - `SpringError::Sql` when:
-    ///   - queue named `queue_name` already exists.
-    pub fn create(&self, queue_name: QueueName) -> Result<()> {
-        let r = self
-            .lock()
-            .insert(queue_name.clone(), Arc::new(InMemoryQueue::default()));
-
-        if r.is_none() {
-            Ok(())
-        } else {
-            Err(SpringError::Sql(anyhow!(
+    ///   - queue named `queue_name` already exists in this repository.
+    pub fn create(&self, queue_name: QueueName, shared: bool) -> Result<Arc<InMemoryQueue>> {
+        match self.lock().entry(queue_name) {
+            Entry::Occupied(entry) => Err(SpringError::Sql(anyhow!(
                 "queue ({}) already exists",
-                queue_name
-            )))
+                entry.key()
+            ))),
+            Entry::Vacant(entry) => {
+                let queue = if shared {
+                    Self::acquire_shared_queue(entry.key())
+                } else {
+                    let queue = Arc::new(InMemoryQueue::default());
+                    queue.acquire();
+                    queue
+                };
+                Ok(entry.insert(queue).clone())
+            }
         }
     }
 
+    /// Do nothing if queue named `queue_name` does not exist.
+    ///
+    /// Pops waiting on the queue return when no repository holds it any more.
+    pub fn remove(&self, queue_name: &QueueName) {
+        if let Some(queue) = self.lock().remove(queue_name) {
+            let _shared_queues = Self::lock_shared_queues();
+            queue.release();
+        }
+    }
+
+    fn acquire_shared_queue(queue_name: &QueueName) -> Arc<InMemoryQueue> {
+        let mut shared_queues = Self::lock_shared_queues();
+        shared_queues.retain(|_, queue| queue.strong_count() > 0);
+
+        let queue = match shared_queues.get(queue_name).and_then(Weak::upgrade) {
+            Some(queue) if !queue.is_removed() => queue,
+            _ => {
+                let queue = Arc::new(InMemoryQueue::default());
+                let _ = shared_queues.insert(queue_name.clone(), Arc::downgrade(&queue));
+                queue
+            }
+        };
+        queue.acquire();
+        queue
+    }
+
+    fn lock_shared_queues() -> MutexGuard<'static, HashMap<QueueName, Weak<InMemoryQueue>>> {
+        SHARED_QUEUES
+            .lock()
+            .expect("another thread sharing the shared in-memory queues got panic")
+    }
+
     fn lock(&self) -> MutexGuard<'_, HashMap<QueueName, Arc<InMemoryQueue>>> {
         self.0
             .lock()
             .expect("another thread sharing the same InMemoryQueueRepository internal got panic")
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::stream_engine::autonomous_executor::SchemalessRow;
+
+    #[test]
+    fn test_queues_are_isolated_per_repository() {
+        let repo1 = InMemoryQueueRepository::default();
+        let repo2 = InMemoryQueueRepository::default();
+        let name = QueueName::new("q_isolated".to_string());
+
+        let q1 = repo1.create(name.clone(), false).unwrap();
+        let q2 = repo2.create(name.clone(), false).unwrap();
+        assert!(!Arc::ptr_eq(&q1, &q2));
+
+        assert!(repo1.create(name, false).is_err());
+    }
+
+    #[test]
+    fn test_shared_queue() {
+        let repo1 = InMemoryQueueRepository::default();
+        let repo2 = InMemoryQueueRepository::default();
+        let name = QueueName::new("q_shared".to_string());
+
+        let q1 = repo1.create(name.clone(), true).unwrap();
+        let q2 = repo2.create(name.clone(), true).unwrap();
+        assert!(Arc::ptr_eq(&q1, &q2));
+        q1.end();
+        assert!(q2.is_ended());
+
+        // freed after all the repositories remove it
+        drop((q1, q2));
+        repo1.remove(&name);
+        repo2.remove(&name);
+        let q3 = repo1.create(name, true).unwrap();
+        assert!(!q3.is_ended());
+    }
+
+    #[test]
+    fn test_shared_queue_recreated_while_handle_alive() {
+        let repo1 = InMemoryQueueRepository::default();
+        let repo2 = InMemoryQueueRepository::default();
+        let name = QueueName::new("q_shared_recreated".to_string());
+
+        // e.g. held by a sink stream or a blocking pop
+        let old = repo1.create(name.clone(), true).unwrap();
+        repo1.remove(&name);
+        assert!(old.is_closed());
+
+        let new = repo1.create(name.clone(), true).unwrap();
+        assert!(!Arc::ptr_eq(&old, &new));
+        assert!(!new.is_closed());
+
+        new.push(SchemalessRow::fx_trade_oracle());
+        assert!(new.pop_non_blocking().is_some());
+
+        // other repositories share the new queue
+        let new2 = repo2.create(name, true).unwrap();
+        assert!(Arc::ptr_eq(&new, &new2));
+    }
+}
@@ -2,15 +2,36 @@
 
 use std::{
     collections::VecDeque,
-    sync::{Mutex, MutexGuard},
+    sync::{
+        atomic::{AtomicBool, AtomicUsize, Ordering},
+        Condvar, Mutex, MutexGuard,
+    },
+    time::Duration,
 };
 
+#[cfg(feature = "async")]
+use std::task::{Context, Poll, Waker};
+
 use crate::stream_engine::autonomous_executor::SchemalessRow;
 
 #[derive(Debug, Default)]
-pub struct InMemoryQueue(
-    Mutex<VecDeque<SchemalessRow>>, // TODO faster (lock-free?) queue
-);
+pub struct InMemoryQueue {
+    rows: Mutex<VecDeque<SchemalessRow>>, // TODO faster (lock-free?) queue
+
+    /// Notified on every push (and on close) to wake up blocking pops.
+    pushed: Condvar,
+
+    /// Woken up on every push (and on close) to resume async pops.
+    #[cfg(feature = "async")]
+    wakers: Mutex<Vec<Waker>>,
+
+    ended: AtomicBool,
+
+    /// Number of repositories holding this queue.
+    n_holders: AtomicUsize,
+    /// `true` after the last repository holding this queue removes it.
+    removed: AtomicBool,
+}
 
 impl InMemoryQueue {
     /// # Returns
@@ -21,13 +42,207 @@ impl InMemoryQueue {
         self.lock().pop_front()
     }
 
+    /// Blocks until a row is pushed or the queue is closed.
+    ///
+    /// # Returns
+    ///
+    /// - `Some` when a row is popped.
+    /// - `None` when the queue is empty and closed (see [is_closed()](Self::is_closed)).
+    pub fn pop(&self) -> Option<SchemalessRow> {
+        let mut rows = self
+            .pushed
+            .wait_while(self.lock(), |rows| rows.is_empty() && !self.is_closed())
+            .expect("another thread sharing the same InMemoryQueue internal got panic");
+        rows.pop_front()
+    }
+
+    /// Blocks until a row is pushed, the queue is closed or `timeout` elapses.
+    ///
+    /// # Returns
+    ///
+    /// - `Some` when a row is popped.
+    /// - `None` when no row is pushed within `timeout`, or the queue is empty and closed.
+    pub fn pop_timeout(&self, timeout: Duration) -> Option<SchemalessRow> {
+        let (mut rows, _) = self
+            .pushed
+            .wait_timeout_while(self.lock(), timeout, |rows| {
+                rows.is_empty() && !self.is_closed()
+            })
+            .expect("another thread sharing the same InMemoryQueue internal got panic");
+        rows.pop_front()
+    }
+
+    /// Pops a row if any, otherwise `cx` is woken up on the next push or close.
+    ///
+    /// # Returns
+    ///
+    /// - `Poll::Ready(Some)` when a row is popped.
+    /// - `Poll::Ready(None)` when the queue is empty and closed (see [is_closed()](Self::is_closed)).
+    #[cfg(feature = "async")]
+    pub fn poll_pop(&self, cx: &mut Context<'_>) -> Poll<Option<SchemalessRow>> {
+        let mut rows = self.lock();
+        if let Some(row) = rows.pop_front() {
+            Poll::Ready(Some(row))
+        } else if self.is_closed() {
+            Poll::Ready(None)
+        } else {
+            // registered while holding `rows` so that a concurrent push cannot be missed
+            let mut wakers = self.lock_wakers();
+            if !wakers.iter().any(|w| w.will_wake(cx.waker())) {
+                wakers.push(cx.waker().clone());
+            }
+            Poll::Pending
+        }
+    }
+
     pub fn push(&self, row: SchemalessRow) {
-        self.lock().push_back(row)
+        self.lock().push_back(row);
+        self.pushed.notify_one();
+        self.wake_async_pops();
+    }
+
+    /// Mark that no more row is pushed. Pops waiting on the empty queue return.
+    pub fn end(&self) {
+        self.close(&self.ended)
+    }
+
+    /// Called by a repository when it starts holding this queue.
+    pub(super) fn acquire(&self) {
+        let _ = self.n_holders.fetch_add(1, Ordering::AcqRel);
+    }
+
+    /// Called by a repository when it removes this queue.
+    /// When no repository holds this queue any more, pops waiting on the empty queue return.
+    pub(super) fn release(&self) {
+        if self.n_holders.fetch_sub(1, Ordering::AcqRel) == 1 {
+            self.close(&self.removed)
+        }
+    }
+
+    /// `true` after `end()` is called or the queue is removed from all the repositories.
+    /// Rows left in a closed queue can still be popped.
+    pub fn is_closed(&self) -> bool {
+        self.is_ended() || self.is_removed()
+    }
+
+    /// `true` after the queue is removed from all the repositories. A removed queue is never held by a repository again.
+    pub(super) fn is_removed(&self) -> bool {
+        self.removed.load(Ordering::Acquire)
+    }
+
+    /// `true` after `end()` is called and all the rows are popped.
+    pub fn is_end_of_stream(&self) -> bool {
+        // check the flag first: a row pushed before end() is visible after the flag is seen
+        self.is_ended() && self.lock().is_empty()
+    }
+
+    pub fn is_ended(&self) -> bool {
+        self.ended.load(Ordering::Acquire)
+    }
+
+    fn close(&self, flag: &AtomicBool) {
+        {
+            // set while holding `rows` so that a pop checking the flag cannot miss the notification
+            let _rows = self.lock();
+            flag.store(true, Ordering::Release);
+        }
+        self.pushed.notify_all();
+        self.wake_async_pops();
+    }
+
+    fn wake_async_pops(&self) {
+        #[cfg(feature = "async")]
+        {
+            // woken up outside the lock since a waker may poll immediately
+            let wakers = std::mem::take(&mut *self.lock_wakers());
+            wakers.into_iter().for_each(Waker::wake);
+        }
     }
 
     fn lock(&self) -> MutexGuard<'_, VecDeque<SchemalessRow>> {
-        self.0
+        self.rows
+            .lock()
+            .expect("another thread sharing the same InMemoryQueue internal got panic")
+    }
+
+    #[cfg(feature = "async")]
+    fn lock_wakers(&self) -> MutexGuard<'_, Vec<Waker>> {
+        self.wakers
             .lock()
             .expect("another thread sharing the same InMemoryQueue internal got panic")
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::{sync::Arc, thread, time::Instant};
+
+    use super::*;
+
+    #[test]
+    fn test_pop_timeout_empty() {
+        let q = InMemoryQueue::default();
+
+        let timeout = Duration::from_millis(100);
+        let started = Instant::now();
+        assert!(q.pop_timeout(timeout).is_none());
+        assert!(started.elapsed() >= timeout);
+    }
+
+    #[test]
+    fn test_pop_woken_up_by_push() {
+        let q = Arc::new(InMemoryQueue::default());
+
+        let popper = {
+            let q = q.clone();
+            thread::spawn(move || (q.pop(), q.pop_timeout(Duration::from_secs(10))))
+        };
+
+        thread::sleep(Duration::from_millis(100));
+        q.push(SchemalessRow::fx_trade_oracle());
+        q.push(SchemalessRow::fx_trade_ibm());
+
+        let (row1, row2) = popper.join().unwrap();
+        assert_eq!(row1, Some(SchemalessRow::fx_trade_oracle()));
+        assert_eq!(row2, Some(SchemalessRow::fx_trade_ibm()));
+        assert!(q.pop_non_blocking().is_none());
+    }
+
+    #[test]
+    fn test_pop_woken_up_by_end() {
+        let q = Arc::new(InMemoryQueue::default());
+        q.push(SchemalessRow::fx_trade_oracle());
+
+        let popper = {
+            let q = q.clone();
+            thread::spawn(move || (q.pop(), q.pop()))
+        };
+
+        thread::sleep(Duration::from_millis(100));
+        q.end();
+
+        let (row1, row2) = popper.join().unwrap();
+        assert_eq!(row1, Some(SchemalessRow::fx_trade_oracle()));
+        assert!(row2.is_none());
+        assert!(q.pop_timeout(Duration::from_secs(10)).is_none());
+    }
+
+    #[test]
+    fn test_pop_woken_up_by_release() {
+        let q = Arc::new(InMemoryQueue::default());
+        q.acquire();
+        q.acquire();
+
+        let popper = {
+            let q = q.clone();
+            thread::spawn(move || q.pop())
+        };
+
+        thread::sleep(Duration::from_millis(100));
+        q.release();
+        assert!(!q.is_closed(), "still held by a repository");
+        q.release();
+
+        assert!(popper.join().unwrap().is_none());
+    }
+}
@@ -0,0 +1,74 @@
+// This file is part of https://github.com/SpringQL/SpringQL which is licensed under MIT OR Apache-2.0. See file LICENSE-MIT or LICENSE-APACHE for full license details.
+
+use std::{
+    collections::HashMap,
+    fmt::Debug,
+    sync::{Arc, Mutex, MutexGuard},
+};
+
+use anyhow::anyhow;
+
+use crate::{
+    api::error::{Result, SpringError},
+    pipeline::CallbackName,
+    stream_engine::autonomous_executor::SchemalessRow,
+};
+
+/// Application code called by `CALLBACK` sink writers for each row.
+pub type SinkCallback = Box<dyn FnMut(SchemalessRow) + Send>;
+
+/// Application code called with the error when a sink callback fails.
+pub type SinkCallbackErrorHandler = Box<dyn FnMut(SpringError) + Send>;
+
+/// A sink callback with its error handler, if any.
+pub struct RegisteredSinkCallback {
+    pub callback: SinkCallback,
+    pub on_error: Option<SinkCallbackErrorHandler>,
+}
+
+/// Sink callbacks registered to a stream engine.
+#[derive(Default)]
+pub struct SinkCallbackRepository(Mutex<HashMap<CallbackName, Arc<Mutex<RegisteredSinkCallback>>>>);
+
+impl Debug for SinkCallbackRepository {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_tuple("SinkCallbackRepository")
+            .field(&self.lock().keys().collect::<Vec<_>>())
+            .finish()
+    }
+}
+
+impl SinkCallbackRepository {
+    /// Replaces the callback with the same name, if any. Sink writers already created keep calling the old one.
+    pub fn register(
+        &self,
+        callback_name: CallbackName,
+        callback: SinkCallback,
+        on_error: Option<SinkCallbackErrorHandler>,
+    ) {
+        let registered = RegisteredSinkCallback { callback, on_error };
+        let _ = self
+            .lock()
+            .insert(callback_name, Arc::new(Mutex::new(registered)));
+    }
+
+    /// # Failure
+    ///
+    /// - `SpringError::Unavailable` when:
+    ///   - callback named `callback_name` is not registered.
+    pub fn get(&self, callback_name: &CallbackName) -> Result<Arc<Mutex<RegisteredSinkCallback>>> {
+        self.lock()
+            .get(callback_name)
+            .cloned()
+            .ok_or_else(|| SpringError::Unavailable {
+                resource: callback_name.to_string(),
+                source: anyhow!("sink callback not registered"),
+            })
+    }
+
+    fn lock(&self) -> MutexGuard<'_, HashMap<CallbackName, Arc<Mutex<RegisteredSinkCallback>>>> {
+        self.0
+            .lock()
+            .expect("another thread sharing the same SinkCallbackRepository internal got panic")
+    }
+}
@@ -5,7 +5,8 @@ use std::sync::Arc;
 use crate::{
     api::error::Result,
     pipeline::{
-        Pipeline, PipelineVersion, PumpModel, SinkWriterModel, SourceReaderModel, StreamModel,
+        Pipeline, PipelineVersion, PumpModel, PumpName, PumpState, SinkWriterModel, SinkWriterName,
+        SourceReaderModel, SourceReaderName, StreamModel, StreamName,
     },
     stream_engine::command::AlterPipelineCommand,
 };
@@ -53,6 +54,20 @@ impl SqlExecutor {
                 Self::create_sink_writer(pipeline, sink_writer)
             }
             AlterPipelineCommand::CreatePump(pump) => Self::create_pump(pipeline, *pump),
+            AlterPipelineCommand::DropStream {
+                stream_name,
+                cascade,
+            } => Self::drop_stream(pipeline, &stream_name, cascade),
+            AlterPipelineCommand::DropSourceReader(source_reader) => {
+                Self::drop_source_reader(pipeline, &source_reader)
+            }
+            AlterPipelineCommand::DropSinkWriter(sink_writer) => {
+                Self::drop_sink_writer(pipeline, &sink_writer)
+            }
+            AlterPipelineCommand::DropPump(pump) => Self::drop_pump(pipeline, &pump),
+            AlterPipelineCommand::AlterPump { pump_names, state } => {
+                Self::alter_pumps(pipeline, &pump_names, state)
+            }
         }
     }
 
@@ -92,4 +107,35 @@ impl SqlExecutor {
         pipeline.add_pump(pump)?;
         Ok(pipeline)
     }
+
+    fn drop_stream(mut pipeline: Pipeline, stream: &StreamName, cascade: bool) -> Result<Pipeline> {
+        pipeline.drop_stream(stream, cascade)?;
+        Ok(pipeline)
+    }
+    fn drop_source_reader(
+        mut pipeline: Pipeline,
+        source_reader: &SourceReaderName,
+    ) -> Result<Pipeline> {
+        pipeline.drop_source_reader(source_reader)?;
+        Ok(pipeline)
+    }
+    fn drop_sink_writer(mut pipeline: Pipeline, sink_writer: &SinkWriterName) -> Result<Pipeline> {
+        pipeline.drop_sink_writer(sink_writer)?;
+        Ok(pipeline)
+    }
+    fn drop_pump(mut pipeline: Pipeline, pump: &PumpName) -> Result<Pipeline> {
+        pipeline.drop_pump(pump)?;
+        Ok(pipeline)
+    }
+    fn alter_pumps(
+        mut pipeline: Pipeline,
+        pumps: &[PumpName],
+        state: PumpState,
+    ) -> Result<Pipeline> {
+        // the pipeline is not updated if any of the pumps does not exist
+        for pump in pumps {
+            pipeline.alter_pump_state(pump, state)?;
+        }
+        Ok(pipeline)
+    }
 }
@@ -4,4 +4,4 @@ mod duration;
 mod timestamp;
 
 pub use duration::{SpringDuration, SpringEventDuration, WallClockDuration, WallClockStopwatch};
-pub use timestamp::{SpringTimestamp, SystemTimestamp, MIN_TIMESTAMP};
+pub use timestamp::{SpringTimestamp, SystemTimestamp, MAX_TIMESTAMP, MIN_TIMESTAMP};
@@ -16,11 +16,13 @@ use serde::{Deserialize, Serialize};
 use crate::{
     api::error::{Result, SpringError},
     mem_size::{chrono_naive_date_time_overhead_size, MemSize},
-    time::{DateTime, Duration, NaiveDateTime, MIN_DATETIME},
+    time::{DateTime, Duration, NaiveDateTime, MAX_DATETIME, MIN_DATETIME},
 };
 
 /// The minimum possible `Timestamp`.
 pub const MIN_TIMESTAMP: SpringTimestamp = SpringTimestamp(MIN_DATETIME);
+/// The maximum possible `Timestamp`.
+pub const MAX_TIMESTAMP: SpringTimestamp = SpringTimestamp(MAX_DATETIME);
 
 /// Timestamp in UTC. Serializable.
 #[derive(Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug, Serialize, Deserialize, new)]
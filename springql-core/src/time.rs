@@ -90,6 +90,7 @@ impl DateTime {
 pub struct NaiveDateTime(#[serde(with = "datetime_format")] time::PrimitiveDateTime);
 
 pub const MIN_DATETIME: NaiveDateTime = NaiveDateTime(time::PrimitiveDateTime::MIN);
+pub const MAX_DATETIME: NaiveDateTime = NaiveDateTime(time::PrimitiveDateTime::MAX);
 
 const FORMAT_DESCRIPTION: &[time::format_description::FormatItem<'static>] =
     format_description!("[year]-[month]-[day] [hour]:[minute]:[second].[subsecond digits:9]");
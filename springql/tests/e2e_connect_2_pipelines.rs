@@ -22,7 +22,8 @@ fn gen_pipeline1_input() -> Vec<serde_json::Value> {
     vec![json1, json2]
 }
 
-fn pipeline1(test_source: &ForeignSource) -> SpringPipeline {
+/// `sink_queue_options`: OPTIONS of the in-memory queue sink writer.
+fn pipeline1(test_source: &ForeignSource, sink_queue_options: &str) -> SpringPipeline {
     let ddls = vec![
         "
         CREATE SOURCE STREAM source_1 (
@@ -47,9 +48,10 @@ fn pipeline1(test_source: &ForeignSource) -> SpringPipeline {
             "
         CREATE SINK WRITER q_sink_1 FOR sink_1
           TYPE IN_MEMORY_QUEUE OPTIONS (
-            NAME 'q_sink_1'
+            {}
         );
         ",
+            sink_queue_options
         ),
         format!(
             "
@@ -68,7 +70,8 @@ fn pipeline1(test_source: &ForeignSource) -> SpringPipeline {
     apply_ddls(&ddls, SpringConfig::default())
 }
 
-fn pipeline2(test_sink: &ForeignSink) -> SpringPipeline {
+/// `source_queue_options`: OPTIONS of the in-memory queue source reader.
+fn pipeline2(test_sink: &ForeignSink, source_queue_options: &str) -> SpringPipeline {
     let ddls = vec![
         "
       CREATE SOURCE STREAM source_2 (
@@ -106,9 +109,10 @@ fn pipeline2(test_sink: &ForeignSink) -> SpringPipeline {
             "
       CREATE SOURCE READER q_source_2 FOR source_2
         TYPE IN_MEMORY_QUEUE OPTIONS (
-          NAME 'q_source_2'
+          {}
         );
       ",
+            source_queue_options
         ),
     ];
 
@@ -120,8 +124,8 @@ fn test_connect_2_pipelines() {
     let test_source = ForeignSource::new().unwrap();
     let test_sink = ForeignSink::start().unwrap();
 
-    let pipeline1 = pipeline1(&test_source);
-    let pipeline2 = pipeline2(&test_sink);
+    let pipeline1 = pipeline1(&test_source, "NAME 'q_sink_1'");
+    let pipeline2 = pipeline2(&test_sink, "NAME 'q_source_2'");
 
     test_source.start(ForeignSourceInput::new_fifo_batch(gen_pipeline1_input()));
 
@@ -130,7 +134,24 @@ fn test_connect_2_pipelines() {
         pipeline2.push("q_source_2", row.into()).unwrap();
     }
 
-    let sink_received = drain_from_sink(&test_sink);
+    assert_sink_received(&test_sink);
+}
+
+#[test]
+fn test_connect_2_pipelines_with_shared_queue() {
+    let test_source = ForeignSource::new().unwrap();
+    let test_sink = ForeignSink::start().unwrap();
+
+    let _pipeline1 = pipeline1(&test_source, "NAME 'q_1_to_2', SHARED 'TRUE'");
+    let _pipeline2 = pipeline2(&test_sink, "NAME 'q_1_to_2', SHARED 'TRUE'");
+
+    test_source.start(ForeignSourceInput::new_fifo_batch(gen_pipeline1_input()));
+
+    assert_sink_received(&test_sink);
+}
+
+fn assert_sink_received(test_sink: &ForeignSink) {
+    let sink_received = drain_from_sink(test_sink);
     assert_eq!(sink_received.len(), 2);
 
     assert_eq!(
@@ -242,3 +242,456 @@ fn test_feat_aggregation_with_2_group_bys() -> Result<()> {
 
     Ok(())
 }
+
+/// Runs `SELECT STREAM ticker, <aggr_expr> AS aggr ... GROUP BY ticker` and returns sink rows sorted by ticker.
+fn run_aggregation_by_ticker(
+    source_input: Vec<serde_json::Value>,
+    aggr_expr: &str,
+    aggr_type: &str,
+) -> Vec<serde_json::Value> {
+    run_aggregation_by_ticker_with_delay(source_input, aggr_expr, aggr_type, 0)
+}
+
+fn run_aggregation_by_ticker_with_delay(
+    source_input: Vec<serde_json::Value>,
+    aggr_expr: &str,
+    aggr_type: &str,
+    allowed_delay_secs: u32,
+) -> Vec<serde_json::Value> {
+    let test_source = ForeignSource::new().unwrap();
+    let test_sink = ForeignSink::start().unwrap();
+
+    let ddls = vec![
+        "
+        CREATE SOURCE STREAM source_trade (
+          ts TIMESTAMP NOT NULL ROWTIME,    
+          ticker TEXT NOT NULL,
+          amount INTEGER
+        );
+        "
+        .to_string(),
+        format!(
+            "
+        CREATE SINK STREAM sink_aggr (
+          ticker TEXT NOT NULL,
+          aggr {aggr_type}
+        );
+        ",
+            aggr_type = aggr_type
+        ),
+        format!(
+            "
+        CREATE PUMP aggr_by_ticker AS
+        INSERT INTO sink_aggr (ticker, aggr)
+        SELECT STREAM
+            source_trade.ticker AS ticker,
+            {aggr_expr} AS aggr
+        FROM source_trade
+        GROUP BY ticker
+        FIXED WINDOW DURATION_SECS(10), DURATION_SECS({allowed_delay_secs});
+        ",
+            aggr_expr = aggr_expr,
+            allowed_delay_secs = allowed_delay_secs
+        ),
+        format!(
+            "
+        CREATE SINK WRITER tcp_sink_trade FOR sink_aggr
+          TYPE NET_CLIENT OPTIONS (
+            PROTOCOL 'TCP',
+            REMOTE_HOST '{remote_host}',
+            REMOTE_PORT '{remote_port}'
+        );
+        ",
+            remote_host = test_sink.host_ip(),
+            remote_port = test_sink.port()
+        ),
+        format!(
+            "
+        CREATE SOURCE READER tcp_trade FOR source_trade
+          TYPE NET_CLIENT OPTIONS (
+            PROTOCOL 'TCP',
+            REMOTE_HOST '{remote_host}',
+            REMOTE_PORT '{remote_port}'
+          );
+        ",
+            remote_host = test_source.host_ip(),
+            remote_port = test_source.port()
+        ),
+    ];
+
+    let mut sink_received = run_and_drain(
+        &ddls,
+        ForeignSourceInput::new_fifo_batch(source_input),
+        test_source,
+        &test_sink,
+    );
+    sink_received.sort_by_key(|r| r["ticker"].as_str().unwrap().to_string());
+    sink_received
+}
+
+#[test]
+fn test_feat_count_sum_min_max() -> Result<()> {
+    setup_test_logger();
+
+    let gen_source_input = || {
+        vec![
+            json!({"ts": "2020-01-01 00:00:00.000000000", "ticker": "ORCL", "amount": 10}),
+            json!({"ts": "2020-01-01 00:00:01.000000000", "ticker": "ORCL", "amount": 30}),
+            json!({"ts": "2020-01-01 00:00:02.000000000", "ticker": "ORCL", "amount": null}),
+            json!({"ts": "2020-01-01 00:00:03.000000000", "ticker": "GOOGL", "amount": 25}),
+            json!({"ts": "2020-01-01 00:00:04.000000000", "ticker": "IBM", "amount": null}),
+            // closes the window [:00, :10)
+            json!({"ts": "2020-01-01 00:00:10.000000000", "ticker": "IBM", "amount": 1}),
+        ]
+    };
+    let aggr_of = |rows: &[serde_json::Value]| {
+        rows.iter()
+            .map(|r| (r["ticker"].as_str().unwrap().to_string(), r["aggr"].clone()))
+            .collect::<Vec<_>>()
+    };
+
+    let rows = run_aggregation_by_ticker(gen_source_input(), "COUNT(*)", "BIGINT NOT NULL");
+    assert_eq!(
+        aggr_of(&rows),
+        vec![
+            ("GOOGL".to_string(), json!(1)),
+            ("IBM".to_string(), json!(1)),
+            ("ORCL".to_string(), json!(3)),
+        ]
+    );
+
+    let rows = run_aggregation_by_ticker(
+        gen_source_input(),
+        "COUNT(source_trade.amount)",
+        "BIGINT NOT NULL",
+    );
+    assert_eq!(
+        aggr_of(&rows),
+        vec![
+            ("GOOGL".to_string(), json!(1)),
+            ("IBM".to_string(), json!(0)),
+            ("ORCL".to_string(), json!(2)),
+        ]
+    );
+
+    let rows = run_aggregation_by_ticker(gen_source_input(), "SUM(source_trade.amount)", "BIGINT");
+    assert_eq!(
+        aggr_of(&rows),
+        vec![
+            ("GOOGL".to_string(), json!(25)),
+            ("IBM".to_string(), serde_json::Value::Null),
+            ("ORCL".to_string(), json!(40)),
+        ]
+    );
+
+    let rows = run_aggregation_by_ticker(gen_source_input(), "MIN(source_trade.amount)", "BIGINT");
+    assert_eq!(aggr_of(&rows)[2], ("ORCL".to_string(), json!(10)));
+
+    let rows = run_aggregation_by_ticker(gen_source_input(), "MAX(source_trade.amount)", "BIGINT");
+    assert_eq!(aggr_of(&rows)[2], ("ORCL".to_string(), json!(30)));
+
+    let rows = run_aggregation_by_ticker(gen_source_input(), "MAX(source_trade.ts)", "TIMESTAMP");
+    assert_eq!(
+        aggr_of(&rows)[2],
+        ("ORCL".to_string(), json!("2020-01-01 00:00:02.000000000"))
+    );
+
+    let rows = run_aggregation_by_ticker(gen_source_input(), "MIN(source_trade.ticker)", "TEXT");
+    assert_eq!(aggr_of(&rows)[0], ("GOOGL".to_string(), json!("GOOGL")));
+
+    let rows = run_aggregation_by_ticker(gen_source_input(), "AVG(source_trade.amount)", "FLOAT");
+    assert_eq!(aggr_of(&rows)[0], ("GOOGL".to_string(), json!(25.0)));
+    assert_eq!(aggr_of(&rows)[2], ("ORCL".to_string(), json!(20.0)));
+
+    Ok(())
+}
+
+#[test]
+fn test_feat_first_last_arg_value() -> Result<()> {
+    setup_test_logger();
+
+    let gen_source_input = || {
+        vec![
+            json!({"ts": "2020-01-01 00:00:02.000000000", "ticker": "ORCL", "amount": 30}),
+            // arrives late but within the allowed delay
+            json!({"ts": "2020-01-01 00:00:01.000000000", "ticker": "ORCL", "amount": 10}),
+            json!({"ts": "2020-01-01 00:00:03.000000000", "ticker": "ORCL", "amount": null}),
+            json!({"ts": "2020-01-01 00:00:04.000000000", "ticker": "GOOGL", "amount": 25}),
+            json!({"ts": "2020-01-01 00:00:05.000000000", "ticker": "ORCL", "amount": 20}),
+            // closes the window [:00, :10) with 1 sec allowed delay
+            json!({"ts": "2020-01-01 00:00:11.000000000", "ticker": "IBM", "amount": 1}),
+        ]
+    };
+    let orcl_aggr = |rows: &[serde_json::Value]| {
+        rows.iter().find(|r| r["ticker"] == json!("ORCL")).unwrap()["aggr"].clone()
+    };
+
+    let rows = run_aggregation_by_ticker_with_delay(
+        gen_source_input(),
+        "FIRST_VALUE(source_trade.amount)",
+        "INTEGER",
+        1,
+    );
+    assert_eq!(orcl_aggr(&rows), json!(10));
+
+    let rows = run_aggregation_by_ticker_with_delay(
+        gen_source_input(),
+        "LAST_VALUE(source_trade.amount)",
+        "INTEGER",
+        1,
+    );
+    assert_eq!(orcl_aggr(&rows), json!(20));
+
+    let rows = run_aggregation_by_ticker_with_delay(
+        gen_source_input(),
+        "ARG_MAX(source_trade.ts, source_trade.amount)",
+        "TIMESTAMP",
+        1,
+    );
+    assert_eq!(orcl_aggr(&rows), json!("2020-01-01 00:00:02.000000000"));
+
+    let rows = run_aggregation_by_ticker_with_delay(
+        gen_source_input(),
+        "ARG_MIN(source_trade.ts, source_trade.amount)",
+        "TIMESTAMP",
+        1,
+    );
+    assert_eq!(orcl_aggr(&rows), json!("2020-01-01 00:00:01.000000000"));
+
+    // NULL of the chosen row is returned instead of being skipped
+    let gen_source_input_with_nulls = || {
+        vec![
+            json!({"ts": "2020-01-01 00:00:01.000000000", "ticker": "ORCL", "amount": null}),
+            json!({"ts": "2020-01-01 00:00:02.000000000", "ticker": "ORCL", "amount": 5}),
+            json!({"ts": "2020-01-01 00:00:03.000000000", "ticker": "ORCL", "amount": null}),
+            // closes the window [:00, :10) with 1 sec allowed delay
+            json!({"ts": "2020-01-01 00:00:11.000000000", "ticker": "IBM", "amount": 1}),
+        ]
+    };
+    for aggr_expr in [
+        "FIRST_VALUE(source_trade.amount)",
+        "LAST_VALUE(source_trade.amount)",
+        "ARG_MAX(source_trade.amount, source_trade.ts)",
+        "ARG_MIN(source_trade.amount, source_trade.ts)",
+    ] {
+        let rows = run_aggregation_by_ticker_with_delay(
+            gen_source_input_with_nulls(),
+            aggr_expr,
+            "INTEGER",
+            1,
+        );
+        assert_eq!(orcl_aggr(&rows), serde_json::Value::Null, "{}", aggr_expr);
+    }
+
+    Ok(())
+}
+
+#[test]
+fn test_feat_multiple_aggregations() -> Result<()> {
+    setup_test_logger();
+
+    let source_input = gen_source_input();
+
+    let test_source = ForeignSource::new().unwrap();
+    let test_sink = ForeignSink::start().unwrap();
+
+    let ddls = vec![
+        "
+        CREATE SOURCE STREAM source_trade (
+          ts TIMESTAMP NOT NULL ROWTIME,    
+          ticker TEXT NOT NULL,
+          amount INTEGER NOT NULL
+        );
+        "
+        .to_string(),
+        "
+        CREATE SINK STREAM sink_stats (
+          avg_amount FLOAT NOT NULL,
+          max_amount BIGINT NOT NULL,
+          min_ticker TEXT NOT NULL,
+          range_amount BIGINT NOT NULL,
+          cnt BIGINT NOT NULL
+        );
+        "
+        .to_string(),
+        "
+        CREATE PUMP stats AS
+        INSERT INTO sink_stats (avg_amount, max_amount, min_ticker, range_amount, cnt)
+        SELECT STREAM
+            AVG(source_trade.amount) AS avg_amount,
+            MAX(source_trade.amount) AS max_amount,
+            MIN(source_trade.ticker) AS min_ticker,
+            MAX(source_trade.amount) - MIN(source_trade.amount) AS range_amount,
+            COUNT(*) AS cnt
+        FROM source_trade
+        FIXED WINDOW DURATION_SECS(10), DURATION_SECS(0);
+        "
+        .to_string(),
+        format!(
+            "
+        CREATE SINK WRITER tcp_sink_trade FOR sink_stats
+          TYPE NET_CLIENT OPTIONS (
+            PROTOCOL 'TCP',
+            REMOTE_HOST '{remote_host}',
+            REMOTE_PORT '{remote_port}'
+        );
+        ",
+            remote_host = test_sink.host_ip(),
+            remote_port = test_sink.port()
+        ),
+        format!(
+            "
+        CREATE SOURCE READER tcp_trade FOR source_trade
+          TYPE NET_CLIENT OPTIONS (
+            PROTOCOL 'TCP',
+            REMOTE_HOST '{remote_host}',
+            REMOTE_PORT '{remote_port}'
+          );
+        ",
+            remote_host = test_source.host_ip(),
+            remote_port = test_source.port()
+        ),
+    ];
+
+    let sink_received = run_and_drain(
+        &ddls,
+        ForeignSourceInput::new_fifo_batch(source_input),
+        test_source,
+        &test_sink,
+    );
+
+    assert_eq!(
+        sink_received,
+        vec![
+            json!({
+                "avg_amount": 20.0,
+                "max_amount": 30,
+                "min_ticker": "GOOGL",
+                "range_amount": 20,
+                "cnt": 2,
+            }),
+            json!({
+                "avg_amount": 50.0,
+                "max_amount": 50,
+                "min_ticker": "IBM",
+                "range_amount": 0,
+                "cnt": 1,
+            }),
+        ]
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_feat_statistical_aggregations() -> Result<()> {
+    setup_test_logger();
+
+    let source_input = [2, 4, 4, 4, 5, 5, 7, 9]
+        .iter()
+        .enumerate()
+        .map(|(i, amount)| {
+            json!({
+                "ts": format!("2020-01-01 00:00:0{}.000000000", i),
+                "ticker": "ORCL",
+                "amount": amount,
+            })
+        })
+        .chain(vec![json!({
+            "ts": "2020-01-01 00:00:10.000000000",
+            "ticker": "ORCL",
+            "amount": 0,
+        })])
+        .collect::<Vec<_>>();
+
+    let test_source = ForeignSource::new().unwrap();
+    let test_sink = ForeignSink::start().unwrap();
+
+    let ddls = vec![
+        "
+        CREATE SOURCE STREAM source_trade (
+          ts TIMESTAMP NOT NULL ROWTIME,    
+          ticker TEXT NOT NULL,
+          amount INTEGER NOT NULL
+        );
+        "
+        .to_string(),
+        "
+        CREATE SINK STREAM sink_stats (
+          sd_pop FLOAT NOT NULL,
+          sd_samp FLOAT NOT NULL,
+          v_pop FLOAT NOT NULL,
+          v_samp FLOAT NOT NULL,
+          median FLOAT NOT NULL,
+          distinct_amounts BIGINT NOT NULL
+        );
+        "
+        .to_string(),
+        "
+        CREATE PUMP stats AS
+        INSERT INTO sink_stats (sd_pop, sd_samp, v_pop, v_samp, median, distinct_amounts)
+        SELECT STREAM
+            STDDEV_POP(source_trade.amount) AS sd_pop,
+            STDDEV_SAMP(source_trade.amount) AS sd_samp,
+            VAR_POP(source_trade.amount) AS v_pop,
+            VAR_SAMP(source_trade.amount) AS v_samp,
+            PERCENTILE_APPROX(source_trade.amount, 0.5) AS median,
+            APPROX_COUNT_DISTINCT(source_trade.amount) AS distinct_amounts
+        FROM source_trade
+        FIXED WINDOW DURATION_SECS(10), DURATION_SECS(0);
+        "
+        .to_string(),
+        format!(
+            "
+        CREATE SINK WRITER tcp_sink_trade FOR sink_stats
+          TYPE NET_CLIENT OPTIONS (
+            PROTOCOL 'TCP',
+            REMOTE_HOST '{remote_host}',
+            REMOTE_PORT '{remote_port}'
+        );
+        ",
+            remote_host = test_sink.host_ip(),
+            remote_port = test_sink.port()
+        ),
+        format!(
+            "
+        CREATE SOURCE READER tcp_trade FOR source_trade
+          TYPE NET_CLIENT OPTIONS (
+            PROTOCOL 'TCP',
+            REMOTE_HOST '{remote_host}',
+            REMOTE_PORT '{remote_port}'
+          );
+        ",
+            remote_host = test_source.host_ip(),
+            remote_port = test_source.port()
+        ),
+    ];
+
+    let sink_received = run_and_drain(
+        &ddls,
+        ForeignSourceInput::new_fifo_batch(source_input),
+        test_source,
+        &test_sink,
+    );
+    assert_eq!(sink_received.len(), 1);
+    let r = &sink_received[0];
+
+    let approx = |key: &str, expected: f64| {
+        let actual = r[key].as_f64().unwrap();
+        assert!(
+            (actual - expected).abs() < 1e-3,
+            "{}: {} != {}",
+            key,
+            actual,
+            expected
+        );
+    };
+    approx("sd_pop", 2.0);
+    approx("sd_samp", (32.0f64 / 7.0).sqrt());
+    approx("v_pop", 4.0);
+    approx("v_samp", 32.0 / 7.0);
+    approx("median", 4.5);
+    assert_eq!(r["distinct_amounts"].as_i64().unwrap(), 5);
+
+    Ok(())
+}
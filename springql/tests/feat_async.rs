@@ -0,0 +1,176 @@
+// This file is part of https://github.com/SpringQL/SpringQL which is licensed under MIT OR Apache-2.0. See file LICENSE-MIT or LICENSE-APACHE for full license details.
+
+#![cfg(feature = "async")]
+
+mod test_support;
+
+use std::{sync::Arc, thread, time::Duration};
+
+use futures::{executor::block_on, SinkExt, StreamExt};
+use pretty_assertions::assert_eq;
+use springql::*;
+use springql_test_logger::setup_test_logger;
+
+use crate::test_support::apply_ddls;
+
+fn in_memory_pass_through_pipeline() -> SpringPipeline {
+    let ddls = vec![
+        "
+        CREATE SOURCE STREAM source_n (
+          ts TIMESTAMP NOT NULL ROWTIME,
+          n INTEGER NOT NULL
+        );
+        "
+        .to_string(),
+        "
+        CREATE SINK STREAM sink_n (
+          ts TIMESTAMP NOT NULL ROWTIME,
+          n INTEGER NOT NULL
+        );
+        "
+        .to_string(),
+        "
+        CREATE PUMP pu_n AS
+          INSERT INTO sink_n (ts, n)
+          SELECT STREAM source_n.ts, source_n.n FROM source_n;
+        "
+        .to_string(),
+        "
+        CREATE SINK WRITER q_sink_n FOR sink_n
+          TYPE IN_MEMORY_QUEUE OPTIONS (
+            NAME 'q_out'
+        );
+        "
+        .to_string(),
+        "
+        CREATE SOURCE READER q_source_n FOR source_n
+          TYPE IN_MEMORY_QUEUE OPTIONS (
+            NAME 'q_in'
+        );
+        "
+        .to_string(),
+    ];
+    apply_ddls(&ddls, SpringConfig::default())
+}
+
+fn source_row(n: i32) -> SpringSourceRow {
+    let json = format!(r#"{{"ts": "2020-01-01 00:00:00.000000000", "n": {}}}"#, n);
+    SpringSourceRow::from_json(&json).unwrap()
+}
+
+/// Sends rows from another thread after a while so that the receiver has to wait for them.
+fn send_later(mut source: SpringSourceSink, ns: Vec<i32>) -> thread::JoinHandle<()> {
+    thread::spawn(move || {
+        thread::sleep(Duration::from_millis(100));
+        block_on(async {
+            for n in ns {
+                source.send(source_row(n)).await.unwrap();
+            }
+        })
+    })
+}
+
+#[test]
+fn test_feat_async_pop() {
+    setup_test_logger();
+
+    let pipeline = in_memory_pass_through_pipeline();
+    let sender = send_later(pipeline.source_sink("q_in").unwrap(), vec![1, 2]);
+
+    let ns = block_on(async {
+        let mut ns = Vec::new();
+        for _ in 0..2 {
+            let row = pipeline.pop_async("q_out").await.unwrap();
+            ns.push(row.get_not_null_by_index::<i32>(1).unwrap());
+        }
+        ns
+    });
+    assert_eq!(ns, vec![1, 2]);
+
+    sender.join().unwrap();
+    assert!(block_on(pipeline.pop_async("q_undefined")).is_err());
+}
+
+#[test]
+fn test_feat_async_sink_stream() {
+    setup_test_logger();
+
+    let pipeline = in_memory_pass_through_pipeline();
+    let sink_stream = pipeline.sink_stream("q_out").unwrap();
+    let sender = send_later(pipeline.source_sink("q_in").unwrap(), vec![1, 2, 3]);
+
+    let ns = block_on(
+        sink_stream
+            .take(3)
+            .map(|row| row.get_not_null_by_index::<i32>(1).unwrap())
+            .collect::<Vec<_>>(),
+    );
+    assert_eq!(ns, vec![1, 2, 3]);
+
+    sender.join().unwrap();
+    assert!(pipeline.sink_stream("q_undefined").is_err());
+}
+
+#[test]
+fn test_feat_async_source_sink_close_ends_stream() {
+    setup_test_logger();
+
+    let pipeline = in_memory_pass_through_pipeline();
+    let mut source = pipeline.source_sink("q_in").unwrap();
+
+    block_on(async {
+        source.send(source_row(1)).await.unwrap();
+        source.close().await.unwrap();
+        assert!(source.send(source_row(2)).await.is_err());
+    });
+
+    let row = pipeline
+        .pop_timeout("q_out", Duration::from_secs(10))
+        .unwrap()
+        .unwrap();
+    assert_eq!(row.get_not_null_by_index::<i32>(1).unwrap(), 1);
+    assert!(pipeline.push("q_in", source_row(3)).is_err());
+    assert!(pipeline.source_sink("q_undefined").is_err());
+}
+
+#[test]
+fn test_feat_async_source_sink_fails_after_shutdown() {
+    setup_test_logger();
+
+    let pipeline = in_memory_pass_through_pipeline();
+    let mut source = pipeline.source_sink("q_in").unwrap();
+
+    pipeline
+        .shutdown(SpringShutdownMode::Immediate, Duration::from_secs(10))
+        .unwrap();
+
+    let push_err = pipeline.push("q_in", source_row(1)).unwrap_err();
+    let send_err = block_on(source.send(source_row(1))).unwrap_err();
+    assert!(matches!(send_err, SpringError::Unavailable { .. }));
+    assert_eq!(send_err.to_string(), push_err.to_string());
+}
+
+#[test]
+fn test_feat_async_pop_ends_on_drop_sink_writer() {
+    setup_test_logger();
+
+    let pipeline = Arc::new(in_memory_pass_through_pipeline());
+    let sink_stream = pipeline.sink_stream("q_out").unwrap();
+
+    let dropper = {
+        let pipeline = pipeline.clone();
+        thread::spawn(move || {
+            thread::sleep(Duration::from_millis(100));
+            pipeline.command("DROP SINK WRITER q_sink_n;").unwrap();
+        })
+    };
+
+    assert!(matches!(
+        block_on(pipeline.pop_async("q_out")),
+        Err(SpringError::Unavailable { .. })
+    ));
+    dropper.join().unwrap();
+
+    // the stream taken before the drop ends
+    assert!(block_on(sink_stream.collect::<Vec<_>>()).is_empty());
+}
@@ -0,0 +1,180 @@
+// This file is part of https://github.com/SpringQL/SpringQL which is licensed under MIT OR Apache-2.0. See file LICENSE-MIT or LICENSE-APACHE for full license details.
+
+mod test_support;
+
+use pretty_assertions::assert_eq;
+use serde_json::json;
+use springql::*;
+use springql_foreign_service::{
+    sink::ForeignSink,
+    source::{ForeignSource, ForeignSourceInput},
+};
+use springql_test_logger::setup_test_logger;
+
+use crate::test_support::{apply_ddls, drain_from_sink};
+
+fn ddls(
+    pump: &str,
+    sink_columns: &str,
+    test_source: &ForeignSource,
+    test_sink: &ForeignSink,
+) -> Vec<String> {
+    vec![
+        "
+        CREATE SOURCE STREAM source_1 (
+          ts TIMESTAMP NOT NULL ROWTIME,
+          ticker TEXT NOT NULL,
+          speed FLOAT NOT NULL
+        );
+        "
+        .to_string(),
+        format!(
+            "
+        CREATE SINK STREAM sink_1 (
+          {sink_columns}
+        );
+        ",
+            sink_columns = sink_columns
+        ),
+        pump.to_string(),
+        format!(
+            "
+        CREATE SINK WRITER tcp_sink_1 FOR sink_1
+          TYPE NET_CLIENT OPTIONS (
+            PROTOCOL 'TCP',
+            REMOTE_HOST '{remote_host}',
+            REMOTE_PORT '{remote_port}'
+        );
+        ",
+            remote_host = test_sink.host_ip(),
+            remote_port = test_sink.port()
+        ),
+        format!(
+            "
+        CREATE SOURCE READER tcp_1 FOR source_1
+          TYPE NET_CLIENT OPTIONS (
+            PROTOCOL 'TCP',
+            REMOTE_HOST '{remote_host}',
+            REMOTE_PORT '{remote_port}'
+          );
+        ",
+            remote_host = test_source.host_ip(),
+            remote_port = test_source.port()
+        ),
+    ]
+}
+
+#[test]
+fn test_feat_comparison_ops() {
+    setup_test_logger();
+
+    let json1 = json!({
+        "ts": "2020-01-01 00:00:00.000000000",
+        "ticker": "ORCL",
+        "speed": 100.0,
+    });
+    let source_input = vec![json1];
+
+    let test_source = ForeignSource::new().unwrap();
+    let test_sink = ForeignSink::start().unwrap();
+
+    let ddls = ddls(
+        "
+        CREATE PUMP pu_cmp AS
+          INSERT INTO sink_1 (ts, ne, lt, le, gt, ge, text_lt, ts_gt, dur_le, between_, in_)
+          SELECT STREAM
+            source_1.ts,
+            source_1.speed <> 100,
+            source_1.speed < 100,
+            source_1.speed <= 100,
+            source_1.speed > 99.5,
+            source_1.speed >= 101,
+            source_1.ticker < 'ZZZZ',
+            source_1.ts > FLOOR_TIME(source_1.ts, DURATION_SECS(10)),
+            DURATION_MILLIS(1000) <= DURATION_SECS(1),
+            source_1.speed BETWEEN 50 AND 100,
+            source_1.ticker IN ('GOOGL', 'ORCL')
+          FROM source_1;
+        ",
+        "
+          ts TIMESTAMP NOT NULL ROWTIME,
+          ne BOOLEAN NOT NULL,
+          lt BOOLEAN NOT NULL,
+          le BOOLEAN NOT NULL,
+          gt BOOLEAN NOT NULL,
+          ge BOOLEAN NOT NULL,
+          text_lt BOOLEAN NOT NULL,
+          ts_gt BOOLEAN NOT NULL,
+          dur_le BOOLEAN NOT NULL,
+          between_ BOOLEAN NOT NULL,
+          in_ BOOLEAN NOT NULL
+        ",
+        &test_source,
+        &test_sink,
+    );
+
+    let _pipeline = apply_ddls(&ddls, SpringConfig::default());
+    test_source.start(ForeignSourceInput::new_fifo_batch(source_input));
+    let sink_received = drain_from_sink(&test_sink);
+    let r = sink_received.get(0).unwrap();
+
+    assert_eq!(r["ne"], false);
+    assert_eq!(r["lt"], false);
+    assert_eq!(r["le"], true);
+    assert_eq!(r["gt"], true);
+    assert_eq!(r["ge"], false);
+    assert_eq!(r["text_lt"], true);
+    assert_eq!(r["ts_gt"], false);
+    assert_eq!(r["dur_le"], true);
+    assert_eq!(r["between_"], true);
+    assert_eq!(r["in_"], true);
+}
+
+#[test]
+fn test_feat_threshold_filter() {
+    setup_test_logger();
+
+    let source_input = vec![
+        json!({
+            "ts": "2020-01-01 00:00:00.000000000",
+            "ticker": "a",
+            "speed": 80.0,
+        }),
+        json!({
+            "ts": "2020-01-01 00:00:01.000000000",
+            "ticker": "b",
+            "speed": 120.0,
+        }),
+        json!({
+            "ts": "2020-01-01 00:00:02.000000000",
+            "ticker": "c",
+            "speed": 100.0,
+        }),
+    ];
+
+    let test_source = ForeignSource::new().unwrap();
+    let test_sink = ForeignSink::start().unwrap();
+
+    let ddls = ddls(
+        "
+        CREATE PUMP pu_alert AS
+          INSERT INTO sink_1 (ts, ticker)
+          SELECT STREAM source_1.ts, source_1.ticker
+          FROM source_1
+          WHERE source_1.speed > 100;
+        ",
+        "
+          ts TIMESTAMP NOT NULL ROWTIME,
+          ticker TEXT NOT NULL
+        ",
+        &test_source,
+        &test_sink,
+    );
+
+    let _pipeline = apply_ddls(&ddls, SpringConfig::default());
+    test_source.start(ForeignSourceInput::new_fifo_batch(source_input));
+    let sink_received = drain_from_sink(&test_sink);
+
+    assert_eq!(sink_received.len(), 1);
+    assert_eq!(sink_received[0]["ticker"], "b");
+}
@@ -0,0 +1,282 @@
+// This file is part of https://github.com/SpringQL/SpringQL which is licensed under MIT OR Apache-2.0. See file LICENSE-MIT or LICENSE-APACHE for full license details.
+
+mod test_support;
+
+use std::{
+    io::Write,
+    net::TcpListener,
+    thread,
+    time::{Duration, Instant},
+};
+
+use pretty_assertions::assert_eq;
+use springql::*;
+use springql_test_logger::setup_test_logger;
+
+use crate::test_support::apply_ddls;
+
+/// Pipeline summing `amount` by ticker in a 10-second fixed window.
+fn sum_by_ticker_ddls(source_reader_ddl: String, sink_queue: &str) -> Vec<String> {
+    vec![
+        "
+        CREATE SOURCE STREAM source_trade (
+          ts TIMESTAMP NOT NULL ROWTIME,
+          ticker TEXT NOT NULL,
+          amount INTEGER NOT NULL
+        );
+        "
+        .to_string(),
+        "
+        CREATE SINK STREAM sink_sum_by_ticker (
+          ts TIMESTAMP NOT NULL ROWTIME,
+          ticker TEXT NOT NULL,
+          sum_amount INTEGER NOT NULL
+        );
+        "
+        .to_string(),
+        "
+        CREATE PUMP sum_by_ticker AS
+          INSERT INTO sink_sum_by_ticker (ts, ticker, sum_amount)
+          SELECT STREAM
+            FLOOR_TIME(source_trade.ts, DURATION_SECS(10)) AS min_ts,
+            source_trade.ticker AS ticker,
+            SUM(source_trade.amount) AS sum_amount
+          FROM source_trade
+          GROUP BY min_ts, ticker
+          FIXED WINDOW DURATION_SECS(10), DURATION_SECS(0);
+        "
+        .to_string(),
+        format!(
+            "
+        CREATE SINK WRITER q_sink FOR sink_sum_by_ticker
+          TYPE IN_MEMORY_QUEUE OPTIONS (
+            NAME '{}'
+        );
+        ",
+            sink_queue
+        ),
+        source_reader_ddl,
+    ]
+}
+
+fn trade_json(ts: &str, amount: i32) -> String {
+    format!(
+        r#"{{"ts": "{}", "ticker": "ORCL", "amount": {}}}"#,
+        ts, amount
+    )
+}
+
+/// Pops `sum_amount`s until no row arrives for a while.
+fn pop_sums(pipeline: &SpringPipeline, sink_queue: &str) -> Vec<i32> {
+    const IDLE_TIMEOUT: Duration = Duration::from_secs(3);
+
+    let mut sums = Vec::new();
+    let mut last_received = Instant::now();
+    while last_received.elapsed() < IDLE_TIMEOUT {
+        if let Some(row) = pipeline.pop_non_blocking(sink_queue).unwrap() {
+            sums.push(row.get_not_null_by_index::<i32>(2).unwrap());
+            last_received = Instant::now();
+        } else {
+            thread::sleep(Duration::from_millis(10));
+        }
+    }
+    sums
+}
+
+#[test]
+fn test_feat_end_of_stream_in_memory_queue() {
+    setup_test_logger();
+
+    let source_queue = "q_eos_source";
+    let sink_queue = "q_eos_sink";
+
+    let ddls = sum_by_ticker_ddls(
+        format!(
+            "
+        CREATE SOURCE READER q_source FOR source_trade
+          TYPE IN_MEMORY_QUEUE OPTIONS (
+            NAME '{}'
+        );
+        ",
+            source_queue
+        ),
+        sink_queue,
+    );
+    let pipeline = apply_ddls(&ddls, SpringConfig::default());
+
+    for (ts, amount) in [
+        ("2022-01-01 13:00:01.000000000", 10),
+        ("2022-01-01 13:00:02.000000000", 20),
+    ] {
+        pipeline
+            .push(
+                source_queue,
+                SpringSourceRow::from_json(&trade_json(ts, amount)).unwrap(),
+            )
+            .unwrap();
+    }
+    pipeline.end_of_stream(source_queue).unwrap();
+
+    assert_eq!(pop_sums(&pipeline, sink_queue), vec![30]);
+
+    assert!(pipeline
+        .push(
+            source_queue,
+            SpringSourceRow::from_json(&trade_json("2022-01-01 13:00:03.000000000", 1)).unwrap()
+        )
+        .is_err());
+}
+
+#[test]
+fn test_feat_end_of_stream_multiple_worker_threads() {
+    setup_test_logger();
+
+    let source_queue = "q_eos_multi_worker_source";
+    let sink_queue = "q_eos_multi_worker_sink";
+
+    let ddls = sum_by_ticker_ddls(
+        format!(
+            "
+        CREATE SOURCE READER q_source FOR source_trade
+          TYPE IN_MEMORY_QUEUE OPTIONS (
+            NAME '{}'
+        );
+        ",
+            source_queue
+        ),
+        sink_queue,
+    );
+    let config = SpringConfig::from_toml(
+        r#"
+        [worker]
+        n_generic_worker_threads = 2
+        "#,
+    )
+    .unwrap();
+    let pipeline = apply_ddls(&ddls, config);
+
+    // two worker threads may run the pump at once; rows taken by one must not be lost by the other ending the stream
+    for _ in 0..30 {
+        pipeline
+            .push(
+                source_queue,
+                SpringSourceRow::from_json(&trade_json("2022-01-01 13:00:01.000000000", 1))
+                    .unwrap(),
+            )
+            .unwrap();
+    }
+    pipeline.end_of_stream(source_queue).unwrap();
+
+    assert_eq!(pop_sums(&pipeline, sink_queue), vec![30]);
+}
+
+#[test]
+fn test_feat_end_of_stream_net_client_connection_closed() {
+    setup_test_logger();
+
+    let sink_queue = "q_eos_net_sink";
+
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let addr = listener.local_addr().unwrap();
+
+    let ddls = sum_by_ticker_ddls(
+        format!(
+            "
+        CREATE SOURCE READER tcp_source FOR source_trade
+          TYPE NET_CLIENT OPTIONS (
+            PROTOCOL 'TCP',
+            REMOTE_HOST '{}',
+            REMOTE_PORT '{}'
+        );
+        ",
+            addr.ip(),
+            addr.port()
+        ),
+        sink_queue,
+    );
+
+    let source = thread::spawn(move || {
+        let (mut stream, _) = listener.accept().unwrap();
+        for (ts, amount) in [
+            ("2022-01-01 13:00:01.000000000", 10),
+            ("2022-01-01 13:00:02.000000000", 20),
+            ("2022-01-01 13:00:11.000000000", 5),
+        ] {
+            stream
+                .write_all(format!("{}\n", trade_json(ts, amount)).as_bytes())
+                .unwrap();
+        }
+        // closing the connection ends the stream
+    });
+
+    let config = SpringConfig::from_toml(
+        r#"
+        [source_reader]
+        reconnect_max_retries = 0
+        "#,
+    )
+    .unwrap();
+    let pipeline = apply_ddls(&ddls, config);
+    source.join().unwrap();
+
+    // the first window is closed by the watermark; the second one on end-of-stream
+    assert_eq!(pop_sums(&pipeline, sink_queue), vec![30, 5]);
+}
+
+#[test]
+fn test_feat_end_of_stream_net_client_reconnect() {
+    setup_test_logger();
+
+    let sink_queue = "q_eos_net_reconnect_sink";
+
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let addr = listener.local_addr().unwrap();
+
+    let ddls = sum_by_ticker_ddls(
+        format!(
+            "
+        CREATE SOURCE READER tcp_source FOR source_trade
+          TYPE NET_CLIENT OPTIONS (
+            PROTOCOL 'TCP',
+            REMOTE_HOST '{}',
+            REMOTE_PORT '{}'
+        );
+        ",
+            addr.ip(),
+            addr.port()
+        ),
+        sink_queue,
+    );
+
+    let source = thread::spawn(move || {
+        // the remote host restarts between the rows
+        for rows in [
+            vec![("2022-01-01 13:00:01.000000000", 10)],
+            vec![
+                ("2022-01-01 13:00:02.000000000", 20),
+                ("2022-01-01 13:00:11.000000000", 5),
+            ],
+        ] {
+            let (mut stream, _) = listener.accept().unwrap();
+            for (ts, amount) in rows {
+                stream
+                    .write_all(format!("{}\n", trade_json(ts, amount)).as_bytes())
+                    .unwrap();
+            }
+        }
+        // stops listening, so that the source reader gives up reconnecting and the stream ends
+    });
+
+    let config = SpringConfig::from_toml(
+        r#"
+        [source_reader]
+        reconnect_max_retries = 3
+        reconnect_initial_backoff_msec = 10
+        "#,
+    )
+    .unwrap();
+    let pipeline = apply_ddls(&ddls, config);
+    source.join().unwrap();
+
+    assert_eq!(pop_sums(&pipeline, sink_queue), vec![30, 5]);
+}
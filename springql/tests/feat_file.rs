@@ -0,0 +1,164 @@
+// This file is part of https://github.com/SpringQL/SpringQL which is licensed under MIT OR Apache-2.0. See file LICENSE-MIT or LICENSE-APACHE for full license details.
+
+mod test_support;
+
+use std::{
+    io::Write,
+    thread,
+    time::{Duration, Instant},
+};
+
+use pretty_assertions::assert_eq;
+use springql::*;
+use springql_test_logger::setup_test_logger;
+use tempfile::NamedTempFile;
+
+use crate::test_support::apply_ddls;
+
+fn file_with(content: &str) -> NamedTempFile {
+    let mut file = NamedTempFile::new().unwrap();
+    file.write_all(content.as_bytes()).unwrap();
+    file.flush().unwrap();
+    file
+}
+
+fn trade_ddls(sink_pump_ddl: &str, sink_queue: &str, source_reader_ddl: String) -> Vec<String> {
+    vec![
+        "
+        CREATE SOURCE STREAM source_trade (
+          ts TIMESTAMP NOT NULL ROWTIME,
+          ticker TEXT NOT NULL,
+          amount INTEGER NOT NULL
+        );
+        "
+        .to_string(),
+        "
+        CREATE SINK STREAM sink_trade (
+          ts TIMESTAMP NOT NULL ROWTIME,
+          ticker TEXT NOT NULL,
+          amount INTEGER NOT NULL
+        );
+        "
+        .to_string(),
+        sink_pump_ddl.to_string(),
+        format!(
+            "
+        CREATE SINK WRITER q_sink FOR sink_trade
+          TYPE IN_MEMORY_QUEUE OPTIONS (
+            NAME '{}'
+        );
+        ",
+            sink_queue
+        ),
+        source_reader_ddl,
+    ]
+}
+
+/// Pops `(ticker, amount)`s until no row arrives for a while.
+fn pop_trades(pipeline: &SpringPipeline, sink_queue: &str) -> Vec<(String, i32)> {
+    const IDLE_TIMEOUT: Duration = Duration::from_secs(3);
+
+    let mut trades = Vec::new();
+    let mut last_received = Instant::now();
+    while last_received.elapsed() < IDLE_TIMEOUT {
+        if let Some(row) = pipeline.pop_non_blocking(sink_queue).unwrap() {
+            trades.push((
+                row.get_not_null_by_index::<String>(1).unwrap(),
+                row.get_not_null_by_index::<i32>(2).unwrap(),
+            ));
+            last_received = Instant::now();
+        } else {
+            thread::sleep(Duration::from_millis(10));
+        }
+    }
+    trades
+}
+
+#[test]
+fn test_feat_file_csv_end_of_stream() {
+    setup_test_logger();
+
+    let sink_queue = "q_file_csv_sink";
+    let file = file_with(
+        "ts,ticker,amount
+2022-01-01 13:00:01.000000000,ORCL,10
+2022-01-01 13:00:02.000000000,GOOGL,20
+2022-01-01 13:00:03.000000000,ORCL,30
+",
+    );
+
+    let ddls = trade_ddls(
+        "
+        CREATE PUMP sum_by_ticker AS
+          INSERT INTO sink_trade (ts, ticker, amount)
+          SELECT STREAM
+            FLOOR_TIME(source_trade.ts, DURATION_SECS(10)) AS min_ts,
+            source_trade.ticker AS ticker,
+            SUM(source_trade.amount) AS sum_amount
+          FROM source_trade
+          GROUP BY min_ts, ticker
+          FIXED WINDOW DURATION_SECS(10), DURATION_SECS(0);
+        ",
+        sink_queue,
+        format!(
+            "
+        CREATE SOURCE READER file_source FOR source_trade
+          TYPE FILE OPTIONS (
+            PATH '{}',
+            FORMAT 'CSV'
+        );
+        ",
+            file.path().display()
+        ),
+    );
+    let pipeline = apply_ddls(&ddls, SpringConfig::default());
+
+    // the open window is emitted at the end of the file
+    let mut trades = pop_trades(&pipeline, sink_queue);
+    trades.sort();
+    assert_eq!(
+        trades,
+        vec![("GOOGL".to_string(), 20), ("ORCL".to_string(), 40)]
+    );
+}
+
+#[test]
+fn test_feat_file_jsonl_replay_speed() {
+    setup_test_logger();
+
+    let sink_queue = "q_file_jsonl_sink";
+    let file = file_with(
+        r#"{"ts": "2022-01-01 13:00:00.000000000", "ticker": "ORCL", "amount": 10}
+{"ts": "2022-01-01 13:00:01.000000000", "ticker": "GOOGL", "amount": 20}
+"#,
+    );
+
+    let ddls = trade_ddls(
+        "
+        CREATE PUMP pu_trade AS
+          INSERT INTO sink_trade (ts, ticker, amount)
+          SELECT STREAM source_trade.ts, source_trade.ticker, source_trade.amount FROM source_trade;
+        ",
+        sink_queue,
+        format!(
+            "
+        CREATE SOURCE READER file_source FOR source_trade
+          TYPE FILE OPTIONS (
+            PATH '{}',
+            REPLAY_SPEED '2'
+        );
+        ",
+            file.path().display()
+        ),
+    );
+    let pipeline = apply_ddls(&ddls, SpringConfig::default());
+
+    let first = pipeline.pop(sink_queue).unwrap();
+    let popped_first_at = Instant::now();
+    let second = pipeline.pop(sink_queue).unwrap();
+
+    // 1 sec in ROWTIME = 0.5 sec in replay
+    assert!(popped_first_at.elapsed() >= Duration::from_millis(300));
+    assert_eq!(first.get_not_null_by_index::<i32>(2).unwrap(), 10);
+    assert_eq!(second.get_not_null_by_index::<i32>(2).unwrap(), 20);
+}
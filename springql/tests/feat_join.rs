@@ -2,6 +2,8 @@
 
 mod test_support;
 
+use std::time::Duration;
+
 use pretty_assertions::assert_eq;
 use serde_json::json;
 use springql_core::api::*;
@@ -183,3 +185,297 @@ fn test_feat_left_outer_join() {
     assert_eq!(r2["amount"].as_i64().unwrap(), 50);
     assert!(r2["temperature"].is_null());
 }
+
+#[test]
+fn test_feat_3_way_join() {
+    setup_test_logger();
+
+    let source_trade = vec![
+        json!({"ts": "2020-01-01 00:00:00.000000000", "ticker": "ORCL", "amount": 10}),
+        // close windows of the lower and then the upper JOIN
+        json!({"ts": "2020-01-01 00:00:10.000000000", "ticker": "IBM", "amount": 50}),
+        json!({"ts": "2020-01-01 00:00:20.000000000", "ticker": "IBM", "amount": 70}),
+        json!({"ts": "2020-01-01 00:00:30.000000000", "ticker": "IBM", "amount": 90}),
+    ];
+    let source_city_temperature = gen_source_city_temperature();
+    let source_city_humidity = vec![json!({
+        "ts": "2020-01-01 00:00:00.000000000",
+        "city": "Tokyo",
+        "humidity": 60,
+    })];
+
+    let test_source_trade = ForeignSource::new().unwrap();
+    let test_source_city_temperature = ForeignSource::new().unwrap();
+    let test_source_city_humidity = ForeignSource::new().unwrap();
+
+    let test_sink = ForeignSink::start().unwrap();
+
+    let source_reader_ddl = |name: &str, stream: &str, source: &ForeignSource| {
+        format!(
+            "
+        CREATE SOURCE READER {name} FOR {stream}
+          TYPE NET_CLIENT OPTIONS (
+            PROTOCOL 'TCP',
+            REMOTE_HOST '{remote_host}',
+            REMOTE_PORT '{remote_port}'
+          );
+        ",
+            name = name,
+            stream = stream,
+            remote_host = source.host_ip(),
+            remote_port = source.port()
+        )
+    };
+
+    let ddls = vec![
+        "
+        CREATE SOURCE STREAM source_trade (
+          ts TIMESTAMP NOT NULL ROWTIME,    
+          ticker TEXT NOT NULL,
+          amount INTEGER NOT NULL
+        );
+        "
+        .to_string(),
+        "
+        CREATE SOURCE STREAM source_city_temperature (
+          ts TIMESTAMP NOT NULL ROWTIME,    
+          city TEXT NOT NULL,
+          temperature INTEGER NOT NULL
+        );
+        "
+        .to_string(),
+        "
+        CREATE SOURCE STREAM source_city_humidity (
+          ts TIMESTAMP NOT NULL ROWTIME,    
+          city TEXT NOT NULL,
+          humidity INTEGER NOT NULL
+        );
+        "
+        .to_string(),
+        "
+        CREATE SINK STREAM sink_joined (
+          ts TIMESTAMP NOT NULL ROWTIME,    
+          amount INTEGER NOT NULL,
+          temperature INTEGER,
+          humidity INTEGER
+        );
+        "
+        .to_string(),
+        "
+        CREATE PUMP pu_join AS
+          INSERT INTO sink_joined (ts, amount, temperature, humidity)
+          SELECT STREAM
+            source_trade.ts,
+            source_trade.amount,
+            source_city_temperature.temperature,
+            source_city_humidity.humidity
+          FROM source_trade
+          LEFT OUTER JOIN source_city_temperature
+            ON source_trade.ts = source_city_temperature.ts
+          LEFT OUTER JOIN source_city_humidity
+            ON source_city_temperature.city = source_city_humidity.city
+            AND source_city_temperature.ts = source_city_humidity.ts
+          FIXED WINDOW DURATION_SECS(10), DURATION_SECS(0);
+        "
+        .to_string(),
+        format!(
+            "
+        CREATE SINK WRITER tcp_sink_joined FOR sink_joined
+          TYPE NET_CLIENT OPTIONS (
+            PROTOCOL 'TCP',
+            REMOTE_HOST '{remote_host}',
+            REMOTE_PORT '{remote_port}'
+        );
+        ",
+            remote_host = test_sink.host_ip(),
+            remote_port = test_sink.port()
+        ),
+        source_reader_ddl("tcp_source_trade", "source_trade", &test_source_trade),
+        source_reader_ddl(
+            "tcp_source_city_temperature",
+            "source_city_temperature",
+            &test_source_city_temperature,
+        ),
+        source_reader_ddl(
+            "tcp_source_city_humidity",
+            "source_city_humidity",
+            &test_source_city_humidity,
+        ),
+    ];
+
+    let _pipeline = apply_ddls(&ddls, SpringConfig::default());
+    test_source_trade.start(ForeignSourceInput::new_fifo_batch(source_trade));
+    test_source_city_temperature.start(ForeignSourceInput::new_fifo_batch(source_city_temperature));
+    test_source_city_humidity.start(ForeignSourceInput::new_fifo_batch(source_city_humidity));
+    let sink_received = drain_from_sink(&test_sink);
+
+    // The upper JOIN allows one more window length of delay, so only the window [:00, :10) is closed.
+    assert_eq!(sink_received.len(), 1);
+
+    let r0 = sink_received[0].clone();
+    assert_eq!(r0["ts"].as_str().unwrap(), "2020-01-01 00:00:00.000000000");
+    assert_eq!(r0["amount"].as_i64().unwrap(), 10);
+    assert!(r0["temperature"].is_null() || r0["temperature"].as_i64().unwrap() == -3);
+    assert!(
+        r0["humidity"].is_null()
+            || (r0["humidity"].as_i64().unwrap() == 60 && !r0["temperature"].is_null())
+    );
+}
+
+/// (trade ticker, amount, company ticker, company name)
+type JoinedRow = (Option<String>, Option<i32>, Option<String>, Option<String>);
+
+/// Pops joined rows until no row arrives for a while after the first one.
+fn pop_joined(pipeline: &SpringPipeline, sink_queue: &str) -> Vec<JoinedRow> {
+    const FIRST_TIMEOUT: Duration = Duration::from_secs(30);
+    const IDLE_TIMEOUT: Duration = Duration::from_secs(1);
+
+    let mut rows = Vec::new();
+    let mut timeout = FIRST_TIMEOUT;
+    while let Some(row) = pipeline.pop_timeout(sink_queue, timeout).unwrap() {
+        timeout = IDLE_TIMEOUT;
+        rows.push((
+            row.get(0).unwrap(),
+            row.get(1).unwrap(),
+            row.get(2).unwrap(),
+            row.get(3).unwrap(),
+        ));
+    }
+    rows.sort();
+    rows
+}
+
+#[test]
+fn test_feat_inner_right_full_outer_join() {
+    setup_test_logger();
+
+    let mut ddls = vec![
+        "
+        CREATE SOURCE STREAM source_trade (
+          ts TIMESTAMP NOT NULL ROWTIME,
+          ticker TEXT,
+          amount INTEGER NOT NULL
+        );
+        "
+        .to_string(),
+        "
+        CREATE SOURCE STREAM source_company (
+          ts TIMESTAMP NOT NULL ROWTIME,
+          ticker TEXT,
+          name TEXT NOT NULL
+        );
+        "
+        .to_string(),
+        "
+        CREATE SOURCE READER q_source_trade FOR source_trade
+          TYPE IN_MEMORY_QUEUE OPTIONS (
+            NAME 'q_trade'
+        );
+        "
+        .to_string(),
+        "
+        CREATE SOURCE READER q_source_company FOR source_company
+          TYPE IN_MEMORY_QUEUE OPTIONS (
+            NAME 'q_company'
+        );
+        "
+        .to_string(),
+    ];
+    for (join, suffix) in [
+        ("INNER JOIN", "inner"),
+        ("RIGHT OUTER JOIN", "right"),
+        ("FULL OUTER JOIN", "full"),
+    ] {
+        ddls.push(format!(
+            "
+            CREATE SINK STREAM sink_{suffix} (
+              trade_ticker TEXT,
+              amount INTEGER,
+              company_ticker TEXT,
+              company TEXT
+            );
+            ",
+            suffix = suffix
+        ));
+        ddls.push(format!(
+            "
+            CREATE PUMP pu_{suffix} AS
+              INSERT INTO sink_{suffix} (trade_ticker, amount, company_ticker, company)
+              SELECT STREAM
+                source_trade.ticker,
+                source_trade.amount,
+                source_company.ticker,
+                source_company.name
+              FROM source_trade
+              {join} source_company
+                ON source_trade.ticker = source_company.ticker
+              FIXED WINDOW DURATION_SECS(10), DURATION_SECS(5);
+            ",
+            join = join,
+            suffix = suffix
+        ));
+        ddls.push(format!(
+            "
+            CREATE SINK WRITER q_sink_{suffix} FOR sink_{suffix}
+              TYPE IN_MEMORY_QUEUE OPTIONS (
+                NAME 'q_{suffix}'
+            );
+            ",
+            suffix = suffix
+        ));
+    }
+    let pipeline = apply_ddls(&ddls, SpringConfig::default());
+
+    for trade in [
+        json!({"ts": "2020-01-01 00:00:01.000000000", "ticker": "ORCL", "amount": 10}),
+        json!({"ts": "2020-01-01 00:00:02.000000000", "ticker": "GOOGL", "amount": 20}),
+        json!({"ts": "2020-01-01 00:00:03.000000000", "ticker": null, "amount": 30}),
+    ] {
+        pipeline
+            .push(
+                "q_trade",
+                SpringSourceRow::from_json(&trade.to_string()).unwrap(),
+            )
+            .unwrap();
+    }
+    for company in [
+        json!({"ts": "2020-01-01 00:00:01.000000000", "ticker": "ORCL", "name": "Oracle"}),
+        json!({"ts": "2020-01-01 00:00:02.000000000", "ticker": "IBM", "name": "IBM"}),
+        json!({"ts": "2020-01-01 00:00:03.000000000", "ticker": null, "name": "Unknown"}),
+    ] {
+        pipeline
+            .push(
+                "q_company",
+                SpringSourceRow::from_json(&company.to_string()).unwrap(),
+            )
+            .unwrap();
+    }
+    // closes the window (the allowed delay keeps rows from the two sources in any order)
+    pipeline.end_of_stream("q_trade").unwrap();
+    pipeline.end_of_stream("q_company").unwrap();
+
+    let s = |v: &str| Some(v.to_string());
+    let matched = (s("ORCL"), Some(10), s("ORCL"), s("Oracle"));
+    // NULL keys never match
+    let unmatched_left = vec![
+        (None, Some(30), None, None),
+        (s("GOOGL"), Some(20), None, None),
+    ];
+    let unmatched_right = vec![
+        (None, None, None, s("Unknown")),
+        (None, None, s("IBM"), s("IBM")),
+    ];
+
+    assert_eq!(pop_joined(&pipeline, "q_inner"), vec![matched.clone()]);
+
+    let mut expected_right = unmatched_right.clone();
+    expected_right.push(matched.clone());
+    expected_right.sort();
+    assert_eq!(pop_joined(&pipeline, "q_right"), expected_right);
+
+    let mut expected_full = unmatched_left;
+    expected_full.extend(unmatched_right);
+    expected_full.push(matched);
+    expected_full.sort();
+    assert_eq!(pop_joined(&pipeline, "q_full"), expected_full);
+}
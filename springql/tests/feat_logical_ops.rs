@@ -82,3 +82,101 @@ fn test_feat_and() {
     assert_eq!(r["answer_true_and_false"], false);
     assert_eq!(r["answer_false_and_true"], false);
 }
+
+#[test]
+fn test_feat_or_not_is_with_null() {
+    setup_test_logger();
+
+    let json_null = json!({
+        "ts": "2020-01-01 00:00:00.000000000",
+        "v": null,
+    });
+    let json_5 = json!({
+        "ts": "2020-01-01 00:00:01.000000000",
+        "v": 5,
+    });
+    let source_input = vec![json_null, json_5];
+
+    let test_source = ForeignSource::new().unwrap();
+    let test_sink = ForeignSink::start().unwrap();
+
+    let ddls = vec![
+        "
+        CREATE SOURCE STREAM source_1 (
+          ts TIMESTAMP NOT NULL ROWTIME,
+          v INTEGER
+        );
+        "
+        .to_string(),
+        "
+        CREATE SINK STREAM sink_1 (
+          ts TIMESTAMP NOT NULL ROWTIME,
+          is_null BOOLEAN NOT NULL,
+          is_not_null BOOLEAN NOT NULL,
+          gt_or_true BOOLEAN,
+          gt_or_false BOOLEAN,
+          not_gt BOOLEAN,
+          not_in BOOLEAN
+        );
+        "
+        .to_string(),
+        "
+        CREATE PUMP pu_or_not_is AS
+          INSERT INTO sink_1 (ts, is_null, is_not_null, gt_or_true, gt_or_false, not_gt, not_in)
+          SELECT STREAM
+            source_1.ts,
+            source_1.v IS NULL,
+            source_1.v IS NOT NULL,
+            TRUE OR source_1.v > 3,
+            FALSE OR source_1.v > 3,
+            NOT source_1.v > 3,
+            source_1.v NOT IN (1, 2)
+          FROM source_1;
+        "
+        .to_string(),
+        format!(
+            "
+        CREATE SINK WRITER tcp_sink_1 FOR sink_1
+          TYPE NET_CLIENT OPTIONS (
+            PROTOCOL 'TCP',
+            REMOTE_HOST '{remote_host}',
+            REMOTE_PORT '{remote_port}'
+        );
+        ",
+            remote_host = test_sink.host_ip(),
+            remote_port = test_sink.port()
+        ),
+        format!(
+            "
+        CREATE SOURCE READER tcp_1 FOR source_1
+          TYPE NET_CLIENT OPTIONS (
+            PROTOCOL 'TCP',
+            REMOTE_HOST '{remote_host}',
+            REMOTE_PORT '{remote_port}'
+          );
+        ",
+            remote_host = test_source.host_ip(),
+            remote_port = test_source.port()
+        ),
+    ];
+
+    let _pipeline = apply_ddls(&ddls, SpringConfig::default());
+    test_source.start(ForeignSourceInput::new_fifo_batch(source_input));
+    let sink_received = drain_from_sink(&test_sink);
+
+    let r_null = &sink_received[0];
+    assert_eq!(r_null["is_null"], true);
+    assert_eq!(r_null["is_not_null"], false);
+    assert_eq!(r_null["gt_or_true"], true);
+    assert_eq!(r_null["gt_or_false"], serde_json::Value::Null);
+    assert_eq!(r_null["not_gt"], serde_json::Value::Null);
+    assert_eq!(r_null["not_in"], serde_json::Value::Null);
+
+    let r_5 = &sink_received[1];
+    assert_eq!(r_5["is_null"], false);
+    assert_eq!(r_5["is_not_null"], true);
+    assert_eq!(r_5["gt_or_true"], true);
+    assert_eq!(r_5["gt_or_false"], true);
+    assert_eq!(r_5["not_gt"], false);
+    assert_eq!(r_5["not_in"], true);
+}
@@ -0,0 +1,96 @@
+// This file is part of https://github.com/SpringQL/SpringQL which is licensed under MIT OR Apache-2.0. See file LICENSE-MIT or LICENSE-APACHE for full license details.
+
+mod test_support;
+
+use std::time::Duration;
+
+use pretty_assertions::assert_eq;
+use serde_json::json;
+use springql::*;
+use springql_foreign_service::mqtt_broker::MqttBroker;
+use springql_test_logger::setup_test_logger;
+
+use crate::test_support::apply_ddls;
+
+#[test]
+fn test_feat_mqtt_source_to_sink() {
+    setup_test_logger();
+
+    let broker = MqttBroker::start().unwrap();
+
+    let ddls = vec![
+        "
+        CREATE SOURCE STREAM source_trade (
+          ts TIMESTAMP NOT NULL ROWTIME,
+          topic TEXT NOT NULL,
+          amount INTEGER NOT NULL
+        );
+        "
+        .to_string(),
+        "
+        CREATE SINK STREAM sink_trade (
+          ts TIMESTAMP NOT NULL ROWTIME,
+          topic TEXT NOT NULL,
+          amount INTEGER NOT NULL
+        );
+        "
+        .to_string(),
+        "
+        CREATE PUMP pu_trade AS
+          INSERT INTO sink_trade (ts, topic, amount)
+          SELECT STREAM source_trade.ts, source_trade.topic, source_trade.amount FROM source_trade;
+        "
+        .to_string(),
+        format!(
+            "
+        CREATE SINK WRITER mqtt_sink FOR sink_trade
+          TYPE MQTT OPTIONS (
+            BROKER_HOST '{}',
+            BROKER_PORT '{}',
+            TOPIC 'out/trade',
+            QOS '1'
+        );
+        ",
+            broker.host_ip(),
+            broker.port()
+        ),
+        format!(
+            "
+        CREATE SOURCE READER mqtt_source FOR source_trade
+          TYPE MQTT OPTIONS (
+            BROKER_HOST '{}',
+            BROKER_PORT '{}',
+            TOPIC 'trade/+',
+            QOS '1',
+            CLIENT_ID 'springql-test'
+        );
+        ",
+            broker.host_ip(),
+            broker.port()
+        ),
+    ];
+    let _pipeline = apply_ddls(&ddls, SpringConfig::default());
+
+    for (topic, amount) in [("trade/ORCL", 100), ("trade/GOOGL", 200)] {
+        let payload = json!({"ts": "2022-01-01 13:00:00.000000000", "amount": amount});
+        broker.publish(topic, payload.to_string().as_bytes(), 1);
+    }
+    // not matching the topic filter
+    broker.publish(
+        "quote/ORCL",
+        json!({"ts": "2022-01-01 13:00:00.000000000", "amount": 300})
+            .to_string()
+            .as_bytes(),
+        1,
+    );
+
+    for (topic, amount) in [("trade/ORCL", 100), ("trade/GOOGL", 200)] {
+        let (sink_topic, payload) = broker.try_receive(Duration::from_secs(10)).unwrap();
+        assert_eq!(sink_topic, "out/trade");
+        assert_eq!(
+            serde_json::from_slice::<serde_json::Value>(&payload).unwrap(),
+            json!({"ts": "2022-01-01 13:00:00.000000000", "topic": topic, "amount": amount})
+        );
+    }
+    assert!(broker.try_receive(Duration::from_secs(1)).is_none());
+}
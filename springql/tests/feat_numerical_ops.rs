@@ -215,3 +215,208 @@ fn test_feat_unsigned_integer() {
 
     assert_eq!(r["u32"], u32::MAX);
 }
+
+fn ddls_int_source(
+    sink_columns: &str,
+    pump: &str,
+    test_source: &ForeignSource,
+    test_sink: &ForeignSink,
+) -> Vec<String> {
+    vec![
+        "
+        CREATE SOURCE STREAM source_1 (
+          ts TIMESTAMP NOT NULL ROWTIME,
+          v INTEGER NOT NULL
+        );
+        "
+        .to_string(),
+        format!(
+            "
+        CREATE SINK STREAM sink_1 (
+          {sink_columns}
+        );
+        ",
+            sink_columns = sink_columns
+        ),
+        pump.to_string(),
+        format!(
+            "
+        CREATE SINK WRITER tcp_sink_1 FOR sink_1
+          TYPE NET_CLIENT OPTIONS (
+            PROTOCOL 'TCP',
+            REMOTE_HOST '{remote_host}',
+            REMOTE_PORT '{remote_port}'
+        );
+        ",
+            remote_host = test_sink.host_ip(),
+            remote_port = test_sink.port()
+        ),
+        format!(
+            "
+        CREATE SOURCE READER tcp_1 FOR source_1
+          TYPE NET_CLIENT OPTIONS (
+            PROTOCOL 'TCP',
+            REMOTE_HOST '{remote_host}',
+            REMOTE_PORT '{remote_port}'
+          );
+        ",
+            remote_host = test_source.host_ip(),
+            remote_port = test_source.port()
+        ),
+    ]
+}
+
+#[test]
+fn test_feat_precedence_sub_div_rem() {
+    setup_test_logger();
+
+    let json1 = json!({
+        "ts": "2020-01-01 00:00:00.000000000",
+        "v": 100,
+    });
+    let source_input = vec![json1];
+
+    let test_source = ForeignSource::new().unwrap();
+    let test_sink = ForeignSink::start().unwrap();
+
+    let ddls = ddls_int_source(
+        "
+          ts TIMESTAMP NOT NULL ROWTIME,
+          add_mul INTEGER NOT NULL,
+          paren INTEGER NOT NULL,
+          sub_left_assoc INTEGER NOT NULL,
+          div INTEGER NOT NULL,
+          rem INTEGER NOT NULL,
+          mixed INTEGER NOT NULL,
+          neg INTEGER NOT NULL,
+          scaled FLOAT NOT NULL,
+          cmp_arith BOOLEAN NOT NULL
+        ",
+        "
+        CREATE PUMP pu_arith AS
+          INSERT INTO sink_1 (ts, add_mul, paren, sub_left_assoc, div, rem, mixed, neg, scaled, cmp_arith)
+          SELECT STREAM
+            source_1.ts,
+            1 + 2 * 3,
+            (1 + 2) * 3,
+            10 - 3 - 2,
+            20 / 3,
+            20 % 3,
+            2 - 3 * 4 / 2,
+            -2 * -3,
+            source_1.v * 0.5 + 40,
+            source_1.v / 10 - 1 = 9 AND NOT source_1.v < 0
+          FROM source_1;
+        ",
+        &test_source,
+        &test_sink,
+    );
+
+    let _pipeline = apply_ddls(&ddls, SpringConfig::default());
+    test_source.start(ForeignSourceInput::new_fifo_batch(source_input));
+    let sink_received = drain_from_sink(&test_sink);
+    let r = sink_received.get(0).unwrap();
+
+    assert_eq!(r["add_mul"], 7);
+    assert_eq!(r["paren"], 9);
+    assert_eq!(r["sub_left_assoc"], 5);
+    assert_eq!(r["div"], 6);
+    assert_eq!(r["rem"], 2);
+    assert_eq!(r["mixed"], -4);
+    assert_eq!(r["neg"], 6);
+    assert!(approx_eq!(f64, r["scaled"].as_f64().unwrap(), 90.0));
+    assert_eq!(r["cmp_arith"], true);
+}
+
+#[test]
+fn test_feat_division_by_zero_null() {
+    setup_test_logger();
+
+    let source_input = vec![
+        json!({
+            "ts": "2020-01-01 00:00:00.000000000",
+            "v": 0,
+        }),
+        json!({
+            "ts": "2020-01-01 00:00:01.000000000",
+            "v": 2,
+        }),
+    ];
+
+    let test_source = ForeignSource::new().unwrap();
+    let test_sink = ForeignSink::start().unwrap();
+
+    let ddls = ddls_int_source(
+        "
+          ts TIMESTAMP NOT NULL ROWTIME,
+          div INTEGER,
+          rem INTEGER
+        ",
+        "
+        CREATE PUMP pu_div AS
+          INSERT INTO sink_1 (ts, div, rem)
+          SELECT STREAM source_1.ts, 10 / source_1.v, 10 % source_1.v FROM source_1;
+        ",
+        &test_source,
+        &test_sink,
+    );
+
+    let _pipeline = apply_ddls(&ddls, SpringConfig::default());
+    test_source.start(ForeignSourceInput::new_fifo_batch(source_input));
+    let sink_received = drain_from_sink(&test_sink);
+
+    assert_eq!(sink_received.len(), 2);
+    assert_eq!(sink_received[0]["div"], serde_json::Value::Null);
+    assert_eq!(sink_received[0]["rem"], serde_json::Value::Null);
+    assert_eq!(sink_received[1]["div"], 5);
+    assert_eq!(sink_received[1]["rem"], 0);
+}
+
+#[test]
+fn test_feat_division_by_zero_error() {
+    setup_test_logger();
+
+    let source_input = vec![
+        json!({
+            "ts": "2020-01-01 00:00:00.000000000",
+            "v": 0,
+        }),
+        json!({
+            "ts": "2020-01-01 00:00:01.000000000",
+            "v": 2,
+        }),
+    ];
+
+    let test_source = ForeignSource::new().unwrap();
+    let test_sink = ForeignSink::start().unwrap();
+
+    let ddls = ddls_int_source(
+        "
+          ts TIMESTAMP NOT NULL ROWTIME,
+          div INTEGER
+        ",
+        "
+        CREATE PUMP pu_div AS
+          INSERT INTO sink_1 (ts, div)
+          SELECT STREAM source_1.ts, 10 / source_1.v FROM source_1;
+        ",
+        &test_source,
+        &test_sink,
+    );
+
+    let config = SpringConfig::from_toml(
+        r#"
+        [sql]
+        division_by_zero = "error"
+        "#,
+    )
+    .unwrap();
+
+    let _pipeline = apply_ddls(&ddls, config);
+    test_source.start(ForeignSourceInput::new_fifo_batch(source_input));
+    let sink_received = drain_from_sink(&test_sink);
+
+    // the row dividing by zero is reported as an error and does not reach the sink
+    assert_eq!(sink_received.len(), 1);
+    assert_eq!(sink_received[0]["div"], 5);
+}
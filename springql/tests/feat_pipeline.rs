@@ -2,6 +2,12 @@
 
 mod test_support;
 
+use std::{
+    sync::Arc,
+    thread,
+    time::{Duration, Instant},
+};
+
 use pretty_assertions::assert_eq;
 use serde_json::json;
 use springql::*;
@@ -96,3 +102,589 @@ fn test_feat_split_from_source() {
     let row = pipeline.pop("q2").unwrap();
     assert_eq!(row.get_not_null_by_index::<i32>(1).unwrap(), 42 + 1);
 }
+
+#[test]
+fn test_feat_drop_objects() {
+    setup_test_logger();
+
+    let json1 = json!({
+        "ts": "2020-01-01 00:00:00.000000000",
+        "c": 42,
+    });
+    let source_input = vec![json1];
+
+    let test_source = ForeignSource::new().unwrap();
+
+    let ddls = vec![
+        "
+        CREATE SOURCE STREAM source_1 (
+          ts TIMESTAMP NOT NULL ROWTIME,
+          c INTEGER NOT NULL
+        );
+        "
+        .to_string(),
+        "
+        CREATE SINK STREAM sink_1 (
+          ts TIMESTAMP NOT NULL ROWTIME,
+          c_mul_10 INTEGER NOT NULL
+        );
+        "
+        .to_string(),
+        "
+        CREATE SINK STREAM sink_2 (
+          ts TIMESTAMP NOT NULL ROWTIME,
+          c_add_1 INTEGER NOT NULL
+        );
+        "
+        .to_string(),
+        "
+        CREATE PUMP pu_mul AS
+          INSERT INTO sink_1 (ts, c_mul_10)
+          SELECT STREAM source_1.ts, source_1.c * 10
+              FROM source_1;
+        "
+        .to_string(),
+        "
+        CREATE PUMP pu_add AS
+          INSERT INTO sink_2 (ts, c_add_1)
+          SELECT STREAM source_1.ts, source_1.c + 1
+              FROM source_1;
+        "
+        .to_string(),
+        "
+        CREATE SINK WRITER q_sink_1 FOR sink_1
+          TYPE IN_MEMORY_QUEUE OPTIONS (
+            NAME 'q_drop_1'
+        );
+        "
+        .to_string(),
+        "
+        CREATE SINK WRITER q_sink_2 FOR sink_2
+          TYPE IN_MEMORY_QUEUE OPTIONS (
+            NAME 'q_drop_2'
+        );
+        "
+        .to_string(),
+    ];
+
+    let pipeline = apply_ddls(&ddls, SpringConfig::default());
+
+    // pu_mul and pu_add still read source_1
+    assert!(pipeline.command("DROP STREAM source_1;").is_err());
+
+    pipeline
+        .command("DROP SINK STREAM sink_2 CASCADE;")
+        .unwrap();
+    assert!(pipeline.command("DROP PUMP pu_add;").is_err());
+
+    pipeline.command("DROP SINK WRITER q_sink_1;").unwrap();
+    pipeline.command("DROP PUMP pu_mul;").unwrap();
+    pipeline.command("DROP STREAM sink_1;").unwrap();
+
+    // dropped names can be reused
+    pipeline
+        .command(
+            "
+            CREATE SINK STREAM sink_1 (
+              ts TIMESTAMP NOT NULL ROWTIME,
+              c_mul_10 INTEGER NOT NULL
+            );
+            ",
+        )
+        .unwrap();
+    pipeline
+        .command(
+            "
+            CREATE PUMP pu_mul AS
+              INSERT INTO sink_1 (ts, c_mul_10)
+              SELECT STREAM source_1.ts, source_1.c * 10
+                  FROM source_1;
+            ",
+        )
+        .unwrap();
+    pipeline
+        .command(
+            "
+            CREATE SINK WRITER q_sink_1 FOR sink_1
+              TYPE IN_MEMORY_QUEUE OPTIONS (
+                NAME 'q_drop_1'
+            );
+            ",
+        )
+        .unwrap();
+    pipeline
+        .command(format!(
+            "
+            CREATE SOURCE READER tcp_1 FOR source_1
+              TYPE NET_CLIENT OPTIONS (
+                PROTOCOL 'TCP',
+                REMOTE_HOST '{remote_host}',
+                REMOTE_PORT '{remote_port}'
+              );
+            ",
+            remote_host = test_source.host_ip(),
+            remote_port = test_source.port()
+        ))
+        .unwrap();
+    test_source.start(ForeignSourceInput::new_fifo_batch(source_input));
+
+    let row = pipeline.pop("q_drop_1").unwrap();
+    assert_eq!(row.get_not_null_by_index::<i32>(1).unwrap(), 42 * 10);
+}
+
+fn push_c(pipeline: &SpringPipeline, source_queue: &str, c: i32) {
+    let json = json!({
+        "ts": "2022-01-01 13:00:00.000000000",
+        "c": c,
+    });
+    pipeline
+        .push(
+            source_queue,
+            SpringSourceRow::from_json(&json.to_string()).unwrap(),
+        )
+        .unwrap();
+}
+
+fn pop_c(pipeline: &SpringPipeline, sink_queue: &str) -> i32 {
+    let row = pipeline.pop(sink_queue).unwrap();
+    row.get_not_null_by_index::<i32>(1).unwrap()
+}
+
+/// source_1 -> (`pump_ddl`) -> sink_1
+fn stopped_pump_pipeline(
+    pump_ddl: &str,
+    source_queue: &str,
+    sink_queue: &str,
+    config: SpringConfig,
+) -> SpringPipeline {
+    let ddls = vec![
+        "
+        CREATE SOURCE STREAM source_1 (
+          ts TIMESTAMP NOT NULL ROWTIME,
+          c INTEGER NOT NULL
+        );
+        "
+        .to_string(),
+        "
+        CREATE SINK STREAM sink_1 (
+          ts TIMESTAMP NOT NULL ROWTIME,
+          c INTEGER NOT NULL
+        );
+        "
+        .to_string(),
+        pump_ddl.to_string(),
+        format!(
+            "
+        CREATE SINK WRITER q_sink_1 FOR sink_1
+          TYPE IN_MEMORY_QUEUE OPTIONS (
+            NAME '{}'
+        );
+        ",
+            sink_queue
+        ),
+        format!(
+            "
+        CREATE SOURCE READER q_source_1 FOR source_1
+          TYPE IN_MEMORY_QUEUE OPTIONS (
+            NAME '{}'
+        );
+        ",
+            source_queue
+        ),
+    ];
+    apply_ddls(&ddls, config)
+}
+
+#[test]
+fn test_feat_alter_pump_start_stop() {
+    setup_test_logger();
+
+    let source_queue = "q_alter_pump_source";
+    let sink_queue = "q_alter_pump_sink";
+    let pipeline = stopped_pump_pipeline(
+        "
+        CREATE PUMP pu_passthrough AS
+          INSERT INTO sink_1 (ts, c)
+          SELECT STREAM source_1.ts, source_1.c
+              FROM source_1;
+        ",
+        source_queue,
+        sink_queue,
+        SpringConfig::default(),
+    );
+
+    assert!(pipeline
+        .command("ALTER PUMP pu_nonexistent START;")
+        .is_err());
+
+    pipeline.command("ALTER PUMP pu_passthrough STOP;").unwrap();
+
+    // kept in the input queue of the stopped pump
+    push_c(&pipeline, source_queue, 1);
+    thread::sleep(Duration::from_millis(500));
+    assert!(pipeline.pop_non_blocking(sink_queue).unwrap().is_none());
+
+    pipeline
+        .command("ALTER PUMP pu_passthrough START;")
+        .unwrap();
+    push_c(&pipeline, source_queue, 2);
+
+    assert_eq!(pop_c(&pipeline, sink_queue), 1);
+    assert_eq!(pop_c(&pipeline, sink_queue), 2);
+}
+
+#[test]
+fn test_feat_stopped_pump_queue_capacity() {
+    setup_test_logger();
+
+    let source_queue = "q_stopped_capacity_source";
+    let sink_queue = "q_stopped_capacity_sink";
+    let config = SpringConfig::from_toml(
+        "
+        [pump]
+        stopped_queue_capacity = 2
+        ",
+    )
+    .unwrap();
+    let pipeline = stopped_pump_pipeline(
+        "
+        CREATE PUMP pu_passthrough STOPPED AS
+          INSERT INTO sink_1 (ts, c)
+          SELECT STREAM source_1.ts, source_1.c
+              FROM source_1;
+        ",
+        source_queue,
+        sink_queue,
+        config,
+    );
+    assert!(!pipeline.pumps().unwrap()[0].is_started());
+
+    for c in 1..=3 {
+        push_c(&pipeline, source_queue, c);
+        thread::sleep(Duration::from_millis(300));
+    }
+    assert!(pipeline.pop_non_blocking(sink_queue).unwrap().is_none());
+
+    pipeline
+        .command("ALTER PUMP pu_passthrough START;")
+        .unwrap();
+
+    // the 3rd row is dropped because the queue is full
+    assert_eq!(pop_c(&pipeline, sink_queue), 1);
+    assert_eq!(pop_c(&pipeline, sink_queue), 2);
+    thread::sleep(Duration::from_millis(500));
+    assert!(pipeline.pop_non_blocking(sink_queue).unwrap().is_none());
+}
+
+#[test]
+fn test_feat_start_pumps_at_once() {
+    setup_test_logger();
+
+    let source_queue = "q_start_at_once_source";
+    let sink_queue = "q_start_at_once_sink";
+    let ddls = vec![
+        "
+        CREATE SOURCE STREAM source_1 (
+          ts TIMESTAMP NOT NULL ROWTIME,
+          c INTEGER NOT NULL
+        );
+        "
+        .to_string(),
+        "
+        CREATE STREAM mid_1 (
+          ts TIMESTAMP NOT NULL ROWTIME,
+          c INTEGER NOT NULL
+        );
+        "
+        .to_string(),
+        "
+        CREATE SINK STREAM sink_1 (
+          ts TIMESTAMP NOT NULL ROWTIME,
+          c INTEGER NOT NULL
+        );
+        "
+        .to_string(),
+        "
+        CREATE PUMP pu_mul STOPPED AS
+          INSERT INTO mid_1 (ts, c)
+          SELECT STREAM source_1.ts, source_1.c * 10
+              FROM source_1;
+        "
+        .to_string(),
+        "
+        CREATE PUMP pu_add STOPPED AS
+          INSERT INTO sink_1 (ts, c)
+          SELECT STREAM mid_1.ts, mid_1.c + 1
+              FROM mid_1;
+        "
+        .to_string(),
+        format!(
+            "
+        CREATE SINK WRITER q_sink_1 FOR sink_1
+          TYPE IN_MEMORY_QUEUE OPTIONS (
+            NAME '{}'
+        );
+        ",
+            sink_queue
+        ),
+        format!(
+            "
+        CREATE SOURCE READER q_source_1 FOR source_1
+          TYPE IN_MEMORY_QUEUE OPTIONS (
+            NAME '{}'
+        );
+        ",
+            source_queue
+        ),
+    ];
+    let pipeline = apply_ddls(&ddls, SpringConfig::default());
+
+    push_c(&pipeline, source_queue, 4);
+
+    // no pump is started if any of them does not exist
+    assert!(pipeline
+        .command("ALTER PUMP pu_mul, pu_nonexistent START;")
+        .is_err());
+    assert!(pipeline
+        .pumps()
+        .unwrap()
+        .iter()
+        .all(|pump| !pump.is_started()));
+
+    pipeline
+        .command("ALTER PUMP pu_mul, pu_add START;")
+        .unwrap();
+    assert!(pipeline
+        .pumps()
+        .unwrap()
+        .iter()
+        .all(|pump| pump.is_started()));
+
+    assert_eq!(pop_c(&pipeline, sink_queue), 41);
+}
+
+#[test]
+fn test_feat_pipeline_introspection() {
+    setup_test_logger();
+
+    let ddls = vec![
+        "
+        CREATE SOURCE STREAM source_1 (
+          ts TIMESTAMP NOT NULL ROWTIME,
+          c INTEGER NOT NULL
+        );
+        "
+        .to_string(),
+        "
+        CREATE SINK STREAM sink_1 (
+          ts TIMESTAMP NOT NULL ROWTIME,
+          c_mul_10 BIGINT
+        );
+        "
+        .to_string(),
+        "
+        CREATE PUMP pu_mul AS
+          INSERT INTO sink_1 (ts, c_mul_10)
+          SELECT STREAM source_1.ts, source_1.c * 10
+              FROM source_1;
+        "
+        .to_string(),
+        "ALTER PUMP pu_mul STOP;".to_string(),
+        "
+        CREATE SINK WRITER q_sink_1 FOR sink_1
+          TYPE IN_MEMORY_QUEUE OPTIONS (
+            NAME 'q_introspection_sink'
+        );
+        "
+        .to_string(),
+        "
+        CREATE SOURCE READER q_source_1 FOR source_1
+          TYPE IN_MEMORY_QUEUE OPTIONS (
+            NAME 'q_introspection_source'
+        );
+        "
+        .to_string(),
+    ];
+
+    let pipeline = apply_ddls(&ddls, SpringConfig::default());
+
+    let streams = pipeline.streams().unwrap();
+    assert_eq!(
+        streams.iter().map(|s| s.name()).collect::<Vec<_>>(),
+        vec!["sink_1", "source_1"]
+    );
+    let sink_1 = &streams[0];
+    assert_eq!(sink_1.rowtime(), Some("ts"));
+    let columns = sink_1.columns();
+    assert_eq!(columns.len(), 2);
+    assert_eq!(columns[0].data_type(), "TIMESTAMP");
+    assert!(!columns[0].is_nullable());
+    assert_eq!(columns[1].name(), "c_mul_10");
+    assert_eq!(columns[1].data_type(), "BIGINT");
+    assert!(columns[1].is_nullable());
+
+    let pumps = pipeline.pumps().unwrap();
+    assert_eq!(pumps.len(), 1);
+    let pump = &pumps[0];
+    assert_eq!(pump.name(), "pu_mul");
+    assert!(pump.sql().starts_with("CREATE PUMP pu_mul AS"));
+    assert_eq!(pump.upstreams(), ["source_1".to_string()]);
+    assert_eq!(pump.downstream(), "sink_1");
+    assert!(!pump.is_started());
+
+    let source_readers = pipeline.source_readers().unwrap();
+    assert_eq!(source_readers.len(), 1);
+    assert_eq!(source_readers[0].reader_type(), "IN_MEMORY_QUEUE");
+    assert_eq!(source_readers[0].stream(), "source_1");
+    assert_eq!(
+        source_readers[0].options(),
+        [("NAME".to_string(), "q_introspection_source".to_string())]
+    );
+
+    let sink_writers = pipeline.sink_writers().unwrap();
+    assert_eq!(sink_writers.len(), 1);
+    assert_eq!(sink_writers[0].name(), "q_sink_1");
+    assert_eq!(sink_writers[0].writer_type(), "IN_MEMORY_QUEUE");
+    assert_eq!(sink_writers[0].stream(), "sink_1");
+}
+
+/// Pipeline passing rows from in-memory queue `q_in` to `q_out`.
+fn in_memory_pass_through_pipeline() -> SpringPipeline {
+    let ddls = vec![
+        "
+        CREATE SOURCE STREAM source_n (
+          ts TIMESTAMP NOT NULL ROWTIME,
+          n INTEGER NOT NULL
+        );
+        "
+        .to_string(),
+        "
+        CREATE SINK STREAM sink_n (
+          ts TIMESTAMP NOT NULL ROWTIME,
+          n INTEGER NOT NULL
+        );
+        "
+        .to_string(),
+        "
+        CREATE PUMP pu_n AS
+          INSERT INTO sink_n (ts, n)
+          SELECT STREAM source_n.ts, source_n.n FROM source_n;
+        "
+        .to_string(),
+        "
+        CREATE SINK WRITER q_sink_n FOR sink_n
+          TYPE IN_MEMORY_QUEUE OPTIONS (
+            NAME 'q_out'
+        );
+        "
+        .to_string(),
+        "
+        CREATE SOURCE READER q_source_n FOR source_n
+          TYPE IN_MEMORY_QUEUE OPTIONS (
+            NAME 'q_in'
+        );
+        "
+        .to_string(),
+    ];
+    apply_ddls(&ddls, SpringConfig::default())
+}
+
+#[test]
+fn test_feat_in_memory_queues_isolated_between_pipelines() {
+    setup_test_logger();
+
+    // same queue names in both pipelines
+    let pipeline1 = in_memory_pass_through_pipeline();
+    let pipeline2 = in_memory_pass_through_pipeline();
+
+    for (pipeline, n) in [(&pipeline1, 1), (&pipeline2, 2)] {
+        let json = format!(r#"{{"ts": "2020-01-01 00:00:00.000000000", "n": {}}}"#, n);
+        pipeline
+            .push("q_in", SpringSourceRow::from_json(&json).unwrap())
+            .unwrap();
+    }
+
+    for (pipeline, n) in [(&pipeline1, 1), (&pipeline2, 2)] {
+        let row = pipeline.pop("q_out").unwrap();
+        assert_eq!(row.get_not_null_by_index::<i32>(1).unwrap(), n);
+    }
+
+    thread::sleep(Duration::from_millis(500));
+    assert!(pipeline1.pop_non_blocking("q_out").unwrap().is_none());
+    assert!(pipeline2.pop_non_blocking("q_out").unwrap().is_none());
+}
+
+#[test]
+fn test_feat_blocking_pop_does_not_block_pipeline() {
+    setup_test_logger();
+
+    let pipeline = Arc::new(in_memory_pass_through_pipeline());
+
+    let popper = {
+        let pipeline = pipeline.clone();
+        thread::spawn(move || pipeline.pop("q_out").unwrap())
+    };
+
+    // push while the popper is blocked
+    thread::sleep(Duration::from_millis(100));
+    pipeline
+        .push(
+            "q_in",
+            SpringSourceRow::from_json(r#"{"ts": "2020-01-01 00:00:00.000000000", "n": 42}"#)
+                .unwrap(),
+        )
+        .unwrap();
+
+    let row = popper.join().unwrap();
+    assert_eq!(row.get_not_null_by_index::<i32>(1).unwrap(), 42);
+
+    let timeout = Duration::from_millis(100);
+    let started = Instant::now();
+    assert!(pipeline.pop_timeout("q_out", timeout).unwrap().is_none());
+    assert!(started.elapsed() >= timeout);
+    assert!(pipeline.pop_timeout("q_no_such_queue", timeout).is_err());
+}
+
+#[test]
+fn test_feat_blocking_pop_woken_up_by_end_of_stream() {
+    setup_test_logger();
+
+    let pipeline = Arc::new(in_memory_pass_through_pipeline());
+
+    let popper = {
+        let pipeline = pipeline.clone();
+        thread::spawn(move || pipeline.pop("q_out"))
+    };
+
+    thread::sleep(Duration::from_millis(100));
+    pipeline.end_of_stream("q_out").unwrap();
+
+    assert!(matches!(
+        popper.join().unwrap(),
+        Err(SpringError::Unavailable { .. })
+    ));
+    assert!(matches!(
+        pipeline.pop_timeout("q_out", Duration::from_secs(10)),
+        Err(SpringError::Unavailable { .. })
+    ));
+}
+
+#[test]
+fn test_feat_blocking_pop_woken_up_by_drop_sink_writer() {
+    setup_test_logger();
+
+    let pipeline = Arc::new(in_memory_pass_through_pipeline());
+
+    let popper = {
+        let pipeline = pipeline.clone();
+        thread::spawn(move || pipeline.pop("q_out"))
+    };
+
+    thread::sleep(Duration::from_millis(100));
+    pipeline.command("DROP SINK WRITER q_sink_n;").unwrap();
+
+    assert!(matches!(
+        popper.join().unwrap(),
+        Err(SpringError::Unavailable { .. })
+    ));
+}
@@ -0,0 +1,185 @@
+// This file is part of https://github.com/SpringQL/SpringQL which is licensed under MIT OR Apache-2.0. See file LICENSE-MIT or LICENSE-APACHE for full license details.
+
+mod test_support;
+
+use pretty_assertions::assert_eq;
+use serde_json::json;
+use springql_core::api::{error::Result, *};
+use springql_foreign_service::{
+    sink::ForeignSink,
+    source::{ForeignSource, ForeignSourceInput},
+};
+use springql_test_logger::setup_test_logger;
+
+use crate::test_support::*;
+
+fn gen_source_input() -> Vec<serde_json::Value> {
+    let json_00_1 = json!({
+        "ts": "2020-01-01 00:00:00.000000000",
+        "ticker": "ORCL",
+        "amount": 10,
+    });
+    let json_00_2 = json!({
+        "ts": "2020-01-01 00:00:05.000000000",
+        "ticker": "GOOGL",
+        "amount": 30,
+    });
+    let json_00_3 = json!({
+        "ts": "2020-01-01 00:00:09.999999999",
+        "ticker": "ORCL",
+        "amount": 50,
+    });
+    let json_10_1 = json!({
+        "ts": "2020-01-01 00:00:10.000000000",
+        "ticker": "GOOGL",
+        "amount": 70,
+    });
+    let json_20_1 = json!({
+        "ts": "2020-01-01 00:00:20.000000000",
+        "ticker": "ORCL",
+        "amount": 90,
+    });
+
+    vec![json_00_1, json_00_2, json_00_3, json_10_1, json_20_1]
+}
+
+fn run_and_drain(
+    ddls: &[String],
+    source_input: ForeignSourceInput,
+    test_source: ForeignSource,
+    test_sink: &ForeignSink,
+) -> Vec<serde_json::Value> {
+    let _pipeline = apply_ddls(ddls, SpringConfig::default());
+    test_source.start(source_input);
+    drain_from_sink(test_sink)
+}
+
+fn ddls(
+    pump: &str,
+    sink_columns: &str,
+    test_source: &ForeignSource,
+    test_sink: &ForeignSink,
+) -> Vec<String> {
+    vec![
+        "
+        CREATE SOURCE STREAM source_trade (
+          ts TIMESTAMP NOT NULL ROWTIME,
+          ticker TEXT NOT NULL,
+          amount INTEGER NOT NULL
+        );
+        "
+        .to_string(),
+        format!(
+            "
+        CREATE SINK STREAM sink_trade (
+          {sink_columns}
+        );
+        ",
+            sink_columns = sink_columns
+        ),
+        pump.to_string(),
+        format!(
+            "
+        CREATE SINK WRITER tcp_sink_trade FOR sink_trade
+          TYPE NET_CLIENT OPTIONS (
+            PROTOCOL 'TCP',
+            REMOTE_HOST '{remote_host}',
+            REMOTE_PORT '{remote_port}'
+        );
+        ",
+            remote_host = test_sink.host_ip(),
+            remote_port = test_sink.port()
+        ),
+        format!(
+            "
+        CREATE SOURCE READER tcp_trade FOR source_trade
+          TYPE NET_CLIENT OPTIONS (
+            PROTOCOL 'TCP',
+            REMOTE_HOST '{remote_host}',
+            REMOTE_PORT '{remote_port}'
+          );
+        ",
+            remote_host = test_source.host_ip(),
+            remote_port = test_source.port()
+        ),
+    ]
+}
+
+#[test]
+fn test_feat_where() -> Result<()> {
+    setup_test_logger();
+
+    let test_source = ForeignSource::new().unwrap();
+    let test_sink = ForeignSink::start().unwrap();
+
+    let ddls = ddls(
+        "
+        CREATE PUMP pu_orcl AS
+          INSERT INTO sink_trade (ts, amount)
+          SELECT STREAM source_trade.ts, source_trade.amount
+          FROM source_trade
+          WHERE source_trade.ticker = 'ORCL';
+        ",
+        "
+          ts TIMESTAMP NOT NULL ROWTIME,
+          amount INTEGER NOT NULL
+        ",
+        &test_source,
+        &test_sink,
+    );
+
+    let sink_received = run_and_drain(
+        &ddls,
+        ForeignSourceInput::new_fifo_batch(gen_source_input()),
+        test_source,
+        &test_sink,
+    );
+
+    assert_eq!(sink_received.len(), 3);
+    assert_eq!(sink_received[0]["amount"], 10);
+    assert_eq!(sink_received[1]["amount"], 50);
+    assert_eq!(sink_received[2]["amount"], 90);
+
+    Ok(())
+}
+
+#[test]
+fn test_feat_where_before_aggregation() -> Result<()> {
+    setup_test_logger();
+
+    let test_source = ForeignSource::new().unwrap();
+    let test_sink = ForeignSink::start().unwrap();
+
+    let ddls = ddls(
+        "
+        CREATE PUMP pu_avg_orcl AS
+          INSERT INTO sink_trade (avg_amount)
+          SELECT STREAM
+            AVG(source_trade.amount) AS avg_amount
+          FROM source_trade
+          WHERE source_trade.ticker = 'ORCL'
+          FIXED WINDOW DURATION_SECS(10), DURATION_SECS(0);
+        ",
+        "
+          avg_amount FLOAT NOT NULL
+        ",
+        &test_source,
+        &test_sink,
+    );
+
+    let sink_received = run_and_drain(
+        &ddls,
+        ForeignSourceInput::new_fifo_batch(gen_source_input()),
+        test_source,
+        &test_sink,
+    );
+
+    // Filtered rows never reach the window: GOOGL at :10 neither closes [:00, :10) nor opens [:10, :20).
+    assert_eq!(sink_received.len(), 1);
+    assert_eq!(
+        sink_received[0]["avg_amount"].as_f64().unwrap().round() as i32,
+        30,
+    );
+
+    Ok(())
+}
@@ -0,0 +1,142 @@
+// This file is part of https://github.com/SpringQL/SpringQL which is licensed under MIT OR Apache-2.0. See file LICENSE-MIT or LICENSE-APACHE for full license details.
+
+mod test_support;
+
+use std::{thread, time::Duration};
+
+use pretty_assertions::assert_eq;
+use springql::*;
+use springql_test_logger::setup_test_logger;
+
+use crate::test_support::apply_ddls;
+
+/// Pipeline summing `amount` by ticker in a 10-second fixed window.
+fn sum_by_ticker_pipeline(source_queue: &str, sink_queue: &str) -> SpringPipeline {
+    let ddls = vec![
+        "
+        CREATE SOURCE STREAM source_trade (
+          ts TIMESTAMP NOT NULL ROWTIME,
+          ticker TEXT NOT NULL,
+          amount INTEGER NOT NULL
+        );
+        "
+        .to_string(),
+        "
+        CREATE SINK STREAM sink_sum_by_ticker (
+          ts TIMESTAMP NOT NULL ROWTIME,
+          ticker TEXT NOT NULL,
+          sum_amount INTEGER NOT NULL
+        );
+        "
+        .to_string(),
+        "
+        CREATE PUMP sum_by_ticker AS
+          INSERT INTO sink_sum_by_ticker (ts, ticker, sum_amount)
+          SELECT STREAM
+            FLOOR_TIME(source_trade.ts, DURATION_SECS(10)) AS min_ts,
+            source_trade.ticker AS ticker,
+            SUM(source_trade.amount) AS sum_amount
+          FROM source_trade
+          GROUP BY min_ts, ticker
+          FIXED WINDOW DURATION_SECS(10), DURATION_SECS(0);
+        "
+        .to_string(),
+        format!(
+            "
+        CREATE SINK WRITER q_sink FOR sink_sum_by_ticker
+          TYPE IN_MEMORY_QUEUE OPTIONS (
+            NAME '{}'
+        );
+        ",
+            sink_queue
+        ),
+        format!(
+            "
+        CREATE SOURCE READER q_source FOR source_trade
+          TYPE IN_MEMORY_QUEUE OPTIONS (
+            NAME '{}'
+        );
+        ",
+            source_queue
+        ),
+    ];
+    apply_ddls(&ddls, SpringConfig::default())
+}
+
+fn push_trades(pipeline: &SpringPipeline, source_queue: &str) {
+    for (ts, amount) in [
+        ("2022-01-01 13:00:01.000000000", 10),
+        ("2022-01-01 13:00:02.000000000", 20),
+    ] {
+        let json = format!(
+            r#"{{"ts": "{}", "ticker": "ORCL", "amount": {}}}"#,
+            ts, amount
+        );
+        pipeline
+            .push(source_queue, SpringSourceRow::from_json(&json).unwrap())
+            .unwrap();
+    }
+}
+
+#[test]
+fn test_feat_shutdown_drain_emits_open_window() {
+    setup_test_logger();
+
+    let pipeline = sum_by_ticker_pipeline("q_shutdown_drain_source", "q_shutdown_drain_sink");
+    push_trades(&pipeline, "q_shutdown_drain_source");
+
+    // the window never closes without later rows
+    thread::sleep(Duration::from_millis(500));
+    assert!(pipeline
+        .pop_non_blocking("q_shutdown_drain_sink")
+        .unwrap()
+        .is_none());
+
+    pipeline
+        .shutdown(SpringShutdownMode::Drain, Duration::from_secs(10))
+        .unwrap();
+
+    let row = pipeline
+        .pop_non_blocking("q_shutdown_drain_sink")
+        .unwrap()
+        .expect("open window must be emitted on drain");
+    assert_eq!(row.get_not_null_by_index::<String>(1).unwrap(), "ORCL");
+    assert_eq!(row.get_not_null_by_index::<i32>(2).unwrap(), 30);
+    assert!(pipeline
+        .pop_non_blocking("q_shutdown_drain_sink")
+        .unwrap()
+        .is_none());
+
+    assert!(pipeline.command("ALTER PUMP sum_by_ticker STOP;").is_err());
+    assert!(pipeline
+        .shutdown(SpringShutdownMode::Drain, Duration::from_secs(1))
+        .is_err());
+}
+
+#[test]
+fn test_feat_shutdown_immediate_discards_rows() {
+    setup_test_logger();
+
+    let pipeline =
+        sum_by_ticker_pipeline("q_shutdown_immediate_source", "q_shutdown_immediate_sink");
+    push_trades(&pipeline, "q_shutdown_immediate_source");
+    thread::sleep(Duration::from_millis(500));
+
+    pipeline
+        .shutdown(SpringShutdownMode::Immediate, Duration::from_secs(10))
+        .unwrap();
+
+    assert!(pipeline
+        .pop_non_blocking("q_shutdown_immediate_sink")
+        .unwrap()
+        .is_none());
+    assert!(pipeline
+        .push(
+            "q_shutdown_immediate_source",
+            SpringSourceRow::from_json(
+                r#"{"ts": "2022-01-01 13:00:03.000000000", "ticker": "ORCL", "amount": 1}"#
+            )
+            .unwrap()
+        )
+        .is_err());
+}
@@ -0,0 +1,143 @@
+// This file is part of https://github.com/SpringQL/SpringQL which is licensed under MIT OR Apache-2.0. See file LICENSE-MIT or LICENSE-APACHE for full license details.
+
+use std::{sync::mpsc, time::Duration};
+
+use pretty_assertions::assert_eq;
+use springql::*;
+use springql_test_logger::setup_test_logger;
+
+const SOURCE_DDLS: [&str; 4] = [
+    "
+    CREATE SOURCE STREAM source_n (
+      ts TIMESTAMP NOT NULL ROWTIME,
+      n INTEGER NOT NULL
+    );
+    ",
+    "
+    CREATE SINK STREAM sink_n (
+      ts TIMESTAMP NOT NULL ROWTIME,
+      n INTEGER NOT NULL
+    );
+    ",
+    "
+    CREATE PUMP pu_n AS
+      INSERT INTO sink_n (ts, n)
+      SELECT STREAM source_n.ts, source_n.n FROM source_n;
+    ",
+    "
+    CREATE SOURCE READER q_source_n FOR source_n
+      TYPE IN_MEMORY_QUEUE OPTIONS (
+        NAME 'q_in'
+    );
+    ",
+];
+
+const CALLBACK_SINK_DDL: &str = "
+    CREATE SINK WRITER cb_sink_n FOR sink_n
+      TYPE CALLBACK OPTIONS (
+        NAME 'cb'
+    );
+    ";
+
+fn pipeline_with_callback<F>(callback: F) -> SpringPipeline
+where
+    F: FnMut(SpringSinkRow) + Send + 'static,
+{
+    let pipeline = SpringPipeline::new(&SpringConfig::default()).unwrap();
+    pipeline.register_sink_callback("cb", callback).unwrap();
+    for ddl in SOURCE_DDLS.iter().chain([CALLBACK_SINK_DDL].iter()) {
+        pipeline.command(ddl).unwrap();
+    }
+    pipeline
+}
+
+fn push_n(pipeline: &SpringPipeline, n: i32) {
+    let json = format!(r#"{{"ts": "2020-01-01 00:00:00.000000000", "n": {}}}"#, n);
+    pipeline
+        .push("q_in", SpringSourceRow::from_json(&json).unwrap())
+        .unwrap();
+}
+
+#[test]
+fn test_feat_sink_callback_to_channel() {
+    setup_test_logger();
+
+    let (tx, rx) = mpsc::channel();
+    let pipeline = pipeline_with_callback(move |row| {
+        tx.send(row.get_not_null_by_index::<i32>(1).unwrap())
+            .unwrap()
+    });
+
+    push_n(&pipeline, 1);
+    push_n(&pipeline, 2);
+
+    assert_eq!(rx.recv_timeout(Duration::from_secs(10)).unwrap(), 1);
+    assert_eq!(rx.recv_timeout(Duration::from_secs(10)).unwrap(), 2);
+}
+
+#[test]
+fn test_feat_sink_callback_panic_isolated() {
+    setup_test_logger();
+
+    let (tx, rx) = mpsc::channel();
+    let pipeline = pipeline_with_callback(move |row| {
+        let n = row.get_not_null_by_index::<i32>(1).unwrap();
+        if n == 1 {
+            panic!("callback failed for n = {}", n);
+        }
+        tx.send(n).unwrap()
+    });
+
+    push_n(&pipeline, 1);
+    push_n(&pipeline, 2);
+
+    // the callback is still called after a panic
+    assert_eq!(rx.recv_timeout(Duration::from_secs(10)).unwrap(), 2);
+    assert!(rx.recv_timeout(Duration::from_millis(500)).is_err());
+}
+
+#[test]
+fn test_feat_sink_callback_error_handler() {
+    setup_test_logger();
+
+    let (tx, rx) = mpsc::channel();
+    let (err_tx, err_rx) = mpsc::channel();
+
+    let pipeline = SpringPipeline::new(&SpringConfig::default()).unwrap();
+    pipeline
+        .register_sink_callback_with_error_handler(
+            "cb",
+            move |row| {
+                let n = row.get_not_null_by_index::<i32>(1).unwrap();
+                if n == 1 {
+                    panic!("callback failed for n = {}", n);
+                }
+                tx.send(n).unwrap()
+            },
+            move |e| err_tx.send(e).unwrap(),
+        )
+        .unwrap();
+    for ddl in SOURCE_DDLS.iter().chain([CALLBACK_SINK_DDL].iter()) {
+        pipeline.command(ddl).unwrap();
+    }
+
+    push_n(&pipeline, 1);
+    push_n(&pipeline, 2);
+
+    let e = err_rx.recv_timeout(Duration::from_secs(10)).unwrap();
+    assert!(matches!(e, SpringError::ForeignIo { .. }));
+    assert!(err_rx.recv_timeout(Duration::from_millis(500)).is_err());
+
+    assert_eq!(rx.recv_timeout(Duration::from_secs(10)).unwrap(), 2);
+}
+
+#[test]
+fn test_feat_sink_callback_not_registered() {
+    setup_test_logger();
+
+    let pipeline = SpringPipeline::new(&SpringConfig::default()).unwrap();
+    for ddl in SOURCE_DDLS {
+        pipeline.command(ddl).unwrap();
+    }
+    assert!(pipeline.command(CALLBACK_SINK_DDL).is_err());
+}
@@ -0,0 +1,136 @@
+// This file is part of https://github.com/SpringQL/SpringQL which is licensed under MIT OR Apache-2.0. See file LICENSE-MIT or LICENSE-APACHE for full license details.
+
+mod test_support;
+
+use std::{
+    io::{BufRead, BufReader},
+    net::TcpListener,
+    sync::mpsc,
+    thread,
+    time::Duration,
+};
+
+use pretty_assertions::assert_eq;
+use springql::*;
+use springql_test_logger::setup_test_logger;
+
+use crate::test_support::apply_ddls;
+
+fn trade_json(amount: i32) -> String {
+    format!(
+        r#"{{"ts": "2022-01-01 13:00:00.000000000", "ticker": "ORCL", "amount": {}}}"#,
+        amount
+    )
+}
+
+/// Reads `n` rows from a connection accepted by `listener` and closes it.
+fn receive_rows(listener: &TcpListener, n: usize) -> Vec<i64> {
+    let (stream, _) = listener.accept().unwrap();
+    BufReader::new(stream)
+        .lines()
+        .take(n)
+        .map(|line| {
+            let json: serde_json::Value = serde_json::from_str(&line.unwrap()).unwrap();
+            json["amount"].as_i64().unwrap()
+        })
+        .collect()
+}
+
+#[test]
+fn test_feat_sink_reconnect_no_rows_lost() {
+    setup_test_logger();
+
+    let source_queue = "q_sink_reconnect_source";
+
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let addr = listener.local_addr().unwrap();
+
+    let ddls = vec![
+        "
+        CREATE SOURCE STREAM source_trade (
+          ts TIMESTAMP NOT NULL ROWTIME,
+          ticker TEXT NOT NULL,
+          amount INTEGER NOT NULL
+        );
+        "
+        .to_string(),
+        "
+        CREATE SINK STREAM sink_trade (
+          ts TIMESTAMP NOT NULL ROWTIME,
+          ticker TEXT NOT NULL,
+          amount INTEGER NOT NULL
+        );
+        "
+        .to_string(),
+        "
+        CREATE PUMP pu_trade AS
+          INSERT INTO sink_trade (ts, ticker, amount)
+          SELECT STREAM source_trade.ts, source_trade.ticker, source_trade.amount FROM source_trade;
+        "
+        .to_string(),
+        format!(
+            "
+        CREATE SINK WRITER tcp_sink FOR sink_trade
+          TYPE NET_CLIENT OPTIONS (
+            PROTOCOL 'TCP',
+            REMOTE_HOST '{}',
+            REMOTE_PORT '{}'
+        );
+        ",
+            addr.ip(),
+            addr.port()
+        ),
+        format!(
+            "
+        CREATE SOURCE READER q_source FOR source_trade
+          TYPE IN_MEMORY_QUEUE OPTIONS (
+            NAME '{}'
+        );
+        ",
+            source_queue
+        ),
+    ];
+
+    let (down_tx, down_rx) = mpsc::channel();
+    let sink = thread::spawn(move || {
+        let mut amounts = receive_rows(&listener, 2);
+
+        // the remote host is down for longer than the sink writer used to keep retrying
+        drop(listener);
+        down_tx.send(()).unwrap();
+        thread::sleep(Duration::from_millis(3_500));
+
+        let listener = TcpListener::bind(addr).unwrap();
+        amounts.extend(receive_rows(&listener, 4));
+        amounts
+    });
+
+    let config = SpringConfig::from_toml(
+        r#"
+        [sink_writer]
+        reconnect_initial_backoff_msec = 100
+        reconnect_max_backoff_msec = 500
+        "#,
+    )
+    .unwrap();
+    let pipeline = apply_ddls(&ddls, config);
+
+    let push = |amount| {
+        pipeline
+            .push(
+                source_queue,
+                SpringSourceRow::from_json(&trade_json(amount)).unwrap(),
+            )
+            .unwrap();
+    };
+
+    push(1);
+    push(2);
+    down_rx.recv().unwrap();
+    for amount in 3..=6 {
+        push(amount);
+        thread::sleep(Duration::from_millis(200));
+    }
+
+    assert_eq!(sink.join().unwrap(), vec![1, 2, 3, 4, 5, 6]);
+}
@@ -0,0 +1,154 @@
+// This file is part of https://github.com/SpringQL/SpringQL which is licensed under MIT OR Apache-2.0. See file LICENSE-MIT or LICENSE-APACHE for full license details.
+
+mod test_support;
+
+use std::time::Duration;
+
+use pretty_assertions::assert_eq;
+use serde::Deserialize;
+use serde_json::json;
+use springql::*;
+use springql_test_logger::setup_test_logger;
+
+use crate::test_support::apply_ddls;
+
+fn pipeline() -> SpringPipeline {
+    let ddls = vec![
+        "
+        CREATE SOURCE STREAM source_trade (
+          ts TIMESTAMP NOT NULL ROWTIME,
+          ticker TEXT NOT NULL,
+          amount INTEGER
+        );
+        "
+        .to_string(),
+        "
+        CREATE SINK STREAM sink_trade (
+          ts TIMESTAMP NOT NULL ROWTIME,
+          amount INTEGER,
+          ticker TEXT NOT NULL
+        );
+        "
+        .to_string(),
+        "
+        CREATE PUMP pu_trade AS
+          INSERT INTO sink_trade (ts, amount, ticker)
+          SELECT STREAM source_trade.ts, source_trade.amount, source_trade.ticker FROM source_trade;
+        "
+        .to_string(),
+        "
+        CREATE SINK WRITER q_sink_trade FOR sink_trade
+          TYPE IN_MEMORY_QUEUE OPTIONS (
+            NAME 'q_out'
+        );
+        "
+        .to_string(),
+        "
+        CREATE SOURCE READER q_source_trade FOR source_trade
+          TYPE IN_MEMORY_QUEUE OPTIONS (
+            NAME 'q_in'
+        );
+        "
+        .to_string(),
+    ];
+    apply_ddls(&ddls, SpringConfig::default())
+}
+
+fn pass_through(pipeline: &SpringPipeline, json: serde_json::Value) -> SpringSinkRow {
+    pipeline
+        .push(
+            "q_in",
+            SpringSourceRow::from_json(&json.to_string()).unwrap(),
+        )
+        .unwrap();
+    pipeline
+        .pop_timeout("q_out", Duration::from_secs(10))
+        .unwrap()
+        .unwrap()
+}
+
+#[test]
+fn test_feat_sink_row_get_by_name() {
+    setup_test_logger();
+
+    let pipeline = pipeline();
+    let row = pass_through(
+        &pipeline,
+        json!({"ts": "2022-01-01 13:00:00.000000000", "ticker": "ORCL", "amount": 10}),
+    );
+
+    assert_eq!(row.column_names(), vec!["ts", "amount", "ticker"]);
+    assert_eq!(row.len(), 3);
+    assert!(!row.is_empty());
+
+    assert_eq!(row.get_by_name::<String>("ticker").unwrap(), "ORCL");
+    assert_eq!(row.get_by_name::<i32>("amount").unwrap(), 10);
+    assert_eq!(row.get_by_name::<Option<i32>>("amount").unwrap(), Some(10));
+    assert_eq!(row.get::<Option<i32>>(1).unwrap(), Some(10));
+    assert!(row.get_by_name::<i32>("no_such_column").is_err());
+    assert!(row.get_by_name::<i32>("ticker").is_err());
+}
+
+#[test]
+fn test_feat_sink_row_null() {
+    setup_test_logger();
+
+    let pipeline = pipeline();
+    let row = pass_through(
+        &pipeline,
+        json!({"ts": "2022-01-01 13:00:00.000000000", "ticker": "ORCL", "amount": null}),
+    );
+
+    assert_eq!(row.get::<Option<i32>>(1).unwrap(), None);
+    assert_eq!(row.get_by_name::<Option<i32>>("amount").unwrap(), None);
+    assert!(matches!(
+        row.get_by_name::<i32>("amount"),
+        Err(SpringError::Null { i_col: 1 })
+    ));
+    assert!(matches!(
+        row.get_not_null_by_index::<i32>(1),
+        Err(SpringError::Null { i_col: 1 })
+    ));
+}
+
+#[test]
+fn test_feat_sink_row_into_json_and_struct() {
+    #[derive(PartialEq, Debug, Deserialize)]
+    struct Trade {
+        ts: String,
+        ticker: String,
+        amount: Option<i32>,
+    }
+
+    setup_test_logger();
+
+    let pipeline = pipeline();
+
+    let row = pass_through(
+        &pipeline,
+        json!({"ts": "2022-01-01 13:00:00.000000000", "ticker": "ORCL", "amount": 10}),
+    );
+    assert_eq!(
+        row.to_json(),
+        json!({"ts": "2022-01-01 13:00:00.000000000", "amount": 10, "ticker": "ORCL"})
+    );
+    assert_eq!(
+        row.deserialize::<Trade>().unwrap(),
+        Trade {
+            ts: "2022-01-01 13:00:00.000000000".to_string(),
+            ticker: "ORCL".to_string(),
+            amount: Some(10),
+        }
+    );
+
+    let row = pass_through(
+        &pipeline,
+        json!({"ts": "2022-01-01 13:00:01.000000000", "ticker": "IBM", "amount": null}),
+    );
+    assert_eq!(row.deserialize::<Trade>().unwrap().amount, None);
+    assert!(row.deserialize::<(i32, i32)>().is_err());
+    assert_eq!(
+        serde_json::Value::from(row),
+        json!({"ts": "2022-01-01 13:00:01.000000000", "amount": null, "ticker": "IBM"})
+    );
+}
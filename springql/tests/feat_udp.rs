@@ -0,0 +1,138 @@
+// This file is part of https://github.com/SpringQL/SpringQL which is licensed under MIT OR Apache-2.0. See file LICENSE-MIT or LICENSE-APACHE for full license details.
+
+mod test_support;
+
+use std::{net::UdpSocket, time::Duration};
+
+use pretty_assertions::assert_eq;
+use serde_json::json;
+use springql::*;
+use springql_test_logger::setup_test_logger;
+
+use crate::test_support::apply_ddls;
+
+fn ephemeral_port() -> u16 {
+    let socket = UdpSocket::bind("127.0.0.1:0").unwrap();
+    socket.local_addr().unwrap().port()
+}
+
+fn pass_through_ddls(source_reader_ddl: String, sink_writer_ddl: String) -> Vec<String> {
+    vec![
+        "
+        CREATE SOURCE STREAM source_n (
+          ts TIMESTAMP NOT NULL ROWTIME,
+          n INTEGER NOT NULL
+        );
+        "
+        .to_string(),
+        "
+        CREATE SINK STREAM sink_n (
+          ts TIMESTAMP NOT NULL ROWTIME,
+          n INTEGER NOT NULL
+        );
+        "
+        .to_string(),
+        "
+        CREATE PUMP pu_n AS
+          INSERT INTO sink_n (ts, n)
+          SELECT STREAM source_n.ts, source_n.n FROM source_n;
+        "
+        .to_string(),
+        sink_writer_ddl,
+        source_reader_ddl,
+    ]
+}
+
+fn n_json(n: i32) -> serde_json::Value {
+    json!({"ts": "2022-01-01 13:00:00.000000000", "n": n})
+}
+
+#[test]
+fn test_feat_udp_net_server_source() {
+    setup_test_logger();
+
+    let port = ephemeral_port();
+    let ddls = pass_through_ddls(
+        format!(
+            "
+        CREATE SOURCE READER udp_source FOR source_n
+          TYPE NET_SERVER OPTIONS (
+            PROTOCOL 'UDP',
+            PORT '{}'
+        );
+        ",
+            port
+        ),
+        "
+        CREATE SINK WRITER q_sink FOR sink_n
+          TYPE IN_MEMORY_QUEUE OPTIONS (
+            NAME 'q_out'
+        );
+        "
+        .to_string(),
+    );
+    let pipeline = apply_ddls(&ddls, SpringConfig::default());
+
+    let client = UdpSocket::bind("127.0.0.1:0").unwrap();
+    client.connect(("127.0.0.1", port)).unwrap();
+    client.send(n_json(1).to_string().as_bytes()).unwrap();
+    // malformed datagrams are reported as errors and skipped
+    client.send(b"{ broken json").unwrap();
+    client.send(n_json(2).to_string().as_bytes()).unwrap();
+
+    for n in [1, 2] {
+        let row = pipeline
+            .pop_timeout("q_out", Duration::from_secs(10))
+            .unwrap()
+            .unwrap();
+        assert_eq!(row.get_by_name::<i32>("n").unwrap(), n);
+    }
+}
+
+#[test]
+fn test_feat_udp_net_sink() {
+    setup_test_logger();
+
+    let remote = UdpSocket::bind("127.0.0.1:0").unwrap();
+    remote
+        .set_read_timeout(Some(Duration::from_secs(10)))
+        .unwrap();
+
+    let ddls = pass_through_ddls(
+        "
+        CREATE SOURCE READER q_source FOR source_n
+          TYPE IN_MEMORY_QUEUE OPTIONS (
+            NAME 'q_in'
+        );
+        "
+        .to_string(),
+        format!(
+            "
+        CREATE SINK WRITER udp_sink FOR sink_n
+          TYPE NET_CLIENT OPTIONS (
+            PROTOCOL 'UDP',
+            REMOTE_HOST '127.0.0.1',
+            REMOTE_PORT '{}'
+        );
+        ",
+            remote.local_addr().unwrap().port()
+        ),
+    );
+    let pipeline = apply_ddls(&ddls, SpringConfig::default());
+
+    for n in [1, 2] {
+        pipeline
+            .push(
+                "q_in",
+                SpringSourceRow::from_json(&n_json(n).to_string()).unwrap(),
+            )
+            .unwrap();
+    }
+
+    for n in [1, 2] {
+        let mut buf = [0; 1024];
+        let n_bytes = remote.recv(&mut buf).unwrap();
+        let received: serde_json::Value = serde_json::from_slice(&buf[..n_bytes]).unwrap();
+        assert_eq!(received, n_json(n));
+    }
+}